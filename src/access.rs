@@ -0,0 +1,108 @@
+use aws_sdk_dynamodb::{error::ProvideErrorMetadata, operation::RequestId, Error};
+use serde::{Deserialize, Serialize};
+
+pub const ACCESS_DENIED_CODES: [&str; 2] = ["AccessDeniedException", "AccessDenied"];
+
+/// A request rejected by IAM, broken down into the parts needed to ask for access.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessDenied {
+    pub operation: String,
+    pub principal: Option<String>,
+    /// The IAM action that was missing, e.g. `dynamodb:Scan`.
+    pub action: String,
+    pub resource: Option<String>,
+    pub message: String,
+    /// The ID AWS gave the request, to quote to AWS Support or look up in CloudTrail.
+    pub request_id: Option<String>,
+}
+
+impl AccessDenied {
+    /// A policy statement that would allow the denied request.
+    pub fn suggested_statement(&self) -> String {
+        let statement = serde_json::json!({
+            "Effect": "Allow",
+            "Action": self.action,
+            "Resource": self.resource.as_deref().unwrap_or("*"),
+        });
+        serde_json::to_string_pretty(&statement).unwrap_or_default()
+    }
+}
+
+/// Inspects an SDK error returned by `operation` and, if IAM denied it, explains what is missing.
+pub fn access_denied(err: &Error, operation: &str) -> Option<AccessDenied> {
+    err.code()
+        .filter(|code| ACCESS_DENIED_CODES.contains(code))?;
+
+    let message = err.message().unwrap_or_default();
+    Some(AccessDenied {
+        request_id: err.request_id().map(str::to_string),
+        ..parse_access_denied(operation, message)
+    })
+}
+
+/// Parses messages of the form `User: <arn> is not authorized to perform: <action> on resource:
+/// <arn> because ...`. Parts that are missing from the message are left out or guessed from the
+/// operation.
+fn parse_access_denied(operation: &str, message: &str) -> AccessDenied {
+    let principal = between(message, "User: ", " is not authorized");
+    let action = between(message, "to perform: ", " on resource")
+        .or_else(|| between(message, "to perform: ", " because"))
+        .unwrap_or_else(|| format!("dynamodb:{operation}"));
+    let resource = between(message, "on resource: ", " because").or_else(|| {
+        message
+            .split_once("on resource: ")
+            .map(|(_, rest)| rest.trim().to_string())
+    });
+
+    AccessDenied {
+        operation: operation.to_string(),
+        principal,
+        action,
+        resource,
+        message: message.to_string(),
+        request_id: None,
+    }
+}
+
+fn between(text: &str, start: &str, end: &str) -> Option<String> {
+    let (_, rest) = text.split_once(start)?;
+    let (value, _) = rest.split_once(end)?;
+    Some(value.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_access_denied_message() {
+        let denied = parse_access_denied(
+            "Scan",
+            "User: arn:aws:sts::123456789012:assumed-role/Dev/alice is not authorized to perform: \
+             dynamodb:Scan on resource: arn:aws:dynamodb:us-east-1:123456789012:table/Orders \
+             because no identity-based policy allows the dynamodb:Scan action",
+        );
+
+        assert_eq!(
+            denied.principal.as_deref(),
+            Some("arn:aws:sts::123456789012:assumed-role/Dev/alice")
+        );
+        assert_eq!(denied.action, "dynamodb:Scan");
+        assert_eq!(
+            denied.resource.as_deref(),
+            Some("arn:aws:dynamodb:us-east-1:123456789012:table/Orders")
+        );
+    }
+
+    #[test]
+    fn test_parse_unstructured_access_denied_message() {
+        let denied = parse_access_denied("ListTables", "Access denied");
+
+        assert_eq!(denied.principal, None);
+        assert_eq!(denied.action, "dynamodb:ListTables");
+        assert_eq!(denied.resource, None);
+        assert!(denied.suggested_statement().contains("\"Resource\": \"*\""));
+    }
+}
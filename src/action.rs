@@ -1,6 +1,31 @@
+use std::{collections::BTreeMap, time::SystemTime};
+
 use serde::{Deserialize, Serialize};
 use strum::Display;
 
+use crate::{
+    access::AccessDenied,
+    api_log::ApiCall,
+    app::Pane,
+    bookmark::Bookmark,
+    cloudtrail::TableEvent,
+    components::debug_overlay::FrameStats,
+    config::LayoutConfig,
+    copy::CopyTarget,
+    cost::{Billing, CostEstimate},
+    credentials::CredentialsFailure,
+    data::{DeepFindResult, KeyAttribute, PartitionStats, RequestId},
+    import::{ImportMode, ImportPreview},
+    job::Job,
+    latency::RegionLatency,
+    permission::Permissions,
+    replica::RegionItem,
+    soft_delete::SoftDeleteView,
+    table_info::TableInfo,
+    table_search::RegionTables,
+    workspace::Workspace,
+};
+
 #[derive(Debug, Clone, PartialEq, Eq, Display, Serialize, Deserialize)]
 pub enum Action {
     Tick,
@@ -11,33 +36,190 @@ pub enum Action {
     Quit,
     ClearScreen,
     Error(String),
+    /// Dismisses the oldest error shown in the error banner.
+    DismissError,
     Help,
+    ToggleDebugOverlay,
+    TransmitFrameStats(FrameStats),
+    TransmitRecordsMemory(usize),
+    /// What keys type while they type text, such as `INSERT`, or `None` while they trigger
+    /// actions.
+    TransmitModeIndicator(Option<String>),
+    /// The keys typed so far of a multi-key binding, empty once it is complete or abandoned.
+    TransmitPendingKeys(String),
+
+    GrowSidebar,
+    ShrinkSidebar,
+    ToggleSidebar,
+    ToggleRowNumbers,
+    ToggleTableColumns,
+    /// Expands the focused pane to the whole terminal, or restores the layout if one already is.
+    ToggleZoom,
+    FindInTableData,
+    NewFindCharacter(char),
+    DeleteFindCharacter,
+    SubmitFind,
+    ExitFind,
+    FindNext,
+    FindPrev,
+    /// Scans the whole table for the find text, beyond the records loaded.
+    DeepFind,
+    /// `DeepFind` with the number of matches to stop at, typed before the key or configured.
+    DeepFindUpTo(usize),
+    StartDeepFind(String, String, usize),
+    TransmitDeepFindResult(String, DeepFindResult),
+    GotoRow,
+    NewGotoRowCharacter(char),
+    DeleteGotoRowCharacter,
+    SubmitGotoRow,
+    ExitGotoRow,
+    DefineComputedColumn,
+    NewComputedColumnCharacter(char),
+    DeleteComputedColumnCharacter,
+    SubmitComputedColumn,
+    ExitComputedColumn,
+    /// Writes the records that pass the filter to a file, or another export destination.
+    ExportResults,
+    NewExportCharacter(char),
+    DeleteExportCharacter,
+    SubmitExport,
+    ExitExport,
+    /// Writes every item of the table to a file, scanning it in the background.
+    DumpTable,
+    /// Dumps the table to the file at the path.
+    StartTableDump(String, String),
+    /// Moves the table on to hiding its soft-deleted items, showing only them, or showing all.
+    CycleSoftDeleteView,
+    /// The view of the table's soft-deleted items picked in the data pane.
+    SetSoftDeleteView(String, SoftDeleteView),
+    TransmitLayout(LayoutConfig),
+    TogglePrivacyMode,
+    /// Whether item values are masked on screen and in copies.
+    TransmitPrivacyMode(bool),
 
     SelectTableMode,
     SelectTablePrev,
     SelectTableNext,
     SelectTableScrollUp,
     SelectTableScrollDown,
+    SelectTableHalfPageUp,
+    SelectTableHalfPageDown,
+    SelectTablePageUp,
+    SelectTablePageDown,
+    /// Moves to the table in the same row of the previous column of the table list.
+    SelectTableColumnLeft,
+    SelectTableColumnRight,
     SelectTableFirst,
     SelectTableLast,
     SelectTable,
+    SelectTableAt(usize),
+    ScrollTables(i32),
     TransmitSelectedTable(String),
+    NextTab,
+    PrevTab,
+    CloseTab,
+    ActivateTab(String),
+    TabClosed(String),
 
     SelectDataMode,
     SelectTableDataRowPrev,
     SelectTableDataRowNext,
     SelectTableDataRowScrollUp,
     SelectTableDataRowScrollDown,
+    SelectTableDataRowHalfPageUp,
+    SelectTableDataRowHalfPageDown,
+    SelectTableDataRowPageUp,
+    SelectTableDataRowPageDown,
     SelectTableDataRowFirst,
     SelectTableDataRowLast,
+    SelectTableDataPrevPage,
+    SelectTableDataNextPage,
     SelectTableDataRow,
+    SelectTableDataRowAt(usize),
+    ScrollTableData(i32),
     TransmitTableData(Vec<String>, bool),
+    /// Why the query submitted from the query form failed.
+    TransmitQueryError(String),
     TransmitNextBatcTableData(Vec<String>, bool),
     FetchTableData(String),
     FetchMoreTableData(String),
+    /// Reads the whole table shown in several segments at once, rather than a page at a time.
+    ParallelScan,
+    StartParallelScan(String),
+    /// Keeps the records shown in their tab under a name, so selecting the table again opens a
+    /// new one. Renames the tab when its results are already pinned.
+    PinResults,
+    NewPinNameCharacter(char),
+    DeletePinNameCharacter,
+    SubmitPinName,
+    ExitPinName,
     LoadMoreTableData(String),
     ApproximateTableDataCount(i64),
     SelectTableDataRowCopyToClipboard,
+    CopyTableDataToClipboard,
+    TakeSnapshot,
+    TransmitSnapshotRecords(String, BTreeMap<String, String>),
+    NewSnapshotLabelCharacter(char),
+    DeleteSnapshotLabelCharacter,
+    SubmitSnapshotLabel,
+    ExitSnapshotLabel,
+    CompareSnapshots,
+    NewItemFromTemplate,
+    ShowItemTemplates(String),
+    SeedItemsFromTemplate,
+    ShowSeedTemplates(String),
+    ItemTemplateNext,
+    ItemTemplatePrev,
+    NewItemTemplateCharacter(char),
+    DeleteItemTemplateCharacter,
+    SubmitItemTemplate,
+    ExitItemTemplate,
+    /// The table to write to and the item, as JSON.
+    PutItem(String, String),
+    /// The table, an item whose generated placeholders are filled anew for every copy, and how
+    /// many copies to write.
+    SeedItems(String, String, usize),
+    TransmitPutItemResult(String, Option<String>),
+    /// Opens the item shown in the detail view in the item editor.
+    EditSelectedItem,
+    /// The table and the item to edit, as JSON.
+    EditItem(String, String),
+    /// The table, the item as JSON, and the names of its key attributes.
+    ShowItemEditor(String, String, Vec<String>),
+    /// The item being edited in the DynamoDB JSON format, to write it back with the types its
+    /// attributes have, or the reason they are not known.
+    TransmitItemTypes(String, Result<serde_json::Value, String>),
+    /// Opens the item editor on a new item of the table shown.
+    NewItem,
+    /// The table and its key attributes, which the new item starts out with.
+    ShowNewItemEditor(String, Vec<KeyAttribute>),
+    ItemEditorNext,
+    ItemEditorPrev,
+    EditItemAttribute,
+    AddItemAttribute,
+    RemoveItemAttribute,
+    /// The value of an attribute is being typed.
+    EditingItemAttribute,
+    NewItemEditorCharacter(char),
+    DeleteItemEditorCharacter,
+    SubmitItemAttribute,
+    CancelItemAttribute,
+    SaveItemEdit,
+    /// Writes the changed value asked about with the type it had.
+    KeepOldAttributeType,
+    /// Writes the changed value asked about with the type of its JSON.
+    TakeNewAttributeType,
+    ExitItemEditor,
+    /// The table to write to and the edited item, as JSON, which replaces the item with its key.
+    /// The item is written in the DynamoDB JSON format when given, with the types its attributes
+    /// were read with.
+    UpdateItem(String, String, Option<serde_json::Value>),
+    ShowSnapshotDiff(String),
+    SnapshotDiffPrevBase,
+    SnapshotDiffNextBase,
+    SnapshotDiffScrollDown,
+    SnapshotDiffScrollUp,
+    ExitSnapshotDiff,
     FilterTableData,
     QueryTableData,
     ExitQueryTableData,
@@ -46,17 +228,67 @@ pub enum Action {
     SubmitFilterDataText,
     NewFilterDataCharacter(char),
     ClearTableDataFilter,
-    TransmitTableDescription((Option<String>, Option<String>)),
+    /// Shows only the records whose attribute at the dotted path holds the value.
+    FilterTableDataByValue(String, serde_json::Value),
+    /// Filters the records by the partition key value of the selected one.
+    FilterTableDataBySelectedPartition,
+    TransmitTableDescription((Option<KeyAttribute>, Option<KeyAttribute>)),
     GetTableDescription(String),
     DeleteQueryDataCharacter,
     NewQueryDataCharacter(char),
     SubmitQueryDataText,
+    /// Queries the table shown for a partition key value and an optional sort key value, as if
+    /// typed into the query form.
+    RunQuery(String, Option<String>),
     ToggleQueryInputFocus,
-    GetTableQueryDataByPk(String, String, String),
-    GetTableQueryDataByPkSk(String, String, String, String, String),
+    /// Fills the sort key with the next preset time range, ending now.
+    CycleSortKeyRange,
+    NextKeySuggestion,
+    PrevKeySuggestion,
+    ToggleKeyBase64,
+    /// Keeps querying the partition on screen for items with a later sort key.
+    ToggleFollowQuery,
+    /// Queries the partition for the items after the sort key value, or for all of them without
+    /// one, to append them to the ones on screen.
+    FollowTableData(String, KeyAttribute, String, KeyAttribute, Option<String>),
+    TransmitFollowedTableData(Vec<String>),
+    InsertUuid,
+    InsertUlid,
+    InsertKsuid,
+    GetTableQueryDataByPk(String, KeyAttribute, String, Option<i32>),
+    ExplorePartition,
+    GetPartitionStats(String, KeyAttribute, String, Option<KeyAttribute>),
+    TransmitPartitionStats(PartitionStats),
+    ExitPartitionExplorer,
+    PartitionExplorerScrollDown,
+    PartitionExplorerScrollUp,
+    GetTableQueryDataByPkSk(
+        String,
+        KeyAttribute,
+        String,
+        KeyAttribute,
+        String,
+        Option<i32>,
+    ),
 
     FilteringTables,
+    /// Opens the list of regions to switch to.
     SelectingRegion,
+    RegionNext,
+    RegionPrev,
+    SwitchToSelectedRegion,
+    ExitRegions,
+    SwitchRegion(String),
+    /// The region every client is built for, once the switch has gone through.
+    TransmitRegion(String),
+    ViewProfiles,
+    ProfileNext,
+    ProfilePrev,
+    SwitchToSelectedProfile,
+    ExitProfiles,
+    SwitchProfile(String),
+    /// The profile every client is built with, once the switch has gone through.
+    TransmitProfile(String),
     EnterInsertMode,
     ExitInsertMode,
     NewCharacter(char),
@@ -64,9 +296,27 @@ pub enum Action {
     SubmitText,
     TransmitSubmittedText(String),
     FetchTables,
+    /// Drops the cached responses behind the focused pane and fetches them again.
+    Refresh,
     TransmitTables(Vec<String>),
-    StartLoading(String),
-    StopLoading,
+    /// The pane and label of every request the fetch worker has not finished, oldest first.
+    TransmitPendingRequests(Vec<(Pane, String)>),
+    ViewWorkspaces,
+    WorkspaceNext,
+    WorkspacePrev,
+    SwitchToSelectedWorkspace,
+    ExitWorkspaces,
+    /// Switches to the workspace, or back to the environment's profile and region if `None`.
+    SwitchWorkspace(Option<Workspace>),
+    /// The workspace every client is built for, once the switch has gone through.
+    TransmitWorkspace(Option<Workspace>),
+    SearchAllRegions,
+    TransmitTableSearch(Vec<RegionTables>),
+    NewTableSearchCharacter(char),
+    DeleteTableSearchCharacter,
+    TableSearchNext,
+    TableSearchPrev,
+    ExitTableSearch,
 
     ViewTableDataRowDetail,
     TransmitSelectedTableDataRow(String),
@@ -75,8 +325,230 @@ pub enum Action {
     ViewTableDataRowScrollRight,
     ViewTableDataRowScrollDown,
     ViewTableDataRowScrollUp,
+    ViewTableDataRowHalfPageUp,
+    ViewTableDataRowHalfPageDown,
+    ViewTableDataRowPageUp,
+    ViewTableDataRowPageDown,
+    ScrollTableDataRowDetail(i32),
     ViewTableDataRowNavigateDown,
     ViewTableDataRowNavigateUp,
     ViewTableDataRowCopyToClipboard,
+    /// Stashes the selected field, with its path, in the notes of the table.
+    ViewTableDataRowStash,
     ViewTableDataRowToggleNode,
+    /// Pins the selected field to the top of the detail view of every item in the table, or
+    /// unpins it.
+    ViewTableDataRowTogglePin,
+    /// Filters the records by the value of the selected field.
+    ViewTableDataRowFilterByValue,
+    /// Follows the selected field to the item it references, if a relation is configured for it.
+    ViewTableDataRowFollowReference,
+    /// Opens the table and queries it for the partition key value.
+    FollowReference(String, String),
+
+    TransmitCredentialsFailure(CredentialsFailure),
+    RetryCredentials,
+    StartSsoLogin,
+    LoginWithSso(String),
+    TransmitSsoDeviceAuthorization(String, String),
+    TransmitSsoLoginFailure(String),
+
+    TransmitAccessDenied(AccessDenied),
+    ExitAccessDenied,
+
+    FetchCallerIdentity,
+    TransmitCallerIdentity(String, String),
+    /// When the credentials stop working, `None` if they do not expire.
+    TransmitCredentialsExpiry(Option<SystemTime>),
+
+    ViewApiLog,
+    ExitApiLog,
+    ApiLogScrollDown,
+    ApiLogScrollUp,
+    TransmitApiCall(ApiCall),
+
+    CompareRegions,
+    /// The table and the key of the item to compare, as a JSON object.
+    ShowRegionComparison(String, String),
+    TransmitReplicaRegions(String, Vec<String>),
+    RegionCompareNext,
+    RegionComparePrev,
+    ToggleRegionCompareRegion,
+    SubmitRegionCompare,
+    /// Reads the item with the given key in each of the regions.
+    GetRegionItems(String, String, Vec<String>),
+    TransmitRegionItems(String, Vec<RegionItem>),
+    ExitRegionCompare,
+
+    ViewTableActivity,
+    /// Looks up the writes CloudTrail recorded to the table.
+    ShowTableActivity(String),
+    TransmitTableEvents(String, Result<Vec<TableEvent>, String>),
+    TableActivityScrollDown,
+    TableActivityScrollUp,
+    ExitTableActivity,
+
+    ProbeLatency,
+    /// Times calls to the table from the current region.
+    ShowLatencyProbe(String),
+    ProbeReplicaLatency,
+    RepeatLatencyProbe,
+    /// Times calls to the table from the current region, and from its replicas if `true`.
+    StartLatencyProbe(String, bool),
+    TransmitLatencies(String, Result<Vec<RegionLatency>, String>),
+    ExitLatencyProbe,
+
+    ViewTableInfo,
+    /// Describes the table highlighted in the tables pane.
+    ShowTableInfo(String),
+    TransmitTableInfo(String, Result<TableInfo, String>),
+    TableInfoScrollDown,
+    TableInfoScrollUp,
+    ExitTableInfo,
+
+    OpenScratchpad,
+    /// Opens the notes kept for the table.
+    ShowScratchpad(String),
+    NewScratchpadCharacter(char),
+    /// Inserts text pasted into the terminal into the notes.
+    PasteIntoScratchpad(String),
+    ScratchpadNewline,
+    DeleteScratchpadCharacter,
+    ScratchpadCursorLeft,
+    ScratchpadCursorRight,
+    ScratchpadCursorUp,
+    ScratchpadCursorDown,
+    /// Adds a snippet on a line of its own at the end of the notes of the table.
+    StashInScratchpad(String, String),
+    ExitScratchpad,
+
+    CopyKey,
+    /// Offers the key of the selected item, as its key attributes with their values, in each
+    /// format it can be copied in.
+    ShowKeyCopy(Vec<(KeyAttribute, serde_json::Value)>),
+    CopyKeyAsJson,
+    CopyKeyAsCliArgument,
+    CopyKeyAsPartiql,
+    ExitKeyCopy,
+
+    BookmarkItem,
+    /// Adds the bookmark, or removes it if its item is already bookmarked.
+    ToggleBookmark(Bookmark),
+    ViewBookmarks,
+    BookmarkNext,
+    BookmarkPrev,
+    JumpToSelectedBookmark,
+    RefreshSelectedBookmark,
+    DeleteSelectedBookmark,
+    RefreshItem,
+    /// Writes the selected item to a file that is kept up to date while browsing on.
+    TearOutItem,
+    ExportBookmarks,
+    ExitBookmarks,
+    /// Selects the item of the bookmark in the data pane, opening its table if needed.
+    JumpToBookmark(Bookmark),
+    /// Reads the latest version of the item in the table with the key, as a JSON object.
+    GetItem(String, String),
+    /// The table and key an item was read with, and the item or the reason it could not be read.
+    TransmitItem(String, String, Result<Option<String>, String>),
+    TransmitPermissions(Permissions),
+
+    CopyTable,
+    ShowTableCopy(String),
+    NewTableCopyCharacter(char),
+    DeleteTableCopyCharacter,
+    NextTableCopyField,
+    SubmitTableCopy,
+    ExitTableCopy,
+    /// Copies every item of the table to the target, as a job.
+    StartTableCopy(String, CopyTarget),
+    /// What an operation will do, and what it is expected to cost if the table could be
+    /// described.
+    ShowCostEstimate(String, Option<(CostEstimate, Billing)>),
+    ConfirmPlannedOperation,
+    CancelPlannedOperation,
+
+    ImportFile,
+    ShowImport(String),
+    NewImportCharacter(char),
+    DeleteImportCharacter,
+    SubmitImportPath,
+    /// Compares the items of the file at the path with the items of the table.
+    PreviewImport(String, String),
+    /// What importing the file would do, or the reason it could not be compared.
+    TransmitImportPreview(Result<ImportPreview, String>),
+    CycleImportMode,
+    ConfirmImport,
+    /// Writes the previewed items that differ from the ones in the table.
+    StartImport(ImportMode),
+    ExitImport,
+
+    ViewJobs,
+    ExitJobs,
+    JobsScrollDown,
+    JobsScrollUp,
+    CancelSelectedJob,
+    CancelJob(RequestId),
+    TransmitJobs(Vec<Job>),
+
+    /// Asks what becomes of the jobs still writing items before quitting.
+    ConfirmQuit,
+    QuitWhenJobsFinish,
+    CancelJobsAndQuit,
+    /// Quits, leaving the jobs to finish once the terminal is restored.
+    DetachJobs,
+    ExitQuitConfirm,
+}
+
+impl Action {
+    /// Whether a count typed before the key, as in `10j`, sends the action that many times.
+    pub fn is_repeatable(&self) -> bool {
+        matches!(
+            self,
+            Action::GrowSidebar
+                | Action::ShrinkSidebar
+                | Action::FindNext
+                | Action::FindPrev
+                | Action::SelectTablePrev
+                | Action::SelectTableNext
+                | Action::SelectTableScrollUp
+                | Action::SelectTableScrollDown
+                | Action::SelectTableHalfPageUp
+                | Action::SelectTableHalfPageDown
+                | Action::SelectTablePageUp
+                | Action::SelectTablePageDown
+                | Action::SelectTableColumnLeft
+                | Action::SelectTableColumnRight
+                | Action::SelectTableDataRowPrev
+                | Action::SelectTableDataRowNext
+                | Action::SelectTableDataRowScrollUp
+                | Action::SelectTableDataRowScrollDown
+                | Action::SelectTableDataRowHalfPageUp
+                | Action::SelectTableDataRowHalfPageDown
+                | Action::SelectTableDataRowPageUp
+                | Action::SelectTableDataRowPageDown
+                | Action::SelectTableDataPrevPage
+                | Action::SelectTableDataNextPage
+                | Action::SnapshotDiffScrollDown
+                | Action::SnapshotDiffScrollUp
+                | Action::PartitionExplorerScrollDown
+                | Action::PartitionExplorerScrollUp
+                | Action::TableActivityScrollDown
+                | Action::TableActivityScrollUp
+                | Action::ViewTableDataRowScrollLeft
+                | Action::ViewTableDataRowScrollRight
+                | Action::ViewTableDataRowScrollDown
+                | Action::ViewTableDataRowScrollUp
+                | Action::ViewTableDataRowHalfPageUp
+                | Action::ViewTableDataRowHalfPageDown
+                | Action::ViewTableDataRowPageUp
+                | Action::ViewTableDataRowPageDown
+                | Action::ViewTableDataRowNavigateDown
+                | Action::ViewTableDataRowNavigateUp
+                | Action::ApiLogScrollDown
+                | Action::ApiLogScrollUp
+                | Action::JobsScrollDown
+                | Action::JobsScrollUp
+        )
+    }
 }
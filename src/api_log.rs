@@ -0,0 +1,94 @@
+use std::{
+    fmt::Display,
+    time::{Instant, SystemTime},
+};
+
+use aws_sdk_dynamodb::{
+    error::ProvideErrorMetadata,
+    operation::RequestId,
+    primitives::{DateTime, DateTimeFormat},
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use crate::data::FetchResponse;
+
+/// One DynamoDB request issued by the fetch worker.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiCall {
+    pub timestamp: String,
+    pub operation: String,
+    pub table: Option<String>,
+    pub duration_ms: u128,
+    /// Capacity units reported by DynamoDB, formatted for display.
+    pub consumed_capacity: Option<String>,
+    pub items: Option<usize>,
+    pub error: Option<String>,
+    /// The ID AWS gave the request, when it was answered.
+    pub request_id: Option<String>,
+}
+
+/// Reports every call to the UI through the fetch response channel.
+#[derive(Clone)]
+pub struct ApiLog {
+    response_tx: mpsc::Sender<FetchResponse>,
+}
+
+impl ApiLog {
+    pub fn new(response_tx: mpsc::Sender<FetchResponse>) -> Self {
+        Self { response_tx }
+    }
+
+    /// Records the outcome of a call that started at `started`. `summarize` extracts the consumed
+    /// capacity and the number of items from a successful response.
+    pub async fn record<T, E, F>(
+        &self,
+        operation: &str,
+        table: Option<&str>,
+        started: Instant,
+        result: &Result<T, E>,
+        summarize: F,
+    ) where
+        T: RequestId,
+        E: ProvideErrorMetadata + RequestId + Display,
+        F: FnOnce(&T) -> (Option<f64>, Option<usize>),
+    {
+        let (consumed_capacity, items, error) = match result {
+            Ok(output) => {
+                let (capacity, items) = summarize(output);
+                (capacity.map(|units| format!("{units:.1}")), items, None)
+            }
+            Err(err) => {
+                let error = match (err.code(), err.message()) {
+                    (Some(code), Some(message)) => format!("{code}: {message}"),
+                    (Some(code), None) => code.to_string(),
+                    _ => err.to_string(),
+                };
+                (None, None, Some(error))
+            }
+        };
+
+        let request_id = result.request_id().map(str::to_string);
+        if let Some(error) = &error {
+            warn!(operation, table, request_id, error, "request failed");
+        } else {
+            debug!(operation, table, request_id, "request answered");
+        }
+
+        let call = ApiCall {
+            timestamp: DateTime::from(SystemTime::now())
+                .fmt(DateTimeFormat::DateTime)
+                .unwrap_or_default(),
+            operation: operation.to_string(),
+            table: table.map(str::to_string),
+            duration_ms: started.elapsed().as_millis(),
+            consumed_capacity,
+            items,
+            error,
+            request_id,
+        };
+
+        let _ = self.response_tx.send(FetchResponse::ApiCall(call)).await;
+    }
+}
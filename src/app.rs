@@ -1,22 +1,75 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime},
+};
 
 use aws_sdk_dynamodb::types::AttributeValue;
 use color_eyre::Result;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::prelude::Rect;
 use serde::{Deserialize, Serialize};
-use tokio::sync::mpsc;
-use tracing::{debug, info};
+use serde_json::{Map, Value};
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tracing::{debug, error, info};
 
 use crate::{
     action::Action,
+    cache::TtlCache,
     components::{
-        collections_box::CollectionsBox, data_box::DataBox, data_detail_box::DataDetailBox,
-        filter_input::FilterInput, loading::LoadingBox, region_box::AWSRegionBox, Component,
+        access_denied_box::AccessDeniedBox,
+        api_log_box::ApiLogBox,
+        bookmark_box::BookmarkBox,
+        collections_box::CollectionsBox,
+        cost_confirm_box::CostConfirmBox,
+        credentials_error_box::CredentialsErrorBox,
+        data_box::DataBox,
+        data_detail_box::DataDetailBox,
+        debug_overlay::{DebugOverlay, FrameStats},
+        error_banner::ErrorBanner,
+        filter_input::FilterInput,
+        import_box::ImportBox,
+        item_editor_box::ItemEditorBox,
+        item_template_box::ItemTemplateBox,
+        jobs_box::JobsBox,
+        key_copy_box::KeyCopyBox,
+        latency_box::LatencyBox,
+        loading::LoadingBox,
+        mode_indicator::ModeIndicator,
+        partition_explorer_box::PartitionExplorerBox,
+        profile_box::ProfileBox,
+        quit_confirm_box::QuitConfirmBox,
+        region_box::AWSRegionBox,
+        region_compare_box::RegionCompareBox,
+        scratchpad_box::ScratchpadBox,
+        snapshot_box::SnapshotBox,
+        table_activity_box::TableActivityBox,
+        table_copy_box::TableCopyBox,
+        table_info_box::TableInfoBox,
+        table_search_box::TableSearchBox,
+        workspace_box::WorkspaceBox,
+        Component,
     },
-    config::Config,
-    data::{FetchRequest, FetchResponse},
+    config::{is_chord_prefix, key_event_to_string, Config, ZoomedPane},
+    copy::CopyTarget,
+    cost::{CostEstimate, TableUsage},
+    credentials::remaining,
+    data::{key_typed_item, FetchRequest, FetchResponse, KeyAttribute, KeyQuery, RequestId},
+    import::{ImportMode, ImportStatus},
+    job::Job,
+    metrics::Metrics,
+    permission::Permissions,
+    schema,
+    script::Command,
+    session::{RecordedResponse, SessionEvent, SessionRecorder},
+    soft_delete::{self, SoftDeleteFilter, SoftDeleteView},
+    table_search::DYNAMODB_REGIONS,
+    template::fill_generated,
+    time_range::after,
     tui::{Event, Tui},
+    typed_json,
+    util::dynamodb_to_json,
 };
 
 pub struct App {
@@ -27,12 +80,213 @@ pub struct App {
     should_quit: bool,
     should_suspend: bool,
     mode: Mode,
-    last_tick_key_events: Vec<KeyEvent>,
+    /// The mode to return to when the API log popup is closed.
+    mode_before_api_log: Mode,
+    mode_before_access_denied: Mode,
+    /// The mode to go back to once the item editor is closed.
+    mode_before_item_editor: Mode,
+    mode_before_jobs: Mode,
+    mode_before_quit: Mode,
+    /// The keys typed so far of a multi-key binding, and when the last one was.
+    pending_keys: Vec<KeyEvent>,
+    pending_keys_at: Instant,
+    /// When the credentials were last resolved, to keep them from expiring while idle.
+    credentials_refreshed_at: Instant,
+    /// When the credentials stop working, if they expire, and the countdown to it last drawn.
+    credentials_expiry: Option<SystemTime>,
+    countdown: Option<String>,
+    /// The count typed before a key, as in `10j`, to repeat the action it is bound to.
+    count: Option<usize>,
     action_tx: mpsc::UnboundedSender<Action>,
     action_rx: mpsc::UnboundedReceiver<Action>,
-    fetch_tx: mpsc::Sender<FetchRequest>,
+    fetch_tx: mpsc::Sender<(RequestId, FetchRequest)>,
     fetch_rx: mpsc::Receiver<FetchResponse>,
+    /// Pagination state of every table open in a tab, keyed by table name.
+    cursors: HashMap<String, TableCursor>,
+    /// The table of the tab that is currently shown.
+    active_table: Option<String>,
+    next_request_id: RequestId,
+    /// Requests the fetch worker has not finished yet, oldest first.
+    pending_requests: Vec<PendingRequest>,
+    /// Running jobs and the most recently finished ones, oldest first.
+    jobs: Vec<Job>,
+    /// A costly operation waiting for its estimate to be confirmed.
+    planned: Option<PlannedOperation>,
+    /// The table and the items of the import being previewed, with what writing each would do.
+    pending_import: Option<(String, Vec<Value>, Vec<ImportStatus>)>,
+    tables_cache: TtlCache<(), Vec<String>>,
+    item_counts_cache: TtlCache<String, i64>,
+    descriptions_cache: TtlCache<String, (Option<KeyAttribute>, Option<KeyAttribute>)>,
+    permissions_cache: TtlCache<String, Permissions>,
+    /// The scan pages fetched for each table, in order, so a table that is selected again is
+    /// restored without scanning it.
+    scan_pages: TtlCache<String, Vec<ScanPage>>,
+    /// Set by anything that may change what is on screen; frames are skipped while it is clear.
+    needs_render: bool,
+    /// Whether the debug overlay is shown and frame statistics are collected.
+    fps_debug: bool,
+    frame_stats: FrameStats,
+    frames_this_second: usize,
+    second_started_at: Instant,
+    actions_this_tick: usize,
+    /// Where the session is being recorded, with `--record`.
+    recorder: Option<SessionRecorder>,
+    /// The key presses and resizes of a recorded session, with `--replay`.
+    replayed_events: Option<mpsc::UnboundedReceiver<Event>>,
+    /// The jobs that were writing items when quitting was first asked, reported on exit.
+    jobs_at_quit: Vec<RequestId>,
+    quit_when_jobs_finish: bool,
+    /// Set when quitting with jobs detached, so they are waited on after the terminal is restored.
+    detached: bool,
+    metrics: Metrics,
+    /// Where the metrics are written on exit, with `--metrics-file`.
+    metrics_file: Option<PathBuf>,
+    /// The commands given with `--cmd` that are still to run.
+    commands: VecDeque<Command>,
+    /// The indicator last shown, to change the cursor and the label only when it changes.
+    mode_indicator: Option<&'static str>,
+}
+
+/// Counts typed before a key are capped at this, so a stray run of digits cannot flood the
+/// action channel.
+const MAX_COUNT: usize = 999;
+
+/// Finished jobs beyond this many are dropped from the jobs popup, oldest first.
+const MAX_FINISHED_JOBS: usize = 50;
+
+/// Table-wide operations, which are only started once their cost estimate has been confirmed.
+enum PlannedOperation {
+    Copy(String, CopyTarget),
+    Seed(String, Value, usize),
+    /// The table, the file and the size of the pages it is scanned in.
+    Dump(String, PathBuf, i32),
+    /// The table, the number of segments, the number of items the scan stops at and the size of
+    /// its pages.
+    ParallelScan(String, i32, usize, i32),
+    /// The table, the text and the number of matches the scan stops at, unless it is zero.
+    DeepFind(String, String, usize),
+    Import(String, Vec<Value>, ImportMode),
+}
+
+impl PlannedOperation {
+    fn table(&self) -> &str {
+        match self {
+            PlannedOperation::Copy(table, _)
+            | PlannedOperation::Seed(table, ..)
+            | PlannedOperation::Dump(table, ..)
+            | PlannedOperation::ParallelScan(table, ..)
+            | PlannedOperation::DeepFind(table, ..)
+            | PlannedOperation::Import(table, ..) => table,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            PlannedOperation::Copy(source, target) => format!("Copy {source} to {}", target.table),
+            PlannedOperation::Seed(table, _, count) => format!("Seed {count} items into {table}"),
+            PlannedOperation::Dump(table, path, _) => {
+                format!("Dump {table} to {}", path.display())
+            }
+            PlannedOperation::ParallelScan(table, segments, ..) => {
+                format!("Scan {table} in {segments} segments")
+            }
+            PlannedOperation::DeepFind(table, needle, _) => {
+                format!("Scan {table} for \"{needle}\"")
+            }
+            PlannedOperation::Import(table, items, mode) => {
+                format!(
+                    "Import {} items into {table} ({})",
+                    items.len(),
+                    mode.label()
+                )
+            }
+        }
+    }
+
+    fn estimate(&self, usage: &TableUsage) -> CostEstimate {
+        match self {
+            // Writes to a local endpoint are free, so only the scan is counted.
+            PlannedOperation::Copy(_, target) => {
+                CostEstimate::scan(usage, target.endpoint_url.is_none())
+            }
+            PlannedOperation::Seed(_, item, count) => {
+                CostEstimate::writes(*count as u64, fill_generated(item).to_string().len())
+            }
+            // A deep find may stop at its last match, so a whole scan is the most it reads.
+            PlannedOperation::Dump(..) | PlannedOperation::DeepFind(..) => {
+                CostEstimate::scan(usage, false)
+            }
+            PlannedOperation::ParallelScan(_, _, max_items, _) => {
+                CostEstimate::scan_up_to(usage, *max_items as u64)
+            }
+            PlannedOperation::Import(_, items, _) => {
+                let bytes: usize = items.iter().map(|item| item.to_string().len()).sum();
+                CostEstimate::writes(items.len() as u64, bytes / items.len().max(1))
+            }
+        }
+    }
+
+    /// Whether the operation is followed in the jobs popup once started, rather than in the
+    /// table it reads.
+    fn shows_jobs(&self) -> bool {
+        matches!(
+            self,
+            PlannedOperation::Copy(..) | PlannedOperation::Seed(..) | PlannedOperation::Import(..)
+        )
+    }
+
+    fn into_request(self) -> FetchRequest {
+        match self {
+            PlannedOperation::Copy(source, target) => FetchRequest::CopyTable(source, target),
+            PlannedOperation::Seed(table, item, count) => {
+                FetchRequest::SeedItems(table, item, count)
+            }
+            PlannedOperation::Dump(table, path, page_size) => {
+                FetchRequest::DumpTable(table, path, page_size)
+            }
+            PlannedOperation::ParallelScan(table, segments, max_items, page_size) => {
+                FetchRequest::ParallelScan(table, segments, max_items, page_size)
+            }
+            PlannedOperation::DeepFind(table, needle, max_matches) => {
+                FetchRequest::DeepFind(table, needle, max_matches)
+            }
+            PlannedOperation::Import(table, items, mode) => {
+                FetchRequest::ImportItems(table, items, mode)
+            }
+        }
+    }
+}
+
+struct PendingRequest {
+    id: RequestId,
+    pane: Pane,
+    label: &'static str,
+}
+
+#[derive(Default)]
+struct TableCursor {
     last_evaluated_key: Option<HashMap<String, AttributeValue>>,
+    /// The query that produced the records on screen, if they did not come from a scan.
+    current_query: Option<KeyQuery>,
+    /// The request of the parallel scan that produced the records on screen. Its pages have no
+    /// key to go on from, so no more are loaded while it runs.
+    parallel_scan: Option<RequestId>,
+}
+
+/// A page returned by a scan. Each page starts at the `last_evaluated_key` of the one before it.
+#[derive(Clone)]
+struct ScanPage {
+    records: Vec<String>,
+    has_more: bool,
+    last_evaluated_key: Option<HashMap<String, AttributeValue>>,
+}
+
+/// The part of the screen that waits on a request and shows its loading indicator.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Pane {
+    Tables,
+    Data,
+    Global,
 }
 
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -45,18 +299,95 @@ pub enum Mode {
     SelectTable,
     SelectTableDataRow,
     ViewTableDataRowDetail,
+    CredentialsError,
+    ApiLog,
+    AccessDenied,
+    PartitionExplorer,
+    SnapshotLabel,
+    SnapshotDiff,
+    ItemTemplate,
+    ItemEditor,
+    /// Typing the value of an attribute in the item editor.
+    ItemEditorInput,
+    Jobs,
+    RegionCompare,
+    TableActivity,
+    LatencyProbe,
+    TableInfo,
+    /// Writing in the notes of a table.
+    Scratchpad,
+    KeyCopy,
+    TableCopy,
+    /// Typing the path of the file to import.
+    Import,
+    ImportPreview,
+    ConfirmCost,
+    TableSearch,
+    Workspaces,
+    Regions,
+    Profiles,
+    GotoRow,
+    /// Typing the name of the result set being pinned.
+    PinName,
+    /// Typing a computed column of the data pane.
+    ComputedColumn,
+    /// Typing where the records of the data pane are exported to.
+    ExportResults,
+    /// Typing the text to find in the data pane.
+    FindData,
+    /// Moving between the records that were found. Keys not bound here act as in
+    /// `SelectTableDataRow`.
+    FindMatches,
+    Bookmarks,
+    QuitConfirm,
+}
+
+impl Mode {
+    /// What keys type in the modes where they type text rather than trigger actions.
+    fn indicator(&self) -> Option<&'static str> {
+        match self {
+            Mode::FilterData => Some("FILTER"),
+            Mode::QueryData => Some("QUERY"),
+            Mode::FindData => Some("FIND"),
+            Mode::Insert
+            | Mode::SnapshotLabel
+            | Mode::ItemTemplate
+            | Mode::ItemEditorInput
+            | Mode::TableCopy
+            | Mode::Import
+            | Mode::TableSearch
+            | Mode::GotoRow
+            | Mode::PinName
+            | Mode::ComputedColumn
+            | Mode::ExportResults
+            | Mode::Scratchpad => Some("INSERT"),
+            _ => None,
+        }
+    }
+
+    /// The pane the mode moves around in, for the modes that focus one.
+    fn pane(&self) -> Option<ZoomedPane> {
+        match self {
+            Mode::SelectTable => Some(ZoomedPane::Tables),
+            Mode::SelectTableDataRow | Mode::FindMatches => Some(ZoomedPane::Data),
+            Mode::ViewTableDataRowDetail => Some(ZoomedPane::Detail),
+            _ => None,
+        }
+    }
 }
 
 impl App {
     pub fn new(
+        config: Config,
         tick_rate: f64,
         frame_rate: f64,
-        fetch_tx: mpsc::Sender<FetchRequest>,
+        fetch_tx: mpsc::Sender<(RequestId, FetchRequest)>,
         fetch_rx: mpsc::Receiver<FetchResponse>,
     ) -> Result<Self> {
         let (action_tx, action_rx) = mpsc::unbounded_channel();
         let region = "us-east-1";
         let filter_collections_title = "Filter Tables";
+        let cache_ttl = Duration::from_secs(config.config.cache_ttl_secs);
 
         Ok(Self {
             tick_rate,
@@ -68,23 +399,110 @@ impl App {
                 Box::new(FilterInput::new(filter_collections_title)),
                 Box::new(LoadingBox::new()),
                 Box::new(DataDetailBox::new()),
+                Box::new(ApiLogBox::new()),
+                Box::new(PartitionExplorerBox::new()),
+                Box::new(SnapshotBox::new()),
+                Box::new(ItemTemplateBox::new()),
+                Box::new(ItemEditorBox::new()),
+                Box::new(RegionCompareBox::new()),
+                Box::new(TableActivityBox::new()),
+                Box::new(LatencyBox::new()),
+                Box::new(TableInfoBox::new()),
+                Box::new(ScratchpadBox::new()),
+                Box::new(KeyCopyBox::new()),
+                Box::new(BookmarkBox::new()),
+                Box::new(TableCopyBox::new()),
+                Box::new(ImportBox::new()),
+                Box::new(CostConfirmBox::new()),
+                Box::new(QuitConfirmBox::new()),
+                Box::new(TableSearchBox::new()),
+                Box::new(WorkspaceBox::new()),
+                Box::new(ProfileBox::new()),
+                Box::new(JobsBox::new()),
+                Box::new(CredentialsErrorBox::new()),
+                Box::new(DebugOverlay::new()),
+                Box::new(ModeIndicator::new()),
+                Box::new(AccessDeniedBox::new()),
+                Box::new(ErrorBanner::new()),
             ],
             should_quit: false,
             should_suspend: false,
-            config: Config::new()?,
+            config,
             mode: Mode::View,
-            last_tick_key_events: Vec::new(),
+            mode_before_api_log: Mode::View,
+            mode_before_access_denied: Mode::View,
+            mode_before_item_editor: Mode::View,
+            mode_before_jobs: Mode::View,
+            mode_before_quit: Mode::View,
+            pending_keys: Vec::new(),
+            pending_keys_at: Instant::now(),
+            credentials_refreshed_at: Instant::now(),
+            credentials_expiry: None,
+            countdown: None,
+            count: None,
             action_tx,
             action_rx,
             fetch_rx,
             fetch_tx,
-            last_evaluated_key: None,
+            cursors: HashMap::new(),
+            active_table: None,
+            next_request_id: 0,
+            pending_requests: Vec::new(),
+            jobs: Vec::new(),
+            planned: None,
+            pending_import: None,
+            tables_cache: TtlCache::new(cache_ttl),
+            item_counts_cache: TtlCache::new(cache_ttl),
+            descriptions_cache: TtlCache::new(cache_ttl),
+            permissions_cache: TtlCache::new(cache_ttl),
+            scan_pages: TtlCache::new(cache_ttl),
+            needs_render: true,
+            fps_debug: false,
+            frame_stats: FrameStats::default(),
+            frames_this_second: 0,
+            second_started_at: Instant::now(),
+            actions_this_tick: 0,
+            recorder: None,
+            replayed_events: None,
+            jobs_at_quit: Vec::new(),
+            quit_when_jobs_finish: false,
+            detached: false,
+            metrics: Metrics::default(),
+            commands: VecDeque::new(),
+            metrics_file: None,
+            mode_indicator: None,
         })
     }
 
+    /// Shows the debug overlay from the start.
+    pub fn fps_debug(mut self, fps_debug: bool) -> Self {
+        self.fps_debug = fps_debug;
+        self
+    }
+
+    pub fn record(mut self, recorder: Option<SessionRecorder>) -> Self {
+        self.recorder = recorder;
+        self
+    }
+
+    pub fn replay(mut self, replayed_events: Option<mpsc::UnboundedReceiver<Event>>) -> Self {
+        self.replayed_events = replayed_events;
+        self
+    }
+
+    pub fn metrics_file(mut self, metrics_file: Option<PathBuf>) -> Self {
+        self.metrics_file = metrics_file;
+        self
+    }
+
+    pub fn commands(mut self, commands: Vec<Command>) -> Self {
+        self.commands = commands.into();
+        self
+    }
+
     pub async fn run(&mut self) -> Result<()> {
         let mut tui = Tui::new()?
-            // .mouse(true) // uncomment this line to enable mouse support
+            .mouse(true)
             .tick_rate(self.tick_rate)
             .frame_rate(self.frame_rate);
         tui.enter()?;
@@ -100,68 +518,155 @@ impl App {
         }
 
         self.action_tx.send(Action::SelectTableMode)?;
+        self.action_tx.send(Action::FetchCallerIdentity)?;
+        // Tables start out hiding their soft-deleted items, even from the first scan.
+        for soft_delete in self.config.config.soft_delete.clone() {
+            if soft_delete.server_side {
+                self.send_request(FetchRequest::FilterSoftDeleted(
+                    soft_delete.table,
+                    Some(SoftDeleteFilter {
+                        attribute: soft_delete.attribute,
+                        view: SoftDeleteView::default(),
+                    }),
+                ))?;
+            }
+        }
+        if self.fps_debug {
+            // Components start with the overlay hidden; toggling shows it everywhere at once.
+            self.fps_debug = false;
+            self.action_tx.send(Action::ToggleDebugOverlay)?;
+        }
 
         let action_tx = self.action_tx.clone();
         loop {
-            while let Ok(response) = self.fetch_rx.try_recv() {
-                match response {
-                    FetchResponse::Tables(tables) => {
-                        self.action_tx.send(Action::TransmitTables(tables))?;
-                        self.action_tx.send(Action::Render)?;
-                        self.action_tx.send(Action::StopLoading)?;
-                    }
-                    FetchResponse::TableData(data, has_more, last_evaluated_key) => {
-                        self.last_evaluated_key = last_evaluated_key;
-                        self.action_tx
-                            .send(Action::TransmitTableData(data, has_more))?;
-                        self.action_tx.send(Action::SelectDataMode)?;
-                        self.action_tx.send(Action::Render)?;
-                    }
-                    FetchResponse::NextBatchTableData(data, has_more, last_evaluated_key) => {
-                        self.last_evaluated_key = last_evaluated_key;
-                        self.action_tx
-                            .send(Action::TransmitNextBatcTableData(data, has_more))?;
-                        self.action_tx.send(Action::Render)?;
-                    }
-                    FetchResponse::ApproximateTableDataCount(count) => {
-                        self.action_tx
-                            .send(Action::ApproximateTableDataCount(count))?;
-                    }
-                    FetchResponse::TableDescription(description) => self
-                        .action_tx
-                        .send(Action::TransmitTableDescription(description))?,
+            // Wake up for whichever comes first so fetch responses are shown without waiting for
+            // the next tick.
+            tokio::select! {
+                event = tui.next_event() => self.handle_events(event)?,
+                Some(response) = self.fetch_rx.recv() => self.handle_fetch_response(response)?,
+                Some(event) = next_replayed_event(&mut self.replayed_events) => {
+                    self.handle_events(Some(event))?
                 }
             }
-
-            self.handle_events(&mut tui).await?;
             self.handle_actions(&mut tui)?;
+            self.update_mode_indicator(&tui)?;
+            self.update_zoom()?;
             if self.should_suspend {
                 tui.suspend()?;
                 action_tx.send(Action::Resume)?;
                 action_tx.send(Action::ClearScreen)?;
-                // tui.mouse(true);
                 tui.enter()?;
+                // Leaving the terminal restored its own cursor.
+                self.mode_indicator = None;
             } else if self.should_quit {
                 tui.stop()?;
                 break;
             }
         }
         tui.exit()?;
+        self.finish_jobs_at_quit().await?;
+        self.write_metrics()
+    }
+
+    fn write_metrics(&mut self) -> Result<()> {
+        let Some(path) = &self.metrics_file else {
+            return Ok(());
+        };
+
+        for lookups in [
+            self.tables_cache.lookups(),
+            self.item_counts_cache.lookups(),
+            self.descriptions_cache.lookups(),
+            self.permissions_cache.lookups(),
+            self.scan_pages.lookups(),
+        ] {
+            self.metrics.record_cache_lookups(lookups);
+        }
+        fs::write(path, self.metrics.render())?;
         Ok(())
     }
 
-    async fn handle_events(&mut self, tui: &mut Tui) -> Result<()> {
-        let Some(event) = tui.next_event().await else {
+    /// Waits for detached jobs, then prints how each job that was writing items at quit ended,
+    /// so none of them is left half applied without a word.
+    async fn finish_jobs_at_quit(&mut self) -> Result<()> {
+        if self.detached && self.writing_jobs_running() {
+            println!("Waiting for the detached jobs to finish. Press Ctrl-c to stop them.");
+            while self.writing_jobs_running() {
+                let Some(response) = self.fetch_rx.recv().await else {
+                    break;
+                };
+                if let FetchResponse::JobProgress(..)
+                | FetchResponse::JobPageProgress(..)
+                | FetchResponse::JobFinished(..) = response
+                {
+                    self.handle_fetch_response(response)?;
+                }
+            }
+        }
+
+        for job in self
+            .jobs
+            .iter()
+            .filter(|job| self.jobs_at_quit.contains(&job.id))
+        {
+            println!("{}", job.summary());
+        }
+        Ok(())
+    }
+
+    fn writing_jobs_running(&self) -> bool {
+        self.jobs.iter().any(|job| job.is_running() && job.writes)
+    }
+
+    /// Quits at once unless jobs are writing items, in which case it is first asked what becomes
+    /// of them. Asked again, it quits anyway.
+    fn quit(&mut self) -> Result<()> {
+        if self.mode == Mode::QuitConfirm || !self.writing_jobs_running() {
+            self.should_quit = true;
+            return Ok(());
+        }
+
+        self.jobs_at_quit = self
+            .jobs
+            .iter()
+            .filter(|job| job.is_running() && job.writes)
+            .map(|job| job.id)
+            .collect();
+        self.mode_before_quit = self.mode;
+        self.mode = Mode::QuitConfirm;
+        self.action_tx.send(Action::ConfirmQuit)?;
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Option<Event>) -> Result<()> {
+        let Some(event) = event else {
             return Ok(());
         };
+        match event {
+            Event::Key(key) => self.record_event(SessionEvent::Key(key))?,
+            Event::Resize(x, y) => self.record_event(SessionEvent::Resize(x, y))?,
+            _ => {}
+        }
         let action_tx = self.action_tx.clone();
         match event {
             Event::Quit => action_tx.send(Action::Quit)?,
-            Event::Tick => action_tx.send(Action::Tick)?,
+            // Spinners only move on ticks, so they need a frame while requests are in flight.
+            Event::Tick => {
+                self.needs_render |= !self.pending_requests.is_empty();
+                self.needs_render |= self.countdown_changed();
+                action_tx.send(Action::Tick)?
+            }
             Event::Render => action_tx.send(Action::Render)?,
             Event::Resize(x, y) => action_tx.send(Action::Resize(x, y))?,
             Event::Key(key) => self.handle_key_event(key)?,
-            _ => {}
+            // Only the notes take pasted text, which can hold line breaks.
+            Event::Paste(ref text) if self.mode == Mode::Scratchpad => {
+                action_tx.send(Action::PasteIntoScratchpad(text.clone()))?
+            }
+            Event::Mouse(_) if !self.accepts_mouse_events() => return Ok(()),
+            // Components turn relevant mouse events into actions, which mark the frame dirty.
+            Event::Mouse(_) => {}
+            _ => self.needs_render = true,
         }
         for component in self.components.iter_mut() {
             if let Some(action) = component.handle_events(Some(event.clone()))? {
@@ -171,71 +676,96 @@ impl App {
         Ok(())
     }
 
-    fn handle_key_event(&mut self, key: KeyEvent) -> Result<()> {
-        let action_tx = self.action_tx.clone();
+    /// Mouse events are ignored while text is being typed or a blocking popup is open, so a stray
+    /// click cannot change the selection underneath it.
+    fn accepts_mouse_events(&self) -> bool {
+        matches!(
+            self.mode,
+            Mode::View
+                | Mode::SelectTable
+                | Mode::SelectTableDataRow
+                | Mode::ViewTableDataRowDetail
+        )
+    }
 
+    fn handle_key_event(&mut self, key: KeyEvent) -> Result<()> {
         match self.mode {
-            Mode::QueryData => {
+            Mode::QueryData => self.handle_typed_key(key, Action::NewQueryDataCharacter),
+            Mode::ItemTemplate => self.handle_typed_key(key, Action::NewItemTemplateCharacter),
+            Mode::ItemEditorInput => self.handle_typed_key(key, Action::NewItemEditorCharacter),
+            Mode::TableCopy => self.handle_typed_key(key, Action::NewTableCopyCharacter),
+            Mode::Import => self.handle_typed_key(key, Action::NewImportCharacter),
+            Mode::FindData => self.handle_typed_key(key, Action::NewFindCharacter),
+            Mode::Scratchpad => self.handle_typed_key(key, Action::NewScratchpadCharacter),
+            Mode::GotoRow => self.handle_typed_key(key, Action::NewGotoRowCharacter),
+            Mode::PinName => self.handle_typed_key(key, Action::NewPinNameCharacter),
+            Mode::ComputedColumn => self.handle_typed_key(key, Action::NewComputedColumnCharacter),
+            Mode::ExportResults => self.handle_typed_key(key, Action::NewExportCharacter),
+            Mode::TableSearch => self.handle_typed_key(key, Action::NewTableSearchCharacter),
+            Mode::SnapshotLabel => self.handle_typed_key(key, Action::NewSnapshotLabelCharacter),
+            Mode::FilterData => self.handle_typed_key(key, Action::NewFilterDataCharacter),
+            Mode::Insert => self.handle_typed_key(key, Action::NewCharacter),
+            _ => {
                 let Some(keymap) = self.config.keybindings.get(&self.mode) else {
                     return Ok(());
                 };
-
-                if let Some(action) = keymap.get(&vec![key]) {
-                    info!("Got action: {action:?}");
-                    action_tx.send(action.clone())?;
-                } else if let Some(character) = self.get_char_from_key_event(key) {
-                    action_tx.send(Action::NewQueryDataCharacter(character))?;
-                }
-
-                Ok(())
-            }
-            Mode::FilterData => {
-                let Some(keymap) = self.config.keybindings.get(&self.mode) else {
-                    return Ok(());
+                let fallback = match self.mode {
+                    Mode::FindMatches => self.config.keybindings.get(&Mode::SelectTableDataRow),
+                    _ => None,
                 };
 
-                if let Some(action) = keymap.get(&vec![key]) {
-                    info!("Got action: {action:?}");
-                    action_tx.send(action.clone())?;
-                } else if let Some(character) = self.get_char_from_key_event(key) {
-                    action_tx.send(Action::NewFilterDataCharacter(character))?;
-                }
-
-                Ok(())
-            }
-            Mode::Insert => {
-                let Some(keymap) = self.config.keybindings.get(&self.mode) else {
-                    return Ok(());
-                };
+                let action = keymap
+                    .get(&vec![key])
+                    .or_else(|| fallback.and_then(|fallback| fallback.get(&vec![key])));
 
-                if let Some(action) = keymap.get(&vec![key]) {
-                    info!("Got action: {action:?}");
-                    action_tx.send(action.clone())?;
-                } else if let Some(character) = self.get_char_from_key_event(key) {
-                    action_tx.send(Action::NewCharacter(character))?;
+                // Digits that are not bound themselves make up a count for the next key. A
+                // leading zero is not a count, so it stays free to be bound.
+                if action.is_none() && self.pending_keys.is_empty() {
+                    if let Some(digit) =
+                        count_digit(key).filter(|&digit| digit > 0 || self.count.is_some())
+                    {
+                        self.count = Some((self.count.unwrap_or(0) * 10 + digit).min(MAX_COUNT));
+                        self.pending_keys_at = Instant::now();
+                        self.transmit_pending_keys()?;
+                        return Ok(());
+                    }
                 }
 
-                Ok(())
-            }
-            _ => {
-                let Some(keymap) = self.config.keybindings.get(&self.mode) else {
-                    return Ok(());
-                };
-
-                match keymap.get(&vec![key]) {
+                match action {
                     Some(action) => {
                         info!("Got action: {action:?}");
-                        action_tx.send(action.clone())?;
+                        let was_pending = !self.pending_keys.is_empty() || self.count.is_some();
+                        self.send_counted(action.clone())?;
+                        if was_pending {
+                            self.pending_keys.clear();
+                            self.transmit_pending_keys()?;
+                        }
                     }
                     _ => {
                         // If the key was not handled as a single key action,
                         // then consider it for multi-key combinations.
-                        self.last_tick_key_events.push(key);
+                        let was_pending = !self.pending_keys.is_empty() || self.count.is_some();
+                        self.pending_keys.push(key);
+                        self.pending_keys_at = Instant::now();
 
                         // Check for multi-key combinations
-                        if let Some(action) = keymap.get(&self.last_tick_key_events) {
+                        if let Some(action) = keymap.get(&self.pending_keys).or_else(|| {
+                            fallback.and_then(|fallback| fallback.get(&self.pending_keys))
+                        }) {
                             info!("Got action: {action:?}");
-                            action_tx.send(action.clone())?;
+                            self.send_counted(action.clone())?;
+                            self.pending_keys.clear();
+                        } else if !is_chord_prefix(keymap, &self.pending_keys)
+                            && !fallback.is_some_and(|fallback| {
+                                is_chord_prefix(fallback, &self.pending_keys)
+                            })
+                        {
+                            // No binding goes on like this, so the keys start over.
+                            self.pending_keys.clear();
+                            self.count = None;
+                        }
+                        if was_pending || !self.pending_keys.is_empty() {
+                            self.transmit_pending_keys()?;
                         }
                     }
                 }
@@ -248,73 +778,515 @@ impl App {
         while let Ok(action) = self.action_rx.try_recv() {
             if action != Action::Tick && action != Action::Render {
                 debug!("{action:?}");
+                self.needs_render = true;
+                self.record_event(SessionEvent::Action(action.to_string()))?;
+            }
+            if !matches!(
+                action,
+                Action::Tick
+                    | Action::Render
+                    | Action::TransmitFrameStats(_)
+                    | Action::TransmitRecordsMemory(_)
+            ) {
+                self.actions_this_tick += 1;
             }
             match action {
                 Action::Tick => {
-                    self.last_tick_key_events.drain(..);
+                    let timeout = Duration::from_millis(self.config.config.chord_timeout_ms);
+                    if (!self.pending_keys.is_empty() || self.count.is_some())
+                        && self.pending_keys_at.elapsed() >= timeout
+                    {
+                        self.pending_keys.clear();
+                        self.count = None;
+                        self.transmit_pending_keys()?;
+                    }
+                    self.update_frame_stats()?;
+                    self.run_next_command()?;
+                    self.keep_credentials_alive()?;
+                }
+                Action::ToggleDebugOverlay => self.fps_debug = !self.fps_debug,
+                Action::Quit => self.quit()?,
+                Action::QuitWhenJobsFinish => self.quit_when_jobs_finish = true,
+                Action::CancelJobsAndQuit => {
+                    self.quit_when_jobs_finish = true;
+                    for id in self.jobs_at_quit.clone() {
+                        self.send_request(FetchRequest::CancelJob(id))?;
+                    }
+                }
+                Action::DetachJobs => {
+                    self.detached = true;
+                    self.should_quit = true;
+                }
+                Action::ExitQuitConfirm => {
+                    self.quit_when_jobs_finish = false;
+                    self.mode = self.mode_before_quit;
                 }
-                Action::Quit => self.should_quit = true,
                 Action::Suspend => self.should_suspend = true,
                 Action::Resume => self.should_suspend = false,
                 Action::ClearScreen => tui.terminal.clear()?,
                 Action::Resize(w, h) => self.handle_resize(tui, w, h)?,
-                Action::Render => self.render(tui)?,
+                Action::Render if self.needs_render => self.render(tui)?,
                 Action::FilterTableData => self.mode = Mode::FilterData,
-                Action::QueryTableData => self.mode = Mode::QueryData,
+                Action::QueryTableData | Action::RunQuery(..) => self.mode = Mode::QueryData,
                 Action::EnterInsertMode => self.mode = Mode::Insert,
                 Action::ExitInsertMode => self.mode = Mode::View,
-                Action::SelectTableMode => self.mode = Mode::SelectTable,
+                Action::SelectTableMode => {
+                    self.mode = Mode::SelectTable;
+                    if self.config.config.layout.sidebar_collapsed {
+                        self.config.config.layout.sidebar_collapsed = false;
+                        self.update_layout()?;
+                    }
+                }
+                Action::GrowSidebar => {
+                    self.config.config.layout.grow();
+                    self.update_layout()?;
+                }
+                Action::ShrinkSidebar => {
+                    self.config.config.layout.shrink();
+                    self.update_layout()?;
+                }
+                Action::TogglePrivacyMode => {
+                    self.config.config.privacy_mode = !self.config.config.privacy_mode;
+                    self.action_tx
+                        .send(Action::TransmitPrivacyMode(self.config.config.privacy_mode))?;
+                }
+                Action::ToggleTableColumns => {
+                    let layout = &mut self.config.config.layout;
+                    layout.table_columns = !layout.table_columns;
+                    self.update_layout()?;
+                }
+                Action::ToggleZoom => {
+                    let layout = &mut self.config.config.layout;
+                    layout.zoomed = match layout.zoomed {
+                        Some(_) => None,
+                        None => self.mode.pane(),
+                    };
+                    // The zoom is not saved, so the next start shows every pane.
+                    self.action_tx.send(Action::TransmitLayout(*layout))?;
+                }
+                Action::ToggleRowNumbers => {
+                    let layout = &mut self.config.config.layout;
+                    layout.row_numbers = !layout.row_numbers;
+                    self.update_layout()?;
+                }
+                Action::FindInTableData => self.mode = Mode::FindData,
+                Action::SubmitFind => self.mode = Mode::FindMatches,
+                Action::ExitFind => self.mode = Mode::SelectTableDataRow,
+                Action::GotoRow => self.mode = Mode::GotoRow,
+                Action::SubmitGotoRow | Action::ExitGotoRow => self.mode = Mode::SelectTableDataRow,
+                Action::PinResults => self.mode = Mode::PinName,
+                Action::SubmitPinName | Action::ExitPinName => self.mode = Mode::SelectTableDataRow,
+                Action::DefineComputedColumn => self.mode = Mode::ComputedColumn,
+                Action::SubmitComputedColumn | Action::ExitComputedColumn => {
+                    self.mode = Mode::SelectTableDataRow
+                }
+                Action::ExportResults | Action::DumpTable => self.mode = Mode::ExportResults,
+                Action::SubmitExport | Action::ExitExport => self.mode = Mode::SelectTableDataRow,
+                Action::ToggleSidebar => {
+                    let layout = &mut self.config.config.layout;
+                    layout.sidebar_collapsed = !layout.sidebar_collapsed;
+                    self.update_layout()?;
+                }
                 Action::SelectDataMode
                 | Action::ExitFilterTableData
                 | Action::ExitQueryTableData
                 | Action::SubmitFilterDataText => self.mode = Mode::SelectTableDataRow,
                 Action::ViewTableDataRowDetail => self.mode = Mode::ViewTableDataRowDetail,
-                Action::FetchTables => {
-                    self.fetch_tx.try_send(FetchRequest::Tables)?;
+                Action::TransmitCredentialsFailure(_) => self.mode = Mode::CredentialsError,
+                Action::ViewApiLog if self.mode != Mode::ApiLog => {
+                    self.mode_before_api_log = self.mode;
+                    self.mode = Mode::ApiLog;
+                }
+                Action::ExitApiLog => self.mode = self.mode_before_api_log,
+                Action::ViewJobs if self.mode != Mode::Jobs => {
+                    self.mode_before_jobs = self.mode;
+                    self.mode = Mode::Jobs;
+                }
+                Action::ExitJobs => self.mode = self.mode_before_jobs,
+                Action::CancelJob(id) => self.send_request(FetchRequest::CancelJob(id))?,
+                Action::TransmitAccessDenied(_) if self.mode != Mode::AccessDenied => {
+                    self.mode_before_access_denied = self.mode;
+                    self.mode = Mode::AccessDenied;
+                }
+                Action::ExitAccessDenied => self.mode = self.mode_before_access_denied,
+                Action::RetryCredentials => {
+                    self.action_tx.send(Action::SelectTableMode)?;
+                    self.action_tx.send(Action::FetchCallerIdentity)?;
+                }
+                Action::FetchCallerIdentity => {
+                    self.send_request(FetchRequest::CallerIdentity)?;
+                    self.send_request(FetchRequest::RefreshCredentials)?;
+                    self.credentials_refreshed_at = Instant::now();
+                }
+                Action::LoginWithSso(ref profile) => {
+                    self.send_request(FetchRequest::SsoLogin(profile.to_string()))?;
+                }
+                Action::ViewWorkspaces => self.mode = Mode::Workspaces,
+                Action::ExitWorkspaces => self.mode = Mode::SelectTable,
+                Action::SwitchWorkspace(ref workspace) => {
+                    self.mode = Mode::SelectTable;
+                    self.send_request(FetchRequest::SwitchWorkspace(workspace.clone()))?;
+                }
+                Action::SelectingRegion => self.mode = Mode::Regions,
+                Action::ExitRegions => self.action_tx.send(Action::SelectTableMode)?,
+                Action::SwitchRegion(ref region) => {
+                    self.mode = Mode::SelectTable;
+                    self.send_request(FetchRequest::SwitchRegion(region.clone()))?;
+                }
+                Action::ViewProfiles => self.mode = Mode::Profiles,
+                Action::ExitProfiles => self.mode = Mode::SelectTable,
+                Action::SwitchProfile(ref profile) => {
+                    self.mode = Mode::SelectTable;
+                    self.send_request(FetchRequest::SwitchProfile(profile.clone()))?;
+                }
+                Action::SearchAllRegions => {
+                    self.mode = Mode::TableSearch;
+                    let regions = match &self.config.config.search_regions {
+                        regions if regions.is_empty() => DYNAMODB_REGIONS
+                            .iter()
+                            .map(|region| region.to_string())
+                            .collect(),
+                        regions => regions.clone(),
+                    };
+                    self.send_request(FetchRequest::SearchTables(regions))?;
+                }
+                Action::ExitTableSearch => self.mode = Mode::SelectTable,
+                Action::FetchTables => match self.tables_cache.get(&()) {
+                    Some(tables) => self.action_tx.send(Action::TransmitTables(tables))?,
+                    None => self.send_request(FetchRequest::Tables)?,
+                },
+                Action::Refresh => match self.mode {
+                    Mode::SelectTable => {
+                        self.tables_cache.invalidate(&());
+                        self.action_tx.send(Action::FetchTables)?;
+                    }
+                    // Re-runs whatever produced the records on screen; the data pane restores its
+                    // selection once they arrive.
+                    Mode::SelectTableDataRow => {
+                        if let Some(table) = self.active_table.clone() {
+                            self.item_counts_cache.invalidate(&table);
+                            self.descriptions_cache.invalidate(&table);
+                            self.scan_pages.invalidate(&table);
+
+                            let query = self
+                                .cursors
+                                .get(&table)
+                                .and_then(|cursor| cursor.current_query.clone());
+                            match query {
+                                Some(query) => {
+                                    self.request_item_count(&table)?;
+                                    self.send_request(FetchRequest::QueryTableData(table, query))?;
+                                }
+                                None => self.action_tx.send(Action::FetchTableData(table))?,
+                            }
+                        }
+                    }
+                    _ => {}
+                },
+                Action::TransmitSelectedTable(ref table) | Action::ActivateTab(ref table) => {
+                    self.active_table = Some(table.to_string());
+                    if self.config.config.probe_permissions {
+                        match self.permissions_cache.get(&table.to_string()) {
+                            Some(permissions) => self
+                                .action_tx
+                                .send(Action::TransmitPermissions(permissions))?,
+                            None => self
+                                .send_request(FetchRequest::ProbePermissions(table.to_string()))?,
+                        }
+                    }
+                }
+                Action::TabClosed(ref table) => {
+                    self.cursors.remove(table);
+                    if self.active_table.as_ref() == Some(table) {
+                        self.active_table = None;
+                    }
                 }
                 Action::FetchTableData(ref collection_name) => {
-                    self.fetch_tx
-                        .try_send(FetchRequest::GetApproximateItemCount(
+                    self.cursors
+                        .entry(collection_name.to_string())
+                        .or_default()
+                        .current_query = None;
+                    self.request_item_count(collection_name)?;
+                    match self.scan_pages.get(collection_name) {
+                        Some(pages) => self.restore_scan_pages(collection_name, pages)?,
+                        None => self.send_request(FetchRequest::TableData(
                             collection_name.to_string(),
-                        ))?;
-                    self.fetch_tx
-                        .try_send(FetchRequest::TableData(collection_name.to_string()))?;
+                            self.config.config.page_size.max(1),
+                        ))?,
+                    }
+                }
+                Action::StartParallelScan(ref table) => {
+                    self.plan(PlannedOperation::ParallelScan(
+                        table.to_string(),
+                        self.config.config.parallel_scan_segments.max(1),
+                        self.config.config.max_items,
+                        self.config.config.page_size.max(1),
+                    ))?;
                 }
+                Action::FetchMoreTableData(ref collection_name)
+                    if self.parallel_scan_running(collection_name) => {}
                 Action::FetchMoreTableData(ref collection_name) => {
-                    self.fetch_tx
-                        .try_send(FetchRequest::GetApproximateItemCount(
+                    self.request_item_count(collection_name)?;
+                    let cursor = self.cursors.entry(collection_name.to_string()).or_default();
+                    let request = match &cursor.current_query {
+                        Some(query) => FetchRequest::NextBatchQueryTableData(
                             collection_name.to_string(),
-                        ))?;
-                    self.fetch_tx.try_send(FetchRequest::NextBatchTableData(
-                        collection_name.to_string(),
-                        self.last_evaluated_key.clone(),
-                    ))?;
+                            query.clone(),
+                            cursor.last_evaluated_key.clone(),
+                        ),
+                        None => FetchRequest::NextBatchTableData(
+                            collection_name.to_string(),
+                            cursor.last_evaluated_key.clone(),
+                            self.config.config.page_size.max(1),
+                        ),
+                    };
+                    self.send_request(request)?;
                 }
                 Action::GetTableDescription(ref table_name) => {
-                    self.fetch_tx
-                        .try_send(FetchRequest::DescribeTable(table_name.to_string()))?;
+                    match self.descriptions_cache.get(table_name) {
+                        Some(description) => self
+                            .action_tx
+                            .send(Action::TransmitTableDescription(description))?,
+                        None => {
+                            self.send_request(FetchRequest::DescribeTable(table_name.to_string()))?
+                        }
+                    }
                 }
-                Action::GetTableQueryDataByPk(ref table_name, ref pk, ref pk_value) => {
-                    self.fetch_tx.try_send(FetchRequest::QueryTableByPk(
+                Action::GetPartitionStats(ref table_name, ref pk, ref pk_value, ref sk) => {
+                    self.mode = Mode::PartitionExplorer;
+                    self.send_request(FetchRequest::ExplorePartition(
                         table_name.to_string(),
-                        pk.to_string(),
+                        pk.clone(),
                         pk_value.to_string(),
+                        sk.clone(),
+                    ))?;
+                }
+                Action::NewItemFromTemplate | Action::SeedItemsFromTemplate => {
+                    self.mode = Mode::ItemTemplate
+                }
+                Action::ExitItemTemplate => self.mode = Mode::SelectTableDataRow,
+                Action::PutItem(ref table, ref item) => self.put_item(table, item, true, None)?,
+                Action::EditItem(ref table, ref item) => {
+                    self.mode_before_item_editor = self.mode;
+                    self.mode = Mode::ItemEditor;
+                    let key_names: Vec<String> = self
+                        .descriptions_cache
+                        .get(table)
+                        .map(|(partition_key, sort_key)| {
+                            partition_key
+                                .into_iter()
+                                .chain(sort_key)
+                                .map(|key| key.name)
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    self.action_tx.send(Action::ShowItemEditor(
+                        table.to_string(),
+                        item.to_string(),
+                        key_names.clone(),
+                    ))?;
+                    self.read_item_types(table, item, &key_names)?;
+                }
+                Action::ShowNewItemEditor(..) => {
+                    self.mode_before_item_editor = self.mode;
+                    self.mode = Mode::ItemEditor;
+                }
+                Action::EditingItemAttribute => self.mode = Mode::ItemEditorInput,
+                Action::SubmitItemAttribute | Action::CancelItemAttribute => {
+                    self.mode = Mode::ItemEditor
+                }
+                Action::ExitItemEditor => self.mode = self.mode_before_item_editor,
+                Action::UpdateItem(ref table, ref item, ref typed) => {
+                    self.put_item(table, item, false, typed.as_ref())?
+                }
+                Action::SeedItems(ref table, ref item, count) => {
+                    self.mode = Mode::SelectTableDataRow;
+                    if let Ok(item @ Value::Object(_)) = serde_json::from_str(item) {
+                        self.plan(PlannedOperation::Seed(table.to_string(), item, count))?;
+                    }
+                }
+                Action::ConfirmPlannedOperation => {
+                    self.mode = Mode::SelectTableDataRow;
+                    if let Some(planned) = self.planned.take() {
+                        if let PlannedOperation::ParallelScan(table, ..) = &planned {
+                            let cursor = self.cursors.entry(table.to_string()).or_default();
+                            cursor.current_query = None;
+                            cursor.parallel_scan = Some(self.next_request_id);
+                            self.request_item_count(table)?;
+                        }
+                        let shows_jobs = planned.shows_jobs();
+                        self.send_request(planned.into_request())?;
+                        if shows_jobs {
+                            // The operation runs in the background, followed in the jobs popup.
+                            self.action_tx.send(Action::ViewJobs)?;
+                        }
+                    }
+                }
+                Action::CancelPlannedOperation => {
+                    self.mode = Mode::SelectTableDataRow;
+                    self.planned = None;
+                }
+                Action::ShowRegionComparison(ref table, _) => {
+                    self.mode = Mode::RegionCompare;
+                    self.send_request(FetchRequest::ReplicaRegions(table.to_string()))?;
+                }
+                Action::GetRegionItems(ref table, ref key, ref regions) => {
+                    let key = match serde_json::from_str(key) {
+                        Ok(Value::Object(key)) => key,
+                        _ => Map::new(),
+                    };
+                    let key = self.typed_item(table, key);
+                    self.send_request(FetchRequest::CompareRegions(
+                        table.to_string(),
+                        key,
+                        regions.clone(),
+                    ))?;
+                }
+                Action::ExitRegionCompare => self.mode = Mode::SelectTableDataRow,
+                Action::ShowTableActivity(ref table) => {
+                    self.mode = Mode::TableActivity;
+                    self.send_request(FetchRequest::TableEvents(table.to_string()))?;
+                }
+                Action::ExitTableActivity => self.mode = Mode::SelectTableDataRow,
+                Action::ShowLatencyProbe(_) => self.mode = Mode::LatencyProbe,
+                Action::StartLatencyProbe(ref table, replicas) => {
+                    self.send_request(FetchRequest::ProbeLatency(table.to_string(), replicas))?;
+                }
+                Action::ExitLatencyProbe => self.mode = Mode::SelectTableDataRow,
+                Action::ShowTableInfo(ref table) => {
+                    self.mode = Mode::TableInfo;
+                    self.send_request(FetchRequest::TableInfo(table.to_string()))?;
+                }
+                Action::ExitTableInfo => self.mode = Mode::SelectTable,
+                Action::ShowScratchpad(_) => self.mode = Mode::Scratchpad,
+                Action::ExitScratchpad => self.mode = Mode::SelectTableDataRow,
+                Action::ShowKeyCopy(_) => self.mode = Mode::KeyCopy,
+                Action::ExitKeyCopy => self.mode = Mode::SelectTableDataRow,
+                Action::SetSoftDeleteView(ref table, view) => {
+                    let server_side =
+                        soft_delete::for_table(&self.config.config.soft_delete, table)
+                            .filter(|soft_delete| soft_delete.server_side);
+                    if let Some(soft_delete) = server_side {
+                        self.send_request(FetchRequest::FilterSoftDeleted(
+                            table.clone(),
+                            Some(SoftDeleteFilter {
+                                attribute: soft_delete.attribute.clone(),
+                                view,
+                            }),
+                        ))?;
+                        // The items loaded were read for the last view.
+                        self.action_tx.send(Action::Refresh)?;
+                    }
+                }
+                Action::ViewBookmarks => self.mode = Mode::Bookmarks,
+                Action::ExitBookmarks => self.mode = Mode::SelectTableDataRow,
+                Action::GetItem(ref table, ref key) => {
+                    let key = match serde_json::from_str(key) {
+                        Ok(Value::Object(key)) => key,
+                        _ => Map::new(),
+                    };
+                    let key = self.typed_item(table, key);
+                    self.send_request(FetchRequest::GetItem(
+                        table.to_string(),
+                        key,
+                        self.config.config.consistent_reads,
+                    ))?;
+                }
+                Action::ShowTableCopy(_) => self.mode = Mode::TableCopy,
+                Action::ExitTableCopy => self.mode = Mode::SelectTableDataRow,
+                Action::StartTableCopy(ref source, ref target) => {
+                    self.mode = Mode::SelectTableDataRow;
+                    self.plan(PlannedOperation::Copy(source.to_string(), target.clone()))?;
+                }
+                Action::ShowImport(_) => self.mode = Mode::Import,
+                Action::PreviewImport(ref table, ref path) => {
+                    self.send_request(FetchRequest::PreviewImport(
+                        table.to_string(),
+                        PathBuf::from(path),
+                    ))?;
+                }
+                Action::ExitImport => {
+                    self.mode = Mode::SelectTableDataRow;
+                    self.pending_import = None;
+                }
+                Action::StartImport(import_mode) => {
+                    self.mode = Mode::SelectTableDataRow;
+                    if let Some(planned) = self.planned_import(import_mode) {
+                        self.plan(planned)?;
+                    }
+                }
+                Action::StartDeepFind(ref table, ref needle, max_matches) => {
+                    self.plan(PlannedOperation::DeepFind(
+                        table.to_string(),
+                        needle.to_string(),
+                        max_matches,
                     ))?;
                 }
+                Action::StartTableDump(ref table, ref path) => {
+                    self.plan(PlannedOperation::Dump(
+                        table.to_string(),
+                        path.into(),
+                        self.config.config.page_size.max(1),
+                    ))?;
+                }
+                Action::TakeSnapshot => self.mode = Mode::SnapshotLabel,
+                Action::CompareSnapshots => self.mode = Mode::SnapshotDiff,
+                Action::SubmitSnapshotLabel
+                | Action::ExitSnapshotLabel
+                | Action::ExitSnapshotDiff => self.mode = Mode::SelectTableDataRow,
+                Action::ExitPartitionExplorer => {
+                    self.mode = Mode::SelectTableDataRow;
+                    self.send_request(FetchRequest::StopPartitionExplorer)?;
+                }
+                Action::GetTableQueryDataByPk(ref table_name, ref pk, ref pk_value, limit) => {
+                    let query = KeyQuery {
+                        partition_key: pk.clone(),
+                        partition_key_value: pk_value.to_string(),
+                        sort_key: None,
+                        limit,
+                    };
+                    self.cursors
+                        .entry(table_name.to_string())
+                        .or_default()
+                        .current_query = Some(query.clone());
+                    self.send_request(FetchRequest::QueryTableData(table_name.to_string(), query))?;
+                }
+                Action::FollowTableData(
+                    ref table_name,
+                    ref pk,
+                    ref pk_value,
+                    ref sk,
+                    ref sk_after,
+                ) => {
+                    let query = KeyQuery {
+                        partition_key: pk.clone(),
+                        partition_key_value: pk_value.to_string(),
+                        sort_key: sk_after
+                            .as_deref()
+                            .map(|sk_after| (sk.clone(), after(sk_after))),
+                        limit: None,
+                    };
+                    self.send_request(FetchRequest::FollowQuery(table_name.to_string(), query))?;
+                }
                 Action::GetTableQueryDataByPkSk(
                     ref table_name,
                     ref pk,
                     ref pk_value,
                     ref sk,
                     ref sk_value,
+                    limit,
                 ) => {
-                    self.fetch_tx.try_send(FetchRequest::QueryTableByPkSk(
-                        table_name.to_string(),
-                        pk.to_string(),
-                        pk_value.to_string(),
-                        sk.to_string(),
-                        sk_value.to_string(),
-                    ))?;
+                    let query = KeyQuery {
+                        partition_key: pk.clone(),
+                        partition_key_value: pk_value.to_string(),
+                        sort_key: Some((sk.clone(), sk_value.to_string())),
+                        limit,
+                    };
+                    self.cursors
+                        .entry(table_name.to_string())
+                        .or_default()
+                        .current_query = Some(query.clone());
+                    self.send_request(FetchRequest::QueryTableData(table_name.to_string(), query))?;
                 }
                 _ => {}
             }
@@ -327,13 +1299,711 @@ impl App {
         Ok(())
     }
 
+    /// Replays the cached pages of `table` as if they had just been scanned.
+    fn restore_scan_pages(&mut self, table: &str, pages: Vec<ScanPage>) -> Result<()> {
+        let mut pages = pages.into_iter();
+        let Some(first) = pages.next() else {
+            return Ok(());
+        };
+
+        let cursor = self.cursors.entry(table.to_string()).or_default();
+        cursor.last_evaluated_key = first.last_evaluated_key;
+        self.action_tx
+            .send(Action::TransmitTableData(first.records, first.has_more))?;
+        for page in pages {
+            cursor.last_evaluated_key = page.last_evaluated_key;
+            self.action_tx.send(Action::TransmitNextBatcTableData(
+                page.records,
+                page.has_more,
+            ))?;
+        }
+        self.action_tx.send(Action::SelectDataMode)?;
+        self.action_tx.send(Action::Render)?;
+        Ok(())
+    }
+
+    fn request_item_count(&mut self, table: &str) -> Result<()> {
+        match self.item_counts_cache.get(&table.to_string()) {
+            Some(count) => self
+                .action_tx
+                .send(Action::ApproximateTableDataCount(count))?,
+            None => self.send_request(FetchRequest::GetApproximateItemCount(table.to_string()))?,
+        }
+        Ok(())
+    }
+
+    fn handle_fetch_response(&mut self, response: FetchResponse) -> Result<()> {
+        if self.recorder.is_some() {
+            self.record_event(SessionEvent::Response(RecordedResponse::new(&response)))?;
+        }
+        match &response {
+            FetchResponse::ApiCall(call) => self.metrics.record_call(call),
+            FetchResponse::TableData(_, data, ..)
+            | FetchResponse::NextBatchTableData(_, data, ..)
+            | FetchResponse::FollowedTableData(_, data) => self.metrics.record_items(data),
+            _ => {}
+        }
+        match response {
+            FetchResponse::Tables(tables) => {
+                let tables = match tables {
+                    Some(tables) => {
+                        self.tables_cache.insert((), tables.clone());
+                        tables
+                    }
+                    None => vec!["Error loading collections.".to_string()],
+                };
+                self.action_tx.send(Action::TransmitTables(tables))?;
+                self.action_tx.send(Action::Render)?;
+            }
+            // Pages for a tab that is no longer shown are dropped; the tab reloads its data
+            // when it is activated again.
+            FetchResponse::TableData(table, ..) | FetchResponse::NextBatchTableData(table, ..)
+                if self.active_table.as_ref() != Some(&table) => {}
+            FetchResponse::TableData(table, data, has_more, last_evaluated_key) => {
+                let cursor = self.cursors.entry(table.clone()).or_default();
+                if cursor.current_query.is_none() {
+                    self.scan_pages.insert(
+                        table,
+                        vec![ScanPage {
+                            records: data.clone(),
+                            has_more,
+                            last_evaluated_key: last_evaluated_key.clone(),
+                        }],
+                    );
+                }
+                cursor.last_evaluated_key = last_evaluated_key;
+                self.action_tx
+                    .send(Action::TransmitTableData(data, has_more))?;
+                self.action_tx.send(Action::SelectDataMode)?;
+                self.action_tx.send(Action::Render)?;
+            }
+            FetchResponse::NextBatchTableData(table, data, has_more, last_evaluated_key) => {
+                let cursor = self.cursors.entry(table.clone()).or_default();
+                if cursor.current_query.is_none() {
+                    if let Some(pages) = self.scan_pages.get_mut(&table) {
+                        pages.push(ScanPage {
+                            records: data.clone(),
+                            has_more,
+                            last_evaluated_key: last_evaluated_key.clone(),
+                        });
+
+                        // Scans too large to keep in memory are not cached; the data pane spills
+                        // them to disk instead.
+                        let bytes: usize = pages
+                            .iter()
+                            .flat_map(|page| &page.records)
+                            .map(String::len)
+                            .sum();
+                        if bytes > self.config.config.records_memory_budget {
+                            self.scan_pages.invalidate(&table);
+                        }
+                    }
+                }
+                cursor.last_evaluated_key = last_evaluated_key;
+                self.action_tx
+                    .send(Action::TransmitNextBatcTableData(data, has_more))?;
+                self.action_tx.send(Action::Render)?;
+            }
+            FetchResponse::ApproximateTableDataCount(table, count) => {
+                if let Some(count) = count {
+                    self.item_counts_cache.insert(table, count);
+                }
+                self.action_tx
+                    .send(Action::ApproximateTableDataCount(count.unwrap_or(0)))?;
+            }
+            FetchResponse::TableDescription(table, description) => {
+                if let Some(description) = description.clone() {
+                    self.descriptions_cache.insert(table, description);
+                }
+                self.action_tx.send(Action::TransmitTableDescription(
+                    description.unwrap_or((None, None)),
+                ))?;
+            }
+            FetchResponse::PartitionStats(stats) => {
+                self.action_tx.send(Action::TransmitPartitionStats(stats))?;
+            }
+            FetchResponse::TableUsage(table, usage) => {
+                if let Some(planned) = self
+                    .planned
+                    .as_ref()
+                    .filter(|planned| planned.table() == table)
+                {
+                    let estimate = usage.map(|usage| (planned.estimate(&usage), usage.billing));
+                    self.mode = Mode::ConfirmCost;
+                    self.action_tx
+                        .send(Action::ShowCostEstimate(planned.describe(), estimate))?;
+                }
+            }
+            FetchResponse::WorkspaceSwitched(workspace) => {
+                self.clear_caches();
+                self.credentials_expiry = None;
+                self.mode = Mode::SelectTable;
+                self.action_tx.send(Action::TransmitWorkspace(workspace))?;
+                self.action_tx.send(Action::SelectTableMode)?;
+                self.action_tx.send(Action::FetchCallerIdentity)?;
+            }
+            FetchResponse::RegionSwitched(region) => {
+                self.clear_caches();
+                self.mode = Mode::SelectTable;
+                self.action_tx.send(Action::TransmitRegion(region))?;
+                self.action_tx.send(Action::SelectTableMode)?;
+                self.action_tx.send(Action::FetchCallerIdentity)?;
+            }
+            FetchResponse::ProfileSwitched(profile) => {
+                self.clear_caches();
+                self.credentials_expiry = None;
+                self.mode = Mode::SelectTable;
+                self.action_tx.send(Action::TransmitProfile(profile))?;
+                self.action_tx.send(Action::SelectTableMode)?;
+                self.action_tx.send(Action::FetchCallerIdentity)?;
+            }
+            FetchResponse::FollowedTableData(table, records) => {
+                if self.active_table.as_ref() == Some(&table) {
+                    self.action_tx
+                        .send(Action::TransmitFollowedTableData(records))?;
+                }
+            }
+            FetchResponse::TableSearch(results) => {
+                self.action_tx.send(Action::TransmitTableSearch(results))?;
+            }
+            FetchResponse::CredentialsExpiry(expiry) => {
+                self.credentials_expiry = expiry;
+                self.action_tx
+                    .send(Action::TransmitCredentialsExpiry(expiry))?;
+            }
+            FetchResponse::Latencies(table, latencies) => {
+                self.action_tx
+                    .send(Action::TransmitLatencies(table, latencies))?;
+            }
+            FetchResponse::TableEvents(table, events) => {
+                self.action_tx
+                    .send(Action::TransmitTableEvents(table, events))?;
+            }
+            FetchResponse::TableInfo(table, info) => {
+                self.action_tx
+                    .send(Action::TransmitTableInfo(table, info))?;
+            }
+            FetchResponse::ReplicaRegions(table, regions) => {
+                self.action_tx.send(Action::TransmitReplicaRegions(
+                    table,
+                    regions.unwrap_or_default(),
+                ))?;
+            }
+            FetchResponse::Item(table, key, item) => {
+                let key: Map<String, Value> = key
+                    .into_iter()
+                    .map(|(name, value)| (name, dynamodb_to_json(value)))
+                    .collect();
+                self.action_tx.send(Action::TransmitItem(
+                    table,
+                    Value::Object(key).to_string(),
+                    item,
+                ))?;
+            }
+            FetchResponse::ItemTypes(table, item) => {
+                self.action_tx
+                    .send(Action::TransmitItemTypes(table, item))?;
+            }
+            FetchResponse::Permissions(permissions) => {
+                self.permissions_cache
+                    .insert(permissions.table.clone(), permissions.clone());
+                self.action_tx
+                    .send(Action::TransmitPermissions(permissions))?;
+            }
+            FetchResponse::RegionItems(table, items) => {
+                self.action_tx
+                    .send(Action::TransmitRegionItems(table, items))?;
+            }
+            FetchResponse::QueryFailed(table, error) => {
+                if self.active_table.as_ref() == Some(&table) {
+                    self.action_tx.send(Action::TransmitQueryError(error))?;
+                    self.action_tx.send(Action::Render)?;
+                }
+            }
+            FetchResponse::ItemPut(table, error) => {
+                if error.is_none() {
+                    self.scan_pages.invalidate(&table);
+                    self.item_counts_cache.invalidate(&table);
+                    if self.mode == Mode::ItemTemplate {
+                        self.mode = Mode::SelectTableDataRow;
+                        // Shows the new item next to the records that were already loaded.
+                        self.action_tx.send(Action::Refresh)?;
+                    }
+                    if self.mode == Mode::ItemEditor {
+                        self.mode = self.mode_before_item_editor;
+                        // Reads an edited item back, so the list and the detail view show what
+                        // was written, and shows a new one next to the records already loaded.
+                        self.action_tx.send(match self.mode {
+                            Mode::ViewTableDataRowDetail => Action::RefreshItem,
+                            _ => Action::Refresh,
+                        })?;
+                    }
+                }
+                self.action_tx
+                    .send(Action::TransmitPutItemResult(table, error))?;
+            }
+            FetchResponse::DeepFindResult(table, result) => {
+                self.action_tx
+                    .send(Action::TransmitDeepFindResult(table, result))?;
+            }
+            FetchResponse::ImportPreview(table, preview) => {
+                let preview = preview.map(|(preview, items, statuses)| {
+                    self.pending_import = Some((table, items, statuses));
+                    self.mode = Mode::ImportPreview;
+                    preview
+                });
+                self.action_tx
+                    .send(Action::TransmitImportPreview(preview))?;
+            }
+            FetchResponse::AccessDenied(denied) => {
+                self.action_tx.send(Action::TransmitAccessDenied(denied))?;
+            }
+            FetchResponse::CredentialsFailure(failure) => {
+                self.action_tx
+                    .send(Action::TransmitCredentialsFailure(failure))?;
+            }
+            FetchResponse::SsoDeviceAuthorization(verification_uri, user_code) => {
+                self.action_tx.send(Action::TransmitSsoDeviceAuthorization(
+                    verification_uri,
+                    user_code,
+                ))?;
+            }
+            FetchResponse::CallerIdentity(account, arn) => {
+                self.action_tx
+                    .send(Action::TransmitCallerIdentity(account, arn))?;
+            }
+            FetchResponse::SsoLoginComplete => {
+                self.action_tx.send(Action::RetryCredentials)?;
+            }
+            FetchResponse::ApiCall(call) => {
+                self.action_tx.send(Action::TransmitApiCall(call))?;
+            }
+            FetchResponse::JobProgress(id, done, total) => {
+                if let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) {
+                    job.progress = Some((done, total));
+                    self.transmit_jobs()?;
+                    self.transmit_pending_requests()?;
+                }
+            }
+            FetchResponse::JobPageProgress(id, done, pages) => {
+                if let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) {
+                    job.progress = Some((done, None));
+                    job.pages = Some(pages);
+                    self.transmit_jobs()?;
+                    self.transmit_pending_requests()?;
+                }
+            }
+            FetchResponse::JobFinished(id, state) => {
+                // A job that was cancelled may still report finishing on its own.
+                if let Some(job) = self
+                    .jobs
+                    .iter_mut()
+                    .find(|job| job.id == id && job.is_running())
+                {
+                    job.finish(state);
+                    self.drop_old_jobs();
+                    self.transmit_jobs()?;
+                }
+                if self.quit_when_jobs_finish && !self.writing_jobs_running() {
+                    self.should_quit = true;
+                }
+            }
+            FetchResponse::Error(message) => self.action_tx.send(Action::Error(message))?,
+            FetchResponse::RequestFinished(id) => {
+                self.pending_requests.retain(|request| request.id != id);
+                self.transmit_pending_requests()?;
+            }
+            FetchResponse::SsoLoginFailed(reason) => {
+                self.action_tx
+                    .send(Action::TransmitSsoLoginFailure(reason))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether a parallel scan is still filling the tab of the table.
+    fn parallel_scan_running(&self, table: &str) -> bool {
+        self.cursors
+            .get(table)
+            .and_then(|cursor| cursor.parallel_scan)
+            .is_some_and(|id| self.pending_requests.iter().any(|request| request.id == id))
+    }
+
+    /// Shares the count and the keys typed so far of a multi-key binding, shown while the rest
+    /// is awaited.
+    fn transmit_pending_keys(&self) -> Result<()> {
+        let keys: Vec<String> = self
+            .count
+            .map(|count| count.to_string())
+            .into_iter()
+            .chain(self.pending_keys.iter().map(key_event_to_string))
+            .collect();
+        self.action_tx
+            .send(Action::TransmitPendingKeys(keys.join(" ")))?;
+        Ok(())
+    }
+
+    /// Sends an action bound to a key, as many times as the count typed before it when it is one
+    /// a count repeats. Any other action drops the count.
+    fn send_counted(&mut self, action: Action) -> Result<()> {
+        // The count of a deep find is the number of matches it stops at.
+        if action == Action::DeepFind {
+            let matches = self
+                .count
+                .take()
+                .unwrap_or(self.config.config.deep_find_matches);
+            return Ok(self.action_tx.send(Action::DeepFindUpTo(matches))?);
+        }
+        let times = match self.count.take() {
+            Some(count) if action.is_repeatable() => count,
+            _ => 1,
+        };
+        for _ in 0..times {
+            self.action_tx.send(action.clone())?;
+        }
+        Ok(())
+    }
+
+    /// The attributes of an item of `table` to send to DynamoDB, with its key attributes of the
+    /// types the table declares once its description was read.
+    fn typed_item(
+        &mut self,
+        table: &str,
+        item: Map<String, Value>,
+    ) -> HashMap<String, AttributeValue> {
+        let key_attributes: Vec<KeyAttribute> = self
+            .descriptions_cache
+            .get(&table.to_string())
+            .map(|(partition_key, sort_key)| partition_key.into_iter().chain(sort_key).collect())
+            .unwrap_or_default();
+        key_typed_item(item, &key_attributes)
+    }
+
+    /// Reads the item being edited again with the types of its attributes, so that saving it
+    /// keeps them. The item is found by its key attributes, which are only known for tables that
+    /// were described.
+    fn read_item_types(&mut self, table: &str, item: &str, key_names: &[String]) -> Result<()> {
+        let item = match serde_json::from_str(item) {
+            Ok(Value::Object(item)) => item,
+            _ => return Ok(()),
+        };
+        let key: HashMap<String, AttributeValue> = self
+            .typed_item(table, item)
+            .into_iter()
+            .filter(|(name, _)| key_names.contains(name))
+            .collect();
+        if key.is_empty() {
+            self.action_tx.send(Action::TransmitItemTypes(
+                table.to_string(),
+                Err("The key of the table is not known yet".to_string()),
+            ))?;
+            return Ok(());
+        }
+        self.send_request(FetchRequest::ItemTypes(table.to_string(), key))
+    }
+
+    /// Whether the countdown to the credentials expiring reads differently than when it was last
+    /// drawn: once a minute, and every second in the last minute.
+    fn countdown_changed(&mut self) -> bool {
+        let countdown = self.credentials_expiry.map(|expiry| {
+            expiry
+                .duration_since(SystemTime::now())
+                .map_or("expired".to_string(), remaining)
+        });
+        if countdown == self.countdown {
+            return false;
+        }
+        self.countdown = countdown;
+        true
+    }
+
+    /// Resolves the credentials again every `credentials_refresh_secs`, so temporary credentials
+    /// are renewed during a long session rather than failing the next request.
+    fn keep_credentials_alive(&mut self) -> Result<()> {
+        let interval = self.config.config.credentials_refresh_secs;
+        if interval == 0 || self.credentials_refreshed_at.elapsed() < Duration::from_secs(interval)
+        {
+            return Ok(());
+        }
+        self.credentials_refreshed_at = Instant::now();
+        self.send_request(FetchRequest::RefreshCredentials)
+    }
+
+    /// Runs the next command given with `--cmd` once nothing is waiting on a request, so each
+    /// command sees what the one before it loaded.
+    fn run_next_command(&mut self) -> Result<()> {
+        if !self.pending_requests.is_empty() {
+            return Ok(());
+        }
+        if let Some(command) = self.commands.pop_front() {
+            info!("Running command: {command:?}");
+            for action in command.actions() {
+                self.action_tx.send(action)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Zooms back out once another pane is focused, as tmux does.
+    fn update_zoom(&mut self) -> Result<()> {
+        let layout = &mut self.config.config.layout;
+        let focused = self.mode.pane();
+        if layout.zoomed.is_some() && focused.is_some() && focused != layout.zoomed {
+            layout.zoomed = None;
+            self.action_tx.send(Action::TransmitLayout(*layout))?;
+        }
+        Ok(())
+    }
+
+    fn update_mode_indicator(&mut self, tui: &Tui) -> Result<()> {
+        let indicator = self.mode.indicator();
+        if indicator == self.mode_indicator {
+            return Ok(());
+        }
+
+        self.mode_indicator = indicator;
+        tui.text_cursor(indicator.is_some())?;
+        self.action_tx
+            .send(Action::TransmitModeIndicator(indicator.map(str::to_string)))?;
+        Ok(())
+    }
+
+    fn record_event(&mut self, event: SessionEvent) -> Result<()> {
+        match &mut self.recorder {
+            Some(recorder) => recorder.record(event),
+            None => Ok(()),
+        }
+    }
+
+    /// Drops everything fetched for the previous workspace or region, which does not apply to
+    /// the new one.
+    fn clear_caches(&mut self) {
+        self.tables_cache.clear();
+        self.item_counts_cache.clear();
+        self.descriptions_cache.clear();
+        self.permissions_cache.clear();
+        self.scan_pages.clear();
+        self.cursors.clear();
+        self.active_table = None;
+    }
+
+    /// Hands a request to the fetch worker and tracks it until the worker reports it finished.
+    fn send_request(&mut self, request: FetchRequest) -> Result<()> {
+        let (pane, label) = match &request {
+            FetchRequest::Tables => (Pane::Tables, "Fetching Tables"),
+            FetchRequest::TableData(..) => (Pane::Data, "Fetching Table Data"),
+            FetchRequest::NextBatchTableData(..) | FetchRequest::NextBatchQueryTableData(..) => {
+                (Pane::Data, "Loading More Table Data")
+            }
+            FetchRequest::GetApproximateItemCount(_) => (Pane::Data, "Counting Items"),
+            FetchRequest::DescribeTable(_) => (Pane::Data, "Describing Table"),
+            FetchRequest::QueryTableData(..) => (Pane::Data, "Querying Data"),
+            FetchRequest::FollowQuery(..) => (Pane::Data, "Following New Items"),
+            FetchRequest::ExplorePartition(..) => (Pane::Data, "Exploring Partition"),
+            FetchRequest::StopPartitionExplorer => (Pane::Data, "Stopping Partition Explorer"),
+            FetchRequest::PutItem(..) => (Pane::Data, "Saving Item"),
+            FetchRequest::SsoLogin(_) => (Pane::Global, "Waiting for SSO login"),
+            FetchRequest::CallerIdentity => (Pane::Global, "Fetching Caller Identity"),
+            FetchRequest::RefreshCredentials => (Pane::Global, "Refreshing Credentials"),
+            FetchRequest::CancelJob(_) => (Pane::Global, "Cancelling Job"),
+            FetchRequest::ReplicaRegions(_) => (Pane::Data, "Listing Replica Regions"),
+            FetchRequest::TableEvents(_) => (Pane::Data, "Looking up CloudTrail Events"),
+            FetchRequest::GetItem(..) | FetchRequest::ItemTypes(..) => (Pane::Data, "Reading Item"),
+            FetchRequest::ProbePermissions(_) => (Pane::Data, "Probing Permissions"),
+            FetchRequest::CompareRegions(..) => (Pane::Data, "Reading Item in Each Region"),
+            FetchRequest::ProbeLatency(..) => (Pane::Data, "Probing Latency"),
+            FetchRequest::TableInfo(_) => (Pane::Tables, "Describing Table"),
+            FetchRequest::CopyTable(..) => (Pane::Global, "Copying Table"),
+            FetchRequest::SeedItems(..) => (Pane::Global, "Seeding Items"),
+            FetchRequest::DeepFind(..) => (Pane::Data, "Scanning for Matches"),
+            FetchRequest::PreviewImport(..) => (Pane::Global, "Comparing Import File"),
+            FetchRequest::ImportItems(..) => (Pane::Global, "Importing Items"),
+            FetchRequest::DumpTable(..) => (Pane::Global, "Dumping Table"),
+            FetchRequest::ParallelScan(..) => (Pane::Data, "Scanning in Parallel"),
+            FetchRequest::TableUsage(_) => (Pane::Global, "Estimating Cost"),
+            FetchRequest::SearchTables(_) => (Pane::Global, "Listing Tables in All Regions"),
+            FetchRequest::SwitchWorkspace(_) => (Pane::Global, "Switching Workspace"),
+            FetchRequest::SwitchRegion(_) => (Pane::Global, "Switching Region"),
+            FetchRequest::SwitchProfile(_) => (Pane::Global, "Switching Profile"),
+            FetchRequest::FilterSoftDeleted(..) => (Pane::Data, "Filtering Deleted Items"),
+        };
+        let job_label = request.job_label();
+        let writes = request.writes_items();
+
+        let id = self.next_request_id;
+        self.next_request_id += 1;
+
+        match self.fetch_tx.try_send((id, request)) {
+            Ok(()) => {}
+            // A full queue turns the request down rather than the app.
+            Err(TrySendError::Full(_)) => {
+                self.action_tx.send(Action::Error(format!(
+                    "Too many requests are waiting, try again once some have finished: {label}"
+                )))?;
+                return Ok(());
+            }
+            Err(err) => return Err(err.into()),
+        }
+        self.pending_requests
+            .push(PendingRequest { id, pane, label });
+        if let Some(job_label) = job_label {
+            self.jobs.push(Job::new(id, job_label, writes));
+            self.transmit_jobs()?;
+        }
+        self.transmit_pending_requests()
+    }
+
+    /// Describes the table of the operation, so its cost can be shown for confirmation.
+    fn plan(&mut self, planned: PlannedOperation) -> Result<()> {
+        let table = planned.table().to_string();
+        self.planned = Some(planned);
+        self.send_request(FetchRequest::TableUsage(table))
+    }
+
+    /// The write of the previewed import. Items identical to the ones in the table are left out.
+    /// Those that would overwrite another are still sent when inserting only, so the condition
+    /// reports them as conflicts.
+    fn planned_import(&mut self, import_mode: ImportMode) -> Option<PlannedOperation> {
+        let (table, items, statuses) = self.pending_import.take()?;
+        let items: Vec<Value> = items
+            .into_iter()
+            .zip(statuses)
+            .filter(|(_, status)| *status != ImportStatus::Identical)
+            .map(|(item, _)| item)
+            .collect();
+        (!items.is_empty()).then_some(PlannedOperation::Import(table, items, import_mode))
+    }
+
+    fn drop_old_jobs(&mut self) {
+        let finished = self.jobs.iter().filter(|job| !job.is_running()).count();
+        let mut excess = finished.saturating_sub(MAX_FINISHED_JOBS);
+        self.jobs.retain(|job| {
+            if excess > 0 && !job.is_running() {
+                excess -= 1;
+                return false;
+            }
+            true
+        });
+    }
+
+    fn transmit_jobs(&self) -> Result<()> {
+        self.action_tx
+            .send(Action::TransmitJobs(self.jobs.clone()))?;
+        Ok(())
+    }
+
+    /// Writes an item given as a JSON object. New items must not replace existing ones, so the
+    /// write is conditional on the partition key when the table has been described, while an
+    /// edited item replaces the one with its key. Items that break the table's schema are
+    /// refused. Edited items are written with the types in `typed` when given, in the DynamoDB
+    /// JSON format.
+    fn put_item(
+        &mut self,
+        table: &str,
+        item: &str,
+        new: bool,
+        typed: Option<&Value>,
+    ) -> Result<()> {
+        let item = match serde_json::from_str(item) {
+            Ok(Value::Object(item)) => item,
+            _ => {
+                return Ok(self.action_tx.send(Action::TransmitPutItemResult(
+                    table.to_string(),
+                    Some("The item must be a JSON object".to_string()),
+                ))?);
+            }
+        };
+        if let Some(schema) = schema::for_table(&self.config.config.schemas, table) {
+            let violations = schema::validate(schema, &Value::Object(item.clone()));
+            if !violations.is_empty() {
+                return Ok(self.action_tx.send(Action::TransmitPutItemResult(
+                    table.to_string(),
+                    Some(violations.join("; ")),
+                ))?);
+            }
+        }
+        let item = match typed.and_then(typed_json::item_from_typed) {
+            Some(typed) => typed,
+            None => self.typed_item(table, item),
+        };
+        let partition_key = self
+            .descriptions_cache
+            .get(&table.to_string())
+            .and_then(|(partition_key, _)| partition_key)
+            .map(|partition_key| partition_key.name)
+            .filter(|_| new);
+
+        self.send_request(FetchRequest::PutItem(
+            table.to_string(),
+            item,
+            partition_key,
+        ))
+    }
+
+    /// Shares the requests in flight, with how far along the jobs among them are.
+    fn transmit_pending_requests(&self) -> Result<()> {
+        let pending = self
+            .pending_requests
+            .iter()
+            .map(|request| {
+                let progress = self
+                    .jobs
+                    .iter()
+                    .find(|job| job.id == request.id)
+                    .map(Job::progress_label)
+                    .unwrap_or_default();
+                let label = if progress.is_empty() {
+                    request.label.to_string()
+                } else {
+                    format!("{} ({progress})", request.label)
+                };
+                (request.pane, label)
+            })
+            .collect();
+        self.action_tx
+            .send(Action::TransmitPendingRequests(pending))?;
+        Ok(())
+    }
+
+    /// Shares the new pane layout with the components and saves it for the next start.
+    fn update_layout(&mut self) -> Result<()> {
+        let layout = self.config.config.layout;
+        if let Err(err) = layout.save(&self.config.config.config_dir) {
+            error!("Failed to save the layout: {err:?}");
+        }
+        self.action_tx.send(Action::TransmitLayout(layout))?;
+        Ok(())
+    }
+
     fn handle_resize(&mut self, tui: &mut Tui, w: u16, h: u16) -> Result<()> {
         tui.resize(Rect::new(0, 0, w, h))?;
         self.render(tui)?;
         Ok(())
     }
 
+    /// Sends the statistics of the last tick to the debug overlay while it is shown.
+    fn update_frame_stats(&mut self) -> Result<()> {
+        self.frame_stats.actions_per_tick = self.actions_this_tick;
+        self.actions_this_tick = 0;
+
+        if !self.fps_debug {
+            return Ok(());
+        }
+
+        if self.second_started_at.elapsed() >= Duration::from_secs(1) {
+            self.frame_stats.frames_per_second = self.frames_this_second;
+            self.frames_this_second = 0;
+            self.second_started_at = Instant::now();
+        }
+        self.frame_stats.pending_requests = self.pending_requests.len();
+        self.action_tx
+            .send(Action::TransmitFrameStats(self.frame_stats.clone()))?;
+        Ok(())
+    }
+
     fn render(&mut self, tui: &mut Tui) -> Result<()> {
+        self.needs_render = false;
+        let started_at = Instant::now();
         tui.draw(|frame| {
             for component in self.components.iter_mut() {
                 if let Err(err) = component.draw(frame, frame.area()) {
@@ -343,6 +2013,25 @@ impl App {
                 }
             }
         })?;
+        self.frame_stats.render_micros = started_at.elapsed().as_micros() as u64;
+        self.frames_this_second += 1;
+        Ok(())
+    }
+
+    /// Sends the action bound to `key` in a mode where keys type text, or the character typed
+    /// through `new_character` when the key is not bound.
+    fn handle_typed_key(&self, key: KeyEvent, new_character: fn(char) -> Action) -> Result<()> {
+        let Some(keymap) = self.config.keybindings.get(&self.mode) else {
+            return Ok(());
+        };
+
+        if let Some(action) = keymap.get(&vec![key]) {
+            info!("Got action: {action:?}");
+            self.action_tx.send(action.clone())?;
+        } else if let Some(character) = self.get_char_from_key_event(key) {
+            self.action_tx.send(new_character(character))?;
+        }
+
         Ok(())
     }
 
@@ -353,3 +2042,76 @@ impl App {
         }
     }
 }
+
+/// Waits for the next replayed event, or forever when no session is being replayed.
+async fn next_replayed_event(
+    replayed_events: &mut Option<mpsc::UnboundedReceiver<Event>>,
+) -> Option<Event> {
+    match replayed_events {
+        Some(replayed_events) => replayed_events.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// The digit typed with a key, when it is a plain digit that can be part of a count.
+fn count_digit(key: KeyEvent) -> Option<usize> {
+    match key.code {
+        KeyCode::Char(c) if key.modifiers == KeyModifiers::NONE => {
+            c.to_digit(10).map(|digit| digit as usize)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+    use crate::cost::Billing;
+
+    const USAGE: TableUsage = TableUsage {
+        item_count: 1_000,
+        size_bytes: 1_000 * 1_500,
+        billing: Billing::OnDemand,
+    };
+
+    #[test]
+    fn test_planned_scan_estimates() {
+        let dump = PlannedOperation::Dump("orders".to_string(), "orders.ndjson".into(), 100);
+        assert_eq!(dump.estimate(&USAGE), CostEstimate::scan(&USAGE, false));
+        assert_eq!(dump.describe(), "Dump orders to orders.ndjson");
+
+        let find = PlannedOperation::DeepFind("orders".to_string(), "ada".to_string(), 10);
+        assert_eq!(find.estimate(&USAGE).read_units, 184);
+
+        let scan = PlannedOperation::ParallelScan("orders".to_string(), 4, 500, 100);
+        let estimate = scan.estimate(&USAGE);
+        assert_eq!(estimate.items, 500);
+        assert_eq!(estimate.read_units, 92);
+        assert_eq!(estimate.write_units, 0);
+        assert!(!scan.shows_jobs());
+    }
+
+    #[test]
+    fn test_planned_import_estimate() {
+        let items = vec![
+            json!({ "pk": "a" }),
+            json!({ "pk": "b", "note": "x".repeat(2_000) }),
+        ];
+        let import = PlannedOperation::Import("orders".to_string(), items, ImportMode::Merge);
+
+        // About 1 KB per item on average, so one write unit each.
+        assert_eq!(
+            import.estimate(&USAGE),
+            CostEstimate {
+                items: 2,
+                read_units: 0,
+                write_units: 2,
+            }
+        );
+        assert_eq!(import.describe(), "Import 2 items into orders (merge)");
+        assert!(import.shows_jobs());
+    }
+}
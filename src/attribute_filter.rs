@@ -0,0 +1,102 @@
+use serde_json::Value;
+
+use crate::pin;
+
+/// A filter keyword that checks an attribute rather than searching for text: `has:path`,
+/// `missing:path`, or `type:path=T`. Paths are dotted, as for pinned fields, and `T` is one of
+/// the DynamoDB type names `S`, `N`, `BOOL`, `NULL`, `M` or `L`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttributeFilter {
+    Has(String),
+    Missing(String),
+    Type(String, String),
+}
+
+impl AttributeFilter {
+    /// The filter a keyword stands for, or `None` for a keyword that searches for text.
+    pub fn parse(keyword: &str) -> Option<Self> {
+        let (kind, argument) = keyword.split_once(':')?;
+        if argument.is_empty() {
+            return None;
+        }
+        match kind {
+            "has" => Some(AttributeFilter::Has(argument.to_string())),
+            "missing" => Some(AttributeFilter::Missing(argument.to_string())),
+            "type" => {
+                let (path, type_name) = argument.split_once('=')?;
+                (!path.is_empty() && !type_name.is_empty()).then(|| {
+                    AttributeFilter::Type(path.to_string(), type_name.to_ascii_uppercase())
+                })
+            }
+            _ => None,
+        }
+    }
+
+    pub fn matches(&self, item: &Value) -> bool {
+        match self {
+            AttributeFilter::Has(path) => pin::lookup(item, path).is_some(),
+            AttributeFilter::Missing(path) => pin::lookup(item, path).is_none(),
+            AttributeFilter::Type(path, type_name) => {
+                pin::lookup(item, path).is_some_and(|value| dynamodb_type(value) == type_name)
+            }
+        }
+    }
+}
+
+/// The DynamoDB type a value read from a table was stored as. Sets and binary values are not
+/// told apart once read as JSON.
+fn dynamodb_type(value: &Value) -> &'static str {
+    match value {
+        Value::String(_) => "S",
+        Value::Number(_) => "N",
+        Value::Bool(_) => "BOOL",
+        Value::Null => "NULL",
+        Value::Object(_) => "M",
+        Value::Array(_) => "L",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_keywords() {
+        assert_eq!(
+            AttributeFilter::parse("has:email"),
+            Some(AttributeFilter::Has("email".to_string()))
+        );
+        assert_eq!(
+            AttributeFilter::parse("missing:address.zip"),
+            Some(AttributeFilter::Missing("address.zip".to_string()))
+        );
+        assert_eq!(
+            AttributeFilter::parse("type:total=n"),
+            Some(AttributeFilter::Type("total".to_string(), "N".to_string()))
+        );
+        assert_eq!(AttributeFilter::parse("has:"), None);
+        assert_eq!(AttributeFilter::parse("type:total"), None);
+        assert_eq!(AttributeFilter::parse("USER#1"), None);
+        assert_eq!(AttributeFilter::parse("url:https"), None);
+    }
+
+    fn matches(keyword: &str, item: &Value) -> bool {
+        AttributeFilter::parse(keyword).unwrap().matches(item)
+    }
+
+    #[test]
+    fn test_match_items() {
+        let item = json!({ "pk": "a", "total": 3, "address": { "city": "Oslo" } });
+
+        assert!(matches("has:address.city", &item));
+        assert!(!matches("has:email", &item));
+        assert!(matches("missing:email", &item));
+        assert!(matches("type:total=N", &item));
+        assert!(matches("type:address=M", &item));
+        assert!(!matches("type:pk=N", &item));
+        assert!(!matches("type:email=S", &item));
+    }
+}
@@ -0,0 +1,298 @@
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::SystemTime,
+};
+
+use aws_sdk_dynamodb::{
+    primitives::{DateTime, DateTimeFormat},
+    types::AttributeValue,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tracing::error;
+
+use crate::{config::get_data_dir, util::dynamodb_to_json};
+
+/// A write made through the app, appended to `audit.jsonl` in the data directory so it can be
+/// told later who changed an item and when.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: String,
+    /// The ARN the write was made as, if the caller identity could be read.
+    pub identity: Option<String>,
+    pub operation: String,
+    pub table: String,
+    /// The key attributes of the item, for writes of a single item.
+    pub key: Option<Value>,
+    pub old_image: Option<Value>,
+    pub new_image: Option<Value>,
+    /// For bulk operations, how many items were asked for when that is known.
+    pub items: Option<usize>,
+    /// For bulk operations, how the job ended and how many items it wrote.
+    #[serde(default)]
+    pub outcome: Option<Outcome>,
+    #[serde(default)]
+    pub written: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    Ok,
+    Failed,
+    Cancelled,
+}
+
+impl AuditEntry {
+    /// A single item written, with the item it replaced if there was one.
+    pub fn put_item(
+        identity: Option<String>,
+        table: &str,
+        key_names: &[String],
+        item: &HashMap<String, AttributeValue>,
+        old_item: Option<HashMap<String, AttributeValue>>,
+    ) -> Self {
+        let key: Map<String, Value> = key_names
+            .iter()
+            .filter_map(|name| {
+                let value = item.get(name)?.clone();
+                Some((name.clone(), dynamodb_to_json(value)))
+            })
+            .collect();
+
+        Self {
+            key: Some(Value::Object(key)),
+            old_image: old_item.map(|old_item| dynamodb_to_json(AttributeValue::M(old_item))),
+            new_image: Some(dynamodb_to_json(AttributeValue::M(item.clone()))),
+            ..Self::new(identity, "PutItem", table)
+        }
+    }
+
+    /// A job that writes many items, such as seeding or copying a table.
+    pub fn bulk(
+        identity: Option<String>,
+        operation: &str,
+        table: &str,
+        items: Option<usize>,
+    ) -> Self {
+        Self {
+            items,
+            ..Self::new(identity, operation, table)
+        }
+    }
+
+    fn new(identity: Option<String>, operation: &str, table: &str) -> Self {
+        Self {
+            timestamp: DateTime::from(SystemTime::now())
+                .fmt(DateTimeFormat::DateTime)
+                .unwrap_or_default(),
+            identity,
+            operation: operation.to_string(),
+            table: table.to_string(),
+            key: None,
+            old_image: None,
+            new_image: None,
+            items: None,
+            outcome: None,
+            written: None,
+        }
+    }
+}
+
+/// The audit log of a bulk job: an entry for each item it writes, as it writes it, then one that
+/// sums the job up once it ends. A cancelled job is dropped without a chance to finish, so a
+/// summary dropped unwritten is appended as cancelled.
+pub struct BulkAudit {
+    summary: Option<AuditEntry>,
+    key_names: Vec<String>,
+    written: AtomicUsize,
+    path: PathBuf,
+}
+
+impl BulkAudit {
+    /// The log of a job summed up by `summary`, writing to a table with these key attributes.
+    pub fn new(summary: AuditEntry, key_names: Vec<String>) -> Self {
+        Self::to_file(summary, key_names, audit_path())
+    }
+
+    fn to_file(summary: AuditEntry, key_names: Vec<String>, path: PathBuf) -> Self {
+        Self {
+            summary: Some(summary),
+            key_names,
+            written: AtomicUsize::new(0),
+            path,
+        }
+    }
+
+    /// Appends the entry of an item the job wrote, as it was written.
+    pub fn written(&self, item: &HashMap<String, AttributeValue>) {
+        let Some(summary) = &self.summary else {
+            return;
+        };
+        let entry = AuditEntry {
+            operation: summary.operation.clone(),
+            ..AuditEntry::put_item(
+                summary.identity.clone(),
+                &summary.table,
+                &self.key_names,
+                item,
+                None,
+            )
+        };
+        append_to_log(&self.path, &entry);
+        self.written.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Appends the summary with the outcome of the job.
+    pub fn finish(mut self, result: &Result<(), String>) {
+        let outcome = match result {
+            Ok(()) => Outcome::Ok,
+            Err(_) => Outcome::Failed,
+        };
+        self.append_summary(outcome);
+    }
+
+    /// Drops the summary of a job that was refused before it wrote anything.
+    pub fn discard(mut self) {
+        self.summary = None;
+    }
+
+    fn append_summary(&mut self, outcome: Outcome) {
+        if let Some(mut summary) = self.summary.take() {
+            summary.outcome = Some(outcome);
+            summary.written = Some(self.written.load(Ordering::Relaxed));
+            append_to_log(&self.path, &summary);
+        }
+    }
+}
+
+impl Drop for BulkAudit {
+    fn drop(&mut self) {
+        self.append_summary(Outcome::Cancelled);
+    }
+}
+
+fn audit_path() -> PathBuf {
+    get_data_dir().join("audit.jsonl")
+}
+
+/// Appends the entry to the audit log. A write that went through is not undone when it cannot
+/// be logged, so failures are only traced.
+pub fn append(entry: &AuditEntry) {
+    append_to_log(&audit_path(), entry);
+}
+
+fn append_to_log(path: &Path, entry: &AuditEntry) {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    if let Err(err) = fs::create_dir_all(dir).and_then(|_| append_to(path, entry)) {
+        error!("Failed to append to the audit log: {err}");
+    }
+}
+
+fn append_to(path: &Path, entry: &AuditEntry) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_audit_put_item() {
+        let item = HashMap::from([
+            ("pk".to_string(), AttributeValue::S("user#1".to_string())),
+            ("name".to_string(), AttributeValue::S("Ada".to_string())),
+        ]);
+        let old_item = HashMap::from([("pk".to_string(), AttributeValue::S("user#1".to_string()))]);
+
+        let entry = AuditEntry::put_item(
+            Some("arn:aws:iam::123456789012:user/ada".to_string()),
+            "users",
+            &["pk".to_string()],
+            &item,
+            Some(old_item),
+        );
+        assert_eq!(entry.key, Some(json!({ "pk": "user#1" })));
+        assert_eq!(entry.old_image, Some(json!({ "pk": "user#1" })));
+        assert_eq!(
+            entry.new_image,
+            Some(json!({ "pk": "user#1", "name": "Ada" }))
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        append_to(&path, &entry).unwrap();
+        append_to(
+            &path,
+            &AuditEntry::bulk(None, "SeedItems", "users", Some(10)),
+        )
+        .unwrap();
+
+        let lines: Vec<AuditEntry> = fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], entry);
+        assert_eq!(lines[1].items, Some(10));
+    }
+
+    #[test]
+    fn test_bulk_audit_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let audit = BulkAudit::to_file(
+            AuditEntry::bulk(None, "ImportItems (merge)", "users", Some(3)),
+            vec!["pk".to_string()],
+            path.clone(),
+        );
+        for pk in ["user#1", "user#2"] {
+            audit.written(&HashMap::from([
+                ("pk".to_string(), AttributeValue::S(pk.to_string())),
+                ("name".to_string(), AttributeValue::S("Ada".to_string())),
+            ]));
+        }
+        audit.finish(&Err("1 items failed".to_string()));
+
+        let lines: Vec<AuditEntry> = fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1].operation, "ImportItems (merge)");
+        assert_eq!(lines[1].key, Some(json!({ "pk": "user#2" })));
+        assert_eq!(
+            lines[1].new_image,
+            Some(json!({ "pk": "user#2", "name": "Ada" }))
+        );
+        assert_eq!(lines[2].outcome, Some(Outcome::Failed));
+        assert_eq!(lines[2].written, Some(2));
+        assert_eq!(lines[2].items, Some(3));
+
+        // A job dropped before it ends, as when cancelled, is summed up as cancelled.
+        drop(BulkAudit::to_file(
+            AuditEntry::bulk(None, "SeedItems", "users", Some(10)),
+            Vec::new(),
+            path.clone(),
+        ));
+        let last = fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .last()
+            .unwrap()
+            .to_string();
+        let last: AuditEntry = serde_json::from_str(&last).unwrap();
+        assert_eq!(last.outcome, Some(Outcome::Cancelled));
+        assert_eq!(last.written, Some(0));
+    }
+}
@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// An item marked during the session so it can be found again, identified by its table and
+/// primary key.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub table: String,
+    /// The key attributes of the item, as a JSON object.
+    pub key: String,
+    /// The item as it was last read, or `None` once a refresh found it deleted.
+    pub item: Option<String>,
+}
+
+impl Bookmark {
+    /// Keys are compared as JSON, so the order their attributes are listed in does not matter.
+    pub fn has_key(&self, table: &str, key: &str) -> bool {
+        self.table == table && parse_key(&self.key) == parse_key(key)
+    }
+
+    /// Whether `record` is the bookmarked item.
+    pub fn matches(&self, record: &str) -> bool {
+        record_has_key(record, &self.key)
+    }
+}
+
+/// Whether `record` holds every attribute of `key`, a JSON object, with the same value.
+pub fn record_has_key(record: &str, key: &str) -> bool {
+    let Ok(Value::Object(record)) = serde_json::from_str::<Value>(record) else {
+        return false;
+    };
+    let key = parse_key(key);
+    !key.is_empty()
+        && key
+            .iter()
+            .all(|(name, value)| record.get(name) == Some(value))
+}
+
+/// The items of the bookmarks, grouped by table. Items found deleted are left out.
+pub fn export(bookmarks: &[Bookmark]) -> Value {
+    let mut tables = Map::new();
+    for bookmark in bookmarks {
+        let Some(item) = bookmark
+            .item
+            .as_deref()
+            .and_then(|item| serde_json::from_str::<Value>(item).ok())
+        else {
+            continue;
+        };
+        if let Value::Array(items) = tables
+            .entry(bookmark.table.clone())
+            .or_insert_with(|| Value::Array(Vec::new()))
+        {
+            items.push(item);
+        }
+    }
+    Value::Object(tables)
+}
+
+fn parse_key(key: &str) -> Map<String, Value> {
+    match serde_json::from_str(key) {
+        Ok(Value::Object(key)) => key,
+        _ => Map::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn bookmark(table: &str, key: &str, item: Option<&str>) -> Bookmark {
+        Bookmark {
+            table: table.to_string(),
+            key: key.to_string(),
+            item: item.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_bookmark_matches() {
+        let bookmark = bookmark("orders", r#"{"pk":"a","sk":1.0}"#, None);
+
+        assert!(bookmark.has_key("orders", r#"{"sk":1.0,"pk":"a"}"#));
+        assert!(!bookmark.has_key("users", r#"{"pk":"a","sk":1.0}"#));
+        assert!(bookmark.matches(r#"{"sk":1.0,"pk":"a","total":3.0}"#));
+        assert!(!bookmark.matches(r#"{"pk":"a","sk":2.0}"#));
+    }
+
+    #[test]
+    fn test_export_bookmarks() {
+        let bookmarks = [
+            bookmark("orders", r#"{"pk":"a"}"#, Some(r#"{"pk":"a"}"#)),
+            bookmark("users", r#"{"pk":"b"}"#, None),
+            bookmark("orders", r#"{"pk":"c"}"#, Some(r#"{"pk":"c"}"#)),
+        ];
+
+        assert_eq!(
+            export(&bookmarks),
+            json!({ "orders": [{ "pk": "a" }, { "pk": "c" }] })
+        );
+    }
+}
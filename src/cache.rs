@@ -0,0 +1,97 @@
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+/// Remembers responses for `ttl`, after which they are treated as missing.
+pub struct TtlCache<K, V> {
+    ttl: Duration,
+    entries: HashMap<K, (Instant, V)>,
+    /// Lookups that found a fresh entry, and those that did not.
+    hits: Cell<u64>,
+    misses: Cell<u64>,
+}
+
+impl<K: Eq + Hash, V: Clone> TtlCache<K, V> {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: HashMap::new(),
+            hits: Cell::new(0),
+            misses: Cell::new(0),
+        }
+    }
+
+    /// Returns the cached value if it has not expired yet.
+    pub fn get(&self, key: &K) -> Option<V> {
+        let value = self
+            .entries
+            .get(key)
+            .filter(|(stored_at, _)| stored_at.elapsed() < self.ttl)
+            .map(|(_, value)| value.clone());
+        let counter = if value.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.set(counter.get() + 1);
+        value
+    }
+
+    /// How many lookups with `get` found a fresh entry, and how many did not.
+    pub fn lookups(&self) -> (u64, u64) {
+        (self.hits.get(), self.misses.get())
+    }
+
+    /// Like `get`, but lets the caller extend the entry without renewing it.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let ttl = self.ttl;
+        self.entries
+            .get_mut(key)
+            .filter(|(stored_at, _)| stored_at.elapsed() < ttl)
+            .map(|(_, value)| value)
+    }
+
+    pub fn insert(&mut self, key: K, value: V) {
+        self.entries.insert(key, (Instant::now(), value));
+    }
+
+    pub fn invalidate(&mut self, key: &K) {
+        self.entries.remove(key);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_get_returns_fresh_entries() {
+        let mut cache = TtlCache::new(Duration::from_secs(60));
+        cache.insert("users", 42);
+
+        assert_eq!(cache.get(&"users"), Some(42));
+        assert_eq!(cache.get(&"orders"), None);
+
+        cache.invalidate(&"users");
+        assert_eq!(cache.get(&"users"), None);
+        assert_eq!(cache.lookups(), (1, 2));
+    }
+
+    #[test]
+    fn test_get_skips_expired_entries() {
+        let mut cache = TtlCache::new(Duration::ZERO);
+        cache.insert("users", 42);
+
+        assert_eq!(cache.get(&"users"), None);
+        assert_eq!(cache.get_mut(&"users"), None);
+    }
+}
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use clap::Parser;
 
 use crate::config::{get_config_dir, get_data_dir};
@@ -12,6 +14,46 @@ pub struct Cli {
     /// Frame rate, i.e. number of frames per second
     #[arg(short, long, value_name = "FLOAT", default_value_t = 60.0)]
     pub frame_rate: f64,
+
+    /// Show the debug overlay with frame timings from the start
+    #[arg(long)]
+    pub fps_debug: bool,
+
+    /// Record the key presses, actions and redacted responses of the session to a file
+    #[arg(long, value_name = "FILE")]
+    pub record: Option<PathBuf>,
+
+    /// Replay a recorded session without connecting to AWS
+    #[arg(long, value_name = "FILE", conflicts_with = "record")]
+    pub replay: Option<PathBuf>,
+
+    /// Write counters of API calls, throttles, bytes fetched and cache hits to a file on exit,
+    /// in the Prometheus text format
+    #[arg(long, value_name = "FILE")]
+    pub metrics_file: Option<PathBuf>,
+
+    /// Copy the items of every scan and query into this SQLite file, in place of the configured
+    /// `sqlite_mirror`
+    #[arg(long, value_name = "FILE")]
+    pub mirror: Option<PathBuf>,
+
+    /// Browse the items copied into the SQLite mirror before, without connecting to AWS
+    #[arg(long, conflicts_with = "replay")]
+    pub offline: bool,
+
+    /// Items a scan reads per page, in place of the configured `page_size`
+    #[arg(long, value_name = "COUNT", value_parser = clap::value_parser!(i32).range(1..))]
+    pub page_size: Option<i32>,
+
+    /// The AWS profile to read tables with, in place of the one `AWS_PROFILE` points at
+    #[arg(short, long, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// A command to run on startup, such as "table users", "query pk=USER#42", "filter paid",
+    /// "find alice", "export users.json" or "import seed.ndjson". Repeat it to run several, each
+    /// once the one before has finished loading
+    #[arg(long = "cmd", value_name = "COMMAND")]
+    pub commands: Vec<String>,
 }
 
 const VERSION_MESSAGE: &str = concat!(env!("CARGO_PKG_VERSION"), "-",);
@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::pin;
+
+/// How many write events of a table are shown, newest first.
+pub const MAX_TABLE_EVENTS: usize = 50;
+
+/// How many pages of DynamoDB events are read looking for the events of a table. CloudTrail
+/// answers two lookups a second, so this bounds how long the panel takes to fill.
+pub const MAX_LOOKUP_PAGES: usize = 10;
+
+/// A write to a table recorded by CloudTrail: who made it, when, and what it was.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableEvent {
+    pub time: String,
+    pub operation: String,
+    /// The ARN of the identity that made the request, or its user name when there is none.
+    pub identity: String,
+    pub source_ip: Option<String>,
+    /// The error code of a request DynamoDB refused.
+    pub error: Option<String>,
+}
+
+/// The event a CloudTrail record describes, if it is a write to `table`. Records are the JSON
+/// CloudTrail keeps for each event, which name the table in their request parameters.
+pub fn table_event(table: &str, record: &str) -> Option<TableEvent> {
+    let record: Value = serde_json::from_str(record).ok()?;
+    let text = |path: &str| pin::lookup(&record, path).and_then(Value::as_str);
+
+    if text("requestParameters.tableName")? != table
+        || record.get("readOnly").and_then(Value::as_bool) == Some(true)
+    {
+        return None;
+    }
+
+    Some(TableEvent {
+        time: text("eventTime")?.to_string(),
+        operation: text("eventName")?.to_string(),
+        identity: text("userIdentity.arn")
+            .or(text("userIdentity.userName"))
+            .or(text("userIdentity.type"))
+            .unwrap_or("-")
+            .to_string(),
+        source_ip: text("sourceIPAddress").map(str::to_string),
+        error: text("errorCode").map(str::to_string),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_table_events() {
+        let record = json!({
+            "eventTime": "2024-11-02T10:15:00Z",
+            "eventName": "UpdateTable",
+            "readOnly": false,
+            "userIdentity": { "type": "AssumedRole", "arn": "arn:aws:sts::1:assumed-role/Dev/ada" },
+            "sourceIPAddress": "203.0.113.7",
+            "requestParameters": { "tableName": "orders" }
+        })
+        .to_string();
+
+        assert_eq!(
+            table_event("orders", &record),
+            Some(TableEvent {
+                time: "2024-11-02T10:15:00Z".to_string(),
+                operation: "UpdateTable".to_string(),
+                identity: "arn:aws:sts::1:assumed-role/Dev/ada".to_string(),
+                source_ip: Some("203.0.113.7".to_string()),
+                error: None,
+            })
+        );
+        assert_eq!(table_event("users", &record), None);
+
+        let read = json!({
+            "eventTime": "2024-11-02T10:16:00Z",
+            "eventName": "DescribeTable",
+            "readOnly": true,
+            "requestParameters": { "tableName": "orders" }
+        })
+        .to_string();
+        assert_eq!(table_event("orders", &read), None);
+    }
+}
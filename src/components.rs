@@ -8,12 +8,40 @@ use tokio::sync::mpsc::UnboundedSender;
 
 use crate::{action::Action, config::Config, tui::Event};
 
+pub mod access_denied_box;
+pub mod api_log_box;
+pub mod bookmark_box;
 pub mod collections_box;
+pub mod cost_confirm_box;
+pub mod credentials_error_box;
 pub mod data_box;
 pub mod data_detail_box;
+pub mod debug_overlay;
+pub mod error_banner;
 pub mod filter_input;
+pub mod import_box;
+pub mod item_editor_box;
+pub mod item_template_box;
+pub mod jobs_box;
+pub mod key_copy_box;
+pub mod key_input;
+pub mod latency_box;
 pub mod loading;
+pub mod mode_indicator;
+pub mod mouse;
+pub mod partition_explorer_box;
+pub mod profile_box;
+pub mod quit_confirm_box;
 pub mod region_box;
+pub mod region_compare_box;
+pub mod scratchpad_box;
+pub mod snapshot_box;
+pub mod table_activity_box;
+pub mod table_copy_box;
+pub mod table_info_box;
+pub mod table_search_box;
+pub mod text_input;
+pub mod workspace_box;
 
 /// `Component` is a trait that represents a visual and interactive element of the user interface.
 ///
@@ -0,0 +1,133 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{access::AccessDenied, action::Action, config::Config};
+
+use super::Component;
+
+/// Popup explaining which IAM permission a failed request was missing.
+#[derive(Default)]
+pub struct AccessDeniedBox {
+    config: Config,
+    denied: Option<AccessDenied>,
+}
+
+impl AccessDeniedBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn render_lines(&self) -> Vec<Line<'_>> {
+        let Some(denied) = &self.denied else {
+            return vec![];
+        };
+
+        let theme = self.config.config.theme;
+        let label = Style::default().add_modifier(Modifier::BOLD);
+
+        let mut lines = vec![
+            Line::from(format!("AWS denied the {} request.", denied.operation)),
+            Line::from(""),
+        ];
+
+        if let Some(principal) = &denied.principal {
+            lines.push(Line::from(vec![
+                Span::styled("Identity: ", label),
+                Span::raw(principal.clone()),
+            ]));
+        }
+        lines.push(Line::from(vec![
+            Span::styled("Missing action: ", label),
+            Span::raw(denied.action.clone()),
+        ]));
+        if let Some(resource) = &denied.resource {
+            lines.push(Line::from(vec![
+                Span::styled("Resource: ", label),
+                Span::raw(resource.clone()),
+            ]));
+        }
+        if let Some(request_id) = &denied.request_id {
+            lines.push(Line::from(vec![
+                Span::styled("Request ID: ", label),
+                Span::raw(request_id.clone()),
+            ]));
+        }
+
+        lines.extend([
+            Line::from(""),
+            Line::from("Ask your administrator for a policy with this statement:"),
+        ]);
+        lines.extend(
+            denied
+                .suggested_statement()
+                .lines()
+                .map(|line| Line::styled(line.to_string(), Style::new().fg(theme.muted))),
+        );
+        lines.extend([
+            Line::from(""),
+            Line::from("<esc> to close - <q> to quit").alignment(Alignment::Center),
+        ]);
+
+        lines
+    }
+}
+
+impl Component for AccessDeniedBox {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::TransmitAccessDenied(denied) => self.denied = Some(denied),
+            Action::ExitAccessDenied => self.denied = None,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if self.denied.is_none() {
+            return Ok(());
+        }
+
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Percentage(25),
+            Constraint::Percentage(50),
+            Constraint::Percentage(25),
+        ])
+        .areas(area);
+
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(20),
+            Constraint::Percentage(60),
+            Constraint::Percentage(20),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.error))
+            .style(theme.popup())
+            .padding(Padding::uniform(1))
+            .title("Access Denied");
+
+        let paragraph = Paragraph::new(self.render_lines())
+            .block(block)
+            .wrap(Wrap { trim: false });
+
+        frame.render_widget(Clear, middle);
+        frame.render_widget(paragraph, middle);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::VecDeque;
+
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use crate::{action::Action, api_log::ApiCall, config::Config};
+
+use super::Component;
+
+/// Older calls are dropped once the log grows past this many entries.
+const MAX_API_CALLS: usize = 1_000;
+
+/// Popup listing the DynamoDB calls made during the session, oldest first.
+#[derive(Default)]
+pub struct ApiLogBox {
+    config: Config,
+    active: bool,
+    calls: VecDeque<ApiCall>,
+    list_state: ListState,
+}
+
+impl ApiLogBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(&mut self, call: ApiCall) {
+        // Keep following the newest call unless the user scrolled away from it.
+        let following = self
+            .list_state
+            .selected()
+            .is_none_or(|selected| selected + 1 >= self.calls.len());
+
+        if self.calls.len() == MAX_API_CALLS {
+            self.calls.pop_front();
+        }
+        self.calls.push_back(call);
+
+        if following {
+            self.list_state.select(Some(self.calls.len() - 1));
+        }
+    }
+
+    fn render_call(&self, call: &ApiCall) -> Line<'static> {
+        let theme = self.config.config.theme;
+
+        // Timestamps are RFC 3339, so the time of day starts at the 12th character.
+        let time = call.timestamp.get(11..19).unwrap_or(&call.timestamp);
+        let mut spans = vec![
+            Span::styled(format!("{time}  "), Style::new().fg(theme.muted)),
+            Span::raw(format!(
+                "{:<14} {:<28} {:>6}ms",
+                call.operation,
+                call.table.as_deref().unwrap_or("-"),
+                call.duration_ms
+            )),
+        ];
+
+        match &call.error {
+            Some(error) => spans.push(Span::styled(
+                format!("  {error}"),
+                Style::new().fg(theme.error),
+            )),
+            None => {
+                if let Some(items) = call.items {
+                    spans.push(Span::raw(format!("  {items:>5} items")));
+                }
+                if let Some(capacity) = &call.consumed_capacity {
+                    spans.push(Span::raw(format!("  {capacity} CU")));
+                }
+            }
+        }
+        if let Some(request_id) = &call.request_id {
+            spans.push(Span::styled(
+                format!("  {request_id}"),
+                Style::new().fg(theme.muted),
+            ));
+        }
+
+        Line::from(spans)
+    }
+}
+
+impl Component for ApiLogBox {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::TransmitApiCall(call) => self.push(call),
+            Action::ViewApiLog => self.active = true,
+            Action::ExitApiLog => self.active = false,
+            Action::ApiLogScrollDown => self.list_state.select_next(),
+            Action::ApiLogScrollUp => self.list_state.select_previous(),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Percentage(10),
+            Constraint::Percentage(80),
+            Constraint::Percentage(10),
+        ])
+        .areas(area);
+
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(10),
+            Constraint::Percentage(80),
+            Constraint::Percentage(10),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .title(format!("Network ({} calls)", self.calls.len()))
+            .title_bottom(Line::from("<j/k> to scroll - <esc> to close").centered());
+
+        let items: Vec<ListItem> = self
+            .calls
+            .iter()
+            .map(|call| ListItem::new(self.render_call(call)))
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(theme.selected().remove_modifier(Modifier::BOLD));
+
+        frame.render_widget(Clear, middle);
+        frame.render_stateful_widget(list, middle, &mut self.list_state);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,245 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Padding, Paragraph},
+    Frame,
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    action::Action,
+    bookmark::{self, Bookmark},
+    config::Config,
+    session::redact_value,
+    sink,
+};
+
+use super::Component;
+
+/// Popup listing the items bookmarked during the session, to jump back to one, read its latest
+/// version, or copy the whole set.
+#[derive(Default)]
+pub struct BookmarkBox {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    active: bool,
+    bookmarks: Vec<Bookmark>,
+    list_state: ListState,
+    /// What became of the last refresh or export.
+    status: Option<String>,
+}
+
+impl BookmarkBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn selected(&self) -> Option<&Bookmark> {
+        self.bookmarks.get(self.list_state.selected()?)
+    }
+
+    fn toggle(&mut self, bookmark: Bookmark) {
+        match self
+            .bookmarks
+            .iter()
+            .position(|known| known.has_key(&bookmark.table, &bookmark.key))
+        {
+            Some(index) => {
+                self.bookmarks.remove(index);
+            }
+            None => self.bookmarks.push(bookmark),
+        }
+    }
+
+    fn export(&mut self) {
+        let mut export = bookmark::export(&self.bookmarks);
+        if self.config.config.privacy_mode {
+            export = redact_value(export);
+        }
+        let json = serde_json::to_string_pretty(&export).unwrap_or_default();
+
+        let count = self
+            .bookmarks
+            .iter()
+            .filter(|bookmark| bookmark.item.is_some())
+            .count();
+        self.status = Some(
+            match sink::parse(&self.config.config.export_destination).and_then(|mut sink| {
+                sink.write(json.as_bytes())
+                    .map(|_| sink.describe())
+                    .map_err(|err| err.to_string())
+            }) {
+                Ok(destination) => format!("Exported {count} bookmarked items to {destination}"),
+                Err(err) => format!("Failed to export the bookmarks: {err}"),
+            },
+        );
+    }
+
+    fn update_item(&mut self, table: &str, key: &str, item: Result<Option<String>, String>) {
+        let Some(bookmark) = self
+            .bookmarks
+            .iter_mut()
+            .find(|bookmark| bookmark.has_key(table, key))
+        else {
+            return;
+        };
+
+        self.status = Some(match item {
+            Ok(Some(item)) => {
+                bookmark.item = Some(item);
+                "Refreshed".to_string()
+            }
+            Ok(None) => {
+                bookmark.item = None;
+                "The item no longer exists".to_string()
+            }
+            Err(error) => error,
+        });
+    }
+
+    fn render_bookmark(&self, bookmark: &Bookmark) -> ListItem<'static> {
+        let theme = self.config.config.theme;
+        let mut spans = vec![
+            Span::styled(
+                format!("{}  ", bookmark.table),
+                Style::new().fg(theme.muted),
+            ),
+            Span::raw(bookmark.key.clone()),
+        ];
+        if bookmark.item.is_none() {
+            spans.push(Span::styled("  deleted", Style::new().fg(theme.error)));
+        }
+        ListItem::new(Line::from(spans))
+    }
+}
+
+impl Component for BookmarkBox {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ToggleBookmark(bookmark) => self.toggle(bookmark),
+            Action::ViewBookmarks => {
+                self.active = true;
+                self.status = None;
+                if self.list_state.selected().is_none() {
+                    self.list_state.select_first();
+                }
+            }
+            Action::BookmarkNext => self.list_state.select_next(),
+            Action::BookmarkPrev => self.list_state.select_previous(),
+            Action::JumpToSelectedBookmark => {
+                if let Some(bookmark) = self.selected().cloned() {
+                    self.active = false;
+                    let command_tx = self.command_tx.as_ref().unwrap();
+                    command_tx.send(Action::SelectDataMode)?;
+                    command_tx.send(Action::JumpToBookmark(bookmark))?;
+                }
+            }
+            Action::RefreshSelectedBookmark => {
+                if let Some(bookmark) = self.selected() {
+                    self.command_tx.as_ref().unwrap().send(Action::GetItem(
+                        bookmark.table.clone(),
+                        bookmark.key.clone(),
+                    ))?;
+                }
+            }
+            Action::TransmitItem(table, key, item) => self.update_item(&table, &key, item),
+            Action::TransmitPrivacyMode(privacy_mode) => {
+                self.config.config.privacy_mode = privacy_mode;
+            }
+            Action::DeleteSelectedBookmark => {
+                if let Some(selected) = self.list_state.selected() {
+                    if selected < self.bookmarks.len() {
+                        self.bookmarks.remove(selected);
+                    }
+                }
+            }
+            Action::ExportBookmarks => self.export(),
+            Action::ExitBookmarks => self.active = false,
+            // The bookmarked items belong to the previous workspace's account and region.
+            Action::TransmitWorkspace(_) => self.bookmarks.clear(),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Percentage(25),
+            Constraint::Percentage(50),
+            Constraint::Percentage(25),
+        ])
+        .areas(area);
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(20),
+            Constraint::Percentage(60),
+            Constraint::Percentage(20),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .padding(Padding::horizontal(1))
+            .title(format!("Bookmarks ({})", self.bookmarks.len()))
+            .title_bottom(
+                Line::from(
+                    "<enter> to jump - <r> to refresh - <d> to remove - <e> to copy all - <esc> to close",
+                )
+                .centered(),
+            );
+
+        let inner = block.inner(middle);
+        frame.render_widget(Clear, middle);
+        frame.render_widget(block, middle);
+
+        let [list_area, status_area] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(u16::from(self.status.is_some())),
+        ])
+        .areas(inner);
+
+        if self.bookmarks.is_empty() {
+            frame.render_widget(
+                Paragraph::new("No bookmarks yet. Press <m> on an item to bookmark it.")
+                    .style(Style::new().fg(theme.muted)),
+                list_area,
+            );
+        } else {
+            let items: Vec<ListItem> = self
+                .bookmarks
+                .iter()
+                .map(|bookmark| self.render_bookmark(bookmark))
+                .collect();
+            let list = List::new(items).highlight_style(theme.selected());
+            frame.render_stateful_widget(list, list_area, &mut self.list_state);
+        }
+
+        if let Some(status) = &self.status {
+            frame.render_widget(
+                Paragraph::new(status.clone()).style(Style::new().fg(theme.muted)),
+                status_area,
+            );
+        }
+
+        Ok(())
+    }
+}
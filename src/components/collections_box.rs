@@ -1,9 +1,9 @@
 use color_eyre::Result;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 use ratatui::prelude::*;
-use ratatui::style::Color;
 use ratatui::widgets::{
-    HighlightSpacing, List, ListItem, ListState, Scrollbar, ScrollbarOrientation, ScrollbarState,
-    StatefulWidget,
+    HighlightSpacing, List, ListItem, ListState, Paragraph, Scrollbar, ScrollbarOrientation,
+    ScrollbarState, StatefulWidget,
 };
 use ratatui::{
     layout::Rect,
@@ -14,13 +14,19 @@ use ratatui::{
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
 use symbols::scrollbar;
+use throbber_widgets_tui::ThrobberState;
 use tokio::sync::mpsc::UnboundedSender;
 
 use crate::action::Action;
-use crate::config::Config;
-use crate::constants::{ACTIVE_PANE_COLOR, LIST_ITEM_SELECTED_STYLE};
-
-use super::Component;
+use crate::app::Pane;
+use crate::config::{Config, ZoomedPane};
+use crate::workspace::Workspace;
+
+use super::{
+    loading::pane_spinner,
+    mouse::{grid_index_at, list_index_at, ClickTracker},
+    Component,
+};
 
 #[derive(Debug, Default)]
 pub struct CollectionsBox {
@@ -33,6 +39,25 @@ pub struct CollectionsBox {
     selected_collection: String,
     filter_text: String,
     scroll_bar_state: ScrollbarState,
+    area: Rect,
+    clicks: ClickTracker,
+    /// Set while the row detail popup is drawn over the list.
+    covered: bool,
+    /// Set while a request for the table list is in flight.
+    loading: bool,
+    loading_state: ThrobberState,
+    /// Limits the list to the tables of the workspace, when it names any.
+    workspace: Option<Workspace>,
+    /// The first column shown when the tables are listed in columns.
+    first_column: usize,
+}
+
+/// How the tables are laid out when they are listed in columns.
+#[derive(Debug, Clone, Copy)]
+struct Grid {
+    rows: usize,
+    columns: usize,
+    column_width: u16,
 }
 
 impl CollectionsBox {
@@ -40,6 +65,15 @@ impl CollectionsBox {
         Self::default()
     }
 
+    fn title(&self) -> Line<'static> {
+        let mut title = Line::from("Tables");
+        if self.loading {
+            title.push_span(" ");
+            title.push_span(pane_spinner(&self.loading_state, &self.config.config.theme));
+        }
+        title
+    }
+
     pub fn apply_filter(&mut self) {
         if self.filter_text.is_empty() {
             self.filtered_collections = self.collections.clone();
@@ -78,14 +112,60 @@ impl CollectionsBox {
         self.update_scroll_pos(self.list_state.selected().unwrap());
     }
 
-    fn scroll_up(&mut self) {
-        self.list_state.scroll_up_by(5);
-        self.update_scroll_pos(self.list_state.selected().unwrap());
+    /// Rows of the list that fit in the pane, inside its borders.
+    fn page_lines(&self) -> i32 {
+        self.area.height.saturating_sub(2).max(1) as i32
     }
 
-    fn scroll_down(&mut self) {
-        self.list_state.scroll_down_by(5);
-        self.update_scroll_pos(self.list_state.selected().unwrap());
+    fn select_at(&mut self, index: usize) {
+        if index < self.filtered_collections.len() {
+            self.list_state.select(Some(index));
+            self.update_scroll_pos(index);
+        }
+    }
+
+    fn scroll_by(&mut self, delta: i32) {
+        if delta < 0 {
+            self.list_state.scroll_up_by(delta.unsigned_abs() as u16);
+        } else {
+            self.list_state.scroll_down_by(delta as u16);
+        }
+        if let Some(selected) = self.list_state.selected() {
+            self.update_scroll_pos(selected);
+        }
+    }
+
+    /// The columns the tables are listed in, when that is turned on and the pane is wide enough
+    /// for more than one.
+    fn grid(&self) -> Option<Grid> {
+        if !self.config.config.layout.table_columns {
+            return None;
+        }
+        let widest = self
+            .filtered_collections
+            .iter()
+            .map(|name| name.chars().count())
+            .max()?;
+        let column_width = widest as u16 + 2;
+        let columns = (self.area.width.saturating_sub(2) / column_width) as usize;
+        (columns > 1).then_some(Grid {
+            rows: self.page_lines() as usize,
+            columns,
+            column_width,
+        })
+    }
+
+    /// Moves the selection to the same row of a neighbouring column.
+    fn select_column(&mut self, forward: bool) {
+        let (Some(grid), Some(selected)) = (self.grid(), self.list_state.selected()) else {
+            return;
+        };
+        let index = if forward {
+            (selected + grid.rows).min(self.filtered_collections.len().saturating_sub(1))
+        } else {
+            selected.saturating_sub(grid.rows)
+        };
+        self.select_at(index);
     }
 
     fn set_selected(&mut self) -> bool {
@@ -110,6 +190,45 @@ impl CollectionsBox {
             self.select_first();
         }
     }
+
+    /// Lists the tables down each column, then across, scrolling sideways a column at a time to
+    /// keep the selection in view.
+    fn render_columns(&mut self, frame: &mut Frame, block: Block, grid: Grid) {
+        let theme = self.config.config.theme;
+        let selected = self.list_state.selected();
+        if let Some(selected) = selected {
+            let column = selected / grid.rows;
+            if column < self.first_column {
+                self.first_column = column;
+            } else if column >= self.first_column + grid.columns {
+                self.first_column = column + 1 - grid.columns;
+            }
+        }
+
+        let lines: Vec<Line> = (0..grid.rows)
+            .map(|row| {
+                let spans: Vec<Span> = (self.first_column..self.first_column + grid.columns)
+                    .filter_map(|column| {
+                        let index = column * grid.rows + row;
+                        let name = self.filtered_collections.get(index)?;
+                        let text =
+                            format!(" {name:<width$}", width = grid.column_width as usize - 1);
+                        Some(if Some(index) == selected {
+                            Span::styled(text, theme.selected())
+                        } else {
+                            Span::raw(text)
+                        })
+                    })
+                    .collect();
+                Line::from(spans)
+            })
+            .collect();
+
+        Paragraph::new(lines)
+            .block(block)
+            .style(Style::default().fg(theme.text))
+            .render(self.area, frame.buffer_mut());
+    }
 }
 
 impl Component for CollectionsBox {
@@ -123,22 +242,70 @@ impl Component for CollectionsBox {
         Ok(())
     }
 
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Result<Option<Action>> {
+        if self.covered || !self.area.contains(Position::new(mouse.column, mouse.row)) {
+            return Ok(None);
+        }
+
+        let action = match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let double_click = self.clicks.click(&mouse);
+                let command_tx = self.command_tx.as_ref().unwrap();
+
+                if !self.active {
+                    command_tx.send(Action::SelectTableMode)?;
+                }
+
+                let index = match self.grid() {
+                    Some(grid) => grid_index_at(
+                        self.area,
+                        grid.rows,
+                        grid.column_width,
+                        self.first_column,
+                        &mouse,
+                    ),
+                    None => list_index_at(self.area, self.list_state.offset(), &mouse),
+                };
+                match index {
+                    Some(index) if index < self.filtered_collections.len() => {
+                        if double_click {
+                            command_tx.send(Action::SelectTableAt(index))?;
+                            Some(Action::SelectTable)
+                        } else {
+                            Some(Action::SelectTableAt(index))
+                        }
+                    }
+                    _ => None,
+                }
+            }
+            MouseEventKind::ScrollDown if self.active => Some(Action::ScrollTables(
+                self.config.config.scroll.wheel_lines as i32,
+            )),
+            MouseEventKind::ScrollUp if self.active => Some(Action::ScrollTables(
+                -(self.config.config.scroll.wheel_lines as i32),
+            )),
+            _ => None,
+        };
+
+        Ok(action)
+    }
+
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
-            Action::Tick => {
-                // add any logic here that should run on every tick
+            Action::Tick if self.loading => {
+                self.loading_state.calc_next();
+            }
+            Action::TransmitPendingRequests(pending) => {
+                self.loading = pending.iter().any(|(pane, _)| *pane == Pane::Tables);
             }
             Action::Render => {
                 // add any logic here that should run on every render
             }
             Action::SelectTableMode => {
                 self.active = true;
+                self.covered = false;
                 let command_ref = self.command_tx.as_ref().unwrap();
 
-                if self.collections.is_empty() {
-                    command_ref.send(Action::StartLoading("Fetching Tables".to_string()))?;
-                }
-
                 command_ref.send(Action::FetchTables)?;
             }
             Action::FilteringTables
@@ -146,6 +313,7 @@ impl Component for CollectionsBox {
             | Action::SelectDataMode
             | Action::ViewTableDataRowDetail => {
                 self.active = false;
+                self.covered = action == Action::ViewTableDataRowDetail;
                 self.list_state.select(None);
             }
             Action::TransmitSubmittedText(text) => {
@@ -154,7 +322,10 @@ impl Component for CollectionsBox {
                 self.apply_filter();
             }
             Action::TransmitTables(tables) => {
-                self.collections = tables;
+                self.collections = match &self.workspace {
+                    Some(workspace) => workspace.visible_tables(&tables),
+                    None => tables,
+                };
                 self.select_first_if_needed();
                 self.apply_filter();
             }
@@ -165,29 +336,59 @@ impl Component for CollectionsBox {
                 self.select_next();
             }
             Action::SelectTableScrollUp => {
-                self.scroll_up();
+                self.scroll_by(-(self.config.config.scroll.list_lines as i32));
             }
             Action::SelectTableScrollDown => {
-                self.scroll_down();
+                self.scroll_by(self.config.config.scroll.list_lines as i32);
             }
+            Action::SelectTableHalfPageUp => self.scroll_by(-(self.page_lines() / 2).max(1)),
+            Action::SelectTableHalfPageDown => self.scroll_by((self.page_lines() / 2).max(1)),
+            Action::SelectTablePageUp => self.scroll_by(-self.page_lines()),
+            Action::SelectTablePageDown => self.scroll_by(self.page_lines()),
+            Action::SelectTableColumnLeft => self.select_column(false),
+            Action::SelectTableColumnRight => self.select_column(true),
             Action::SelectTableFirst => {
                 self.select_first();
             }
             Action::SelectTableLast => {
                 self.select_last();
             }
+            Action::SelectTableAt(index) => {
+                self.select_at(index);
+            }
+            Action::ScrollTables(delta) => {
+                self.scroll_by(delta);
+            }
             Action::SelectTable => {
                 self.set_selected();
                 let command_ref = self.command_tx.as_ref().unwrap();
 
-                command_ref.send(Action::StartLoading("Fetching Table Data".to_string()))?;
-
                 command_ref.send(Action::TransmitSelectedTable(
                     self.selected_collection.clone(),
                 ))?;
 
                 command_ref.send(Action::FetchTableData(self.selected_collection.clone()))?;
             }
+            Action::ViewTableInfo if self.active => {
+                let highlighted = self
+                    .list_state
+                    .selected()
+                    .and_then(|index| self.filtered_collections.get(index));
+                if let Some(table) = highlighted {
+                    self.command_tx
+                        .as_ref()
+                        .unwrap()
+                        .send(Action::ShowTableInfo(table.clone()))?;
+                }
+            }
+            Action::TransmitWorkspace(workspace) => {
+                self.workspace = workspace;
+                self.collections.clear();
+                self.filtered_collections.clear();
+                self.selected_collection.clear();
+                self.list_state.select(None);
+            }
+            Action::TransmitLayout(layout) => self.config.config.layout = layout,
             _ => {}
         }
         Ok(None)
@@ -196,22 +397,32 @@ impl Component for CollectionsBox {
         let [top, _] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
 
         let [left, _] =
-            Layout::horizontal([Constraint::Percentage(30), Constraint::Min(0)]).areas(top);
-
+            Layout::horizontal([self.config.config.layout.sidebar(), Constraint::Min(0)])
+                .areas(top);
+
+        // Zoomed, the list also takes the place of the region and filter boxes.
+        let edge = match self.config.config.layout.zoomed {
+            Some(ZoomedPane::Tables) => 0,
+            _ => 3,
+        };
         let [_, middle_left, _] = Layout::vertical([
-            Constraint::Length(3),
+            Constraint::Length(edge),
             Constraint::Min(0),
-            Constraint::Length(3),
+            Constraint::Length(edge),
         ])
         .areas(left);
+        self.area = middle_left;
 
-        let mut block = Block::new()
+        let theme = self.config.config.theme;
+        let block = Block::new()
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .title("Tables");
+            .border_style(theme.border(self.active))
+            .title(self.title());
 
-        if self.active {
-            block = block.border_style(Style::default().fg(ACTIVE_PANE_COLOR));
+        if let Some(grid) = self.grid() {
+            self.render_columns(frame, block, grid);
+            return Ok(());
         }
 
         let items: Vec<ListItem> = self
@@ -230,8 +441,8 @@ impl Component for CollectionsBox {
 
         let collection_list = List::new(items)
             .block(block)
-            .style(Style::default().fg(Color::White))
-            .highlight_style(LIST_ITEM_SELECTED_STYLE)
+            .style(Style::default().fg(theme.text))
+            .highlight_style(theme.selected())
             .highlight_spacing(HighlightSpacing::Always);
 
         StatefulWidget::render(
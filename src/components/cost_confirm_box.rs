@@ -0,0 +1,135 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    action::Action,
+    config::Config,
+    cost::{Billing, CostEstimate},
+};
+
+use super::Component;
+
+/// Popup that shows what a table-wide operation is expected to consume before it is started.
+#[derive(Default)]
+pub struct CostConfirmBox {
+    config: Config,
+    /// The operation being confirmed, and its estimate when the table could be described.
+    pending: Option<(String, Option<(CostEstimate, Billing)>)>,
+}
+
+impl CostConfirmBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn estimate_lines(estimate: &CostEstimate, billing: &Billing) -> Vec<Line<'static>> {
+        let mut lines = vec![Line::from(format!(
+            "About {} items: {} read units and {} write units.",
+            estimate.items, estimate.read_units, estimate.write_units
+        ))];
+
+        lines.push(match billing {
+            Billing::OnDemand => Line::from(format!(
+                "On-demand, this costs about ${:.4} at us-east-1 prices.",
+                estimate.on_demand_dollars()
+            )),
+            Billing::Provisioned(read_capacity, write_capacity) => Line::from(format!(
+                "At {read_capacity} RCU and {write_capacity} WCU, this takes at least {} using \
+                 all of the table's capacity.",
+                format_seconds(estimate.provisioned_seconds(*read_capacity, *write_capacity))
+            )),
+        });
+
+        lines
+    }
+}
+
+fn format_seconds(seconds: f64) -> String {
+    match seconds {
+        seconds if seconds < 60.0 => format!("{}s", seconds.ceil()),
+        seconds if seconds < 3600.0 => format!("{}m", (seconds / 60.0).ceil()),
+        seconds => format!("{:.1}h", seconds / 3600.0),
+    }
+}
+
+impl Component for CostConfirmBox {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ShowCostEstimate(operation, estimate) => {
+                self.pending = Some((operation, estimate))
+            }
+            Action::ConfirmPlannedOperation | Action::CancelPlannedOperation => self.pending = None,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let Some((operation, estimate)) = &self.pending else {
+            return Ok(());
+        };
+
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(10),
+            Constraint::Min(0),
+        ])
+        .areas(area);
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(25),
+            Constraint::Percentage(50),
+            Constraint::Percentage(25),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .padding(Padding::uniform(1))
+            .title("Confirm")
+            .title_bottom(Line::from("<y> to start - <n> to cancel").centered());
+
+        let mut lines = vec![
+            Line::styled(
+                operation.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Line::from(""),
+        ];
+        match estimate {
+            Some((estimate, billing)) => {
+                lines.extend(Self::estimate_lines(estimate, billing));
+                lines.push(Line::styled(
+                    "Item counts come from DescribeTable, which refreshes them about every six hours.",
+                    Style::new().fg(theme.muted),
+                ));
+            }
+            None => lines.push(Line::styled(
+                "The table could not be described, so the cost is unknown.",
+                Style::new().fg(theme.error),
+            )),
+        }
+
+        frame.render_widget(Clear, middle);
+        frame.render_widget(
+            Paragraph::new(lines).block(block).wrap(Wrap { trim: true }),
+            middle,
+        );
+
+        Ok(())
+    }
+}
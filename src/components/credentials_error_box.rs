@@ -0,0 +1,195 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph, Wrap},
+    Frame,
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{action::Action, config::Config, credentials::CredentialsFailure};
+
+use super::Component;
+
+#[derive(Default)]
+pub struct CredentialsErrorBox {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    active: bool,
+    failure: Option<CredentialsFailure>,
+    sso_login: Option<SsoLoginState>,
+}
+
+enum SsoLoginState {
+    Starting,
+    Pending {
+        verification_uri: String,
+        user_code: String,
+    },
+    Failed(String),
+}
+
+impl CredentialsErrorBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn render_lines(&self) -> Vec<Line<'_>> {
+        let Some(failure) = &self.failure else {
+            return vec![];
+        };
+
+        let label = Style::default().add_modifier(Modifier::BOLD);
+
+        let mut lines = vec![
+            Line::from("Unable to authenticate with AWS."),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("Profile: ", label),
+                Span::raw(failure.profile.clone()),
+            ]),
+            Line::from(vec![
+                Span::styled("Reason: ", label),
+                Span::raw(failure.reason.clone()),
+            ]),
+        ];
+
+        if let Some(expiry) = &failure.sso_session_expiry {
+            lines.push(Line::from(vec![
+                Span::styled("SSO session expires at: ", label),
+                Span::raw(expiry.clone()),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+
+        match &self.sso_login {
+            Some(SsoLoginState::Starting) => {
+                lines.push(Line::from("Starting SSO login..."));
+            }
+            Some(SsoLoginState::Pending {
+                verification_uri,
+                user_code,
+            }) => {
+                lines.extend([
+                    Line::from("Approve the login in your browser:"),
+                    Line::from(verification_uri.clone()),
+                    Line::from(vec![
+                        Span::styled("Code: ", label),
+                        Span::raw(user_code.clone()),
+                    ]),
+                    Line::from(""),
+                    Line::from("Waiting for approval..."),
+                ]);
+            }
+            Some(SsoLoginState::Failed(reason)) => {
+                lines.push(Line::from(vec![
+                    Span::styled("SSO login failed: ", label),
+                    Span::raw(reason.clone()),
+                ]));
+            }
+            None => {
+                lines.push(Line::from(format!(
+                    "Refresh your credentials (e.g. `aws sso login --profile {}`), then retry.",
+                    failure.profile
+                )));
+            }
+        }
+
+        let help = if failure.sso_login_available {
+            "<l> to log in with SSO - <r> to retry - <q> to quit"
+        } else {
+            "<r> to retry - <q> to quit"
+        };
+
+        lines.extend([
+            Line::from(""),
+            Line::from(help).alignment(Alignment::Center),
+        ]);
+
+        lines
+    }
+}
+
+impl Component for CredentialsErrorBox {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::TransmitCredentialsFailure(failure) => {
+                self.active = true;
+                self.failure = Some(failure);
+            }
+            Action::RetryCredentials => {
+                self.active = false;
+                self.failure = None;
+                self.sso_login = None;
+            }
+            Action::StartSsoLogin => {
+                if let Some(failure) = self.failure.as_ref().filter(|f| f.sso_login_available) {
+                    let command_tx = self.command_tx.as_ref().unwrap();
+
+                    self.sso_login = Some(SsoLoginState::Starting);
+                    command_tx.send(Action::LoginWithSso(failure.profile.clone()))?;
+                }
+            }
+            Action::TransmitSsoDeviceAuthorization(verification_uri, user_code) => {
+                self.sso_login = Some(SsoLoginState::Pending {
+                    verification_uri,
+                    user_code,
+                });
+            }
+            Action::TransmitSsoLoginFailure(reason) => {
+                self.sso_login = Some(SsoLoginState::Failed(reason));
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Percentage(25),
+            Constraint::Percentage(50),
+            Constraint::Percentage(25),
+        ])
+        .areas(area);
+
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(20),
+            Constraint::Percentage(60),
+            Constraint::Percentage(20),
+        ])
+        .areas(y_middle);
+
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.config.config.theme.error))
+            .style(self.config.config.theme.popup())
+            .padding(Padding::uniform(1))
+            .title("Credentials Error");
+
+        let paragraph = Paragraph::new(self.render_lines())
+            .block(block)
+            .wrap(Wrap { trim: false });
+
+        frame.render_widget(Clear, middle);
+        frame.render_widget(paragraph, middle);
+
+        Ok(())
+    }
+}
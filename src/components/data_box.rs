@@ -1,11 +1,15 @@
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant, SystemTime},
+};
+
 use color_eyre::Result;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
 use fuzzy_matcher::skim::SkimMatcherV2;
-use fuzzy_matcher::FuzzyMatcher;
 use ratatui::prelude::*;
-use ratatui::style::palette::tailwind::{EMERALD, VIOLET};
 
 use clipboard::{ClipboardContext, ClipboardProvider};
-use ratatui::style::Color;
 use ratatui::widgets::{
     Clear, List, ListItem, ListState, Padding, Paragraph, Scrollbar, ScrollbarOrientation,
     ScrollbarState, StatefulWidget, Wrap,
@@ -16,40 +20,210 @@ use ratatui::{
     widgets::{Block, BorderType, Borders},
 };
 use serde_json::Value;
-use style::palette::material::INDIGO;
 use symbols::scrollbar;
+use throbber_widgets_tui::ThrobberState;
 use tokio::sync::mpsc::UnboundedSender;
 
 use crate::action::Action;
+use crate::app::Pane;
+use crate::attribute_filter::AttributeFilter;
 use crate::config::Config;
+use crate::data::{DeepFindResult, KeyAttribute, KeyAttributeType, KeyQuery};
+use crate::explain::explain;
+use crate::id;
+use crate::record_store::RecordStore;
+use crate::time_range::{
+    after_bound, relative_range, sort_key_text, split_range, TimeRange, TimestampFormat,
+};
+use crate::{
+    bookmark::{record_has_key, Bookmark},
+    computed::{self, Expr},
+    csv_export, encryption, key_order, parquet_export,
+    permission::{Permission, Permissions, TableOperation},
+    pin, relevance, schema,
+    session::{redact_record, redact_value},
+    sink,
+    snapshot::snapshot_key,
+    soft_delete::{self, SoftDeleteView},
+    tear_out::TearOut,
+};
+
+use super::{
+    key_input::{apply_key_type, decoded_base64, toggle_base64, KeySuggestions},
+    loading::pane_spinner,
+    mouse::{list_index_at, ClickTracker},
+    text_input::TextInput,
+    Component,
+};
 
-use super::Component;
+/// How long a request to copy a large result set waits to be confirmed.
+const COPY_CONFIRM_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often a followed query looks for new items.
+const FOLLOW_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Records sampled to tell whether a sort key holds timestamps.
+const TIMESTAMP_SAMPLE_SIZE: usize = 20;
+
+/// Records of each page whose keys are offered as suggestions in the query form.
+const KEY_SUGGESTION_SAMPLE_SIZE: usize = 10;
+
+/// Why a pinned tab does not reload or query its table.
+const PINNED_STATUS: &str = "pinned results stay as they are; select the table to reload it";
 
 #[derive(Default)]
 pub struct DataBox {
     command_tx: Option<UnboundedSender<Action>>,
     config: Config,
     active: bool,
+    /// The tab that is shown. Its slot in `tabs` holds an empty placeholder until it is switched
+    /// out.
+    tab: TableTab,
+    tabs: Vec<TableTab>,
+    active_tab: usize,
+    mode: Mode,
+    query_error: Option<String>,
+    /// Which items of each table with a soft-delete attribute are shown, kept when its tab is
+    /// closed.
+    soft_delete_views: HashMap<String, SoftDeleteView>,
+    /// Set once the query form is submitted. The form stays open until the results arrive, so
+    /// a failed query can be corrected.
+    query_pending: bool,
+    query_focus: QueryFocus,
+    /// The row number typed after `:`.
+    goto_input: TextInput,
+    /// The name typed after `B` for the result set being pinned.
+    pin_input: TextInput,
+    /// The computed column typed after `E`, as `name = expression`.
+    column_input: TextInput,
+    /// Where the records are exported to, typed after `X`, or the whole table dumped to.
+    export_input: TextInput,
+    /// The text typed after `/`, which is highlighted without hiding the records around it.
+    find_input: TextInput,
+    /// Indices in `filtered_records` of the records containing the found text.
+    find_matches: Vec<usize>,
+    /// The bookmark being jumped to, until its table is shown and its record found.
+    pending_bookmark: Option<Bookmark>,
+    /// The table and partition key value of a followed reference, until the table's key is known.
+    pending_reference: Option<(String, String)>,
+    /// What became of the last item read again with GetItem.
+    refresh_status: Option<String>,
+    /// How the last scan of the table for the find text went, or that it is running.
+    deep_find_status: Option<String>,
+    /// The items written to files to be kept open elsewhere, written again when read anew.
+    torn_out: Vec<TearOut>,
+    area: Rect,
+    clicks: ClickTracker,
+    /// Set while the row detail popup is drawn over the list.
+    covered: bool,
+    /// When copying a large result set was first requested; repeating it in time confirms.
+    copy_requested_at: Option<Instant>,
+    /// When a copy made in privacy mode came out redacted; repeating it in time copies the raw
+    /// values.
+    raw_copy_requested_at: Option<Instant>,
+    /// Set while the debug overlay is shown, which reports the memory held by records.
+    debug_overlay: bool,
+    /// Set while a request for table data is in flight.
+    loading: bool,
+    loading_state: ThrobberState,
+}
+
+/// Everything a table remembers while its tab is in the background.
+#[derive(Default)]
+struct TableTab {
     title: String,
-    records: Vec<String>,
-    filtered_records: Vec<String>,
+    records: RecordStore,
+    /// Indices in `records` of the records that pass the filter.
+    filtered_records: Vec<usize>,
     has_more: bool,
     list_state: ListState,
     selected_row: String,
     collection_name: String,
     fetching: bool,
+    /// Whether the first page has been received; pages for background tabs are dropped, so a tab
+    /// may have to reload when it is activated.
+    loaded: bool,
     aprox_count: i64,
     scroll_bar_state: ScrollbarState,
-    mode: Mode,
-    filter_input: String,
-    character_index: usize,
-    sort_key_index: usize,
-    partition_key_index: usize,
-    partition_key: Option<String>,
-    sort_key: Option<String>,
-    partition_key_value: String,
-    sort_key_value: String,
-    query_focus: QueryFocus,
+    filter_input: TextInput,
+    /// Set from a value picked in an item, applied together with the typed filter.
+    value_filter: Option<ValueFilter>,
+    partition_key: Option<KeyAttribute>,
+    sort_key: Option<KeyAttribute>,
+    partition_key_input: TextInput,
+    sort_key_input: TextInput,
+    partition_key_suggestions: KeySuggestions,
+    sort_key_suggestions: KeySuggestions,
+    /// The preset the sort key input was last filled with, until it is edited.
+    sort_key_range: Option<TimeRange>,
+    limit_input: TextInput,
+    truncated: bool,
+    /// Index in `records` of the first record of every page received so far.
+    page_starts: Vec<usize>,
+    /// What the credentials may do with the table, once probed.
+    permissions: Option<Permissions>,
+    /// Set by a refresh until the previously selected record is found again.
+    restore: Option<RestoreSelection>,
+    /// How the sort key stores timestamps, when the loaded records suggest it does.
+    timestamp_format: Option<TimestampFormat>,
+    /// The partition key value of the query on screen, or `None` for a scan.
+    queried_partition: Option<String>,
+    follow: Option<Follow>,
+    /// The row to select once enough pages are loaded to reach it.
+    pending_goto: Option<usize>,
+    /// Set once the result set is pinned, which keeps it as it is in its own tab while the
+    /// table is opened again in another.
+    pinned: bool,
+}
+
+/// An attribute, named by its dotted path, that records must hold with exactly this value.
+struct ValueFilter {
+    path: String,
+    value: Value,
+}
+
+/// A query that keeps being run for items with a sort key past the latest one on screen, which
+/// are appended below the others like `tail -f`.
+struct Follow {
+    /// The latest sort key value seen so far, which each query asks for the items after. `None`
+    /// while the partition is empty, until the first query reads it from its start.
+    after: Option<String>,
+    next_at: Instant,
+    pending: bool,
+}
+
+/// Where the selection was when the records on screen were refreshed.
+struct RestoreSelection {
+    key: Option<RecordKey>,
+    index: usize,
+    /// Rows between the top of the list and the selected row.
+    rows_above: usize,
+    /// How many records were loaded; at most as many are fetched again to find the record.
+    loaded: usize,
+}
+
+/// The partition key value, and sort key value if the table has one, of a record.
+type RecordKey = (Value, Option<Value>);
+
+impl TableTab {
+    fn new() -> Self {
+        Self {
+            title: "Data".to_string(),
+            limit_input: TextInput::new()
+                .placeholder("no limit")
+                .char_filter(|c| c.is_ascii_digit()),
+            ..Self::default()
+        }
+    }
+
+    fn is_filtered(&self) -> bool {
+        !self.filter_input.is_empty() || self.value_filter.is_some()
+    }
+
+    fn clear_filter(&mut self) {
+        self.filter_input.clear();
+        self.value_filter = None;
+    }
 }
 
 #[derive(Default)]
@@ -58,6 +232,12 @@ enum Mode {
     View,
     Filtering,
     Querying,
+    GoingTo,
+    NamingPin,
+    DefiningColumn,
+    Exporting,
+    DumpingTable,
+    Finding,
 }
 
 #[derive(Default)]
@@ -65,274 +245,1533 @@ enum QueryFocus {
     #[default]
     PartitionKey,
     SortKey,
+    Limit,
 }
 
 impl DataBox {
     pub fn new() -> Self {
         Self {
-            title: "Data".to_string(),
+            tab: TableTab::new(),
             ..Self::default()
         }
     }
 
+    /// Shows the tab at `index`, parking the current one in its slot.
+    fn switch_to(&mut self, index: usize) {
+        if index == self.active_tab || index >= self.tabs.len() {
+            return;
+        }
+
+        std::mem::swap(&mut self.tab, &mut self.tabs[self.active_tab]);
+        std::mem::swap(&mut self.tab, &mut self.tabs[index]);
+        self.active_tab = index;
+
+        // A page requested before the switch was dropped, so it has to be requested again.
+        self.tab.fetching = false;
+        if let Some(follow) = &mut self.tab.follow {
+            follow.pending = false;
+        }
+    }
+
+    /// Opens `table` in a new tab, or shows its tab if it is already open.
+    fn open_tab(&mut self, table: &str) {
+        if self.tab.collection_name == table && !self.tab.pinned {
+            return;
+        }
+
+        if let Some(index) = self
+            .tabs
+            .iter()
+            .position(|tab| tab.collection_name == table && !tab.pinned)
+        {
+            return self.switch_to(index);
+        }
+
+        if self.tabs.is_empty() {
+            self.tabs.push(TableTab::default());
+            self.tab = TableTab::new();
+            self.active_tab = 0;
+        } else {
+            self.tabs.push(TableTab::new());
+            std::mem::swap(&mut self.tab, &mut self.tabs[self.active_tab]);
+            self.active_tab = self.tabs.len() - 1;
+            std::mem::swap(&mut self.tab, &mut self.tabs[self.active_tab]);
+        }
+    }
+
+    fn close_tab(&mut self) -> Result<()> {
+        if self.tabs.is_empty() {
+            return Ok(());
+        }
+
+        let command_tx = self.command_tx.as_ref().unwrap();
+        // The table may still be open in a tab of its own.
+        if !self.tab.pinned {
+            command_tx.send(Action::TabClosed(self.tab.collection_name.clone()))?;
+        }
+
+        self.tabs.remove(self.active_tab);
+        if self.tabs.is_empty() {
+            self.tab = TableTab::new();
+            self.active_tab = 0;
+            command_tx.send(Action::SelectTableMode)?;
+            return Ok(());
+        }
+
+        self.active_tab = self.active_tab.min(self.tabs.len() - 1);
+        self.tab = std::mem::take(&mut self.tabs[self.active_tab]);
+        self.activate_tab()
+    }
+
+    /// Asks for the name of the result set to pin, offering the table and the partition queried,
+    /// or the name it was pinned under when renaming it.
+    fn name_pin(&mut self) -> Result<()> {
+        if !self.tab.loaded {
+            return self
+                .command_tx
+                .as_ref()
+                .unwrap()
+                .send(Action::ExitPinName)
+                .map_err(Into::into);
+        }
+
+        self.pin_input = TextInput::new().placeholder(&self.default_pin_name());
+        if self.tab.pinned {
+            self.pin_input
+                .insert_str(self.tab.title.trim_start_matches("📌 "));
+        }
+        self.mode = Mode::NamingPin;
+        Ok(())
+    }
+
+    /// The name a result set is pinned under when none is typed.
+    fn default_pin_name(&self) -> String {
+        match &self.tab.queried_partition {
+            Some(partition) => format!("{} {partition}", self.tab.collection_name),
+            None => self.tab.collection_name.clone(),
+        }
+    }
+
+    /// Keeps the result set shown in its tab under `name`, so selecting the table again opens it
+    /// in a new tab rather than replacing the results.
+    fn pin_results(&mut self, name: &str) {
+        self.tab.pinned = true;
+        self.tab.follow = None;
+        self.tab.fetching = false;
+        self.tab.title = format!("📌 {name}");
+    }
+
+    /// Tells the app which table is shown and reloads it if its data never arrived.
+    fn activate_tab(&mut self) -> Result<()> {
+        let command_tx = self.command_tx.as_ref().unwrap();
+        let table = self.tab.collection_name.clone();
+
+        command_tx.send(Action::ActivateTab(table.clone()))?;
+        if !self.tab.loaded {
+            command_tx.send(Action::FetchTableData(table))?;
+        }
+        Ok(())
+    }
+
+    fn title(&self) -> Line<'static> {
+        let mut title = self.tab_titles();
+        if self.tab.follow.is_some() {
+            title.push_span(Span::styled(
+                " following",
+                Style::new().fg(self.config.config.theme.loading),
+            ));
+        }
+        if self.config.config.privacy_mode {
+            title.push_span(Span::styled(
+                " private",
+                Style::new().fg(self.config.config.theme.muted),
+            ));
+        }
+        if self.loading {
+            title.push_span(" ");
+            title.push_span(pane_spinner(&self.loading_state, &self.config.config.theme));
+        }
+        title
+    }
+
+    fn tab_titles(&self) -> Line<'static> {
+        let theme = self.config.config.theme;
+        if self.tabs.len() < 2 {
+            return Line::from(self.tab.title.clone());
+        }
+
+        let mut spans = Vec::new();
+        for (index, tab) in self.tabs.iter().enumerate() {
+            if index > 0 {
+                spans.push(Span::raw(" | "));
+            }
+            if index == self.active_tab {
+                spans.push(Span::styled(
+                    self.tab.title.clone(),
+                    Style::new()
+                        .fg(theme.active_border)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            } else {
+                spans.push(Span::styled(
+                    tab.title.clone(),
+                    Style::new().fg(theme.inactive_border),
+                ));
+            }
+        }
+        Line::from(spans)
+    }
+
+    /// The probed operations, with the ones that are not allowed greyed out.
+    fn permissions_line(&self) -> Option<Line<'static>> {
+        let theme = self.config.config.theme;
+        let permissions = self.tab.permissions.as_ref()?;
+
+        let mut spans = vec![Span::raw(format!(" {}: ", permissions.summary()))];
+        for (index, (operation, permission)) in permissions.operations.iter().enumerate() {
+            if index > 0 {
+                spans.push(Span::raw(" "));
+            }
+            spans.push(match permission {
+                Permission::Allowed => Span::styled(operation.label(), Style::new().fg(theme.text)),
+                Permission::Denied(_) => Span::styled(
+                    operation.label(),
+                    Style::new()
+                        .fg(theme.muted)
+                        .add_modifier(Modifier::CROSSED_OUT),
+                ),
+                Permission::Unknown(_) => Span::styled(
+                    format!("{}?", operation.label()),
+                    Style::new().fg(theme.muted),
+                ),
+            });
+        }
+        spans.push(Span::raw(" "));
+        Some(Line::from(spans).right_aligned())
+    }
+
     pub fn apply_filter(&mut self) {
-        if self.filter_input.is_empty() {
+        let soft_delete = self.soft_delete_view();
+        let encrypted = encryption::attributes_of(
+            &self.config.config.encrypted_attributes,
+            &self.tab.collection_name,
+        );
+        if !self.hides_records() {
             // If no filter input, show all records
-            self.filtered_records = self.records.clone();
+            self.tab.filtered_records = (0..self.tab.records.len()).collect();
         } else {
             let matcher = SkimMatcherV2::default();
-            let keywords: Vec<&str> = self.filter_input.split_whitespace().collect();
+            // Keywords like `has:email` check an attribute; the others search for text.
+            let (attribute_filters, keywords): (Vec<_>, Vec<_>) = self
+                .tab
+                .filter_input
+                .value()
+                .split_whitespace()
+                .map(|keyword| (AttributeFilter::parse(keyword), keyword))
+                .partition(|(filter, _)| filter.is_some());
+            let attribute_filters: Vec<AttributeFilter> = attribute_filters
+                .into_iter()
+                .filter_map(|(filter, _)| filter)
+                .collect();
+            let keywords: Vec<&str> = keywords.into_iter().map(|(_, keyword)| keyword).collect();
 
-            self.filtered_records = self
-                .records
-                .iter()
-                .filter(|row| {
+            let key_names = self.key_names();
+            let mut scored: Vec<(usize, i64)> = (0..self.tab.records.len())
+                .filter_map(|index| {
+                    let row = self.tab.records.get(index)?;
                     // Parse each record as JSON
-                    if let Ok(parsed_row) = serde_json::from_str::<Value>(row) {
-                        // Check if all keywords are found in the JSON object
-                        keywords.iter().all(|keyword| {
-                            self.keyword_matches_json(keyword, &parsed_row, &matcher)
-                        })
-                    } else {
-                        false
+                    let parsed_row = serde_json::from_str::<Value>(&row).ok()?;
+                    let value_matches = self.tab.value_filter.as_ref().is_none_or(|filter| {
+                        pin::lookup(&parsed_row, &filter.path) == Some(&filter.value)
+                    });
+                    let view_shows = soft_delete.is_none_or(|(attribute, view)| {
+                        view.shows(soft_delete::is_deleted(&parsed_row, attribute))
+                    });
+                    if !value_matches
+                        || !view_shows
+                        || !attribute_filters
+                            .iter()
+                            .all(|filter| filter.matches(&parsed_row))
+                    {
+                        return None;
                     }
+                    // Ciphertext would match keywords at random.
+                    let searched = if encrypted.is_empty() {
+                        Cow::Borrowed(&parsed_row)
+                    } else {
+                        Cow::Owned(encryption::without_encrypted(&parsed_row, encrypted))
+                    };
+                    // Every keyword must be found in the JSON object.
+                    let score = keywords.iter().try_fold(0, |total, keyword| {
+                        relevance::keyword_score(&matcher, keyword, &searched, &key_names)
+                            .map(|score| total + score)
+                    })?;
+                    Some((index, score))
                 })
-                .cloned()
                 .collect();
+            // The most relevant records first, in the order they were read when equally so.
+            if !keywords.is_empty() {
+                scored.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+            }
+            self.tab.filtered_records = scored.into_iter().map(|(index, _)| index).collect();
+        }
+        if !self.find_input.is_empty() {
+            self.update_find_matches();
         }
     }
 
-    // Helper function to check if a keyword matches any field or value in the JSON
-    fn keyword_matches_json(&self, keyword: &str, json: &Value, matcher: &SkimMatcherV2) -> bool {
-        match json {
-            Value::Object(map) => {
-                for (key, value) in map {
-                    // Check if the keyword matches the field name
-                    if matcher.fuzzy_match(key, keyword).is_some() {
-                        return true;
-                    }
-                    // Recursively check values in case of nested objects/arrays
-                    if self.keyword_matches_json(keyword, value, matcher) {
-                        return true;
+    /// The attribute marking the table's items as deleted, and which of its items are shown.
+    fn soft_delete_view(&self) -> Option<(&str, SoftDeleteView)> {
+        let table = &self.tab.collection_name;
+        let soft_delete = soft_delete::for_table(&self.config.config.soft_delete, table)?;
+        let view = self
+            .soft_delete_views
+            .get(table)
+            .copied()
+            .unwrap_or_default();
+        Some((&soft_delete.attribute, view))
+    }
+
+    /// Whether some of the records are left out, by the filter or the view of soft-deleted items.
+    fn hides_records(&self) -> bool {
+        self.tab.is_filtered()
+            || self
+                .soft_delete_view()
+                .is_some_and(|(_, view)| view != SoftDeleteView::All)
+    }
+
+    /// Moves the table on to its next view of soft-deleted items. Tables without a soft-delete
+    /// attribute show every item.
+    fn cycle_soft_delete_view(&mut self) -> Result<()> {
+        let Some((_, view)) = self.soft_delete_view() else {
+            self.refresh_status = Some("no soft-delete attribute configured".to_string());
+            return Ok(());
+        };
+        let view = view.next();
+        let table = self.tab.collection_name.clone();
+        self.soft_delete_views.insert(table.clone(), view);
+        self.select_first();
+        self.apply_filter();
+        self.command_tx
+            .as_ref()
+            .unwrap()
+            .send(Action::SetSoftDeleteView(table, view))?;
+        Ok(())
+    }
+
+    /// Shows only the records holding `value` at `path`, keeping the typed filter.
+    fn filter_by_value(&mut self, path: String, value: Value) {
+        self.tab.value_filter = Some(ValueFilter { path, value });
+        self.select_first();
+        self.apply_filter();
+    }
+
+    fn update_find_matches(&mut self) {
+        let needle = self.find_input.value().to_ascii_lowercase();
+        self.find_matches = (0..self.tab.filtered_records.len())
+            .filter(|index| {
+                self.filtered_record(*index)
+                    .is_some_and(|record| record.to_ascii_lowercase().contains(&needle))
+            })
+            .collect();
+    }
+
+    /// Selects the next match after the selected record, or the one before it, wrapping around
+    /// at either end.
+    fn jump_to_match(&mut self, forward: bool) {
+        let selected = self.tab.list_state.selected().unwrap_or(0);
+        let next = if forward {
+            self.find_matches
+                .iter()
+                .find(|index| **index > selected)
+                .or(self.find_matches.first())
+        } else {
+            self.find_matches
+                .iter()
+                .rev()
+                .find(|index| **index < selected)
+                .or(self.find_matches.last())
+        };
+        if let Some(index) = next.copied() {
+            self.select_at(index);
+        }
+    }
+
+    fn clear_find(&mut self) {
+        self.find_input.clear();
+        self.find_matches.clear();
+        self.deep_find_status = None;
+    }
+
+    /// Scans the table for the find text, stopping at `max_matches` matches unless it is zero.
+    fn deep_find(&mut self, max_matches: usize) -> Result<()> {
+        if self.find_input.is_empty() {
+            return Ok(());
+        }
+        let needle = self.find_input.value().to_string();
+        self.deep_find_status = Some(format!("scanning the table for \"{needle}\""));
+        self.command_tx
+            .as_ref()
+            .unwrap()
+            .send(Action::StartDeepFind(
+                self.tab.collection_name.clone(),
+                needle,
+                max_matches,
+            ))?;
+        Ok(())
+    }
+
+    /// Adds the items a deep find matched that are not loaded yet as a page of their own, and
+    /// selects the first of them.
+    fn add_deep_find_matches(&mut self, result: DeepFindResult) {
+        if let Some(error) = result.error {
+            self.deep_find_status = Some(format!("scan failed: {error}"));
+            return;
+        }
+
+        let loaded: HashSet<String> = (0..self.tab.records.len())
+            .filter_map(|index| self.tab.records.get(index))
+            .map(Cow::into_owned)
+            .collect();
+        let new_records: Vec<String> = result
+            .matches
+            .iter()
+            .filter(|record| !loaded.contains(*record))
+            .cloned()
+            .collect();
+        if !new_records.is_empty() {
+            self.append_page(new_records);
+            self.truncate_to_max_items();
+            self.apply_filter();
+        }
+
+        let first = result.matches.first().and_then(|first| {
+            self.find_matches.iter().copied().find(|index| {
+                self.filtered_record(*index)
+                    .is_some_and(|record| record == first.as_str())
+            })
+        });
+        if let Some(index) = first {
+            self.select_at(index);
+        }
+
+        self.deep_find_status = Some(format!(
+            "{} found scanning {} items{}",
+            result.matches.len(),
+            result.scanned,
+            if result.complete {
+                ""
+            } else {
+                ", stopped early"
+            }
+        ));
+    }
+
+    fn filtered_record(&self, index: usize) -> Option<Cow<'_, str>> {
+        self.tab.records.get(*self.tab.filtered_records.get(index)?)
+    }
+
+    fn iter_filtered_records(&self) -> impl Iterator<Item = Cow<'_, str>> {
+        (0..self.tab.filtered_records.len()).filter_map(|index| self.filtered_record(index))
+    }
+
+    /// Sends the records that pass the filter, keyed by primary key, to be kept as a snapshot.
+    /// Records whose key cannot be read are keyed by their contents.
+    fn take_snapshot(&self) -> Result<()> {
+        let records = self
+            .iter_filtered_records()
+            .map(|record| {
+                let key = match self.record_key(&record) {
+                    Some((partition_key, sort_key)) => {
+                        snapshot_key(&partition_key, sort_key.as_ref())
                     }
+                    None => record.to_string(),
+                };
+                (key, record.into_owned())
+            })
+            .collect();
+
+        self.command_tx
+            .as_ref()
+            .unwrap()
+            .send(Action::TransmitSnapshotRecords(
+                self.tab.collection_name.clone(),
+                records,
+            ))?;
+        Ok(())
+    }
+
+    /// The key attributes of the selected record, as a JSON object.
+    fn selected_key(&self) -> Option<String> {
+        let ((partition_key, sort_key), partition_key_attribute) = self
+            .tab
+            .list_state
+            .selected()
+            .and_then(|index| self.filtered_record(index))
+            .and_then(|record| self.record_key(&record))
+            .zip(self.tab.partition_key.as_ref())?;
+
+        let mut key = serde_json::Map::new();
+        key.insert(partition_key_attribute.name.clone(), partition_key);
+        if let (Some(sort_key_attribute), Some(sort_key)) = (&self.tab.sort_key, sort_key) {
+            key.insert(sort_key_attribute.name.clone(), sort_key);
+        }
+        Some(Value::Object(key).to_string())
+    }
+
+    /// Offers the key of the selected record to be copied in the format another tool takes.
+    fn copy_key(&mut self) -> Result<()> {
+        let Some(((partition_key, sort_key), partition_key_attribute)) = self
+            .tab
+            .list_state
+            .selected()
+            .and_then(|index| self.filtered_record(index))
+            .and_then(|record| self.record_key(&record))
+            .zip(self.tab.partition_key.clone())
+        else {
+            self.refresh_status = Some("the key of the table is not known yet".to_string());
+            return Ok(());
+        };
+
+        let mut key = vec![(partition_key_attribute, partition_key)];
+        if let (Some(sort_key_attribute), Some(sort_key)) = (self.tab.sort_key.clone(), sort_key) {
+            key.push((sort_key_attribute, sort_key));
+        }
+        self.command_tx
+            .as_ref()
+            .unwrap()
+            .send(Action::ShowKeyCopy(key))?;
+        Ok(())
+    }
+
+    /// Opens the region comparison for the key of the selected record.
+    fn compare_regions(&self) -> Result<()> {
+        let Some(key) = self.selected_key() else {
+            return Ok(());
+        };
+
+        self.command_tx
+            .as_ref()
+            .unwrap()
+            .send(Action::ShowRegionComparison(
+                self.tab.collection_name.clone(),
+                key,
+            ))?;
+        Ok(())
+    }
+
+    fn bookmark_selected(&self) -> Result<()> {
+        let Some((key, record)) = self.selected_key().zip(
+            self.tab
+                .list_state
+                .selected()
+                .and_then(|index| self.filtered_record(index)),
+        ) else {
+            return Ok(());
+        };
+
+        self.command_tx
+            .as_ref()
+            .unwrap()
+            .send(Action::ToggleBookmark(Bookmark {
+                table: self.tab.collection_name.clone(),
+                key,
+                item: Some(record.into_owned()),
+            }))?;
+        Ok(())
+    }
+
+    /// The key attributes shown first in the records of the table, when they are shown first.
+    fn first_attributes(&self) -> Vec<String> {
+        if !self.config.config.key_attributes_first {
+            return Vec::new();
+        }
+        self.key_names()
+    }
+
+    /// The names of the table's key attributes, once it has been described.
+    fn key_names(&self) -> Vec<String> {
+        [&self.tab.partition_key, &self.tab.sort_key]
+            .into_iter()
+            .flatten()
+            .map(|key| key.name.clone())
+            .collect()
+    }
+
+    /// Writes the selected record to a file, which is kept up to date as the record is read
+    /// again by a refresh.
+    fn tear_out_selected(&mut self) {
+        let Some((key, record)) = self.selected_key().zip(
+            self.tab
+                .list_state
+                .selected()
+                .and_then(|index| self.filtered_record(index))
+                .map(Cow::into_owned),
+        ) else {
+            return;
+        };
+
+        let table = &self.tab.collection_name;
+        let index = match self
+            .torn_out
+            .iter()
+            .position(|tear_out| tear_out.matches(table, &record))
+        {
+            Some(index) => index,
+            None => {
+                self.torn_out.push(TearOut::new(table, &key));
+                self.torn_out.len() - 1
+            }
+        };
+        let record = if self.config.config.privacy_mode {
+            redact_record(&record)
+        } else {
+            record
+        };
+        let first = self.first_attributes();
+        let tear_out = &mut self.torn_out[index];
+        self.refresh_status = Some(match tear_out.write(&record, &first) {
+            Ok(_) => format!("torn out to {}", tear_out.path.display()),
+            Err(error) => error,
+        });
+    }
+
+    /// Writes the torn out items of the table again if the records just read hold a newer
+    /// version of them.
+    fn update_torn_out(&mut self) {
+        let first = self.first_attributes();
+        let table = &self.tab.collection_name;
+        for tear_out in self
+            .torn_out
+            .iter_mut()
+            .filter(|tear_out| &tear_out.table == table)
+        {
+            let Some(record) = (0..self.tab.records.len())
+                .filter_map(|index| self.tab.records.get(index))
+                .find(|record| tear_out.matches(table, record))
+            else {
+                continue;
+            };
+            let record = if self.config.config.privacy_mode {
+                redact_record(&record)
+            } else {
+                record.into_owned()
+            };
+            if let Err(error) = tear_out.write(&record, &first) {
+                self.refresh_status = Some(error);
+            }
+        }
+    }
+
+    /// Reads the selected record again, to replace it with its latest version.
+    fn refresh_selected(&self) -> Result<()> {
+        let Some(key) = self.selected_key() else {
+            return Ok(());
+        };
+
+        self.command_tx
+            .as_ref()
+            .unwrap()
+            .send(Action::GetItem(self.tab.collection_name.clone(), key))?;
+        Ok(())
+    }
+
+    /// Puts an item that was read again in place of its record, and in the detail view if that
+    /// shows it.
+    fn update_item(&mut self, key: &str, item: Result<Option<String>, String>) -> Result<()> {
+        let Some(index) = (0..self.tab.records.len()).find(|index| {
+            self.tab
+                .records
+                .get(*index)
+                .is_some_and(|record| record_has_key(&record, key))
+        }) else {
+            return Ok(());
+        };
+
+        let item = match item {
+            Ok(Some(item)) => item,
+            Ok(None) => {
+                self.refresh_status = Some("item no longer exists".to_string());
+                return Ok(());
+            }
+            Err(error) => {
+                self.refresh_status = Some(format!("refresh failed: {error}"));
+                return Ok(());
+            }
+        };
+
+        let shown = self
+            .tab
+            .records
+            .get(index)
+            .is_some_and(|record| record == self.tab.selected_row);
+        self.tab.records.replace(index, item.clone());
+        self.apply_filter();
+        self.refresh_status = Some("item refreshed".to_string());
+        self.update_torn_out();
+
+        if self.covered && shown {
+            self.tab.selected_row = item.clone();
+            self.command_tx
+                .as_ref()
+                .unwrap()
+                .send(Action::TransmitSelectedTableDataRow(item))?;
+        }
+        Ok(())
+    }
+
+    /// Shows the table of the bookmark, then selects its record once it is loaded.
+    fn jump_to_bookmark(&mut self, bookmark: Bookmark) -> Result<()> {
+        self.clear_find();
+        if self.tab.collection_name != bookmark.table {
+            match self
+                .tabs
+                .iter()
+                .position(|tab| tab.collection_name == bookmark.table)
+            {
+                Some(index) => {
+                    self.switch_to(index);
+                    self.activate_tab()?;
+                }
+                None => {
+                    let command_tx = self.command_tx.as_ref().unwrap();
+                    command_tx.send(Action::TransmitSelectedTable(bookmark.table.clone()))?;
+                    command_tx.send(Action::FetchTableData(bookmark.table.clone()))?;
                 }
-                false
             }
-            Value::Array(arr) => {
-                // Check each item in the array
-                arr.iter()
-                    .any(|value| self.keyword_matches_json(keyword, value, matcher))
+        }
+
+        self.pending_bookmark = Some(bookmark);
+        self.select_bookmark()
+    }
+
+    /// Selects the record of the pending bookmark, clearing the filter so it is shown and
+    /// fetching pages until it is found.
+    fn select_bookmark(&mut self) -> Result<()> {
+        let Some(bookmark) = self.pending_bookmark.take() else {
+            return Ok(());
+        };
+        if bookmark.table != self.tab.collection_name || !self.tab.loaded {
+            self.pending_bookmark = Some(bookmark);
+            return Ok(());
+        }
+
+        if self.tab.is_filtered() {
+            self.tab.clear_filter();
+            self.apply_filter();
+        }
+        let found = (0..self.tab.filtered_records.len()).find(|index| {
+            self.filtered_record(*index)
+                .is_some_and(|record| bookmark.matches(&record))
+        });
+
+        match found {
+            Some(index) => self.select_at(index),
+            None if self.can_fetch_more() => {
+                self.pending_bookmark = Some(bookmark);
+                self.tab.fetching = true;
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::FetchMoreTableData(self.tab.collection_name.clone()))?;
             }
-            Value::String(s) => matcher.fuzzy_match(s, keyword).is_some(),
-            Value::Number(n) => matcher.fuzzy_match(&n.to_string(), keyword).is_some(),
-            Value::Bool(b) => matcher.fuzzy_match(&b.to_string(), keyword).is_some(),
-            Value::Null => false,
+            None => {}
         }
+        Ok(())
+    }
+
+    /// Queries the table a reference was followed to for the referenced item, once its partition
+    /// key is known.
+    fn follow_pending_reference(&mut self) -> Result<()> {
+        let Some((table, value)) = self
+            .pending_reference
+            .take_if(|(table, _)| *table == self.tab.collection_name)
+        else {
+            return Ok(());
+        };
+        let Some(partition_key) = self.tab.partition_key.clone() else {
+            return Ok(());
+        };
+
+        self.tab.clear_filter();
+        self.reset_query_inputs();
+        self.tab.partition_key_input.insert_str(&value);
+        self.tab.queried_partition = Some(value.clone());
+        self.tab.follow = None;
+        self.query_pending = true;
+        self.command_tx
+            .as_ref()
+            .unwrap()
+            .send(Action::GetTableQueryDataByPk(
+                table,
+                partition_key,
+                value,
+                None,
+            ))?;
+        Ok(())
     }
 
     pub fn set_title(&mut self, new_title: &str) {
-        self.title = new_title.to_string();
+        self.tab.title = new_title.to_string();
     }
 
     fn select_next(&mut self) {
-        self.list_state.select_next();
-        self.update_scroll_pos(self.list_state.selected().unwrap());
+        self.tab.list_state.select_next();
+        self.update_scroll_pos(self.tab.list_state.selected().unwrap());
     }
 
     fn select_previous(&mut self) {
-        self.list_state.select_previous();
-        self.update_scroll_pos(self.list_state.selected().unwrap());
+        self.tab.list_state.select_previous();
+        self.update_scroll_pos(self.tab.list_state.selected().unwrap());
     }
 
     pub fn select_first(&mut self) {
-        self.list_state.select_first();
-        self.update_scroll_pos(self.list_state.selected().unwrap());
+        self.tab.list_state.select_first();
+        self.update_scroll_pos(self.tab.list_state.selected().unwrap());
     }
 
     fn select_last(&mut self) {
-        self.list_state.select_last();
-        self.update_scroll_pos(self.list_state.selected().unwrap());
+        self.tab.list_state.select_last();
+        self.update_scroll_pos(self.tab.list_state.selected().unwrap());
     }
 
-    fn scroll_up(&mut self) {
-        self.list_state.scroll_up_by(5);
-        self.update_scroll_pos(self.list_state.selected().unwrap());
+    /// Rows of records that fit in the pane, inside its borders.
+    fn page_lines(&self) -> i32 {
+        self.area.height.saturating_sub(2).max(1) as i32
     }
 
-    fn scroll_down(&mut self) {
-        self.list_state.scroll_down_by(5);
-        self.update_scroll_pos(self.list_state.selected().unwrap());
+    fn set_selected(&mut self) {
+        if let Some(i) = self.tab.list_state.selected() {
+            if let Some(record) = self.tab.records.get(i) {
+                self.tab.selected_row = record.into_owned();
+            }
+        }
     }
 
-    fn set_selected(&mut self) {
-        if let Some(i) = self.list_state.selected() {
-            self.selected_row = self.records[i].to_string();
+    /// Drops records past `max_items` so an unbounded scan or query cannot exhaust memory.
+    fn truncate_to_max_items(&mut self) {
+        let max_items = self.config.config.max_items;
+        if self.tab.records.len() >= max_items
+            && (self.tab.has_more || self.tab.records.len() > max_items)
+        {
+            self.tab.records.truncate(max_items);
+            self.tab.page_starts.retain(|start| *start < max_items);
+            self.tab.truncated = true;
+        }
+    }
+
+    /// Bytes held in memory by the records of every open tab and their filter indices.
+    fn records_bytes(&self) -> usize {
+        std::iter::once(&self.tab)
+            .chain(&self.tabs)
+            .map(|tab| {
+                tab.records.resident_bytes() + tab.filtered_records.len() * size_of::<usize>()
+            })
+            .sum()
+    }
+
+    fn record_key(&self, record: &str) -> Option<RecordKey> {
+        let record: Value = serde_json::from_str(record).ok()?;
+        let partition_key = record.get(&self.tab.partition_key.as_ref()?.name)?.clone();
+        let sort_key = self
+            .tab
+            .sort_key
+            .as_ref()
+            .and_then(|sort_key| record.get(&sort_key.name).cloned());
+        Some((partition_key, sort_key))
+    }
+
+    /// Pairs each record with its primary key, as text, once the key attributes are known.
+    fn keyed(&self, records: Vec<String>) -> Vec<(Option<String>, String)> {
+        records
+            .into_iter()
+            .map(|record| {
+                let key = self.record_key(&record).map(|(partition_key, sort_key)| {
+                    Value::Array(vec![partition_key, sort_key.unwrap_or_default()]).to_string()
+                });
+                (key, record)
+            })
+            .collect()
+    }
+
+    /// Appends a page of records after the ones loaded. Pages overlapping the loaded records, as
+    /// when paging through items written meanwhile, update the records with the same key in
+    /// place rather than repeating them.
+    fn append_page(&mut self, records: Vec<String>) {
+        let start = self.tab.records.len();
+        let records = self.keyed(records);
+        if self.tab.records.merge(records) > 0 {
+            self.tab.page_starts.push(start);
+        }
+    }
+
+    /// Remembers the selected record so it can be selected again after a refresh.
+    fn start_restoring_selection(&mut self) {
+        let Some(index) = self.tab.list_state.selected() else {
+            return;
+        };
+
+        self.tab.restore = Some(RestoreSelection {
+            key: self
+                .filtered_record(index)
+                .and_then(|record| self.record_key(&record)),
+            index,
+            rows_above: index.saturating_sub(self.tab.list_state.offset()),
+            loaded: self.tab.records.len(),
+        });
+    }
+
+    /// Selects the record that was selected before a refresh, fetching more pages until it is
+    /// found or as many records as before are loaded. Falls back to the previous index.
+    fn restore_selection(&mut self) -> Result<()> {
+        let Some(restore) = self.tab.restore.take() else {
+            return Ok(());
+        };
+
+        let found = restore.key.as_ref().and_then(|key| {
+            (0..self.tab.filtered_records.len()).position(|index| {
+                self.filtered_record(index)
+                    .and_then(|record| self.record_key(&record))
+                    .as_ref()
+                    == Some(key)
+            })
+        });
+
+        let index = match found {
+            Some(index) => index,
+            None if self.tab.records.len() < restore.loaded && self.can_fetch_more() => {
+                self.tab.restore = Some(restore);
+                self.tab.fetching = true;
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::FetchMoreTableData(self.tab.collection_name.clone()))?;
+                return Ok(());
+            }
+            None => restore
+                .index
+                .min(self.tab.filtered_records.len().saturating_sub(1)),
+        };
+
+        self.tab.list_state.select(Some(index));
+        *self.tab.list_state.offset_mut() = index.saturating_sub(restore.rows_above);
+        self.update_scroll_pos(index);
+        Ok(())
+    }
+
+    /// Moves the selection to the start of the page before the selected row, or to the start of
+    /// its own page if the row is in the middle of one. Pages are only tracked without a filter.
+    fn select_previous_page(&mut self) {
+        let Some(selected) = self.tab.list_state.selected() else {
+            return;
+        };
+        if self.hides_records() {
+            return;
+        }
+
+        let start = self
+            .tab
+            .page_starts
+            .iter()
+            .rev()
+            .find(|start| **start < selected)
+            .copied()
+            .unwrap_or(0);
+        self.select_at(start);
+    }
+
+    fn select_next_page(&mut self) -> Result<()> {
+        let Some(selected) = self.tab.list_state.selected() else {
+            return Ok(());
+        };
+        if self.hides_records() {
+            return Ok(());
         }
+
+        match self.tab.page_starts.iter().find(|start| **start > selected) {
+            Some(&start) => self.select_at(start),
+            None => self.select_last(),
+        }
+        self.fetch_more_if_near_end()
+    }
+
+    /// The 1-based number of the page holding the selected row.
+    fn current_page(&self) -> usize {
+        let selected = self.tab.list_state.selected().unwrap_or(0);
+        self.tab
+            .page_starts
+            .iter()
+            .filter(|start| **start <= selected)
+            .count()
+            .max(1)
     }
 
     fn update_scroll_pos(&mut self, pos: usize) {
-        self.scroll_bar_state = self.scroll_bar_state.position(pos);
+        self.tab.scroll_bar_state = self.tab.scroll_bar_state.position(pos);
     }
 
-    fn copy_selected_row_to_clipboard(&self) {
-        if let Some(i) = self.list_state.selected() {
-            let selected_row = &self.records[i];
+    fn copy_selected_row_to_clipboard(&mut self) {
+        if let Some(i) = self.tab.list_state.selected() {
+            let Some(selected_row) = self.tab.records.get(i).map(Cow::into_owned) else {
+                return;
+            };
+            let text = if self.copies_redacted() {
+                redact_record(&selected_row)
+            } else {
+                selected_row
+            };
+            let text = key_order::reorder(Cow::Owned(text), &self.first_attributes()).into_owned();
 
             let mut ctx: ClipboardContext =
                 ClipboardProvider::new().expect("Failed to access clipboard");
-            ctx.set_contents(selected_row.clone())
-                .expect("Failed to copy to clipboard");
+            ctx.set_contents(text).expect("Failed to copy to clipboard");
         }
     }
 
-    fn enter_char(&mut self, new_char: char) {
-        let index = self.byte_index(&self.filter_input, self.character_index);
-        self.filter_input.insert(index, new_char);
-        self.move_cursor_right();
+    /// Whether a copy has to be redacted. In privacy mode copies are redacted, unless the same
+    /// copy is repeated within `COPY_CONFIRM_TIMEOUT` to confirm the raw values are wanted.
+    fn copies_redacted(&mut self) -> bool {
+        if !self.config.config.privacy_mode {
+            return false;
+        }
+        let confirmed = self
+            .raw_copy_requested_at
+            .take()
+            .is_some_and(|requested_at| requested_at.elapsed() < COPY_CONFIRM_TIMEOUT);
+        if !confirmed {
+            self.raw_copy_requested_at = Some(Instant::now());
+        }
+        !confirmed
     }
 
-    fn enter_sort_key_char(&mut self, new_char: char) {
-        let index = self.byte_index(&self.sort_key_value, self.sort_key_index);
-        self.sort_key_value.insert(index, new_char);
-        self.move_sort_key_cursor_right();
+    fn raw_copy_pending(&self) -> bool {
+        self.raw_copy_requested_at
+            .is_some_and(|requested_at| requested_at.elapsed() < COPY_CONFIRM_TIMEOUT)
     }
 
-    fn enter_partition_key_char(&mut self, new_char: char) {
-        let index = self.byte_index(&self.partition_key_value, self.partition_key_index);
-        self.partition_key_value.insert(index, new_char);
-        self.move_partition_key_cursor_right();
+    /// Copies every record that passes the filter as a JSON array. Sets above
+    /// `copy_confirm_bytes` are only copied when the action is repeated within
+    /// `COPY_CONFIRM_TIMEOUT`.
+    fn copy_filtered_records_to_clipboard(&mut self) {
+        let records: Vec<Value> = self
+            .iter_filtered_records()
+            .filter_map(|record| serde_json::from_str(&record).ok())
+            .collect();
+        let first = self.first_attributes();
+        let records = Value::Array(records);
+        let json = key_order::to_string_pretty(&records, &first);
+
+        let confirmed = self
+            .copy_requested_at
+            .take()
+            .is_some_and(|requested_at| requested_at.elapsed() < COPY_CONFIRM_TIMEOUT);
+        if json.len() > self.config.config.copy_confirm_bytes && !confirmed {
+            self.copy_requested_at = Some(Instant::now());
+            return;
+        }
+        let json = if self.copies_redacted() {
+            key_order::to_string_pretty(&redact_value(records), &first)
+        } else {
+            json
+        };
+
+        let mut ctx: ClipboardContext =
+            ClipboardProvider::new().expect("Failed to access clipboard");
+        ctx.set_contents(json).expect("Failed to copy to clipboard");
     }
 
-    /// Returns the byte index based on the character position.
-    ///
-    /// Since each character in a string can be contain multiple bytes, it's necessary to calculate
-    /// the byte index based on the index of the character.
-    fn byte_index(&self, input: &str, character_index: usize) -> usize {
-        input
-            .char_indices()
-            .map(|(i, _)| i)
-            .nth(character_index)
-            .unwrap_or(input.len())
+    /// Writes every record that passes the filter as a JSON array to the typed destination, or
+    /// to a file named after the table if none is typed. Destinations ending in `.csv` or
+    /// `.parquet` are written as CSV or Parquet instead. Exports are redacted in privacy mode.
+    fn export_results(&mut self) {
+        let destination = match self.export_input.value().trim() {
+            "" => format!("{}.json", self.tab.collection_name),
+            destination => destination.to_string(),
+        };
+        let records: Vec<Value> = self
+            .iter_filtered_records()
+            .filter_map(|record| serde_json::from_str(&record).ok())
+            .collect();
+        let count = records.len();
+        let mut records = Value::Array(records);
+        if self.config.config.privacy_mode {
+            records = redact_value(records);
+        }
+        let data = match records {
+            Value::Array(items) if csv_export::is_csv(&destination) => {
+                Ok(csv_export::to_csv(&items, &self.first_attributes()).into_bytes())
+            }
+            Value::Array(items) if parquet_export::is_parquet(&destination) => {
+                parquet_export::to_parquet(&items, &self.first_attributes())
+                    .map_err(|err| err.to_string())
+            }
+            records => {
+                Ok(key_order::to_string_pretty(&records, &self.first_attributes()).into_bytes())
+            }
+        };
+
+        self.refresh_status = Some(
+            match data.and_then(|data| {
+                let mut sink = sink::parse(&destination)?;
+                sink.write(&data)
+                    .map(|_| sink.describe())
+                    .map_err(|err| err.to_string())
+            }) {
+                Ok(destination) => format!("exported {count} items to {destination}"),
+                Err(err) => format!("export failed: {err}"),
+            },
+        );
     }
 
-    fn move_cursor_left(&mut self) {
-        let cursor_moved_left = self.character_index.saturating_sub(1);
-        self.character_index = self.clamp_cursor(cursor_moved_left, &self.filter_input);
+    fn copy_confirmation_pending(&self) -> bool {
+        self.copy_requested_at
+            .is_some_and(|requested_at| requested_at.elapsed() < COPY_CONFIRM_TIMEOUT)
     }
 
-    fn move_cursor_right(&mut self) {
-        let cursor_moved_right = self.character_index.saturating_add(1);
-        self.character_index = self.clamp_cursor(cursor_moved_right, &self.filter_input);
+    /// Checks the query inputs against the key attribute types before they are sent to AWS.
+    fn validate_query(&self) -> Result<(), String> {
+        if let Some(err) = self.tab.partition_key_input.error() {
+            return Err(format!("Partition key: {err}"));
+        }
+
+        if let Some(err) = self.tab.sort_key_input.error() {
+            return Err(format!("Sort key: {err}"));
+        }
+
+        Ok(())
     }
 
-    fn move_sort_key_cursor_left(&mut self) {
-        let cursor_moved_left = self.sort_key_index.saturating_sub(1);
-        self.sort_key_index = self.clamp_cursor(cursor_moved_left, &self.sort_key_value);
+    fn reset_query_inputs(&mut self) {
+        self.tab.partition_key_input.clear();
+        self.tab.sort_key_input.clear();
+        self.tab.sort_key_range = None;
+        self.tab.limit_input.clear();
     }
 
-    fn move_sort_key_cursor_right(&mut self) {
-        let cursor_moved_right = self.sort_key_index.saturating_add(1);
-        self.sort_key_index = self.clamp_cursor(cursor_moved_right, &self.sort_key_value);
+    fn cycle_sort_key_range(&mut self) {
+        let Some(sort_key) = &self.tab.sort_key else {
+            return;
+        };
+
+        let format = self
+            .tab
+            .timestamp_format
+            .unwrap_or_else(|| TimestampFormat::for_attribute_type(sort_key.attribute_type));
+        self.tab.sort_key_range = TimeRange::next(self.tab.sort_key_range);
+        self.tab.sort_key_input.clear();
+        if let Some(range) = self.tab.sort_key_range {
+            self.tab
+                .sort_key_input
+                .insert_str(&range.bounds(SystemTime::now(), format));
+        }
+        self.query_focus = QueryFocus::SortKey;
+        self.query_error = None;
     }
 
-    fn move_partition_key_cursor_left(&mut self) {
-        let cursor_moved_left = self.partition_key_index.saturating_sub(1);
-        self.partition_key_index = self.clamp_cursor(cursor_moved_left, &self.partition_key_value);
+    /// The input being typed into. Editing the sort key drops the preset it was filled with, and
+    /// editing either key starts its suggestions over.
+    fn focused_query_input(&mut self) -> &mut TextInput {
+        match self.query_focus {
+            QueryFocus::PartitionKey => {
+                self.tab.partition_key_suggestions.stop_cycling();
+                &mut self.tab.partition_key_input
+            }
+            QueryFocus::SortKey => {
+                self.tab.sort_key_range = None;
+                self.tab.sort_key_suggestions.stop_cycling();
+                &mut self.tab.sort_key_input
+            }
+            QueryFocus::Limit => &mut self.tab.limit_input,
+        }
     }
 
-    fn move_partition_key_cursor_right(&mut self) {
-        let cursor_moved_right = self.partition_key_index.saturating_add(1);
-        self.partition_key_index = self.clamp_cursor(cursor_moved_right, &self.partition_key_value);
+    /// The focused key input with its suggestions and the type of its attribute.
+    fn focused_key_input(
+        &mut self,
+    ) -> Option<(&mut TextInput, &mut KeySuggestions, KeyAttributeType)> {
+        let tab = &mut self.tab;
+        match self.query_focus {
+            QueryFocus::PartitionKey => Some((
+                &mut tab.partition_key_input,
+                &mut tab.partition_key_suggestions,
+                tab.partition_key.as_ref()?.attribute_type,
+            )),
+            QueryFocus::SortKey => {
+                tab.sort_key_range = None;
+                Some((
+                    &mut tab.sort_key_input,
+                    &mut tab.sort_key_suggestions,
+                    tab.sort_key.as_ref()?.attribute_type,
+                ))
+            }
+            QueryFocus::Limit => None,
+        }
     }
 
-    fn clamp_cursor(&self, new_cursor_pos: usize, input: &str) -> usize {
-        new_cursor_pos.clamp(0, input.chars().count())
+    /// Remembers the key values of the first records of a page, to suggest them in the query
+    /// form.
+    fn remember_key_values(&mut self, records: &[String]) {
+        for record in records.iter().take(KEY_SUGGESTION_SAMPLE_SIZE).rev() {
+            let Some((partition_key, sort_key)) = self.record_key(record) else {
+                continue;
+            };
+            if let Some(value) = sort_key_text(&partition_key) {
+                self.tab.partition_key_suggestions.remember(value);
+            }
+            if let Some(value) = sort_key.as_ref().and_then(sort_key_text) {
+                self.tab.sort_key_suggestions.remember(value);
+            }
+        }
     }
 
-    fn delete_char(&mut self) {
-        let is_not_cursor_leftmost = self.character_index != 0;
-        if is_not_cursor_leftmost {
-            // Method "remove" is not used on the saved text for deleting the selected char.
-            // Reason: Using remove on String works on bytes instead of the chars.
-            // Using remove would require special care because of char boundaries.
+    fn query_limit(&self) -> Option<i32> {
+        self.tab
+            .limit_input
+            .value()
+            .parse::<i32>()
+            .ok()
+            .filter(|limit| *limit > 0)
+    }
 
-            let current_index = self.character_index;
-            let from_left_to_current_index = current_index - 1;
+    /// The query the form would submit, or `None` while it has no partition key value.
+    fn planned_query(&self) -> Option<KeyQuery> {
+        let partition_key = self.tab.partition_key.clone()?;
+        if self.tab.partition_key_input.is_empty() {
+            return None;
+        }
+        let sort_key = self
+            .tab
+            .sort_key
+            .clone()
+            .filter(|_| !self.tab.sort_key_input.is_empty())
+            .map(|sort_key| (sort_key, self.sort_key_query_value()));
+        Some(KeyQuery {
+            partition_key,
+            partition_key_value: self.tab.partition_key_input.value().to_string(),
+            sort_key,
+            limit: self.query_limit(),
+        })
+    }
 
-            // Getting all characters before the selected character.
-            let before_char_to_delete = self.filter_input.chars().take(from_left_to_current_index);
-            // Getting all characters after selected character.
-            let after_char_to_delete = self.filter_input.chars().skip(current_index);
+    /// Queries the table for the key values in the query form.
+    fn submit_query(&mut self) -> Result<()> {
+        if let Err(error) = self.validate_query() {
+            self.query_error = Some(error);
+            return Ok(());
+        }
+        self.query_error = None;
+
+        let command_tx = self.command_tx.as_ref().unwrap();
+        if !self.tab.partition_key_input.is_empty() && !self.tab.sort_key_input.is_empty() {
+            command_tx.send(Action::GetTableQueryDataByPkSk(
+                self.tab.collection_name.clone(),
+                self.tab.partition_key.as_ref().unwrap().clone(),
+                self.tab.partition_key_input.value().to_string(),
+                self.tab.sort_key.as_ref().unwrap().clone(),
+                self.sort_key_query_value(),
+                self.query_limit(),
+            ))?;
+        } else if !self.tab.partition_key_input.is_empty() {
+            command_tx.send(Action::GetTableQueryDataByPk(
+                self.tab.collection_name.clone(),
+                self.tab.partition_key.as_ref().unwrap().clone(),
+                self.tab.partition_key_input.value().to_string(),
+                self.query_limit(),
+            ))?;
+        } else {
+            self.query_error = Some("Enter a partition key to query".to_string());
+            return Ok(());
+        }
 
-            // Put all characters together except the selected one.
-            // By leaving the selected one out, it is forgotten and therefore deleted.
-            self.filter_input = before_char_to_delete.chain(after_char_to_delete).collect();
-            self.move_cursor_left();
+        self.tab.queried_partition = Some(self.tab.partition_key_input.value().to_string());
+        let (partition_key, sort_key) = (
+            self.tab.partition_key_input.value().to_string(),
+            self.tab.sort_key_input.value().to_string(),
+        );
+        self.tab.partition_key_suggestions.remember(partition_key);
+        if self.tab.sort_key_range.is_none() {
+            self.tab.sort_key_suggestions.remember(sort_key);
         }
+        self.tab.follow = None;
+        self.query_pending = true;
+        Ok(())
     }
 
-    fn delete_char_sort_key(&mut self) {
-        let is_not_cursor_leftmost = self.sort_key_index != 0;
-        if is_not_cursor_leftmost {
-            // Method "remove" is not used on the saved text for deleting the selected char.
-            // Reason: Using remove on String works on bytes instead of the chars.
-            // Using remove would require special care because of char boundaries.
+    fn toggle_query_input_focus(&mut self) {
+        self.query_focus = match self.query_focus {
+            QueryFocus::PartitionKey if self.tab.sort_key.is_some() => QueryFocus::SortKey,
+            QueryFocus::PartitionKey | QueryFocus::SortKey => QueryFocus::Limit,
+            QueryFocus::Limit => QueryFocus::PartitionKey,
+        };
+    }
 
-            let current_index = self.sort_key_index;
-            let from_left_to_current_index = current_index - 1;
+    /// Requests the next page once the selection gets within a few rows of the last record.
+    fn fetch_more_if_near_end(&mut self) -> Result<()> {
+        if let Some(selected) = self.tab.list_state.selected() {
+            if selected >= self.tab.records.len().saturating_sub(5) && self.can_fetch_more() {
+                self.tab.fetching = true;
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::FetchMoreTableData(self.tab.collection_name.clone()))?;
+            }
+        }
+        Ok(())
+    }
 
-            // Getting all characters before the selected character.
-            let before_char_to_delete =
-                self.sort_key_value.chars().take(from_left_to_current_index);
-            // Getting all characters after selected character.
-            let after_char_to_delete = self.sort_key_value.chars().skip(current_index);
+    fn select_at(&mut self, index: usize) {
+        if index < self.tab.filtered_records.len() {
+            self.tab.list_state.select(Some(index));
+            self.update_scroll_pos(index);
+        }
+    }
 
-            // Put all characters together except the selected one.
-            // By leaving the selected one out, it is forgotten and therefore deleted.
-            self.sort_key_value = before_char_to_delete.chain(after_char_to_delete).collect();
-            self.move_sort_key_cursor_left();
+    fn scroll_by(&mut self, delta: i32) {
+        if delta < 0 {
+            self.tab
+                .list_state
+                .scroll_up_by(delta.unsigned_abs() as u16);
+        } else {
+            self.tab.list_state.scroll_down_by(delta as u16);
+        }
+        if let Some(selected) = self.tab.list_state.selected() {
+            self.update_scroll_pos(selected);
         }
     }
 
-    fn delete_char_partition_key(&mut self) {
-        let is_not_cursor_leftmost = self.partition_key_index != 0;
-        if is_not_cursor_leftmost {
-            // Method "remove" is not used on the saved text for deleting the selected char.
-            // Reason: Using remove on String works on bytes instead of the chars.
-            // Using remove would require special care because of char boundaries.
+    /// Returns whether another page may be requested without exceeding `max_items`.
+    fn can_fetch_more(&self) -> bool {
+        !self.tab.pinned
+            && self.tab.has_more
+            && !self.tab.fetching
+            && self.tab.records.len() < self.config.config.max_items
+    }
 
-            let current_index = self.partition_key_index;
-            let from_left_to_current_index = current_index - 1;
+    /// Samples the loaded records to tell whether the sort key holds timestamps, which enables
+    /// relative ranges such as `-15m`.
+    fn detect_timestamp_format(&mut self) {
+        let Some(sort_key) = self.tab.sort_key.clone() else {
+            self.tab.timestamp_format = None;
+            return;
+        };
+
+        let values: Vec<Value> = (0..self.tab.records.len().min(TIMESTAMP_SAMPLE_SIZE))
+            .filter_map(|index| self.tab.records.get(index))
+            .filter_map(|record| serde_json::from_str::<Value>(&record).ok())
+            .filter_map(|record| record.get(&sort_key.name).cloned())
+            .collect();
+        self.tab.timestamp_format = TimestampFormat::detect(&values);
+
+        let attribute_type = sort_key.attribute_type;
+        let timestamps = self.tab.timestamp_format.is_some();
+        apply_key_type(&mut self.tab.sort_key_input, attribute_type, timestamps);
+        self.tab.sort_key_input.set_validator(move |value| {
+            match (split_range(value), after_bound(value)) {
+                (Some((from, to)), _) => attribute_type
+                    .validate(from)
+                    .and_then(|_| attribute_type.validate(to)),
+                (None, Some(from)) => attribute_type.validate(from),
+                (None, None)
+                    if timestamps
+                        && relative_range(value, SystemTime::now(), TimestampFormat::Iso8601)
+                            .is_some() =>
+                {
+                    Ok(())
+                }
+                (None, None) => attribute_type.validate(value),
+            }
+        });
+    }
 
-            // Getting all characters before the selected character.
-            let before_char_to_delete = self
-                .partition_key_value
-                .chars()
-                .take(from_left_to_current_index);
-            // Getting all characters after selected character.
-            let after_char_to_delete = self.partition_key_value.chars().skip(current_index);
+    /// The sort key value to query for, with a relative range such as `-15m` resolved against
+    /// the current time.
+    fn sort_key_query_value(&self) -> String {
+        let value = self.tab.sort_key_input.value();
+        self.tab
+            .timestamp_format
+            .and_then(|format| relative_range(value, SystemTime::now(), format))
+            .unwrap_or_else(|| value.to_string())
+    }
 
-            // Put all characters together except the selected one.
-            // By leaving the selected one out, it is forgotten and therefore deleted.
-            self.partition_key_value = before_char_to_delete.chain(after_char_to_delete).collect();
-            self.move_partition_key_cursor_left();
+    /// The latest sort key value among the loaded records.
+    fn latest_sort_key(&self) -> Option<String> {
+        let sort_key = self.tab.sort_key.as_ref()?;
+        (0..self.tab.records.len())
+            .filter_map(|index| self.tab.records.get(index))
+            .filter_map(|record| serde_json::from_str::<Value>(&record).ok())
+            .filter_map(|record| record.get(&sort_key.name).cloned())
+            .max_by(|a, b| match (a.as_f64(), b.as_f64()) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                _ => a.as_str().cmp(&b.as_str()),
+            })
+            .and_then(|value| sort_key_text(&value))
+    }
+
+    /// Selects the record at `row`, counted from 1 over every record loaded, fetching pages
+    /// until it is reached. A row hidden by the filter selects the next one that is shown.
+    fn goto_row(&mut self, row: usize) -> Result<()> {
+        let index = row.saturating_sub(1);
+        if index >= self.tab.records.len() && self.can_fetch_more() {
+            self.tab.pending_goto = Some(row);
+            self.tab.fetching = true;
+            self.command_tx
+                .as_ref()
+                .unwrap()
+                .send(Action::FetchMoreTableData(self.tab.collection_name.clone()))?;
+            return Ok(());
         }
+
+        let position = self
+            .tab
+            .filtered_records
+            .partition_point(|record| *record < index)
+            .min(self.tab.filtered_records.len().saturating_sub(1));
+        self.select_at(position);
+        Ok(())
     }
 
-    fn toggle_query_input_focus(&mut self) {
-        if self.sort_key.is_some() && self.partition_key.is_some() {
-            match self.query_focus {
-                QueryFocus::SortKey => self.query_focus = QueryFocus::PartitionKey,
-                QueryFocus::PartitionKey => self.query_focus = QueryFocus::SortKey,
+    /// The values of the table's computed columns for each of `records`, shown as text.
+    fn computed_values(&self, records: &[(usize, Cow<str>)]) -> Vec<Vec<String>> {
+        let expressions: Vec<Expr> = computed::for_table(
+            &self.config.config.computed_columns,
+            &self.tab.collection_name,
+        )
+        .map(|column| Expr::parse(&column.expression).unwrap_or(Expr::Text("?".to_string())))
+        .collect();
+
+        records
+            .iter()
+            .map(|(_, record)| {
+                let item = serde_json::from_str(record).unwrap_or_default();
+                expressions
+                    .iter()
+                    .map(|expression| computed::display(&expression.eval(&item)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Adds the typed column to the table until the app exits, replacing the column of the same
+    /// name. Submitting nothing removes every computed column of the table.
+    fn define_computed_column(&mut self) {
+        let table = self.tab.collection_name.clone();
+        let columns = &mut self.config.config.computed_columns;
+        let definition = self.column_input.value().trim();
+        if definition.is_empty() {
+            columns.retain(|column| column.table != table);
+            self.refresh_status = Some("computed columns removed".to_string());
+            return;
+        }
+
+        match computed::parse_definition(&table, definition) {
+            Ok(column) => {
+                match columns
+                    .iter_mut()
+                    .find(|existing| existing.table == table && existing.name == column.name)
+                {
+                    Some(existing) => *existing = column,
+                    None => columns.push(column),
+                }
+                self.refresh_status = None;
             }
+            Err(error) => self.refresh_status = Some(format!("computed column: {error}")),
+        }
+    }
+
+    fn toggle_follow(&mut self) {
+        if self.tab.follow.take().is_some() {
+            return;
+        }
+
+        if self.tab.sort_key.is_none() || self.tab.queried_partition.is_none() {
+            return;
+        }
+        // Without items on screen, a timestamp sort key still tells which items are new.
+        let after = self.latest_sort_key().or_else(|| {
+            self.tab
+                .timestamp_format
+                .map(|format| format.format(SystemTime::now()))
+        });
+        self.tab.follow = Some(Follow {
+            after,
+            next_at: Instant::now(),
+            pending: false,
+        });
+        self.select_last();
+    }
+
+    /// Runs the followed query again once it is due.
+    fn poll_follow(&mut self) -> Result<()> {
+        let (Some(follow), Some(partition), Some(partition_key), Some(sort_key)) = (
+            &mut self.tab.follow,
+            &self.tab.queried_partition,
+            &self.tab.partition_key,
+            &self.tab.sort_key,
+        ) else {
+            return Ok(());
+        };
+        if follow.pending || Instant::now() < follow.next_at {
+            return Ok(());
+        }
+
+        follow.pending = true;
+        self.command_tx
+            .as_ref()
+            .unwrap()
+            .send(Action::FollowTableData(
+                self.tab.collection_name.clone(),
+                partition_key.clone(),
+                partition.clone(),
+                sort_key.clone(),
+                follow.after.clone(),
+            ))?;
+        Ok(())
+    }
+
+    fn append_followed_records(&mut self, records: Vec<String>) {
+        let Some(follow) = &mut self.tab.follow else {
+            return;
+        };
+        follow.pending = false;
+        follow.next_at = Instant::now() + FOLLOW_INTERVAL;
+
+        let sort_key_name = self
+            .tab
+            .sort_key
+            .as_ref()
+            .map(|sort_key| sort_key.name.clone())
+            .unwrap_or_default();
+        let sort_key_of = |record: &String| {
+            serde_json::from_str::<Value>(record)
+                .ok()
+                .and_then(|record| record.get(&sort_key_name).and_then(sort_key_text))
+        };
+        // Queries return the items in sort key order, so the last one is the latest.
+        let Some(latest) = records.last().and_then(sort_key_of) else {
+            return;
+        };
+        follow.after = Some(latest);
+
+        // The view keeps up with the new items, unless a row further up was selected.
+        let at_bottom = self
+            .tab
+            .list_state
+            .selected()
+            .is_none_or(|selected| selected + 1 >= self.tab.filtered_records.len());
+        self.append_page(records);
+        self.truncate_to_max_items();
+        self.apply_filter();
+        if at_bottom {
+            self.select_last();
         }
     }
 
     fn render_query_form(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let theme = self.config.config.theme;
         let [_, y_middle, _] = Layout::vertical([
-            Constraint::Percentage(30),
-            Constraint::Percentage(40),
-            Constraint::Percentage(30),
+            Constraint::Percentage(20),
+            Constraint::Percentage(60),
+            Constraint::Percentage(20),
         ])
         .areas(area);
 
@@ -345,212 +1784,203 @@ impl DataBox {
 
         frame.render_widget(Clear, middle);
 
-        if self.partition_key.is_none() && self.sort_key.is_none() {
-            let block = Block::new()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(EMERALD.c300))
-                .style(Style::new().bg(Color::Black))
-                .padding(Padding::uniform(1))
-                .title("Query Table");
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .padding(Padding::uniform(1))
+            .title("Query Table");
 
+        let Some(partition_key) = &self.tab.partition_key else {
             Paragraph::new("We don't have support for this Table Definition.")
                 .block(block)
-                .style(Style::new().bg(Color::Black).fg(INDIGO.c700))
+                .style(theme.popup().fg(theme.muted))
                 .wrap(Wrap { trim: false })
                 .alignment(Alignment::Center)
                 .render(middle, frame.buffer_mut());
 
             return Ok(());
+        };
+
+        let inner = block.inner(middle);
+        block.render(middle, frame.buffer_mut());
+
+        let mut fields = vec![(
+            format!(
+                "Partition Key ({}: {:?}):",
+                partition_key.name, partition_key.attribute_type
+            ),
+            &self.tab.partition_key_input,
+            matches!(self.query_focus, QueryFocus::PartitionKey),
+            key_hint(
+                partition_key.attribute_type,
+                &self.tab.partition_key_input,
+                &self.tab.partition_key_suggestions,
+            ),
+        )];
+
+        if let Some(sort_key) = &self.tab.sort_key {
+            let range = match self.tab.sort_key_range {
+                Some(range) => format!(", {}", range.label()),
+                None if self.tab.timestamp_format.is_some() => ", a..b or -15m".to_string(),
+                None => ", a..b for a range".to_string(),
+            };
+            fields.push((
+                format!(
+                    "Sort Key ({}: {:?}{range}):",
+                    sort_key.name, sort_key.attribute_type
+                ),
+                &self.tab.sort_key_input,
+                matches!(self.query_focus, QueryFocus::SortKey),
+                key_hint(
+                    sort_key.attribute_type,
+                    &self.tab.sort_key_input,
+                    &self.tab.sort_key_suggestions,
+                ),
+            ));
         }
 
-        if self.partition_key.is_some() && self.sort_key.is_some() {
-            let partition_key = self.partition_key.as_ref().unwrap();
-            let sort_key = self.sort_key.as_ref().unwrap();
-
-            let [top, middle_top, middle_bottom, bottom, rest, help] = Layout::vertical([
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Length(1),
-                Constraint::Length(1),
-                Constraint::Min(0),
-                Constraint::Length(3),
-            ])
-            .areas(middle);
-
-            let top_block = Block::new()
-                .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(EMERALD.c300))
-                .style(Style::new().bg(Color::Black))
-                .padding(Padding {
-                    top: 1,
-                    left: 1,
-                    right: 1,
-                    bottom: 0,
-                })
-                .title("Query Table");
-
-            let middle_top_block = Block::new()
-                .borders(Borders::LEFT | Borders::RIGHT)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(EMERALD.c300))
-                .padding(Padding {
-                    top: 0,
-                    left: 1,
-                    right: 1,
-                    bottom: 0,
-                })
-                .style(Style::new().bg(Color::Black));
-
-            let middle_bottom_block = Block::new()
-                .borders(Borders::LEFT | Borders::RIGHT)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(EMERALD.c300))
-                .padding(Padding {
-                    top: 0,
-                    left: 1,
-                    right: 1,
-                    bottom: 0,
-                })
-                .style(Style::new().bg(Color::Black));
-
-            let bottom_block = Block::new()
-                .borders(Borders::LEFT | Borders::RIGHT)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(EMERALD.c300))
-                .padding(Padding {
-                    top: 0,
-                    left: 1,
-                    right: 1,
-                    bottom: 0,
-                })
-                .style(Style::new().bg(Color::Black));
-
-            let rest_block = Block::new()
-                .borders(Borders::LEFT | Borders::RIGHT)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(EMERALD.c300))
-                .padding(Padding {
-                    top: 0,
-                    left: 1,
-                    right: 1,
-                    bottom: 0,
-                })
-                .style(Style::new().bg(Color::Black));
-
-            let help_block = Block::new()
-                .borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(EMERALD.c300))
-                .padding(Padding {
-                    top: 0,
-                    left: 1,
-                    right: 1,
-                    bottom: 1,
-                })
-                .style(Style::new().bg(Color::Black));
-
-            Paragraph::new(format!("Partition Key ({}):", partition_key))
-                .block(top_block)
-                .style(Style::new().bg(Color::Black).fg(INDIGO.c700))
-                .render(top, frame.buffer_mut());
-
-            Paragraph::new(self.partition_key_value.clone())
-                .block(middle_top_block)
-                .style(Style::new().bg(Color::Black))
-                .render(middle_top, frame.buffer_mut());
-
-            Paragraph::new(format!("Sort Key ({}):", sort_key))
-                .block(middle_bottom_block)
-                .style(Style::new().bg(Color::Black).fg(INDIGO.c700))
-                .render(middle_bottom, frame.buffer_mut());
-
-            Paragraph::new(self.sort_key_value.clone())
-                .block(bottom_block)
-                .style(Style::new().bg(Color::Black))
-                .render(bottom, frame.buffer_mut());
-
-            Paragraph::new("")
-                .block(rest_block)
-                .style(Style::new().bg(Color::Black))
-                .render(rest, frame.buffer_mut());
-
-            Paragraph::new("<enter> to submit - <esc> to cancel - <tab> to switch fields")
-                .block(help_block)
-                .alignment(Alignment::Center)
-                .style(Style::new().bg(Color::Black).fg(INDIGO.c700))
-                .render(help, frame.buffer_mut());
-
-            match self.query_focus {
-                QueryFocus::PartitionKey => {
-                    frame.set_cursor_position(Position::new(
-                        middle_top.x + self.partition_key_index as u16 + 2,
-                        middle_top.y,
-                    ));
-                }
-                QueryFocus::SortKey => {
-                    frame.set_cursor_position(Position::new(
-                        bottom.x + self.sort_key_index as u16 + 2,
-                        bottom.y,
-                    ));
-                }
-            }
+        fields.push((
+            "Limit (optional):".to_string(),
+            &self.tab.limit_input,
+            matches!(self.query_focus, QueryFocus::Limit),
+            None,
+        ));
 
-            return Ok(());
+        let mut constraints = vec![Constraint::Length(2); fields.len()];
+        constraints.extend([Constraint::Min(0), Constraint::Length(1)]);
+        let rows = Layout::vertical(constraints).split(inner);
+
+        if let Some(error) = &self.query_error {
+            Paragraph::new(error.clone())
+                .style(theme.popup().fg(theme.error))
+                .wrap(Wrap { trim: false })
+                .render(rows[rows.len() - 2], frame.buffer_mut());
+        } else if self.query_pending {
+            Paragraph::new("Querying...")
+                .style(theme.popup().fg(theme.loading))
+                .render(rows[rows.len() - 2], frame.buffer_mut());
+        } else {
+            // What will run, for those new to the difference between a query and a scan.
+            let plan = explain(
+                &self.tab.collection_name,
+                self.planned_query().as_ref(),
+                self.tab.filter_input.value(),
+            );
+            let mut lines = vec![Line::from("")];
+            lines.extend(plan.into_iter().map(Line::from));
+            Paragraph::new(lines)
+                .style(theme.popup().fg(theme.muted))
+                .wrap(Wrap { trim: false })
+                .render(rows[rows.len() - 2], frame.buffer_mut());
         }
 
-        if self.partition_key.is_some() {
-            let partition_key = self.partition_key.as_ref().unwrap();
+        for (index, (label, input, focused, hint)) in fields.into_iter().enumerate() {
+            let [label_area, value_area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(rows[index]);
+
+            // Checked on every frame, so a value that does not fit the key type is flagged while
+            // it is typed instead of coming back from DynamoDB as a ValidationException.
+            let mut spans = vec![Span::styled(label, theme.popup().fg(theme.muted))];
+            if let Some(error) = input.error() {
+                spans.push(Span::styled(
+                    format!(" {error}"),
+                    theme.popup().fg(theme.error),
+                ));
+            } else if let Some(hint) = hint.filter(|_| focused) {
+                spans.push(Span::styled(
+                    format!(" {hint}"),
+                    theme.popup().fg(theme.muted).add_modifier(Modifier::ITALIC),
+                ));
+            }
+            Paragraph::new(Line::from(spans)).render(label_area, frame.buffer_mut());
 
-            let [top, bottom] =
-                Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(middle);
+            input.render(frame, value_area, theme.popup(), focused);
+        }
 
-            let top_block = Block::new()
-                .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(EMERALD.c300))
-                .style(Style::new().bg(Color::Black))
-                .padding(Padding {
-                    top: 1,
-                    left: 1,
-                    right: 1,
-                    bottom: 0,
-                })
-                .title("Query Table");
-
-            let bottom_block = Block::new()
-                .borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(EMERALD.c300))
-                .padding(Padding {
-                    top: 0,
-                    left: 1,
-                    right: 1,
-                    bottom: 1,
-                })
-                .style(Style::new().bg(Color::Black));
+        Paragraph::new(concat!(
+            "<enter> to submit - <ctrl-e> to explore partition - <ctrl-t> for a time range - ",
+            "<esc> to cancel - <tab> to switch fields",
+        ))
+        .alignment(Alignment::Center)
+        .style(theme.popup().fg(theme.muted))
+        .render(rows[rows.len() - 1], frame.buffer_mut());
 
-            Paragraph::new(format!("Partition Key ({}):", partition_key))
-                .block(top_block)
-                .style(Style::new().bg(Color::Black).fg(INDIGO.c700))
-                .render(top, frame.buffer_mut());
+        Ok(())
+    }
+}
 
-            Paragraph::new(self.partition_key_value.clone())
-                .block(bottom_block)
-                .style(Style::new().bg(Color::Black))
-                .render(bottom, frame.buffer_mut());
+/// What the query form tells about a key input besides its errors: the text a binary key
+/// decodes to, or how many recently seen values match what was typed.
+fn key_hint(
+    attribute_type: KeyAttributeType,
+    input: &TextInput,
+    suggestions: &KeySuggestions,
+) -> Option<String> {
+    if attribute_type == KeyAttributeType::B {
+        return Some(match decoded_base64(input.value()) {
+            Some(text) => format!("= \"{text}\" (<ctrl-b> to edit as text)"),
+            None => "<ctrl-b> to encode as base64".to_string(),
+        });
+    }
+    match suggestions.count(input) {
+        0 => None,
+        1 => Some("<up/down> for 1 recent value".to_string()),
+        count => Some(format!("<up/down> for {count} recent values")),
+    }
+}
 
-            frame.set_cursor_position(Position::new(
-                bottom.x + self.partition_key_index as u16 + 2,
-                bottom.y,
-            ));
+/// Splits `text` into spans in `style`, with every case-insensitive occurrence of `needle` also
+/// reversed.
+fn highlight_matches(text: &str, needle: &str, style: Style) -> Vec<Span<'static>> {
+    // Lowercasing ASCII keeps every byte where it was, so offsets carry over to `text`.
+    let haystack = text.to_ascii_lowercase();
+    let needle = needle.to_ascii_lowercase();
+    if needle.is_empty() {
+        return vec![Span::styled(text.to_string(), style)];
+    }
 
-            return Ok(());
+    let mut spans = Vec::new();
+    let mut start = 0;
+    for (at, found) in haystack.match_indices(&needle) {
+        if at > start {
+            spans.push(Span::styled(text[start..at].to_string(), style));
         }
+        spans.push(Span::styled(
+            text[at..at + found.len()].to_string(),
+            style.add_modifier(Modifier::REVERSED),
+        ));
+        start = at + found.len();
+    }
+    if start < text.len() {
+        spans.push(Span::styled(text[start..].to_string(), style));
+    }
+    spans
+}
 
-        Ok(())
+/// Clamps the selection to `len` rows and scrolls `offset` so the selected row is among the
+/// `height` rows on screen, the way `List` does when it is given every row.
+fn visible_window(
+    selected: Option<usize>,
+    offset: usize,
+    len: usize,
+    height: usize,
+) -> (Option<usize>, usize) {
+    if len == 0 {
+        return (None, 0);
+    }
+
+    let selected = selected.map(|selected| selected.min(len - 1));
+    let mut offset = offset.min(len - 1);
+    if let Some(selected) = selected {
+        if selected >= offset + height {
+            offset = selected + 1 - height.max(1);
+        } else if selected < offset {
+            offset = selected;
+        }
     }
+    (selected, offset)
 }
 
 impl Component for DataBox {
@@ -565,246 +1995,704 @@ impl Component for DataBox {
         Ok(())
     }
 
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Result<Option<Action>> {
+        if self.covered
+            || !matches!(self.mode, Mode::View)
+            || self.tab.collection_name.is_empty()
+            || !self.area.contains(Position::new(mouse.column, mouse.row))
+        {
+            return Ok(None);
+        }
+
+        let action = match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let double_click = self.clicks.click(&mouse);
+                let command_tx = self.command_tx.as_ref().unwrap();
+
+                if !self.active {
+                    command_tx.send(Action::SelectDataMode)?;
+                }
+
+                match list_index_at(self.area, self.tab.list_state.offset(), &mouse) {
+                    Some(index) if index < self.tab.filtered_records.len() => {
+                        if double_click {
+                            command_tx.send(Action::SelectTableDataRowAt(index))?;
+                            Some(Action::SelectTableDataRow)
+                        } else {
+                            Some(Action::SelectTableDataRowAt(index))
+                        }
+                    }
+                    _ => None,
+                }
+            }
+            MouseEventKind::ScrollDown if self.active => Some(Action::ScrollTableData(
+                self.config.config.scroll.wheel_lines as i32,
+            )),
+            MouseEventKind::ScrollUp if self.active => Some(Action::ScrollTableData(
+                -(self.config.config.scroll.wheel_lines as i32),
+            )),
+            _ => None,
+        };
+
+        Ok(action)
+    }
+
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
             Action::Tick => {
-                // add any logic here that should run on every tick
+                if self.loading {
+                    self.loading_state.calc_next();
+                }
+                if self.debug_overlay {
+                    self.command_tx
+                        .as_ref()
+                        .unwrap()
+                        .send(Action::TransmitRecordsMemory(self.records_bytes()))?;
+                }
+                self.poll_follow()?;
+            }
+            Action::ToggleDebugOverlay => self.debug_overlay = !self.debug_overlay,
+            Action::TransmitPendingRequests(pending) => {
+                self.loading = pending.iter().any(|(pane, _)| *pane == Pane::Data);
             }
             Action::Render => {
                 // add any logic here that should run on every render
             }
-            Action::SelectDataMode => self.active = true,
-            Action::SelectingRegion
-            | Action::FilteringTables
-            | Action::SelectTableMode
-            | Action::ViewTableDataRowDetail => self.active = false,
+            Action::SelectDataMode => {
+                self.active = true;
+                self.covered = false;
+            }
+            Action::SelectingRegion | Action::FilteringTables | Action::SelectTableMode => {
+                self.active = false;
+                self.covered = false;
+                self.clear_find();
+            }
+            Action::ViewTableDataRowDetail => {
+                self.active = false;
+                self.covered = true;
+            }
             Action::TransmitSelectedTable(table) => {
+                self.open_tab(&table);
                 self.set_title(&table);
-                self.collection_name = table.clone();
+                self.tab.collection_name = table.clone();
 
                 self.command_tx
                     .as_ref()
                     .unwrap()
                     .send(Action::GetTableDescription(table.clone()))?;
             }
-            Action::TransmitTableData(data, has_more) => {
-                self.records = data;
-                self.has_more = has_more;
-                self.list_state.select_first();
-                self.apply_filter();
+            // Pinned results are kept as they are; new ones for the table go to its own tab once
+            // it is opened again.
+            Action::TransmitTableData(..)
+            | Action::TransmitNextBatcTableData(..)
+            | Action::TransmitFollowedTableData(_)
+            | Action::FetchTableData(_)
+                if self.tab.pinned => {}
+            Action::Refresh | Action::ToggleFollowQuery if self.active && self.tab.pinned => {
+                self.refresh_status = Some(PINNED_STATUS.to_string());
+            }
+            Action::QueryTableData | Action::RunQuery(..) if self.tab.pinned => {
+                self.refresh_status = Some(PINNED_STATUS.to_string());
                 self.command_tx
                     .as_ref()
                     .unwrap()
-                    .send(Action::StopLoading)?;
+                    .send(Action::ExitQueryTableData)?;
+            }
+            Action::PinResults if self.active => self.name_pin()?,
+            Action::NewPinNameCharacter(c) => self.pin_input.enter_char(c),
+            Action::DeletePinNameCharacter => self.pin_input.delete_char(),
+            Action::SubmitPinName if matches!(self.mode, Mode::NamingPin) => {
+                self.mode = Mode::View;
+                let name = match self.pin_input.value().trim() {
+                    "" => self.default_pin_name(),
+                    name => name.to_string(),
+                };
+                self.pin_results(&name);
+            }
+            Action::ExitPinName => self.mode = Mode::View,
+            Action::TransmitTableData(data, has_more) => {
+                self.tab.loaded = true;
+                self.refresh_status = None;
+                self.tab.records = RecordStore::new(self.config.config.records_memory_budget);
+                self.remember_key_values(&data);
+                let records = self.keyed(data);
+                self.tab.records.merge(records);
+                self.tab.page_starts = vec![0];
+                self.tab.has_more = has_more;
+                self.tab.truncated = false;
+                self.truncate_to_max_items();
+                self.tab.list_state.select_first();
+                self.apply_filter();
+                self.restore_selection()?;
+                self.select_bookmark()?;
+                self.detect_timestamp_format();
+                self.update_torn_out();
+
+                if self.query_pending {
+                    self.query_pending = false;
+                    self.mode = Mode::View;
+                }
+            }
+            Action::TransmitQueryError(error) if self.query_pending => {
+                self.query_pending = false;
+                self.query_error = Some(error);
+            }
+            Action::Refresh if self.active && self.tab.loaded => {
+                self.start_restoring_selection();
             }
             Action::SelectTableDataRowPrev => {
                 self.select_previous();
             }
             Action::SelectTableDataRowNext => {
                 self.select_next();
-                if let Some(selected) = self.list_state.selected() {
-                    if selected >= self.records.len() - 5 && self.has_more && !self.fetching {
-                        self.fetching = true;
-                        let command_ref = self.command_tx.as_ref().unwrap();
-                        command_ref
-                            .send(Action::StartLoading("Loading More Table Data".to_string()))?;
-                        command_ref
-                            .send(Action::FetchMoreTableData(self.collection_name.clone()))?;
-                    }
-                }
+                self.fetch_more_if_near_end()?;
             }
             Action::SelectTableDataRowScrollUp => {
-                self.scroll_up();
+                self.scroll_by(-(self.config.config.scroll.list_lines as i32));
             }
             Action::SelectTableDataRowScrollDown => {
-                self.scroll_down();
-                if let Some(selected) = self.list_state.selected() {
-                    if selected >= self.records.len() - 5 && self.has_more && !self.fetching {
-                        self.fetching = true;
-                        let command_ref = self.command_tx.as_ref().unwrap();
-                        command_ref
-                            .send(Action::StartLoading("Loading More Table Data".to_string()))?;
-                        command_ref
-                            .send(Action::FetchMoreTableData(self.collection_name.clone()))?;
-                    }
-                }
+                self.scroll_by(self.config.config.scroll.list_lines as i32);
+                self.fetch_more_if_near_end()?;
+            }
+            Action::SelectTableDataRowHalfPageUp => {
+                self.scroll_by(-(self.page_lines() / 2).max(1));
+            }
+            Action::SelectTableDataRowHalfPageDown => {
+                self.scroll_by((self.page_lines() / 2).max(1));
+                self.fetch_more_if_near_end()?;
+            }
+            Action::SelectTableDataRowPageUp => self.scroll_by(-self.page_lines()),
+            Action::SelectTableDataRowPageDown => {
+                self.scroll_by(self.page_lines());
+                self.fetch_more_if_near_end()?;
+            }
+            Action::NextTab if self.tabs.len() > 1 => {
+                self.switch_to((self.active_tab + 1) % self.tabs.len());
+                self.activate_tab()?;
+            }
+            Action::PrevTab if self.tabs.len() > 1 => {
+                self.switch_to((self.active_tab + self.tabs.len() - 1) % self.tabs.len());
+                self.activate_tab()?;
+            }
+            Action::CloseTab => self.close_tab()?,
+            Action::SelectTableDataRowAt(index) => {
+                self.select_at(index);
+            }
+            Action::ScrollTableData(delta) => {
+                self.scroll_by(delta);
+                self.fetch_more_if_near_end()?;
             }
             Action::SelectTableDataRowFirst => {
                 self.select_first();
             }
             Action::SelectTableDataRowLast => {
                 self.select_last();
-                if self.has_more && !self.fetching {
-                    self.fetching = true;
-                    let command_ref = self.command_tx.as_ref().unwrap();
-                    command_ref
-                        .send(Action::StartLoading("Loading More Table Data".to_string()))?;
-                    command_ref.send(Action::FetchMoreTableData(self.collection_name.clone()))?;
+                if self.can_fetch_more() {
+                    self.tab.fetching = true;
+                    self.command_tx
+                        .as_ref()
+                        .unwrap()
+                        .send(Action::FetchMoreTableData(self.tab.collection_name.clone()))?;
                 }
             }
+            Action::SelectTableDataPrevPage => self.select_previous_page(),
+            Action::SelectTableDataNextPage => self.select_next_page()?,
             Action::SelectTableDataRow => {
                 self.set_selected();
 
-                if !self.selected_row.is_empty() {
+                if !self.tab.selected_row.is_empty() {
                     let command_tx = self.command_tx.as_ref().unwrap();
 
                     command_tx.send(Action::ViewTableDataRowDetail)?;
                     command_tx.send(Action::TransmitSelectedTableDataRow(
-                        self.selected_row.clone(),
+                        self.tab.selected_row.clone(),
                     ))?;
                 }
             }
             Action::TransmitNextBatcTableData(data, has_more) => {
-                self.fetching = false;
-                self.has_more = has_more;
-                self.records.extend(data);
+                self.tab.fetching = false;
+                self.tab.has_more = has_more;
+                self.remember_key_values(&data);
+                self.append_page(data);
+                self.truncate_to_max_items();
                 self.apply_filter();
-
-                self.command_tx
-                    .as_ref()
-                    .unwrap()
-                    .send(Action::StopLoading)?;
+                self.update_torn_out();
+                self.restore_selection()?;
+                if let Some(row) = self.tab.pending_goto.take() {
+                    self.goto_row(row)?;
+                }
+                self.select_bookmark()?;
             }
+            Action::TransmitFollowedTableData(records) => self.append_followed_records(records),
+            Action::ToggleFollowQuery if self.active => self.toggle_follow(),
             Action::FetchTableData(_) => {
-                self.records = Vec::new();
+                self.tab.queried_partition = None;
+                self.tab.follow = None;
+                self.tab.records = RecordStore::default();
             }
             Action::ApproximateTableDataCount(count) => {
-                self.aprox_count = count;
+                self.tab.aprox_count = count;
             }
             Action::SelectTableDataRowCopyToClipboard => {
                 self.copy_selected_row_to_clipboard();
             }
-            Action::FilterTableData => self.mode = Mode::Filtering,
+            Action::CopyTableDataToClipboard => {
+                self.copy_filtered_records_to_clipboard();
+            }
+            Action::TakeSnapshot => self.take_snapshot()?,
+            Action::CompareRegions => self.compare_regions()?,
+            Action::ViewTableActivity if self.active => {
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::ShowTableActivity(self.tab.collection_name.clone()))?;
+            }
+            Action::ParallelScan if self.active => {
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::StartParallelScan(self.tab.collection_name.clone()))?;
+            }
+            Action::ProbeLatency if self.active => {
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::ShowLatencyProbe(self.tab.collection_name.clone()))?;
+            }
+            Action::OpenScratchpad if self.active => {
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::ShowScratchpad(self.tab.collection_name.clone()))?;
+            }
+            Action::CopyKey if self.active => self.copy_key()?,
+            Action::BookmarkItem if self.active => self.bookmark_selected()?,
+            Action::RefreshItem if self.active || self.covered => self.refresh_selected()?,
+            Action::TearOutItem if self.active || self.covered => self.tear_out_selected(),
+            Action::NewItem if self.active => {
+                let Some(partition_key) = self.tab.partition_key.clone() else {
+                    return Ok(None);
+                };
+                let keys = std::iter::once(partition_key)
+                    .chain(self.tab.sort_key.clone())
+                    .collect();
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::ShowNewItemEditor(
+                        self.tab.collection_name.clone(),
+                        keys,
+                    ))?;
+            }
+            Action::TransmitItem(table, key, item) if table == self.tab.collection_name => {
+                self.update_item(&key, item)?
+            }
+            Action::JumpToBookmark(bookmark) => self.jump_to_bookmark(bookmark)?,
+            Action::CopyTable => {
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::ShowTableCopy(self.tab.collection_name.clone()))?;
+            }
+            Action::ImportFile => {
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::ShowImport(self.tab.collection_name.clone()))?;
+            }
+            Action::NewItemFromTemplate => {
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::ShowItemTemplates(self.tab.collection_name.clone()))?;
+            }
+            Action::SeedItemsFromTemplate => {
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::ShowSeedTemplates(self.tab.collection_name.clone()))?;
+            }
+            Action::CompareSnapshots => {
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::ShowSnapshotDiff(self.tab.collection_name.clone()))?;
+            }
+            Action::FilterTableData => {
+                self.clear_find();
+                self.mode = Mode::Filtering;
+            }
+            Action::FindInTableData if self.active => {
+                self.clear_find();
+                self.mode = Mode::Finding;
+            }
+            Action::NewFindCharacter(c) => {
+                self.find_input.enter_char(c);
+                self.update_find_matches();
+            }
+            Action::DeleteFindCharacter => {
+                self.find_input.delete_char();
+                self.update_find_matches();
+            }
+            Action::SubmitFind => {
+                self.mode = Mode::View;
+                // Start from the selected record itself when it matches.
+                let selected = self.tab.list_state.selected().unwrap_or(0);
+                if !self.find_matches.contains(&selected) {
+                    self.jump_to_match(true);
+                }
+            }
+            Action::DeepFindUpTo(max_matches) if self.active => self.deep_find(max_matches)?,
+            Action::TransmitDeepFindResult(table, result) if table == self.tab.collection_name => {
+                self.add_deep_find_matches(result)
+            }
+            // A deep find waits for its cost to be confirmed, and is not run when cancelled.
+            Action::CancelPlannedOperation => self.deep_find_status = None,
+            Action::FindNext => self.jump_to_match(true),
+            Action::FindPrev => self.jump_to_match(false),
+            Action::ExitFind => {
+                self.mode = Mode::View;
+                self.clear_find();
+            }
+            Action::GotoRow if self.active => {
+                self.goto_input = TextInput::new().char_filter(|c| c.is_ascii_digit());
+                self.mode = Mode::GoingTo;
+            }
+            Action::NewGotoRowCharacter(c) => self.goto_input.enter_char(c),
+            Action::DeleteGotoRowCharacter => self.goto_input.delete_char(),
+            Action::SubmitGotoRow => {
+                self.mode = Mode::View;
+                if let Ok(row) = self.goto_input.value().parse() {
+                    self.goto_row(row)?;
+                }
+            }
+            Action::ExitGotoRow => self.mode = Mode::View,
+            Action::CycleSoftDeleteView if self.active => self.cycle_soft_delete_view()?,
+            Action::DefineComputedColumn if self.active => {
+                self.column_input = TextInput::new();
+                self.mode = Mode::DefiningColumn;
+            }
+            Action::NewComputedColumnCharacter(c) => self.column_input.enter_char(c),
+            Action::DeleteComputedColumnCharacter => self.column_input.delete_char(),
+            Action::SubmitComputedColumn => {
+                self.mode = Mode::View;
+                self.define_computed_column();
+            }
+            Action::ExitComputedColumn => self.mode = Mode::View,
+            Action::ExportResults if self.active => {
+                self.export_input =
+                    TextInput::new().placeholder(&format!("{}.json", self.tab.collection_name));
+                self.mode = Mode::Exporting;
+            }
+            Action::DumpTable if self.active => {
+                self.export_input =
+                    TextInput::new().placeholder(&format!("{}.ndjson", self.tab.collection_name));
+                self.mode = Mode::DumpingTable;
+            }
+            Action::NewExportCharacter(c) => self.export_input.enter_char(c),
+            Action::DeleteExportCharacter => self.export_input.delete_char(),
+            Action::SubmitExport if matches!(self.mode, Mode::DumpingTable) => {
+                self.mode = Mode::View;
+                let path = match self.export_input.value().trim() {
+                    "" => format!("{}.ndjson", self.tab.collection_name),
+                    path => path.to_string(),
+                };
+                return Ok(Some(Action::StartTableDump(
+                    self.tab.collection_name.clone(),
+                    path,
+                )));
+            }
+            Action::SubmitExport => {
+                self.mode = Mode::View;
+                self.export_results();
+            }
+            Action::ExitExport => self.mode = Mode::View,
             Action::ExitFilterTableData => {
                 self.mode = Mode::View;
-                self.filter_input = String::new();
-                self.character_index = 0;
+                self.tab.filter_input.clear();
                 self.apply_filter();
             }
             Action::ExitQueryTableData => {
-                self.filter_input = String::new();
-                self.partition_key_value = String::new();
-                self.sort_key_value = String::new();
-                self.character_index = 0;
-                self.sort_key_index = 0;
-                self.partition_key_index = 0;
+                self.tab.clear_filter();
+                self.reset_query_inputs();
+                self.query_error = None;
+                self.query_pending = false;
 
                 self.query_focus = QueryFocus::PartitionKey;
 
                 self.mode = Mode::View
             }
-            Action::NewFilterDataCharacter(c) => {
-                if self.active {
-                    self.enter_char(c);
-                    self.select_first();
-                    self.apply_filter();
-                }
+            Action::NewFilterDataCharacter(c) if self.active => {
+                self.tab.filter_input.enter_char(c);
+                self.select_first();
+                self.apply_filter();
             }
-            Action::DeleteFilterDataCharacter => {
-                if self.active {
-                    self.delete_char();
-                    self.apply_filter();
-                }
+            Action::DeleteFilterDataCharacter if self.active => {
+                self.tab.filter_input.delete_char();
+                self.apply_filter();
             }
             Action::SubmitFilterDataText => {
                 self.mode = Mode::View;
             }
             Action::ClearTableDataFilter => {
-                self.filter_input = String::new();
-                self.partition_key_value = String::new();
-                self.sort_key_value = String::new();
-                self.character_index = 0;
-                self.sort_key_index = 0;
-                self.partition_key_index = 0;
+                self.tab.clear_filter();
+                self.reset_query_inputs();
                 self.apply_filter();
             }
-            Action::QueryTableData => self.mode = Mode::Querying,
+            Action::FollowReference(table, value) => {
+                self.pending_reference = Some((table.clone(), value));
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::TransmitSelectedTable(table))?;
+            }
+            Action::FilterTableDataByValue(path, value) => self.filter_by_value(path, value),
+            Action::FilterTableDataBySelectedPartition if self.active => {
+                let partition = self
+                    .tab
+                    .list_state
+                    .selected()
+                    .and_then(|index| self.filtered_record(index))
+                    .and_then(|record| self.record_key(&record));
+                if let (Some((value, _)), Some(partition_key)) =
+                    (partition, self.tab.partition_key.as_ref())
+                {
+                    self.filter_by_value(partition_key.name.clone(), value);
+                }
+            }
+            Action::QueryTableData => {
+                self.clear_find();
+                self.mode = Mode::Querying;
+            }
+            Action::TransmitPermissions(permissions) => {
+                let table = permissions.table.clone();
+                if let Some(tab) = std::iter::once(&mut self.tab)
+                    .chain(&mut self.tabs)
+                    .find(|tab| tab.collection_name == table)
+                {
+                    tab.permissions = Some(permissions);
+                }
+            }
             Action::TransmitTableDescription(description) => {
                 let (partition_key, sort_key) = description;
-                self.partition_key = partition_key;
-                self.sort_key = sort_key;
+
+                if let Some(key) = &partition_key {
+                    let attribute_type = key.attribute_type;
+                    apply_key_type(&mut self.tab.partition_key_input, attribute_type, false);
+                    self.tab
+                        .partition_key_input
+                        .set_validator(move |value| attribute_type.validate(value));
+                }
+
+                self.tab.partition_key = partition_key;
+                self.tab.sort_key = sort_key;
+                self.detect_timestamp_format();
+                self.follow_pending_reference()?;
+            }
+            Action::NewQueryDataCharacter(c) => {
+                self.focused_query_input().enter_char(c);
+                self.query_error = None;
             }
-            Action::NewQueryDataCharacter(c) => match self.query_focus {
-                QueryFocus::PartitionKey => {
-                    self.enter_partition_key_char(c);
+            Action::InsertUuid | Action::InsertUlid | Action::InsertKsuid
+                if matches!(self.mode, Mode::Querying) =>
+            {
+                if let Some(id) = id::generate_for(&action) {
+                    self.focused_query_input().insert_str(&id);
                 }
-                QueryFocus::SortKey => {
-                    self.enter_sort_key_char(c);
+            }
+            Action::DeleteQueryDataCharacter if self.active => {
+                self.focused_query_input().delete_char();
+                self.query_error = None;
+            }
+            Action::CycleSortKeyRange => self.cycle_sort_key_range(),
+            Action::NextKeySuggestion | Action::PrevKeySuggestion
+                if matches!(self.mode, Mode::Querying) =>
+            {
+                if let Some((input, suggestions, _)) = self.focused_key_input() {
+                    suggestions.cycle(input, action == Action::NextKeySuggestion);
                 }
-            },
-            Action::DeleteQueryDataCharacter => {
-                if self.active {
-                    match self.query_focus {
-                        QueryFocus::PartitionKey => {
-                            self.delete_char_partition_key();
-                        }
-                        QueryFocus::SortKey => {
-                            self.delete_char_sort_key();
-                        }
-                    }
+                self.query_error = None;
+            }
+            Action::ToggleKeyBase64 if matches!(self.mode, Mode::Querying) => {
+                if let Some((input, suggestions, KeyAttributeType::B)) = self.focused_key_input() {
+                    suggestions.stop_cycling();
+                    toggle_base64(input);
                 }
+                self.query_error = None;
             }
             Action::ToggleQueryInputFocus => {
                 self.toggle_query_input_focus();
             }
-            Action::SubmitQueryDataText => {
-                let command_tx = self.command_tx.as_ref().unwrap();
-                if !self.partition_key_value.is_empty() && !self.sort_key_value.is_empty() {
-                    command_tx.send(Action::StartLoading("Querying Data".to_string()))?;
-                    command_tx.send(Action::GetTableQueryDataByPkSk(
-                        self.collection_name.clone(),
-                        self.partition_key.as_ref().unwrap().clone(),
-                        self.partition_key_value.clone(),
-                        self.sort_key.as_ref().unwrap().clone(),
-                        self.sort_key_value.clone(),
-                    ))?;
-                } else if !self.partition_key_value.is_empty() {
-                    command_tx.send(Action::StartLoading("Querying Data".to_string()))?;
-                    command_tx.send(Action::GetTableQueryDataByPk(
-                        self.collection_name.clone(),
-                        self.partition_key.as_ref().unwrap().clone(),
-                        self.partition_key_value.clone(),
-                    ))?;
+            Action::ExplorePartition => {
+                if self.tab.partition_key_input.is_empty() {
+                    self.query_error = Some("Enter a partition key to explore".to_string());
+                    return Ok(None);
                 }
+                if let Err(error) = self.validate_query() {
+                    self.query_error = Some(error);
+                    return Ok(None);
+                }
+                self.query_error = None;
 
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::GetPartitionStats(
+                        self.tab.collection_name.clone(),
+                        self.tab.partition_key.as_ref().unwrap().clone(),
+                        self.tab.partition_key_input.value().to_string(),
+                        self.tab.sort_key.clone(),
+                    ))?;
                 self.mode = Mode::View;
             }
+            Action::SubmitQueryDataText => self.submit_query()?,
+            Action::RunQuery(partition_key, sort_key) => {
+                self.clear_find();
+                self.mode = Mode::Querying;
+                self.reset_query_inputs();
+                self.tab.partition_key_input.insert_str(&partition_key);
+                if let Some(sort_key) = sort_key {
+                    self.tab.sort_key_input.insert_str(&sort_key);
+                }
+                self.submit_query()?;
+            }
+            // The open tabs belong to the previous workspace's account and region.
+            Action::TransmitWorkspace(_) => {
+                self.tab = TableTab::new();
+                self.tabs.clear();
+                self.active_tab = 0;
+                self.active = false;
+                self.clear_find();
+                self.pending_bookmark = None;
+                self.pending_reference = None;
+            }
+            Action::TransmitLayout(layout) => self.config.config.layout = layout,
+            Action::TransmitPrivacyMode(privacy_mode) => {
+                self.config.config.privacy_mode = privacy_mode;
+                self.raw_copy_requested_at = None;
+            }
             _ => {}
         }
         Ok(None)
     }
 
     fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let theme = self.config.config.theme;
         let [top, bottom] =
             Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
 
         let [_, right] =
-            Layout::horizontal([Constraint::Percentage(30), Constraint::Min(0)]).areas(top);
+            Layout::horizontal([self.config.config.layout.sidebar(), Constraint::Min(0)])
+                .areas(top);
 
         let [_, bottom_right] =
-            Layout::horizontal([Constraint::Percentage(30), Constraint::Min(0)]).areas(bottom);
+            Layout::horizontal([self.config.config.layout.status(), Constraint::Min(0)])
+                .areas(bottom);
+
+        self.area = right;
 
         let mut block = Block::new()
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .title(self.title.clone());
+            .title(self.title());
 
-        if self.active {
-            block = block.border_style(Style::default().fg(EMERALD.c300));
+        block = block.border_style(theme.border(self.active));
+        if let Some(permissions) = self.permissions_line() {
+            block = block.title_bottom(permissions);
         }
 
-        let items: Vec<ListItem> = self
-            .filtered_records
-            .iter()
-            .map(|record| ListItem::new(record.clone()))
+        // Only the rows on screen are built, so spilled records are read back as they scroll
+        // into view instead of all at once.
+        let len = self.tab.filtered_records.len();
+        let (selected, offset) = visible_window(
+            self.tab.list_state.selected(),
+            self.tab.list_state.offset(),
+            len,
+            right.height.saturating_sub(2) as usize,
+        );
+        self.tab.list_state.select(selected);
+        *self.tab.list_state.offset_mut() = offset;
+
+        let number_width = self.tab.records.len().to_string().len();
+        let find = self.find_input.value();
+        let table_schema =
+            schema::for_table(&self.config.config.schemas, &self.tab.collection_name);
+        let first = self.first_attributes();
+        let encrypted = encryption::attributes_of(
+            &self.config.config.encrypted_attributes,
+            &self.tab.collection_name,
+        );
+        let records: Vec<(usize, Cow<str>)> = (offset..len)
+            .take(right.height as usize)
+            .filter_map(|index| {
+                let mut record = self.filtered_record(index)?;
+                if self.config.config.privacy_mode {
+                    record = Cow::Owned(redact_record(&record));
+                }
+                let record = encryption::badge_record(record, encrypted);
+                Some((index, key_order::reorder(record, &first)))
+            })
+            .collect();
+        let computed = self.computed_values(&records);
+        let widths: Vec<usize> = (0..computed.first().map_or(0, Vec::len))
+            .map(|column| {
+                computed
+                    .iter()
+                    .map(|values| values[column].chars().count())
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+        let items: Vec<ListItem> = records
+            .into_iter()
+            .zip(&computed)
+            .map(|((index, record), values)| {
+                let mut spans = Vec::new();
+                if self.config.config.layout.row_numbers {
+                    let row = self.tab.filtered_records[index] + 1;
+                    spans.push(Span::styled(
+                        format!("{row:>number_width$} "),
+                        Style::new().fg(theme.muted),
+                    ));
+                }
+                if let Some(table_schema) = table_schema {
+                    let invalid = serde_json::from_str(&record)
+                        .is_ok_and(|item| !schema::validate(table_schema, &item).is_empty());
+                    spans.push(if invalid {
+                        Span::styled("✗ ", Style::new().fg(theme.error))
+                    } else {
+                        Span::raw("  ")
+                    });
+                }
+                for (value, width) in values.iter().zip(&widths) {
+                    spans.push(Span::styled(
+                        format!("{value:<width$} "),
+                        Style::new().fg(theme.loading),
+                    ));
+                    spans.push(Span::styled("│ ", Style::new().fg(theme.muted)));
+                }
+                if self.find_matches.binary_search(&index).is_ok() {
+                    spans.extend(highlight_matches(
+                        &record,
+                        find,
+                        Style::new().fg(theme.active_border),
+                    ));
+                } else {
+                    spans.push(Span::raw(record.into_owned()));
+                }
+                ListItem::new(Line::from(spans))
+            })
             .collect();
+        let mut window_state = ListState::default()
+            .with_selected(selected.map(|selected| selected - offset))
+            .with_offset(0);
 
-        self.scroll_bar_state = self.scroll_bar_state.content_length(items.len());
+        self.tab.scroll_bar_state = self.tab.scroll_bar_state.content_length(len);
 
         let list = List::new(items)
             .block(block)
-            .style(Style::default().fg(Color::White))
-            .highlight_style(Style::new().bg(VIOLET.c600).add_modifier(Modifier::BOLD));
+            .style(Style::default().fg(theme.text))
+            .highlight_style(theme.selected());
 
         let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
             .symbols(scrollbar::VERTICAL)
@@ -812,7 +2700,7 @@ impl Component for DataBox {
             .track_symbol(None)
             .end_symbol(None);
 
-        StatefulWidget::render(list, right, frame.buffer_mut(), &mut self.list_state);
+        StatefulWidget::render(list, right, frame.buffer_mut(), &mut window_state);
 
         StatefulWidget::render(
             scrollbar,
@@ -821,26 +2709,106 @@ impl Component for DataBox {
                 horizontal: 0,
             }),
             frame.buffer_mut(),
-            &mut self.scroll_bar_state,
+            &mut self.tab.scroll_bar_state,
         );
 
         match self.mode {
             Mode::View => {
-                let view_mode = if self.filter_input.is_empty() {
+                let view_mode = if !self.hides_records() {
                     "Fetched"
                 } else {
                     "Viewing"
                 };
-                let status_text = format!(
+                let mut status_text = format!(
                     "{} {} Items (Scanned: {})",
                     view_mode,
-                    self.filtered_records.len(),
-                    self.aprox_count
+                    self.tab.filtered_records.len(),
+                    self.tab.aprox_count
                 );
 
+                if let Some(filter) = &self.tab.value_filter {
+                    status_text.push_str(&format!(" - where {} = {}", filter.path, filter.value));
+                }
+
+                if let Some((_, view)) = self.soft_delete_view() {
+                    status_text.push_str(&format!(" - {}", view.label()));
+                }
+
+                if !self.hides_records() && self.tab.page_starts.len() > 1 {
+                    status_text.push_str(&format!(
+                        " - page {} of {}",
+                        self.current_page(),
+                        self.tab.page_starts.len()
+                    ));
+                }
+
+                let column_names: Vec<&str> = computed::for_table(
+                    &self.config.config.computed_columns,
+                    &self.tab.collection_name,
+                )
+                .map(|column| column.name.as_str())
+                .collect();
+                if !column_names.is_empty() {
+                    status_text.push_str(&format!(" - columns: {}", column_names.join(", ")));
+                }
+
+                if self.tab.truncated {
+                    status_text.push_str(&format!(" - truncated at {}", self.tab.records.len()));
+                }
+
+                if self.tab.records.spilled() > 0 {
+                    status_text.push_str(&format!(" - {} on disk", self.tab.records.spilled()));
+                }
+
+                if !self.find_input.is_empty() {
+                    let current = self
+                        .tab
+                        .list_state
+                        .selected()
+                        .and_then(|selected| self.find_matches.binary_search(&selected).ok());
+                    match current {
+                        Some(current) => status_text.push_str(&format!(
+                            " - match {} of {}",
+                            current + 1,
+                            self.find_matches.len()
+                        )),
+                        None => {
+                            status_text.push_str(&format!(" - {} matches", self.find_matches.len()))
+                        }
+                    }
+                }
+
+                let scan_denied = self
+                    .tab
+                    .permissions
+                    .as_ref()
+                    .is_some_and(|permissions| permissions.is_denied(TableOperation::Scan));
+                if scan_denied && self.tab.records.len() == 0 {
+                    status_text = "Scanning is not allowed - press <s> to query".to_string();
+                }
+
+                if let Some(refresh_status) = &self.refresh_status {
+                    status_text.push_str(&format!(" - {refresh_status}"));
+                }
+
+                if let Some(deep_find_status) = &self.deep_find_status {
+                    status_text.push_str(&format!(" - {deep_find_status}"));
+                }
+
+                if self.raw_copy_pending() {
+                    status_text = "Copied redacted - copy again to copy the raw values".to_string();
+                }
+
+                if self.copy_confirmation_pending() {
+                    status_text = format!(
+                        "Copy all {} items? Press <C> again to confirm",
+                        self.tab.filtered_records.len()
+                    );
+                }
+
                 Paragraph::new(status_text)
                     .block(Block::default().padding(Padding::horizontal(2)))
-                    .style(Style::new().fg(INDIGO.c700))
+                    .style(Style::new().fg(theme.muted))
                     .render(bottom_right, frame.buffer_mut());
             }
             Mode::Querying => {
@@ -851,20 +2819,174 @@ impl Component for DataBox {
                     Layout::horizontal([Constraint::Length(8), Constraint::Min(0)])
                         .areas(bottom_right);
 
-                let paragraph = Paragraph::new(self.filter_input.clone());
-                paragraph.render(search_right, frame.buffer_mut());
-
-                frame.set_cursor_position(Position::new(
-                    search_right.x + self.character_index as u16,
-                    search_right.y,
-                ));
+                self.tab
+                    .filter_input
+                    .render(frame, search_right, Style::default(), true);
 
                 Paragraph::new("Search:")
-                    .style(Style::new().fg(INDIGO.c700))
+                    .style(Style::new().fg(theme.muted))
                     .render(search_left, frame.buffer_mut());
             }
+            Mode::GoingTo => {
+                let [goto_left, goto_right] =
+                    Layout::horizontal([Constraint::Length(11), Constraint::Min(0)])
+                        .areas(bottom_right);
+
+                self.goto_input
+                    .render(frame, goto_right, Style::default(), true);
+
+                Paragraph::new("Go to row:")
+                    .style(Style::new().fg(theme.muted))
+                    .render(goto_left, frame.buffer_mut());
+            }
+            Mode::NamingPin => {
+                let [pin_left, pin_right] =
+                    Layout::horizontal([Constraint::Length(10), Constraint::Min(0)])
+                        .areas(bottom_right);
+
+                self.pin_input
+                    .render(frame, pin_right, Style::default(), true);
+
+                Paragraph::new("Pin as:")
+                    .style(Style::new().fg(theme.muted))
+                    .render(pin_left, frame.buffer_mut());
+            }
+            Mode::DefiningColumn => {
+                let [column_left, column_right] =
+                    Layout::horizontal([Constraint::Length(8), Constraint::Min(0)])
+                        .areas(bottom_right);
+
+                self.column_input
+                    .render(frame, column_right, Style::default(), true);
+
+                Paragraph::new("Column:")
+                    .style(Style::new().fg(theme.muted))
+                    .render(column_left, frame.buffer_mut());
+            }
+            Mode::Exporting | Mode::DumpingTable => {
+                let [export_left, export_right] =
+                    Layout::horizontal([Constraint::Length(11), Constraint::Min(0)])
+                        .areas(bottom_right);
+
+                self.export_input
+                    .render(frame, export_right, Style::default(), true);
+
+                let prompt = if matches!(self.mode, Mode::DumpingTable) {
+                    "Dump to:"
+                } else {
+                    "Export to:"
+                };
+                Paragraph::new(prompt)
+                    .style(Style::new().fg(theme.muted))
+                    .render(export_left, frame.buffer_mut());
+            }
+            Mode::Finding => {
+                let [find_left, find_right] =
+                    Layout::horizontal([Constraint::Length(6), Constraint::Min(0)])
+                        .areas(bottom_right);
+
+                self.find_input
+                    .render(frame, find_right, Style::default(), true);
+
+                Paragraph::new("Find:")
+                    .style(Style::new().fg(theme.muted))
+                    .render(find_left, frame.buffer_mut());
+            }
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+    use super::*;
+
+    fn data_box() -> (DataBox, UnboundedReceiver<Action>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let mut data_box = DataBox::new();
+        data_box.register_action_handler(tx).unwrap();
+        data_box.register_config_handler(Config::default()).unwrap();
+        data_box.update(Action::SelectDataMode).unwrap();
+        (data_box, rx)
+    }
+
+    fn open(data_box: &mut DataBox, table: &str, records: &[&str]) {
+        let records = records.iter().map(|record| record.to_string()).collect();
+        data_box
+            .update(Action::TransmitSelectedTable(table.to_string()))
+            .unwrap();
+        data_box
+            .update(Action::TransmitTableData(records, false))
+            .unwrap();
+    }
+
+    fn pin(data_box: &mut DataBox, name: &str) {
+        data_box.update(Action::PinResults).unwrap();
+        for c in name.chars() {
+            data_box.update(Action::NewPinNameCharacter(c)).unwrap();
+        }
+        data_box.update(Action::SubmitPinName).unwrap();
+    }
+
+    fn records(tab: &TableTab) -> Vec<String> {
+        (0..tab.records.len())
+            .map(|index| tab.records.get(index).unwrap().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_pinned_results_kept_when_table_reopened() {
+        let (mut data_box, _rx) = data_box();
+        open(&mut data_box, "orders", &[r#"{"id":"1"}"#]);
+        pin(&mut data_box, "first order");
+        assert_eq!(data_box.tab.title, "📌 first order");
+
+        open(&mut data_box, "orders", &[r#"{"id":"2"}"#]);
+        assert_eq!(data_box.tabs.len(), 2);
+        assert_eq!(data_box.active_tab, 1);
+        assert_eq!(records(&data_box.tab), vec![r#"{"id":"2"}"#]);
+
+        // Opening the table again shows its unpinned tab rather than another new one.
+        open(&mut data_box, "orders", &[r#"{"id":"3"}"#]);
+        assert_eq!(data_box.tabs.len(), 2);
+
+        data_box.switch_to(0);
+        assert_eq!(data_box.tab.title, "📌 first order");
+        assert_eq!(records(&data_box.tab), vec![r#"{"id":"1"}"#]);
+    }
+
+    #[test]
+    fn test_pinned_results_ignore_incoming_pages() {
+        let (mut data_box, _rx) = data_box();
+        open(&mut data_box, "orders", &[r#"{"id":"1"}"#]);
+        pin(&mut data_box, "");
+        assert_eq!(data_box.tab.title, "📌 orders");
+
+        data_box
+            .update(Action::TransmitTableData(
+                vec![r#"{"id":"2"}"#.to_string()],
+                true,
+            ))
+            .unwrap();
+        data_box
+            .update(Action::TransmitNextBatcTableData(
+                vec![r#"{"id":"3"}"#.to_string()],
+                false,
+            ))
+            .unwrap();
+        assert_eq!(records(&data_box.tab), vec![r#"{"id":"1"}"#]);
+    }
+
+    #[test]
+    fn test_rename_pinned_results() {
+        let (mut data_box, _rx) = data_box();
+        open(&mut data_box, "orders", &[r#"{"id":"1"}"#]);
+        pin(&mut data_box, "first");
+        pin(&mut data_box, " order");
+        assert_eq!(data_box.tab.title, "📌 first order");
+    }
+}
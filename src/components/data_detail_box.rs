@@ -1,10 +1,11 @@
-use std::collections::HashMap;
+use std::{borrow::Cow, collections::HashMap};
 
 use clipboard::{ClipboardContext, ClipboardProvider};
 use color_eyre::Result;
+use crossterm::event::{MouseEvent, MouseEventKind};
 use ratatui::{
     layout::{Constraint, Layout, Margin, Rect},
-    style::{palette::tailwind::EMERALD, Color, Style},
+    style::Style,
     symbols::scrollbar,
     text::{Line, Span},
     widgets::{
@@ -15,27 +16,78 @@ use ratatui::{
 };
 use serde_json::Value;
 use tokio::sync::mpsc::UnboundedSender;
-use tracing::info;
-
-use crate::{action::Action, config::Config};
+use tracing::error;
+
+use crate::{
+    action::Action,
+    config::{save_pinned_fields, Config, ZoomedPane},
+    encryption::{self, LOCK_BADGE},
+    key_order, pin, relation,
+    session::{redact_record, redact_value},
+    util::json_within,
+};
 
 use super::Component;
 
 #[derive(Clone, Debug)]
 struct TreeNode {
     key: String,
-    value: Value,
+    /// Maps and lists only keep their size, so building the tree of a large item does not copy
+    /// it over and over. Their content is read from the item when needed.
+    value: NodeValue,
     depth: usize,
     expanded: bool,
     path: Vec<String>,
+    /// A copy of a pinned field shown above the item, which does not expand.
+    pinned: bool,
+}
+
+#[derive(Clone, Debug)]
+enum NodeValue {
+    Scalar(Value),
+    /// A map, with the number of attributes in it.
+    Map(usize),
+    /// A list, with the number of values in it.
+    List(usize),
 }
 
+impl NodeValue {
+    fn of(value: &Value) -> Self {
+        match value {
+            Value::Object(map) => NodeValue::Map(map.len()),
+            Value::Array(list) => NodeValue::List(list.len()),
+            value => NodeValue::Scalar(value.clone()),
+        }
+    }
+
+    fn is_scalar(&self) -> bool {
+        matches!(self, NodeValue::Scalar(_))
+    }
+
+    /// What a collapsed map or list shows instead of its content.
+    fn size(&self) -> String {
+        match self {
+            NodeValue::Map(1) => "{1 attribute}".to_string(),
+            NodeValue::Map(count) => format!("{{{count} attributes}}"),
+            NodeValue::List(1) => "[1 value]".to_string(),
+            NodeValue::List(count) => format!("[{count} values]"),
+            NodeValue::Scalar(value) => value.to_string(),
+        }
+    }
+}
+
+/// Pinned maps and lists longer than this, as JSON, show their size rather than their content.
+const PINNED_PREVIEW_LENGTH: usize = 200;
+
 pub struct DataDetailBox {
     command_tx: Option<UnboundedSender<Action>>,
     config: Config,
     active: bool,
     title: String,
+    table: String,
     row: String,
+    /// The item shown, parsed once when it arrives.
+    item: Value,
     tree: Vec<TreeNode>,
     vertical_scroll: usize,
     horizontal_scroll: usize,
@@ -43,6 +95,12 @@ pub struct DataDetailBox {
     horizontal_scroll_state: ScrollbarState,
     selected_index: usize,
     expanded_states: HashMap<Vec<String>, bool>,
+    /// The key attribute names of each table whose description was read.
+    key_names: HashMap<String, Vec<String>>,
+    /// The plaintext of each encrypted attribute of the item, for tables with a decrypt command.
+    decrypted: HashMap<String, Result<String, String>>,
+    /// Height of the popup when it was last drawn, to scroll by half or a whole page.
+    height: u16,
 }
 
 impl DataDetailBox {
@@ -52,7 +110,9 @@ impl DataDetailBox {
             config: Config::default(),
             active: false,
             title: "JSON Viewer".to_string(),
+            table: String::new(),
             row: "".to_string(),
+            item: Value::Null,
             tree: vec![],
             vertical_scroll: 0,
             horizontal_scroll: 0,
@@ -60,24 +120,35 @@ impl DataDetailBox {
             vertical_scroll_state: ScrollbarState::default(),
             selected_index: 0,
             expanded_states: HashMap::new(),
+            key_names: HashMap::new(),
+            decrypted: HashMap::new(),
+            height: 0,
         }
     }
 
-    fn json_to_tree(&mut self, json: &Value, depth: usize, path: Vec<String>) -> Vec<TreeNode> {
+    /// The nodes of `json`, with those of the maps and lists in it only made for the ones that
+    /// are expanded.
+    fn json_to_tree(&self, json: &Value, depth: usize, path: Vec<String>) -> Vec<TreeNode> {
         let mut nodes = Vec::new();
 
         match json {
             Value::Object(map) => {
-                for (key, value) in map {
+                let first = if path.is_empty() {
+                    self.first_attributes()
+                } else {
+                    Vec::new()
+                };
+                for (key, value) in key_order::ordered(map, &first) {
                     let new_path = [path.clone(), vec![key.clone()]].concat();
                     let expanded = *self.expanded_states.get(&new_path).unwrap_or(&false);
 
                     nodes.push(TreeNode {
                         key: key.clone(),
-                        value: value.clone(),
+                        value: NodeValue::of(value),
                         depth,
                         expanded,
                         path: new_path.clone(),
+                        pinned: false,
                     });
 
                     if expanded {
@@ -92,10 +163,11 @@ impl DataDetailBox {
 
                     nodes.push(TreeNode {
                         key: index.to_string(),
-                        value: value.clone(),
+                        value: NodeValue::of(value),
                         depth,
                         expanded,
                         path: new_path.clone(),
+                        pinned: false,
                     });
 
                     if expanded {
@@ -106,10 +178,11 @@ impl DataDetailBox {
             _ => {
                 nodes.push(TreeNode {
                     key: "".to_string(),
-                    value: json.clone(),
+                    value: NodeValue::of(json),
                     depth,
                     expanded: false,
                     path,
+                    pinned: false,
                 });
             }
         }
@@ -117,8 +190,67 @@ impl DataDetailBox {
         nodes
     }
 
+    /// The pinned fields of the table that the item has, followed by the item itself.
+    fn build_tree(&self) -> Vec<TreeNode> {
+        let mut nodes: Vec<TreeNode> =
+            pin::paths_for(&self.config.config.pinned_fields, &self.table)
+                .iter()
+                .filter_map(|path| {
+                    pin::lookup(&self.item, path).map(|value| TreeNode {
+                        key: path.clone(),
+                        value: NodeValue::of(value),
+                        depth: 0,
+                        expanded: false,
+                        path: path.split('.').map(str::to_string).collect(),
+                        pinned: true,
+                    })
+                })
+                .collect();
+        nodes.extend(self.json_to_tree(&self.item, 0, vec![]));
+        nodes
+    }
+
+    /// The value of a node, read from the item.
+    fn value_of(&self, node: &TreeNode) -> Option<&Value> {
+        pin::lookup_segments(&self.item, &node.path)
+    }
+
+    fn toggle_pin(&mut self) -> Result<()> {
+        let Some(node) = self.get_visible_nodes().get(self.selected_index).copied() else {
+            return Ok(());
+        };
+        let path = pin::join_path(&node.path);
+        let pinned = pin::toggle(&mut self.config.config.pinned_fields, &self.table, &path);
+        if let Err(err) = save_pinned_fields(
+            &self.config.config.config_dir,
+            &self.config.config.pinned_fields,
+        ) {
+            error!("Failed to save the pinned fields: {err:?}");
+        }
+
+        self.tree = self.build_tree();
+        // Keep the same field selected while the pinned rows above it come and go.
+        let pinned_count = self.tree.iter().filter(|node| node.pinned).count();
+        self.selected_index = if pinned {
+            self.tree
+                .iter()
+                .position(|node| node.pinned && node.key == path)
+                .unwrap_or(0)
+        } else {
+            self.tree
+                .iter()
+                .position(|node| !node.pinned && pin::join_path(&node.path) == path)
+                .unwrap_or(pinned_count)
+        };
+        Ok(())
+    }
+
     fn toggle_node(&mut self, path: &[String]) {
-        if let Some(node) = self.tree.iter_mut().find(|node| node.path == path) {
+        if let Some(node) = self
+            .tree
+            .iter_mut()
+            .find(|node| !node.pinned && node.path == path)
+        {
             node.expanded = !node.expanded;
             self.expanded_states.insert(path.to_vec(), node.expanded); // Update expanded state
 
@@ -126,15 +258,13 @@ impl DataDetailBox {
             let index = self
                 .tree
                 .iter()
-                .position(|n| n.path == path)
+                .position(|n| !n.pinned && n.path == path)
                 .unwrap_or(self.selected_index);
             self.center_scroll_on(index);
         }
 
         // Rebuild the tree to reflect the updated expanded state
-        if let Ok(json) = self.parse_json() {
-            self.tree = self.json_to_tree(&json, 0, vec![]);
-        }
+        self.tree = self.build_tree();
     }
 
     fn center_scroll_on(&mut self, index: usize) {
@@ -155,6 +285,21 @@ impl DataDetailBox {
         }
     }
 
+    /// Lines of the tree that fit in the popup, inside its borders.
+    fn page_lines(&self) -> isize {
+        self.height.saturating_sub(2).max(1) as isize
+    }
+
+    fn scroll_by(&mut self, delta: isize) {
+        let last_line = self.get_visible_nodes().len().saturating_sub(1);
+        self.vertical_scroll = self
+            .vertical_scroll
+            .saturating_add_signed(delta)
+            .min(last_line);
+        self.vertical_scroll_state = self.vertical_scroll_state.position(self.vertical_scroll);
+        self.selected_index = self.vertical_scroll;
+    }
+
     fn get_visible_nodes(&self) -> Vec<&TreeNode> {
         let mut visible_nodes = Vec::new();
 
@@ -171,47 +316,142 @@ impl DataDetailBox {
         visible_nodes
     }
 
-    fn render_tree(&mut self) -> Vec<Line> {
+    /// The lines of the `height` nodes from `top` on. Only the lines on screen are made, so items
+    /// with thousands of fields expanded stay quick to scroll.
+    fn render_tree(&self, top: usize, height: usize) -> Vec<Line<'_>> {
         let mut lines = Vec::new();
 
         let selected_index = self.selected_index;
-
-        for (index, node) in self.get_visible_nodes().iter().enumerate() {
+        let encrypted =
+            encryption::attributes_of(&self.config.config.encrypted_attributes, &self.table);
+
+        for (index, node) in self
+            .get_visible_nodes()
+            .iter()
+            .enumerate()
+            .skip(top)
+            .take(height)
+        {
             let indent = " ".repeat(node.depth * 2);
-            let line_content = if matches!(node.value, Value::Object(_) | Value::Array(_)) {
-                format!(
-                    "{}{} {}",
-                    indent,
-                    if node.expanded { "▼" } else { "▶" },
-                    node.key
-                )
+            let encrypted_attribute = match node.path.as_slice() {
+                [name] if encrypted.contains(name) => Some(name),
+                _ => None,
+            };
+            let decrypted = encrypted_attribute.and_then(|name| self.decrypted.get(name));
+            let line_content = if node.pinned || node.value.is_scalar() {
+                let value = match decrypted {
+                    Some(Ok(plaintext)) => self.shown(Cow::Owned(Value::String(plaintext.clone()))),
+                    _ => self.shown_value(node),
+                };
+                let value = match encrypted_attribute {
+                    Some(_) => format!("{LOCK_BADGE} {value}"),
+                    None => value,
+                };
+                if node.pinned {
+                    format!("📌 {}: {}", node.key, value)
+                } else {
+                    format!("{}{}: {}", indent, node.key, value)
+                }
+            } else if node.expanded {
+                format!("{}▼ {}", indent, node.key)
             } else {
-                format!("{}{}: {}", indent, node.key, node.value)
+                format!("{}▶ {} {}", indent, node.key, node.value.size())
             };
+            let target = relation::find(
+                &self.config.config.relations,
+                &self.table,
+                &pin::join_path(&node.path),
+            )
+            .map(|relation| format!(" → {}", relation.target_table));
 
             // Highlight the selected node
             let style = if index == selected_index {
-                Style::default().fg(Color::White).bg(Color::Blue)
+                self.config.config.theme.selected()
             } else {
                 Style::default()
             };
 
-            lines.push(Line::from(Span::styled(line_content, style)));
+            let mut line = Line::from(Span::styled(line_content, style));
+            if let Some(Err(error)) = decrypted {
+                line.push_span(Span::styled(
+                    format!(" (decrypt failed: {error})"),
+                    Style::new().fg(self.config.config.theme.error),
+                ));
+            }
+            if let Some(target) = target {
+                line.push_span(Span::styled(
+                    target,
+                    Style::new().fg(self.config.config.theme.muted),
+                ));
+            }
+            lines.push(line);
         }
 
         lines
     }
 
-    fn parse_json(&self) -> Result<Value, serde_json::Error> {
-        serde_json::from_str(&self.row)
+    /// The value of a node as shown next to its name. Maps and lists, which only show next to the
+    /// name when pinned, show their size if they are too long to fit.
+    fn shown_value(&self, node: &TreeNode) -> String {
+        if let NodeValue::Scalar(value) = &node.value {
+            return self.shown(Cow::Borrowed(value));
+        }
+        match self
+            .value_of(node)
+            .filter(|value| json_within(value, PINNED_PREVIEW_LENGTH).is_some())
+        {
+            Some(value) => self.shown(Cow::Borrowed(value)),
+            None => node.value.size(),
+        }
+    }
+
+    fn shown(&self, value: Cow<Value>) -> String {
+        if self.config.config.privacy_mode {
+            redact_value(value.into_owned()).to_string()
+        } else {
+            value.to_string()
+        }
+    }
+
+    /// The key attributes shown first in the items of the table, when they are shown first.
+    fn first_attributes(&self) -> Vec<String> {
+        if !self.config.config.key_attributes_first {
+            return Vec::new();
+        }
+        self.key_names.get(&self.table).cloned().unwrap_or_default()
+    }
+
+    /// Decrypts the encrypted attributes of the item with the decrypt command of its table.
+    fn decrypt(&mut self, json: &Value) {
+        self.decrypted.clear();
+        let Some(encrypted) =
+            encryption::for_table(&self.config.config.encrypted_attributes, &self.table)
+        else {
+            return;
+        };
+        let Some(command) = &encrypted.decrypt_command else {
+            return;
+        };
+        for attribute in &encrypted.attributes {
+            if let Some(value) = json.get(attribute) {
+                let plaintext = encryption::decrypt(command, &self.table, attribute, value);
+                self.decrypted.insert(attribute.clone(), plaintext);
+            }
+        }
     }
 
     fn copy_selected_row_to_clipboard(&self) {
         let mut ctx: ClipboardContext =
             ClipboardProvider::new().expect("Failed to access clipboard");
 
-        ctx.set_contents(self.row.clone())
-            .expect("Failed to copy to clipboard");
+        // The raw values can still be copied from the list, which asks first.
+        let row = if self.config.config.privacy_mode {
+            redact_record(&self.row)
+        } else {
+            self.row.clone()
+        };
+        let row = key_order::reorder(Cow::Owned(row), &self.first_attributes()).into_owned();
+        ctx.set_contents(row).expect("Failed to copy to clipboard");
     }
 }
 
@@ -226,6 +466,24 @@ impl Component for DataDetailBox {
         Ok(())
     }
 
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Result<Option<Action>> {
+        if !self.active {
+            return Ok(None);
+        }
+
+        let action = match mouse.kind {
+            MouseEventKind::ScrollDown => Some(Action::ScrollTableDataRowDetail(
+                self.config.config.scroll.detail_wheel_lines as i32,
+            )),
+            MouseEventKind::ScrollUp => Some(Action::ScrollTableDataRowDetail(
+                -(self.config.config.scroll.detail_wheel_lines as i32),
+            )),
+            _ => None,
+        };
+
+        Ok(action)
+    }
+
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
             Action::Tick => {
@@ -245,17 +503,16 @@ impl Component for DataDetailBox {
             | Action::SelectDataMode => self.active = false,
 
             Action::ViewTableDataRowScrollUp => {
-                self.vertical_scroll = self.vertical_scroll.saturating_sub(10);
-                self.vertical_scroll_state =
-                    self.vertical_scroll_state.position(self.vertical_scroll);
-                self.selected_index = self.vertical_scroll;
+                self.scroll_by(-(self.config.config.scroll.detail_lines as isize));
             }
             Action::ViewTableDataRowScrollDown => {
-                self.vertical_scroll = self.vertical_scroll.saturating_add(10);
-                self.vertical_scroll_state =
-                    self.vertical_scroll_state.position(self.vertical_scroll);
-                self.selected_index = self.vertical_scroll;
+                self.scroll_by(self.config.config.scroll.detail_lines as isize);
             }
+            Action::ViewTableDataRowHalfPageUp => self.scroll_by(-(self.page_lines() / 2).max(1)),
+            Action::ViewTableDataRowHalfPageDown => self.scroll_by((self.page_lines() / 2).max(1)),
+            Action::ViewTableDataRowPageUp => self.scroll_by(-self.page_lines()),
+            Action::ViewTableDataRowPageDown => self.scroll_by(self.page_lines()),
+            Action::ScrollTableDataRowDetail(delta) => self.scroll_by(delta as isize),
             Action::ViewTableDataRowScrollLeft => {
                 self.horizontal_scroll = self.horizontal_scroll.saturating_sub(1);
                 self.horizontal_scroll_state = self
@@ -270,13 +527,31 @@ impl Component for DataDetailBox {
             }
             Action::TransmitSelectedTableDataRow(row) => {
                 self.row = row.clone();
-                if let Ok(json) = self.parse_json() {
-                    self.tree = self.json_to_tree(&json, 0, vec![]);
+                if let Ok(json) = serde_json::from_str(&self.row) {
+                    self.decrypt(&json);
+                    self.item = json;
+                    self.tree = self.build_tree();
                 }
             }
+            // Descriptions arrive after the table they describe is selected.
+            Action::TransmitTableDescription((partition_key, sort_key)) => {
+                let names = [partition_key, sort_key]
+                    .into_iter()
+                    .flatten()
+                    .map(|key| key.name)
+                    .collect();
+                self.key_names.insert(self.table.clone(), names);
+            }
+            Action::TransmitSelectedTable(table) | Action::ActivateTab(table) => {
+                self.table = table;
+            }
             Action::ViewTableDataRowToggleNode => {
                 let index = self.selected_index;
-                if let Some(node) = self.get_visible_nodes().get(index) {
+                if let Some(node) = self
+                    .get_visible_nodes()
+                    .get(index)
+                    .filter(|node| !node.pinned)
+                {
                     let path = node.path.clone();
                     self.toggle_node(&path);
                     self.command_tx.as_ref().unwrap().send(Action::Render)?;
@@ -295,9 +570,69 @@ impl Component for DataDetailBox {
                     .unwrap()
                     .send(Action::SelectDataMode)?;
             }
+            Action::ViewTableDataRowTogglePin => {
+                self.toggle_pin()?;
+                self.command_tx.as_ref().unwrap().send(Action::Render)?;
+            }
+            Action::ViewTableDataRowFilterByValue => {
+                let Some(node) = self.get_visible_nodes().get(self.selected_index).copied() else {
+                    return Ok(None);
+                };
+                let Some(value) = self.value_of(node).cloned() else {
+                    return Ok(None);
+                };
+                let filter = Action::FilterTableDataByValue(pin::join_path(&node.path), value);
+                let command_tx = self.command_tx.as_ref().unwrap();
+                command_tx.send(Action::SelectDataMode)?;
+                command_tx.send(filter)?;
+            }
+            Action::ViewTableDataRowFollowReference => {
+                let Some(node) = self.get_visible_nodes().get(self.selected_index).copied() else {
+                    return Ok(None);
+                };
+                let path = pin::join_path(&node.path);
+                let follow = relation::find(&self.config.config.relations, &self.table, &path)
+                    .zip(self.value_of(node).and_then(relation::key_value))
+                    .map(|(relation, value)| {
+                        Action::FollowReference(relation.target_table.clone(), value)
+                    });
+                if let Some(follow) = follow {
+                    let command_tx = self.command_tx.as_ref().unwrap();
+                    command_tx.send(Action::SelectDataMode)?;
+                    command_tx.send(follow)?;
+                }
+            }
+            Action::TransmitLayout(layout) => self.config.config.layout = layout,
+            Action::TransmitPrivacyMode(privacy_mode) => {
+                self.config.config.privacy_mode = privacy_mode;
+            }
+            Action::EditSelectedItem if self.active => {
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::EditItem(self.table.clone(), self.row.clone()))?;
+            }
             Action::ViewTableDataRowCopyToClipboard => {
                 self.copy_selected_row_to_clipboard();
             }
+            Action::ViewTableDataRowStash => {
+                let Some(node) = self.get_visible_nodes().get(self.selected_index).copied() else {
+                    return Ok(None);
+                };
+                // Like copying, stashing keeps values hidden in privacy mode.
+                let Some(value) = self.value_of(node) else {
+                    return Ok(None);
+                };
+                let snippet = format!(
+                    "{}: {}",
+                    pin::join_path(&node.path),
+                    self.shown(Cow::Borrowed(value))
+                );
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::StashInScratchpad(self.table.clone(), snippet))?;
+            }
             _ => {}
         }
         Ok(None)
@@ -308,38 +643,45 @@ impl Component for DataDetailBox {
             return Ok(());
         }
 
+        let margin = match self.config.config.layout.zoomed {
+            Some(ZoomedPane::Detail) => 0,
+            _ => 10,
+        };
         let [_, y_middle, _] = Layout::vertical([
-            Constraint::Percentage(10),
-            Constraint::Percentage(80),
-            Constraint::Percentage(10),
+            Constraint::Percentage(margin),
+            Constraint::Percentage(100 - 2 * margin),
+            Constraint::Percentage(margin),
         ])
         .areas(area);
 
         let [_, middle, _] = Layout::horizontal([
-            Constraint::Percentage(10),
-            Constraint::Percentage(80),
-            Constraint::Percentage(10),
+            Constraint::Percentage(margin),
+            Constraint::Percentage(100 - 2 * margin),
+            Constraint::Percentage(margin),
         ])
         .areas(y_middle);
+        self.height = middle.height;
 
         let block = Block::new()
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(EMERALD.c300))
-            .style(Style::new().bg(Color::Black))
+            .border_style(self.config.config.theme.border(true))
+            .style(self.config.config.theme.popup())
             .title(self.title.clone());
 
-        let vertical_scroll = self.vertical_scroll;
         let horizontal_scroll = self.horizontal_scroll;
+        let total_lines = self.get_visible_nodes().len();
 
-        let lines = self.render_tree();
+        let lines = self.render_tree(
+            self.vertical_scroll,
+            usize::from(middle.height.saturating_sub(2)),
+        );
 
         let longest_line_width = lines.iter().map(|line| line.width()).max().unwrap_or(0);
-        let total_lines = lines.len();
 
         let paragraph = Paragraph::new(lines)
             .block(block)
-            .scroll((vertical_scroll as u16, horizontal_scroll as u16));
+            .scroll((0, horizontal_scroll as u16));
 
         frame.render_widget(Clear, middle);
         frame.render_widget(paragraph, middle);
@@ -0,0 +1,90 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{action::Action, config::Config};
+
+use super::Component;
+
+/// Timings the app measures while the overlay is shown, refreshed on every tick.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrameStats {
+    pub render_micros: u64,
+    pub frames_per_second: usize,
+    pub actions_per_tick: usize,
+    pub pending_requests: usize,
+}
+
+/// Corner overlay with render and data statistics, toggled with `<f12>` or `--fps-debug`.
+#[derive(Default)]
+pub struct DebugOverlay {
+    config: Config,
+    active: bool,
+    stats: FrameStats,
+    records_bytes: usize,
+}
+
+impl DebugOverlay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Component for DebugOverlay {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ToggleDebugOverlay => self.active = !self.active,
+            Action::TransmitFrameStats(stats) => self.stats = stats,
+            Action::TransmitRecordsMemory(bytes) => self.records_bytes = bytes,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+
+        let [_, right] =
+            Layout::horizontal([Constraint::Min(0), Constraint::Length(32)]).areas(area);
+        let [top, _] = Layout::vertical([Constraint::Length(7), Constraint::Min(0)]).areas(right);
+
+        let theme = self.config.config.theme;
+        let lines = vec![
+            Line::from(format!(
+                "frame:    {:.2} ms",
+                self.stats.render_micros as f64 / 1000.0
+            )),
+            Line::from(format!("fps:      {}", self.stats.frames_per_second)),
+            Line::from(format!("actions:  {} / tick", self.stats.actions_per_tick)),
+            Line::from(format!("requests: {} pending", self.stats.pending_requests)),
+            Line::from(format!(
+                "records:  {:.1} MiB",
+                self.records_bytes as f64 / (1024.0 * 1024.0)
+            )),
+        ];
+
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(false))
+            .style(theme.popup())
+            .title("Debug");
+
+        frame.render_widget(Clear, top);
+        frame.render_widget(Paragraph::new(lines).block(block), top);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+use std::{collections::VecDeque, time::SystemTime};
+
+use aws_sdk_dynamodb::primitives::{DateTime, DateTimeFormat};
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+use tracing::error;
+
+use crate::{action::Action, config::Config};
+
+use super::Component;
+
+/// The most errors kept waiting to be dismissed. Older ones are dropped once there are more.
+const MAX_QUEUED_ERRORS: usize = 50;
+/// The most errors the banner lists at once.
+const SHOWN_ERRORS: usize = 3;
+
+struct QueuedError {
+    message: String,
+    /// When the error last happened, in RFC 3339.
+    timestamp: String,
+    /// How many times in a row it happened, such as a draw failing on every frame.
+    repeats: usize,
+}
+
+/// Banner along the bottom of the screen listing the errors sent as `Action::Error`, oldest
+/// first, until they are dismissed. Every error is also written to the log.
+#[derive(Default)]
+pub struct ErrorBanner {
+    config: Config,
+    errors: VecDeque<QueuedError>,
+}
+
+impl ErrorBanner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(&mut self, message: String) {
+        error!("{message}");
+        let timestamp = DateTime::from(SystemTime::now())
+            .fmt(DateTimeFormat::DateTime)
+            .unwrap_or_default();
+        if let Some(last) = self
+            .errors
+            .back_mut()
+            .filter(|last| last.message == message)
+        {
+            last.timestamp = timestamp;
+            last.repeats += 1;
+            return;
+        }
+        self.errors.push_back(QueuedError {
+            message,
+            timestamp,
+            repeats: 1,
+        });
+        if self.errors.len() > MAX_QUEUED_ERRORS {
+            self.errors.pop_front();
+        }
+    }
+
+    fn error_line(&self, error: &QueuedError) -> Line<'static> {
+        let theme = self.config.config.theme;
+        // Timestamps are RFC 3339, so the time of day starts at the 12th character.
+        let time = error.timestamp.get(11..19).unwrap_or(&error.timestamp);
+        let mut spans = vec![
+            Span::styled(format!("{time}  "), Style::new().fg(theme.muted)),
+            Span::styled(
+                error.message.replace('\n', " "),
+                Style::new().fg(theme.error),
+            ),
+        ];
+        if error.repeats > 1 {
+            spans.push(Span::styled(
+                format!("  ({} times)", error.repeats),
+                Style::new().fg(theme.muted),
+            ));
+        }
+        Line::from(spans)
+    }
+}
+
+impl Component for ErrorBanner {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::Error(message) => self.push(message),
+            Action::DismissError => {
+                self.errors.pop_front();
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if self.errors.is_empty() {
+            return Ok(());
+        }
+
+        let shown = self.errors.len().min(SHOWN_ERRORS);
+        let [_, banner] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(shown as u16 + 2)])
+                .areas(area);
+
+        let theme = self.config.config.theme;
+        let title = if self.errors.len() == 1 {
+            "Error".to_string()
+        } else {
+            format!("Errors ({})", self.errors.len())
+        };
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().fg(theme.error))
+            .style(theme.popup())
+            .title(title)
+            .title_bottom(Line::from("<Ctrl-g> to dismiss the oldest").centered());
+
+        let lines: Vec<Line> = self
+            .errors
+            .iter()
+            .take(shown)
+            .map(|error| self.error_line(error))
+            .collect();
+
+        frame.render_widget(Clear, banner);
+        frame.render_widget(Paragraph::new(lines).block(block), banner);
+        Ok(())
+    }
+}
@@ -0,0 +1,264 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph, Wrap},
+    Frame,
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    action::Action,
+    config::Config,
+    import::{ImportMode, ImportPreview},
+};
+
+use super::{text_input::TextInput, Component};
+
+/// Popup that asks for a JSON or NDJSON file to import into a table, then shows what the import
+/// would create and overwrite before anything is written.
+#[derive(Default)]
+pub struct ImportBox {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    /// The table being imported into, while the popup is open.
+    table: Option<String>,
+    path: TextInput,
+    preview: Option<ImportPreview>,
+    mode: ImportMode,
+    /// Set while the file is being compared with the table.
+    comparing: bool,
+    error: Option<String>,
+}
+
+impl ImportBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn submit_path(&mut self) -> Result<()> {
+        let Some(table) = self.table.clone() else {
+            return Ok(());
+        };
+        let path = self.path.value().trim();
+        if path.is_empty() {
+            self.error = Some("Enter the path of a JSON or NDJSON file".to_string());
+            return Ok(());
+        }
+        self.comparing = true;
+        self.error = None;
+        self.command_tx
+            .as_ref()
+            .unwrap()
+            .send(Action::PreviewImport(table, path.to_string()))?;
+        Ok(())
+    }
+
+    fn confirm(&mut self) -> Result<()> {
+        if self.preview.is_none() {
+            return Ok(());
+        }
+        let mode = self.mode;
+        self.close();
+        self.command_tx
+            .as_ref()
+            .unwrap()
+            .send(Action::StartImport(mode))?;
+        Ok(())
+    }
+
+    fn close(&mut self) {
+        self.table = None;
+        self.preview = None;
+        self.comparing = false;
+        self.error = None;
+    }
+
+    fn preview_lines(&self, preview: &ImportPreview) -> Vec<Line<'static>> {
+        let theme = self.config.config.theme;
+        let (overwrites, writes) = match self.mode {
+            ImportMode::Overwrite => (
+                "replace a different one",
+                preview.creates + preview.overwrites,
+            ),
+            ImportMode::InsertOnly => (
+                "have a key that is taken and are reported as conflicts",
+                preview.creates,
+            ),
+            ImportMode::Merge => (
+                "are merged into a different one",
+                preview.creates + preview.overwrites,
+            ),
+        };
+
+        let mut lines = vec![
+            Line::styled(
+                preview.path.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Line::from(format!("Mode: {}", self.mode.label())),
+            Line::from(""),
+            Line::from(format!("{} new items", preview.creates)),
+            Line::from(format!("{} items {overwrites}", preview.overwrites)),
+            Line::from(format!(
+                "{} items are identical and are not written",
+                preview.identical
+            )),
+            Line::from(""),
+            Line::styled(
+                match writes {
+                    0 => "Nothing to write.".to_string(),
+                    writes => format!("{writes} items will be written."),
+                },
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+        ];
+
+        if !preview.samples.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::styled(
+                "Overwritten items:",
+                Style::new().fg(theme.muted),
+            ));
+        }
+        for sample in &preview.samples {
+            lines.push(Line::from(sample.key.clone()));
+            lines.extend(sample.changes.iter().map(|change| {
+                let color = match change.chars().next() {
+                    Some('+') => theme.active_border,
+                    Some('-') => theme.error,
+                    _ => theme.loading,
+                };
+                Line::from(Span::styled(format!("  {change}"), Style::new().fg(color)))
+            }));
+        }
+
+        lines
+    }
+}
+
+impl Component for ImportBox {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            // The path is kept, so a file can be imported into several tables in a row.
+            Action::ShowImport(table) => {
+                self.table = Some(table);
+                self.preview = None;
+                self.mode = ImportMode::default();
+                self.comparing = false;
+                self.error = None;
+            }
+            Action::NewImportCharacter(c) if !self.comparing => {
+                self.path.enter_char(c);
+                self.error = None;
+            }
+            Action::DeleteImportCharacter if !self.comparing => {
+                self.path.delete_char();
+                self.error = None;
+            }
+            Action::SubmitImportPath if !self.comparing => self.submit_path()?,
+            Action::TransmitImportPreview(preview) if self.table.is_some() => {
+                self.comparing = false;
+                match preview {
+                    Ok(preview) => self.preview = Some(preview),
+                    Err(error) => self.error = Some(error),
+                }
+            }
+            Action::CycleImportMode => self.mode = self.mode.next(),
+            Action::ConfirmImport => self.confirm()?,
+            Action::ExitImport => self.close(),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let Some(table) = &self.table else {
+            return Ok(());
+        };
+
+        let height = match &self.preview {
+            Some(_) => area.height.saturating_sub(4).min(30),
+            None => 8,
+        };
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(height),
+            Constraint::Min(0),
+        ])
+        .areas(area);
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(20),
+            Constraint::Percentage(60),
+            Constraint::Percentage(20),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let hint = match &self.preview {
+            Some(_) => "<enter> to import - <m> to change mode - <esc> to cancel",
+            None => "<enter> to preview - <esc> to cancel",
+        };
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .padding(Padding::uniform(1))
+            .title(format!("Import into {table}"))
+            .title_bottom(Line::from(hint).centered());
+
+        let inner = block.inner(middle);
+        frame.render_widget(Clear, middle);
+        frame.render_widget(block, middle);
+
+        if let Some(preview) = &self.preview {
+            frame.render_widget(
+                Paragraph::new(self.preview_lines(preview)).wrap(Wrap { trim: false }),
+                inner,
+            );
+            return Ok(());
+        }
+
+        let [label_area, value_area, message_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .areas(inner);
+        frame.render_widget(
+            Paragraph::new("File (a JSON array of items, or one item per line):")
+                .style(theme.popup().fg(theme.muted)),
+            label_area,
+        );
+        self.path
+            .render(frame, value_area, theme.popup(), !self.comparing);
+
+        let message = match (&self.error, self.comparing) {
+            (Some(error), _) => Some((error.clone(), theme.error)),
+            (None, true) => Some(("Comparing with the table...".to_string(), theme.muted)),
+            (None, false) => None,
+        };
+        if let Some((message, color)) = message {
+            frame.render_widget(
+                Paragraph::new(message)
+                    .style(Style::new().fg(color))
+                    .wrap(Wrap { trim: true }),
+                message_area,
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,498 @@
+use std::collections::HashMap;
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{
+        Block, BorderType, Borders, Clear, List, ListItem, ListState, Padding, Paragraph, Wrap,
+    },
+    Frame,
+};
+use serde_json::Value;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    action::Action,
+    config::Config,
+    data::KeyAttribute,
+    typed_json::{item_from_typed, item_to_typed, retype_item, Ambiguity, TypeChoice},
+};
+
+use super::{text_input::TextInput, Component};
+
+/// Popup that edits the attributes of an item, one value at a time, and writes the whole item
+/// with PutItem. Existing items keep their key attributes, as changing them would write a
+/// different item, while new items start out with empty key attributes to fill in. Edited items
+/// are read again with the types of their attributes, which the attributes left alone keep.
+#[derive(Default)]
+pub struct ItemEditorBox {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    /// The table of the item being edited, while the popup is open.
+    table: Option<String>,
+    /// Set when creating an item rather than editing one, which must not replace another.
+    new_item: bool,
+    attributes: Vec<(String, Value)>,
+    key_names: Vec<String>,
+    /// The attributes that were changed, added or removed.
+    changed: Vec<String>,
+    list_state: ListState,
+    /// What is being typed, if anything.
+    editing: Option<Field>,
+    input: TextInput,
+    saving: bool,
+    error: Option<String>,
+    /// The attributes of the item being edited as DynamoDB holds them, once read, or the reason
+    /// they could not be.
+    types: Option<Result<HashMap<String, AttributeValue>, String>>,
+    /// How to type the changed values that could be typed two ways, by path.
+    choices: HashMap<String, TypeChoice>,
+    /// The changed value asked about before saving.
+    question: Option<Ambiguity>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Field {
+    /// The name of an attribute being added.
+    Name,
+    /// The value of the selected attribute.
+    Value,
+}
+
+impl ItemEditorBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn open(&mut self, table: String, item: &str, key_names: Vec<String>) {
+        let Ok(Value::Object(item)) = serde_json::from_str::<Value>(item) else {
+            return;
+        };
+        self.table = Some(table);
+        self.new_item = false;
+        self.attributes = item.into_iter().collect();
+        self.key_names = key_names;
+        self.changed.clear();
+        self.editing = None;
+        self.saving = false;
+        self.error = None;
+        self.types = None;
+        self.choices.clear();
+        self.question = None;
+        self.list_state.select_first();
+    }
+
+    fn open_new(&mut self, table: String, keys: Vec<KeyAttribute>) {
+        self.table = Some(table);
+        self.new_item = true;
+        self.attributes = keys
+            .iter()
+            .map(|key| (key.name.clone(), Value::String(String::new())))
+            .collect();
+        self.key_names = keys.into_iter().map(|key| key.name).collect();
+        self.changed.clear();
+        self.editing = None;
+        self.saving = false;
+        self.error = None;
+        self.forget_choices();
+        self.list_state.select_first();
+    }
+
+    fn close(&mut self) {
+        self.table = None;
+        self.attributes.clear();
+        self.editing = None;
+        self.saving = false;
+    }
+
+    fn selected(&self) -> Option<&(String, Value)> {
+        self.list_state
+            .selected()
+            .and_then(|selected| self.attributes.get(selected))
+    }
+
+    fn is_locked(&self, name: &str) -> bool {
+        !self.new_item && self.key_names.iter().any(|key| key == name)
+    }
+
+    /// Starts typing the value of the selected attribute, prefilled with its JSON so that
+    /// keeping it as it is leaves its type alone. Empty strings start out empty.
+    fn start_editing(&mut self) -> Result<()> {
+        let Some((name, value)) = self.selected() else {
+            return Ok(());
+        };
+        if self.is_locked(name) {
+            self.error = Some(format!("{name} is a key attribute and cannot be changed"));
+            return Ok(());
+        }
+
+        let text = match value {
+            Value::String(text) if text.is_empty() => String::new(),
+            value => value.to_string(),
+        };
+        self.start_typing(Field::Value, &text)
+    }
+
+    fn start_adding(&mut self) -> Result<()> {
+        self.start_typing(Field::Name, "")
+    }
+
+    fn start_typing(&mut self, field: Field, text: &str) -> Result<()> {
+        self.input.clear();
+        self.input.insert_str(text);
+        self.editing = Some(field);
+        self.error = None;
+        self.forget_choices();
+        self.command_tx
+            .as_ref()
+            .unwrap()
+            .send(Action::EditingItemAttribute)?;
+        Ok(())
+    }
+
+    fn submit(&mut self) -> Result<()> {
+        match self.editing {
+            Some(Field::Name) => self.submit_name(),
+            Some(Field::Value) => {
+                self.submit_value();
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Adds an attribute with the typed name, then asks for its value.
+    fn submit_name(&mut self) -> Result<()> {
+        let name = self.input.value().trim().to_string();
+        self.editing = None;
+        if name.is_empty() {
+            return Ok(());
+        }
+        if self
+            .attributes
+            .iter()
+            .any(|(existing, _)| *existing == name)
+        {
+            self.error = Some(format!("The item already has {name}"));
+            return Ok(());
+        }
+
+        self.attributes
+            .push((name.clone(), Value::String(String::new())));
+        self.changed.push(name);
+        self.list_state.select(Some(self.attributes.len() - 1));
+        self.start_editing()
+    }
+
+    fn submit_value(&mut self) {
+        self.editing = None;
+        let Some(selected) = self.list_state.selected() else {
+            return;
+        };
+        let Some((name, value)) = self.attributes.get_mut(selected) else {
+            return;
+        };
+        let new_value = parse_value(self.input.value());
+        if *value != new_value {
+            *value = new_value;
+            if !self.changed.contains(name) {
+                self.changed.push(name.clone());
+            }
+        }
+    }
+
+    fn remove_selected(&mut self) {
+        let Some(selected) = self.list_state.selected() else {
+            return;
+        };
+        let Some((name, _)) = self.attributes.get(selected) else {
+            return;
+        };
+        if self.key_names.contains(name) {
+            self.error = Some(format!("{name} is a key attribute and cannot be removed"));
+            return;
+        }
+
+        let (name, _) = self.attributes.remove(selected);
+        if !self.changed.contains(&name) {
+            self.changed.push(name);
+        }
+        self.error = None;
+        self.forget_choices();
+    }
+
+    /// Drops the answers about how to type changed values, which may no longer apply once the
+    /// item changes again.
+    fn forget_choices(&mut self) {
+        self.choices.clear();
+        self.question = None;
+    }
+
+    fn receive_types(&mut self, types: Result<serde_json::Value, String>) {
+        let types = types.and_then(|types| {
+            item_from_typed(&types)
+                .ok_or_else(|| "The item was read in an unknown format".to_string())
+        });
+        if let Err(error) = &types {
+            self.error = Some(format!(
+                "The types of the attributes could not be read, so the item is written as JSON: \
+                 {error}"
+            ));
+        }
+        self.types = Some(types);
+    }
+
+    /// Answers the question about how to type a changed value, and goes on saving.
+    fn choose_type(&mut self, choice: TypeChoice) -> Result<()> {
+        let Some(question) = self.question.take() else {
+            return Ok(());
+        };
+        self.choices.insert(question.path, choice);
+        self.save()
+    }
+
+    fn save(&mut self) -> Result<()> {
+        let Some(table) = self.table.clone() else {
+            return Ok(());
+        };
+        if self.changed.is_empty() {
+            self.error = Some("Nothing was changed".to_string());
+            return Ok(());
+        }
+        if let Some((name, _)) = self.attributes.iter().find(|(name, value)| {
+            self.key_names.contains(name) && *value == Value::String(String::new())
+        }) {
+            self.error = Some(format!("Enter a value for the key attribute {name}"));
+            return Ok(());
+        }
+
+        let attributes = self.attributes.iter().cloned().collect();
+        let typed = match &self.types {
+            _ if self.new_item => None,
+            None => {
+                self.error = Some("The item is still being read, to keep its types".to_string());
+                return Ok(());
+            }
+            Some(Err(_)) => None,
+            Some(Ok(original)) => match retype_item(original, &attributes, &self.choices) {
+                Ok(typed) => Some(item_to_typed(&typed)),
+                Err(question) => {
+                    self.question = Some(question);
+                    return Ok(());
+                }
+            },
+        };
+
+        let item = Value::Object(attributes).to_string();
+        self.saving = true;
+        self.error = None;
+        self.command_tx.as_ref().unwrap().send(if self.new_item {
+            Action::PutItem(table, item)
+        } else {
+            Action::UpdateItem(table, item, typed)
+        })?;
+        Ok(())
+    }
+
+    fn render_attribute(&self, name: &str, value: &Value) -> ListItem<'static> {
+        let theme = self.config.config.theme;
+        let mut name_style = Style::default().add_modifier(Modifier::BOLD);
+        if self.is_locked(name) {
+            name_style = name_style.fg(theme.muted);
+        }
+        let marker = if self.changed.iter().any(|changed| changed == name) {
+            "* "
+        } else {
+            "  "
+        };
+        ListItem::new(Line::from(vec![
+            Span::raw(marker),
+            Span::styled(format!("{name}: "), name_style),
+            Span::raw(value.to_string()),
+        ]))
+    }
+}
+
+/// The value typed for an attribute: JSON when it parses as JSON, such as `"paid"`, `12` or
+/// `{"a": 1}`, and otherwise the text itself as a string, so quotes can be left out.
+fn parse_value(text: &str) -> Value {
+    serde_json::from_str(text).unwrap_or_else(|_| Value::String(text.to_string()))
+}
+
+impl Component for ItemEditorBox {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ShowItemEditor(table, item, key_names) => self.open(table, &item, key_names),
+            Action::ShowNewItemEditor(table, keys) => self.open_new(table, keys),
+            Action::ItemEditorNext if self.editing.is_none() => self.list_state.select_next(),
+            Action::ItemEditorPrev if self.editing.is_none() => self.list_state.select_previous(),
+            Action::EditItemAttribute if !self.saving => self.start_editing()?,
+            Action::AddItemAttribute if !self.saving => self.start_adding()?,
+            Action::RemoveItemAttribute if !self.saving => self.remove_selected(),
+            Action::NewItemEditorCharacter(c) if self.editing.is_some() => self.input.enter_char(c),
+            Action::DeleteItemEditorCharacter if self.editing.is_some() => self.input.delete_char(),
+            Action::SubmitItemAttribute => self.submit()?,
+            Action::CancelItemAttribute => self.editing = None,
+            Action::SaveItemEdit if !self.saving => self.save()?,
+            Action::TransmitItemTypes(table, types)
+                if !self.new_item && self.table.as_ref() == Some(&table) =>
+            {
+                self.receive_types(types)
+            }
+            Action::KeepOldAttributeType if !self.saving => self.choose_type(TypeChoice::Old)?,
+            Action::TakeNewAttributeType if !self.saving => self.choose_type(TypeChoice::New)?,
+            Action::TransmitPutItemResult(table, error)
+                if self.saving && self.table.as_ref() == Some(&table) =>
+            {
+                self.saving = false;
+                match error {
+                    Some(error) => self.error = Some(error),
+                    None => self.close(),
+                }
+            }
+            Action::ExitItemEditor => self.close(),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let Some(table) = self.table.clone() else {
+            return Ok(());
+        };
+
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Percentage(15),
+            Constraint::Percentage(70),
+            Constraint::Percentage(15),
+        ])
+        .areas(area);
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(15),
+            Constraint::Percentage(70),
+            Constraint::Percentage(15),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let hint = match (self.saving, self.editing) {
+            (true, _) => "Saving...",
+            (false, Some(_)) => "<enter> to keep - <esc> to discard",
+            (false, None) if self.question.is_some() => {
+                "<o> for the type it had - <n> for the new type - <enter> to edit"
+            }
+            (false, None) => {
+                "<enter> to edit - <a> to add - <d> to remove - <Ctrl-s> to save - <esc> to cancel"
+            }
+        };
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .padding(Padding::uniform(1))
+            .title(if self.new_item {
+                format!("New Item - {table}")
+            } else {
+                format!("Edit Item - {table}")
+            })
+            .title_bottom(Line::from(hint).centered());
+
+        let inner = block.inner(middle);
+        frame.render_widget(Clear, middle);
+        frame.render_widget(block, middle);
+
+        let [message_area, input_area, list_area] = Layout::vertical([
+            Constraint::Length(if self.error.is_some() || self.question.is_some() {
+                2
+            } else {
+                0
+            }),
+            Constraint::Length(if self.editing.is_some() { 2 } else { 0 }),
+            Constraint::Min(0),
+        ])
+        .areas(inner);
+
+        if let Some(question) = &self.question {
+            frame.render_widget(
+                Paragraph::new(format!(
+                    "{} changed type. Write it as {}, or as {}?",
+                    question.path, question.old, question.new
+                ))
+                .style(Style::new().fg(theme.loading))
+                .wrap(Wrap { trim: true }),
+                message_area,
+            );
+        } else if let Some(error) = &self.error {
+            frame.render_widget(
+                Paragraph::new(error.clone())
+                    .style(Style::new().fg(theme.error))
+                    .wrap(Wrap { trim: true }),
+                message_area,
+            );
+        }
+
+        if let Some(field) = self.editing {
+            let label = match field {
+                Field::Name => "New attribute: ".to_string(),
+                Field::Value => {
+                    let name = self.selected().map(|(name, _)| name.clone());
+                    format!("{}: ", name.unwrap_or_default())
+                }
+            };
+            let [label_area, value_area] =
+                Layout::horizontal([Constraint::Length(label.len() as u16), Constraint::Min(0)])
+                    .areas(input_area);
+            frame.render_widget(
+                Paragraph::new(Span::styled(
+                    label,
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                label_area,
+            );
+            self.input
+                .render(frame, value_area, Style::default().fg(theme.text), true);
+        }
+
+        let items: Vec<ListItem> = self
+            .attributes
+            .iter()
+            .map(|(name, value)| self.render_attribute(name, value))
+            .collect();
+        let list = List::new(items).highlight_style(theme.selected());
+        frame.render_stateful_widget(list, list_area, &mut self.list_state);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_value() {
+        assert_eq!(parse_value("12"), json!(12));
+        assert_eq!(parse_value("true"), json!(true));
+        assert_eq!(parse_value(r#"{"a": [1]}"#), json!({ "a": [1] }));
+        assert_eq!(parse_value(r#""12""#), json!("12"));
+        assert_eq!(parse_value("paid"), json!("paid"));
+        assert_eq!(parse_value(""), json!(""));
+    }
+}
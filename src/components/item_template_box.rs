@@ -0,0 +1,397 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{
+        Block, BorderType, Borders, Clear, List, ListItem, ListState, Padding, Paragraph, Wrap,
+    },
+    Frame,
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    action::Action,
+    config::Config,
+    id,
+    template::{fill_generated, is_generated, ItemTemplate, Placeholder},
+};
+
+use super::{text_input::TextInput, Component};
+
+/// Items seeded when the count is left empty.
+const DEFAULT_SEED_COUNT: usize = 100;
+
+/// Popup that creates an item from one of the templates configured for a table, prompting for
+/// each placeholder in turn. When seeding, it asks how many items to create instead, and each
+/// gets its own generated values.
+#[derive(Default)]
+pub struct ItemTemplateBox {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    /// The table the item is created in, while the popup is open.
+    table: Option<String>,
+    seeding: bool,
+    step: Step,
+    list_state: ListState,
+    /// The value typed for the placeholder being prompted for.
+    input: TextInput,
+    error: Option<String>,
+}
+
+#[derive(Default)]
+enum Step {
+    #[default]
+    Choosing,
+    Filling {
+        template: ItemTemplate,
+        /// The placeholders that are prompted for, in order.
+        prompts: Vec<Placeholder>,
+        values: HashMap<String, String>,
+    },
+    /// Asking how many items to seed from the item, whose generated values are still to fill.
+    Counting {
+        item: Value,
+    },
+    Saving,
+}
+
+impl ItemTemplateBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn open(&mut self, table: String, seeding: bool) {
+        self.table = Some(table);
+        self.seeding = seeding;
+        self.step = Step::Choosing;
+        self.error = None;
+        self.list_state.select_first();
+    }
+
+    fn templates(&self) -> Vec<&ItemTemplate> {
+        self.config
+            .config
+            .templates
+            .iter()
+            .filter(|template| Some(&template.table) == self.table.as_ref())
+            .collect()
+    }
+
+    fn choose(&mut self) -> Result<()> {
+        let Some(template) = self
+            .list_state
+            .selected()
+            .and_then(|selected| self.templates().get(selected).copied())
+            .cloned()
+        else {
+            return Ok(());
+        };
+
+        // Generated values are picked once, so the preview matches the item that is saved.
+        // Seeded items each get their own, so those are left until the items are made.
+        let template = if self.seeding {
+            template
+        } else {
+            ItemTemplate {
+                item: fill_generated(&template.item),
+                ..template
+            }
+        };
+        let prompts = template
+            .placeholders()
+            .into_iter()
+            .filter(|placeholder| !is_generated(&placeholder.name))
+            .collect();
+
+        self.step = Step::Filling {
+            template,
+            prompts,
+            values: HashMap::new(),
+        };
+        self.prompt_next()
+    }
+
+    /// Stores the value typed for the current placeholder and moves on to the next one.
+    fn submit_value(&mut self) -> Result<()> {
+        if let Step::Filling {
+            prompts, values, ..
+        } = &mut self.step
+        {
+            if let Some(placeholder) = prompts.get(values_entered(prompts, values)) {
+                let value = match (self.input.value(), &placeholder.default) {
+                    ("", Some(default)) => default.clone(),
+                    (value, _) => value.to_string(),
+                };
+                values.insert(placeholder.name.clone(), value);
+                self.input.clear();
+            }
+        }
+        self.prompt_next()
+    }
+
+    /// Prefills the input for the next placeholder, or saves the item once all are filled.
+    fn prompt_next(&mut self) -> Result<()> {
+        let Step::Filling {
+            template,
+            prompts,
+            values,
+        } = &mut self.step
+        else {
+            return Ok(());
+        };
+
+        if let Some(placeholder) = prompts.get(values_entered(prompts, values)) {
+            self.input = TextInput::new().placeholder(placeholder.default.as_deref().unwrap_or(""));
+            return Ok(());
+        }
+
+        let item = template.fill(values);
+        if self.seeding {
+            self.input = TextInput::new()
+                .placeholder(&DEFAULT_SEED_COUNT.to_string())
+                .char_filter(|c| c.is_ascii_digit());
+            self.step = Step::Counting { item };
+            return Ok(());
+        }
+
+        self.step = Step::Saving;
+        self.error = None;
+        self.command_tx.as_ref().unwrap().send(Action::PutItem(
+            self.table.clone().unwrap_or_default(),
+            item.to_string(),
+        ))?;
+        Ok(())
+    }
+
+    fn submit_count(&mut self) -> Result<()> {
+        let Step::Counting { item } = &self.step else {
+            return Ok(());
+        };
+
+        let count = self.input.value().parse().unwrap_or(DEFAULT_SEED_COUNT);
+        self.command_tx.as_ref().unwrap().send(Action::SeedItems(
+            self.table.clone().unwrap_or_default(),
+            item.to_string(),
+            count,
+        ))?;
+        self.close();
+        Ok(())
+    }
+
+    fn close(&mut self) {
+        self.table = None;
+        self.step = Step::Choosing;
+        self.error = None;
+    }
+
+    fn render_templates(&mut self, frame: &mut Frame, area: Rect) {
+        let theme = self.config.config.theme;
+        if self.templates().is_empty() {
+            frame.render_widget(
+                Paragraph::new(
+                    "No templates for this table. Add them under `templates` in the config.",
+                )
+                .style(Style::new().fg(theme.muted))
+                .wrap(Wrap { trim: false }),
+                area,
+            );
+            return;
+        }
+
+        let items: Vec<ListItem> = self
+            .templates()
+            .into_iter()
+            .map(|template| ListItem::new(template.name.clone()))
+            .collect();
+        let list = List::new(items).highlight_style(theme.selected());
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    fn render_filling(&self, frame: &mut Frame, area: Rect) {
+        let theme = self.config.config.theme;
+        let (prompt, preview) = match &self.step {
+            Step::Filling {
+                template,
+                prompts,
+                values,
+            } => (
+                prompts
+                    .get(values_entered(prompts, values))
+                    .map(|placeholder| placeholder.name.clone()),
+                template.fill(values),
+            ),
+            Step::Counting { item } => (Some("Items to create".to_string()), item.clone()),
+            _ => return,
+        };
+
+        let [prompt_area, _, preview_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .areas(area);
+
+        if let Some(prompt) = prompt {
+            let label = format!("{prompt}: ");
+            let [label_area, input_area] =
+                Layout::horizontal([Constraint::Length(label.len() as u16), Constraint::Min(0)])
+                    .areas(prompt_area);
+            frame.render_widget(
+                Paragraph::new(Span::styled(
+                    label,
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                label_area,
+            );
+            self.input
+                .render(frame, input_area, Style::default().fg(theme.text), true);
+        }
+
+        let preview = serde_json::to_string_pretty(&preview).unwrap_or_default();
+        frame.render_widget(
+            Paragraph::new(preview)
+                .style(Style::new().fg(theme.muted))
+                .wrap(Wrap { trim: false }),
+            preview_area,
+        );
+    }
+}
+
+/// How many of `prompts` have a value, which is also the index of the one being prompted for.
+fn values_entered(prompts: &[Placeholder], values: &HashMap<String, String>) -> usize {
+    prompts
+        .iter()
+        .take_while(|placeholder| values.contains_key(&placeholder.name))
+        .count()
+}
+
+impl Component for ItemTemplateBox {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ShowItemTemplates(table) => self.open(table, false),
+            Action::ShowSeedTemplates(table) => self.open(table, true),
+            Action::ItemTemplateNext if matches!(self.step, Step::Choosing) => {
+                self.list_state.select_next()
+            }
+            Action::ItemTemplatePrev if matches!(self.step, Step::Choosing) => {
+                self.list_state.select_previous()
+            }
+            Action::NewItemTemplateCharacter(c)
+                if matches!(self.step, Step::Filling { .. } | Step::Counting { .. }) =>
+            {
+                self.input.enter_char(c)
+            }
+            Action::InsertUuid | Action::InsertUlid | Action::InsertKsuid
+                if matches!(self.step, Step::Filling { .. }) =>
+            {
+                if let Some(id) = id::generate_for(&action) {
+                    self.input.insert_str(&id);
+                }
+            }
+            Action::DeleteItemTemplateCharacter
+                if matches!(self.step, Step::Filling { .. } | Step::Counting { .. }) =>
+            {
+                self.input.delete_char()
+            }
+            Action::SubmitItemTemplate => match self.step {
+                Step::Choosing => self.choose()?,
+                Step::Filling { .. } => self.submit_value()?,
+                Step::Counting { .. } => self.submit_count()?,
+                Step::Saving => {}
+            },
+            Action::TransmitPutItemResult(table, error) if self.table.as_ref() == Some(&table) => {
+                match error {
+                    Some(error) => {
+                        self.error = Some(error);
+                        self.step = Step::Choosing;
+                    }
+                    None => self.close(),
+                }
+            }
+            Action::ExitItemTemplate => self.close(),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let Some(table) = self.table.clone() else {
+            return Ok(());
+        };
+
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Percentage(20),
+            Constraint::Percentage(60),
+            Constraint::Percentage(20),
+        ])
+        .areas(area);
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(25),
+            Constraint::Percentage(50),
+            Constraint::Percentage(25),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let hint = match self.step {
+            Step::Choosing => "<up/down> to choose - <enter> to use - <esc> to cancel",
+            Step::Filling { .. } | Step::Counting { .. } => "<enter> to continue - <esc> to cancel",
+            Step::Saving => "Saving...",
+        };
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .padding(Padding::uniform(1))
+            .title(if self.seeding {
+                format!("Seed Items - {table}")
+            } else {
+                format!("New Item - {table}")
+            })
+            .title_bottom(Line::from(hint).centered());
+
+        let inner = block.inner(middle);
+        frame.render_widget(Clear, middle);
+        frame.render_widget(block, middle);
+
+        let [error_area, body] = Layout::vertical([
+            Constraint::Length(if self.error.is_some() { 2 } else { 0 }),
+            Constraint::Min(0),
+        ])
+        .areas(inner);
+
+        if let Some(error) = &self.error {
+            frame.render_widget(
+                Paragraph::new(error.clone())
+                    .style(Style::new().fg(theme.error))
+                    .wrap(Wrap { trim: true }),
+                error_area,
+            );
+        }
+
+        match self.step {
+            Step::Choosing => self.render_templates(frame, body),
+            Step::Filling { .. } | Step::Counting { .. } | Step::Saving => {
+                self.render_filling(frame, body)
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    action::Action,
+    config::Config,
+    job::{Job, JobState},
+};
+
+use super::Component;
+
+/// Popup listing the jobs started during the session, newest first, from which a running job can
+/// be cancelled.
+#[derive(Default)]
+pub struct JobsBox {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    active: bool,
+    jobs: Vec<Job>,
+    list_state: ListState,
+}
+
+impl JobsBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn selected_job(&self) -> Option<&Job> {
+        self.list_state
+            .selected()
+            .and_then(|selected| self.jobs.iter().rev().nth(selected))
+    }
+
+    fn render_job(&self, job: &Job) -> Line<'static> {
+        let theme = self.config.config.theme;
+
+        // Each state has its own symbol, so it reads without telling the colors apart.
+        let (state, color) = match &job.state {
+            JobState::Running => ("… running", theme.loading),
+            JobState::Finished => ("✓ done", theme.active_border),
+            JobState::Failed(_) => ("✗ failed", theme.error),
+            JobState::Cancelled => ("- cancelled", theme.muted),
+        };
+        let progress = job.progress_label();
+
+        let mut spans = vec![
+            Span::styled(format!("{state:<12}"), Style::new().fg(color)),
+            Span::raw(format!(
+                "{:>6}s  {:<48} {progress}",
+                job.elapsed().as_secs(),
+                job.label
+            )),
+        ];
+        if let JobState::Failed(reason) = &job.state {
+            spans.push(Span::styled(
+                format!("  {reason}"),
+                Style::new().fg(theme.error),
+            ));
+        }
+
+        Line::from(spans)
+    }
+}
+
+impl Component for JobsBox {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::TransmitJobs(jobs) => {
+                self.jobs = jobs;
+                if self.list_state.selected().is_none() {
+                    self.list_state.select_first();
+                }
+            }
+            Action::ViewJobs => {
+                self.active = true;
+                self.list_state.select_first();
+            }
+            Action::ExitJobs => self.active = false,
+            Action::JobsScrollDown => self.list_state.select_next(),
+            Action::JobsScrollUp => self.list_state.select_previous(),
+            Action::CancelSelectedJob => {
+                if let Some(job) = self.selected_job().filter(|job| job.is_running()) {
+                    self.command_tx
+                        .as_ref()
+                        .unwrap()
+                        .send(Action::CancelJob(job.id))?;
+                }
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Percentage(20),
+            Constraint::Percentage(60),
+            Constraint::Percentage(20),
+        ])
+        .areas(area);
+
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(15),
+            Constraint::Percentage(70),
+            Constraint::Percentage(15),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let running = self.jobs.iter().filter(|job| job.is_running()).count();
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .title(format!("Jobs ({running} running)"))
+            .title_bottom(
+                Line::from("<j/k> to scroll - <x> to cancel - <esc> to close").centered(),
+            );
+
+        frame.render_widget(Clear, middle);
+
+        if self.jobs.is_empty() {
+            frame.render_widget(
+                Paragraph::new("No jobs have been started yet.")
+                    .style(Style::new().fg(theme.muted))
+                    .block(block),
+                middle,
+            );
+            return Ok(());
+        }
+
+        let items: Vec<ListItem> = self
+            .jobs
+            .iter()
+            .rev()
+            .map(|job| ListItem::new(self.render_job(job)))
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(theme.selected().remove_modifier(Modifier::BOLD));
+
+        frame.render_stateful_widget(list, middle, &mut self.list_state);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use clipboard::{ClipboardContext, ClipboardProvider};
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph},
+    Frame,
+};
+use serde_json::Value;
+
+use crate::{action::Action, config::Config, data::KeyAttribute, key_format::KeyFormat};
+
+use super::Component;
+
+/// Popup offering the key of the selected item in the formats other tools take it in, each
+/// copied with its own key.
+#[derive(Default)]
+pub struct KeyCopyBox {
+    config: Config,
+    /// The key attributes of the item with their values, while the popup is open.
+    key: Option<Vec<(KeyAttribute, Value)>>,
+}
+
+impl KeyCopyBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn copy(&mut self, format: KeyFormat) -> Option<Action> {
+        let key = self.key.take()?;
+        let mut ctx: ClipboardContext =
+            ClipboardProvider::new().expect("Failed to access clipboard");
+        ctx.set_contents(format.format(&key))
+            .expect("Failed to copy to clipboard");
+        Some(Action::ExitKeyCopy)
+    }
+}
+
+/// The key pressed to copy the key in each format, as bound in the default config.
+fn shortcut(format: KeyFormat) -> &'static str {
+    match format {
+        KeyFormat::Json => "<j>",
+        KeyFormat::CliArgument => "<c>",
+        KeyFormat::PartiqlWhere => "<p>",
+    }
+}
+
+impl Component for KeyCopyBox {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ShowKeyCopy(key) => self.key = Some(key),
+            Action::CopyKeyAsJson => return Ok(self.copy(KeyFormat::Json)),
+            Action::CopyKeyAsCliArgument => return Ok(self.copy(KeyFormat::CliArgument)),
+            Action::CopyKeyAsPartiql => return Ok(self.copy(KeyFormat::PartiqlWhere)),
+            Action::ExitKeyCopy => self.key = None,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let Some(key) = &self.key else {
+            return Ok(());
+        };
+
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(KeyFormat::ALL.len() as u16 * 2 + 3),
+            Constraint::Min(0),
+        ])
+        .areas(area);
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(15),
+            Constraint::Percentage(70),
+            Constraint::Percentage(15),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .padding(Padding::horizontal(1))
+            .title("Copy Key")
+            .title_bottom(Line::from("<esc> to close").centered());
+
+        let lines: Vec<Line> = KeyFormat::ALL
+            .into_iter()
+            .flat_map(|format| {
+                [
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{} ", shortcut(format)),
+                            Style::new().add_modifier(Modifier::BOLD),
+                        ),
+                        Span::raw(format.label()),
+                    ]),
+                    Line::styled(
+                        format!("    {}", format.format(key)),
+                        Style::new().fg(theme.muted),
+                    ),
+                ]
+            })
+            .collect();
+
+        frame.render_widget(Clear, middle);
+        frame.render_widget(Paragraph::new(lines).block(block), middle);
+
+        Ok(())
+    }
+}
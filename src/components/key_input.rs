@@ -0,0 +1,171 @@
+use std::collections::VecDeque;
+
+use base64::{prelude::BASE64_STANDARD, Engine};
+
+use crate::data::KeyAttributeType;
+
+use super::text_input::TextInput;
+
+/// Values remembered per key attribute for suggestions.
+const MAX_RECENT_VALUES: usize = 20;
+
+/// Characters of a number in any notation DynamoDB accepts, and of a `a..b` range of them.
+fn is_number_char(c: char) -> bool {
+    c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')
+}
+
+/// Also the units of relative ranges such as `-15m`.
+fn is_number_or_relative_range_char(c: char) -> bool {
+    is_number_char(c) || matches!(c, 'm' | 'h' | 'd')
+}
+
+/// Restricts what can be typed for a key of `attribute_type`. Numeric sort keys that hold
+/// timestamps also take relative ranges.
+pub fn apply_key_type(
+    input: &mut TextInput,
+    attribute_type: KeyAttributeType,
+    relative_ranges: bool,
+) {
+    input.set_char_filter(match attribute_type {
+        KeyAttributeType::N if relative_ranges => Some(is_number_or_relative_range_char),
+        KeyAttributeType::N => Some(is_number_char),
+        KeyAttributeType::S | KeyAttributeType::B => None,
+    });
+}
+
+/// Replaces the text typed for a B key with its base64 encoding, or valid base64 with the text
+/// it decodes to, so binary keys can be typed as the text they hold.
+pub fn toggle_base64(input: &mut TextInput) {
+    let value = match decoded_base64(input.value()) {
+        Some(decoded) => decoded,
+        None => BASE64_STANDARD.encode(input.value()),
+    };
+    input.clear();
+    input.insert_str(&value);
+}
+
+/// The text a base64 value decodes to, when it is printable.
+pub fn decoded_base64(value: &str) -> Option<String> {
+    let bytes = BASE64_STANDARD.decode(value.trim()).ok()?;
+    String::from_utf8(bytes)
+        .ok()
+        .filter(|text| !text.is_empty() && !text.chars().any(char::is_control))
+}
+
+/// The values of a key attribute seen in results and earlier queries, offered while typing it.
+#[derive(Default)]
+pub struct KeySuggestions {
+    /// Most recent first.
+    recent: VecDeque<String>,
+    /// The text typed before cycling through the suggestions, and the one shown.
+    cycling: Option<(String, usize)>,
+}
+
+impl KeySuggestions {
+    pub fn remember(&mut self, value: String) {
+        if value.is_empty() {
+            return;
+        }
+        self.recent.retain(|known| *known != value);
+        self.recent.push_front(value);
+        self.recent.truncate(MAX_RECENT_VALUES);
+    }
+
+    /// The remembered values that start with `prefix`, other than `prefix` itself.
+    pub fn matching(&self, prefix: &str) -> Vec<&str> {
+        self.recent
+            .iter()
+            .filter(|value| value.starts_with(prefix) && *value != prefix)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// The suggestions for what was typed, counting those passed while cycling.
+    pub fn count(&self, input: &TextInput) -> usize {
+        match &self.cycling {
+            Some((typed, _)) => self.matching(typed).len(),
+            None => self.matching(input.value()).len(),
+        }
+    }
+
+    /// Fills `input` with the next suggestion for what was typed before cycling began, or the
+    /// previous one.
+    pub fn cycle(&mut self, input: &mut TextInput, forward: bool) {
+        let (typed, position) = match self.cycling.take() {
+            Some((typed, position)) => (typed, Some(position)),
+            None => (input.value().to_string(), None),
+        };
+        let suggestions = self.matching(&typed);
+        if suggestions.is_empty() {
+            return;
+        }
+
+        let len = suggestions.len();
+        let next = match (position, forward) {
+            (None, true) => 0,
+            (None, false) => len - 1,
+            (Some(position), true) => (position + 1) % len,
+            (Some(position), false) => (position + len - 1) % len,
+        };
+        let suggestion = suggestions[next].to_string();
+        input.clear();
+        input.insert_str(&suggestion);
+        self.cycling = Some((typed, next));
+    }
+
+    /// Called whenever the input is edited, so the next cycle starts from what was typed.
+    pub fn stop_cycling(&mut self) {
+        self.cycling = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_number_key_filter() {
+        let mut input = TextInput::new();
+        apply_key_type(&mut input, KeyAttributeType::N, false);
+        input.insert_str("-1.5e3m");
+        assert_eq!(input.value(), "-1.5e3");
+
+        apply_key_type(&mut input, KeyAttributeType::N, true);
+        input.clear();
+        input.insert_str("-15m");
+        assert_eq!(input.value(), "-15m");
+    }
+
+    #[test]
+    fn test_toggle_base64() {
+        let mut input = TextInput::new();
+        input.insert_str("user#1");
+        toggle_base64(&mut input);
+        assert_eq!(input.value(), "dXNlciMx");
+        toggle_base64(&mut input);
+        assert_eq!(input.value(), "user#1");
+    }
+
+    #[test]
+    fn test_cycle_suggestions() {
+        let mut suggestions = KeySuggestions::default();
+        for value in ["order#1", "user#1", "order#2", "order#1"] {
+            suggestions.remember(value.to_string());
+        }
+
+        let mut input = TextInput::new();
+        input.insert_str("order");
+        assert_eq!(suggestions.count(&input), 2);
+
+        suggestions.cycle(&mut input, true);
+        assert_eq!(input.value(), "order#1");
+        suggestions.cycle(&mut input, true);
+        assert_eq!(input.value(), "order#2");
+        suggestions.cycle(&mut input, true);
+        assert_eq!(input.value(), "order#1");
+        suggestions.cycle(&mut input, false);
+        assert_eq!(input.value(), "order#2");
+    }
+}
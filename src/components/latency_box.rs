@@ -0,0 +1,187 @@
+use std::time::Duration;
+
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    action::Action,
+    config::Config,
+    latency::{fastest_region, RegionLatency, LATENCY_SAMPLES},
+};
+
+use super::Component;
+
+/// Popup timing round trips to a table from the current region, and from its replicas on
+/// request, to pick the region to work from.
+#[derive(Default)]
+pub struct LatencyBox {
+    config: Config,
+    /// The table probed, while the popup is open.
+    table: Option<String>,
+    /// Whether the last probe took in the replica regions.
+    replicas: bool,
+    /// `None` while a probe is in flight.
+    latencies: Option<Result<Vec<RegionLatency>, String>>,
+}
+
+impl LatencyBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn probe(&mut self, replicas: bool) -> Option<Action> {
+        let table = self.table.clone()?;
+        self.replicas = replicas;
+        self.latencies = None;
+        Some(Action::StartLatencyProbe(table, replicas))
+    }
+
+    fn latency_lines(&self, latencies: &[RegionLatency]) -> Vec<Line<'static>> {
+        let theme = self.config.config.theme;
+        let fastest = fastest_region(latencies).filter(|_| latencies.len() > 1);
+        latencies
+            .iter()
+            .enumerate()
+            .map(|(index, latency)| {
+                let mut spans = vec![Span::styled(
+                    format!("{:<16}", latency.region),
+                    Style::new().add_modifier(Modifier::BOLD),
+                )];
+                match (&latency.error, latency.median()) {
+                    (Some(error), _) => {
+                        spans.push(Span::styled(error.clone(), Style::new().fg(theme.error)))
+                    }
+                    (None, Some(median)) => {
+                        spans.push(Span::raw(format!("median {:>6}", millis(median))));
+                        spans.push(Span::styled(
+                            format!(
+                                "  min {} / max {}",
+                                millis(latency.fastest().unwrap_or(median)),
+                                millis(latency.slowest().unwrap_or(median))
+                            ),
+                            Style::new().fg(theme.muted),
+                        ));
+                    }
+                    (None, None) => spans.push(Span::raw("no answer")),
+                }
+                if index == 0 {
+                    spans.push(Span::styled("  current", Style::new().fg(theme.muted)));
+                }
+                if fastest == Some(latency.region.as_str()) {
+                    spans.push(Span::styled("  fastest", Style::new().fg(theme.loading)));
+                }
+                Line::from(spans)
+            })
+            .collect()
+    }
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{} ms", duration.as_millis())
+}
+
+impl Component for LatencyBox {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ShowLatencyProbe(table) => {
+                self.table = Some(table);
+                return Ok(self.probe(false));
+            }
+            Action::ProbeReplicaLatency => return Ok(self.probe(true)),
+            Action::RepeatLatencyProbe => return Ok(self.probe(self.replicas)),
+            // The answer to a probe of a popup that was closed can still be in flight.
+            Action::TransmitLatencies(table, latencies) if self.table.as_ref() == Some(&table) => {
+                self.latencies = Some(latencies);
+            }
+            Action::ExitLatencyProbe => self.table = None,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let Some(table) = &self.table else {
+            return Ok(());
+        };
+
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Percentage(25),
+            Constraint::Percentage(50),
+            Constraint::Percentage(25),
+        ])
+        .areas(area);
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(15),
+            Constraint::Percentage(70),
+            Constraint::Percentage(15),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .padding(Padding::horizontal(1))
+            .title(format!("Latency - {table}"))
+            .title_bottom(
+                Line::from("<a> to probe the replicas - <r> to probe again - <esc> to close")
+                    .centered(),
+            );
+
+        let inner = block.inner(middle);
+        frame.render_widget(Clear, middle);
+        frame.render_widget(block, middle);
+
+        let [latencies_area, note_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(2)]).areas(inner);
+
+        match &self.latencies {
+            None => frame.render_widget(
+                Paragraph::new("Probing...").style(Style::new().fg(theme.loading)),
+                latencies_area,
+            ),
+            Some(Err(error)) => frame.render_widget(
+                Paragraph::new(error.clone())
+                    .style(Style::new().fg(theme.error))
+                    .wrap(Wrap { trim: true }),
+                latencies_area,
+            ),
+            Some(Ok(latencies)) => {
+                let mut lines = self.latency_lines(latencies);
+                if self.replicas && latencies.len() == 1 {
+                    lines.push(Line::default());
+                    lines.push(Line::styled(
+                        "The table has no replicas in other regions.",
+                        Style::new().fg(theme.muted),
+                    ));
+                }
+                frame.render_widget(Paragraph::new(lines), latencies_area);
+            }
+        }
+
+        frame.render_widget(
+            Paragraph::new(format!(
+                "Each region answers {LATENCY_SAMPLES} DescribeTable calls after one that sets up \
+                 the connection."
+            ))
+            .style(Style::new().fg(theme.muted))
+            .wrap(Wrap { trim: true }),
+            note_area,
+        );
+
+        Ok(())
+    }
+}
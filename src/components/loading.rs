@@ -1,13 +1,16 @@
 use color_eyre::Result;
 
 use ratatui::layout::{Constraint, Layout};
-use ratatui::style::Color;
+use ratatui::text::Span;
 use ratatui::widgets::{Paragraph, StatefulWidget, Widget};
 use ratatui::Frame;
 use ratatui::{layout::Rect, style::Style};
-use throbber_widgets_tui::ThrobberState;
+use throbber_widgets_tui::{Throbber, ThrobberState};
 
 use crate::action::Action;
+use crate::app::Pane;
+use crate::config::{Config, LayoutConfig};
+use crate::theme::Theme;
 
 use super::Component;
 
@@ -15,6 +18,8 @@ pub struct LoadingBox {
     pub active: bool,
     pub loading_state: ThrobberState,
     pub message: String,
+    layout: LayoutConfig,
+    theme: Theme,
 }
 
 impl LoadingBox {
@@ -23,6 +28,8 @@ impl LoadingBox {
             active: false,
             loading_state: ThrobberState::default(),
             message: "Loading...".to_string(),
+            layout: LayoutConfig::default(),
+            theme: Theme::default(),
         }
     }
 
@@ -33,17 +40,38 @@ impl LoadingBox {
     pub fn set_message(&mut self, message: &str) {
         self.message = message.to_string();
     }
+
+    fn set_pending(&mut self, pending: &[(Pane, String)]) {
+        self.active = !pending.is_empty();
+        if let Some((_, label)) = pending.first() {
+            match pending.len() {
+                1 => self.set_message(label),
+                count => self.set_message(&format!("{label} (+{} more)", count - 1)),
+            }
+        }
+    }
+}
+
+/// The spinner a pane appends to its title while one of its requests is in flight.
+pub fn pane_spinner(state: &ThrobberState, theme: &Theme) -> Span<'static> {
+    Throbber::default()
+        .throbber_style(Style::default().fg(theme.loading))
+        .throbber_set(throbber_widgets_tui::ASCII)
+        .to_symbol_span(state)
 }
 
 impl Component for LoadingBox {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.layout = config.config.layout;
+        self.theme = config.config.theme;
+        Ok(())
+    }
+
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
             Action::Tick => self.on_tick(),
-            Action::StartLoading(message) => {
-                self.active = true;
-                self.set_message(&message);
-            }
-            Action::StopLoading => self.active = false,
+            Action::TransmitPendingRequests(pending) => self.set_pending(&pending),
+            Action::TransmitLayout(layout) => self.layout = layout,
             _ => {}
         };
 
@@ -51,17 +79,16 @@ impl Component for LoadingBox {
     }
 
     fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
-        let [left, _] =
-            Layout::horizontal([Constraint::Percentage(30), Constraint::Min(0)]).areas(area);
+        let [left, _] = Layout::horizontal([self.layout.status(), Constraint::Min(0)]).areas(area);
         let [_, bottom] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(left);
 
         if self.active {
-            let full = throbber_widgets_tui::Throbber::default()
+            let full = Throbber::default()
                 .label(self.message.clone())
-                .style(Style::default().fg(ratatui::style::Color::Cyan))
+                .style(Style::default().fg(self.theme.loading))
                 .throbber_style(
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(self.theme.loading)
                         .add_modifier(ratatui::style::Modifier::BOLD),
                 )
                 .throbber_set(throbber_widgets_tui::ASCII)
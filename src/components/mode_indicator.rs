@@ -0,0 +1,73 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use crate::{action::Action, config::Config};
+
+use super::Component;
+
+/// `-- INSERT --` style label in the bottom right corner, shown while keys type text rather
+/// than trigger actions. The keys typed so far of a multi-key binding are shown next to it.
+#[derive(Default)]
+pub struct ModeIndicator {
+    config: Config,
+    label: Option<String>,
+    pending_keys: String,
+}
+
+impl ModeIndicator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Component for ModeIndicator {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::TransmitModeIndicator(label) => self.label = label,
+            Action::TransmitPendingKeys(keys) => self.pending_keys = keys,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let theme = self.config.config.theme;
+        let mut spans = Vec::new();
+        if !self.pending_keys.is_empty() {
+            spans.push(Span::styled(
+                format!(" {} ", self.pending_keys),
+                Style::new().fg(theme.text).bg(theme.popup_bg),
+            ));
+        }
+        if let Some(label) = &self.label {
+            spans.push(Span::styled(format!(" -- {label} -- "), theme.selected()));
+        }
+        if spans.is_empty() {
+            return Ok(());
+        }
+
+        let line = Line::from(spans);
+        let [_, bottom] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+        // Kept clear of the rounded corner of the pane underneath.
+        let [_, right, _] = Layout::horizontal([
+            Constraint::Min(0),
+            Constraint::Length(line.width() as u16),
+            Constraint::Length(2),
+        ])
+        .areas(bottom);
+
+        frame.render_widget(Paragraph::new(line), right);
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::MouseEvent;
+use ratatui::layout::{Margin, Position, Rect};
+
+/// Two clicks on the same cell within this interval count as a double-click.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Remembers the previous click so components can tell single clicks from double-clicks,
+/// which the terminal does not report on its own.
+#[derive(Debug, Default)]
+pub struct ClickTracker {
+    last_click: Option<(Instant, Position)>,
+}
+
+impl ClickTracker {
+    /// Records a click and returns whether it completes a double-click.
+    pub fn click(&mut self, mouse: &MouseEvent) -> bool {
+        let now = Instant::now();
+        let position = Position::new(mouse.column, mouse.row);
+
+        let double_click = self.last_click.is_some_and(|(at, last_position)| {
+            last_position == position && now.duration_since(at) <= DOUBLE_CLICK_INTERVAL
+        });
+
+        // A third click starts a new sequence instead of counting as another double-click.
+        self.last_click = if double_click {
+            None
+        } else {
+            Some((now, position))
+        };
+
+        double_click
+    }
+}
+
+/// Returns the index of the item under the mouse in a bordered list drawn in `area`, given the
+/// index of the first visible item.
+pub fn list_index_at(area: Rect, offset: usize, mouse: &MouseEvent) -> Option<usize> {
+    let inner = area.inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+
+    inner
+        .contains(Position::new(mouse.column, mouse.row))
+        .then(|| offset + (mouse.row - inner.y) as usize)
+}
+
+/// Returns the index of the item under the mouse in a bordered list drawn in `area` as columns
+/// of `rows` items, each `column_width` wide, given the index of the first visible column.
+pub fn grid_index_at(
+    area: Rect,
+    rows: usize,
+    column_width: u16,
+    first_column: usize,
+    mouse: &MouseEvent,
+) -> Option<usize> {
+    let inner = area.inner(Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+    if !inner.contains(Position::new(mouse.column, mouse.row)) || column_width == 0 {
+        return None;
+    }
+
+    let column = first_column + ((mouse.column - inner.x) / column_width) as usize;
+    Some(column * rows + (mouse.row - inner.y) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyModifiers, MouseButton, MouseEventKind};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn click_at(column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_double_click() {
+        let mut tracker = ClickTracker::default();
+        assert!(!tracker.click(&click_at(3, 4)));
+        assert!(tracker.click(&click_at(3, 4)));
+        assert!(!tracker.click(&click_at(3, 4)));
+        assert!(!tracker.click(&click_at(5, 4)));
+    }
+
+    #[test]
+    fn test_list_index_at() {
+        let area = Rect::new(0, 3, 20, 10);
+        assert_eq!(list_index_at(area, 0, &click_at(2, 3)), None);
+        assert_eq!(list_index_at(area, 0, &click_at(2, 4)), Some(0));
+        assert_eq!(list_index_at(area, 7, &click_at(2, 6)), Some(9));
+        assert_eq!(list_index_at(area, 0, &click_at(2, 12)), None);
+    }
+
+    #[test]
+    fn test_grid_index_at() {
+        let area = Rect::new(0, 3, 32, 10);
+        assert_eq!(grid_index_at(area, 8, 10, 0, &click_at(2, 4)), Some(0));
+        assert_eq!(grid_index_at(area, 8, 10, 0, &click_at(12, 5)), Some(9));
+        assert_eq!(grid_index_at(area, 8, 10, 2, &click_at(21, 4)), Some(32));
+        assert_eq!(grid_index_at(area, 8, 10, 0, &click_at(2, 3)), None);
+    }
+}
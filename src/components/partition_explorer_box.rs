@@ -0,0 +1,176 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Padding, Paragraph},
+    Frame,
+};
+
+use crate::{action::Action, config::Config, data::PartitionStats};
+
+use super::Component;
+
+/// Width of the bar drawn next to the largest sort key prefix.
+const MAX_BAR_WIDTH: usize = 30;
+
+/// Popup showing how the items of one partition are spread over their sort keys.
+#[derive(Default)]
+pub struct PartitionExplorerBox {
+    config: Config,
+    active: bool,
+    stats: PartitionStats,
+    list_state: ListState,
+}
+
+impl PartitionExplorerBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn summary_lines(&self) -> Vec<Line<'static>> {
+        let theme = self.config.config.theme;
+        let label = Style::default().add_modifier(Modifier::BOLD);
+        let stats = &self.stats;
+
+        let status = match (&stats.error, stats.complete) {
+            (Some(error), _) => Span::styled(error.clone(), Style::new().fg(theme.error)),
+            (None, true) => Span::raw("complete"),
+            (None, false) => Span::styled("reading...", Style::new().fg(theme.loading)),
+        };
+
+        vec![
+            Line::from(vec![
+                Span::styled("Partition: ", label),
+                Span::raw(stats.partition_key_value.clone()),
+            ]),
+            Line::from(vec![
+                Span::styled("Items: ", label),
+                Span::raw(format!("{} in {} pages - ", stats.items, stats.pages)),
+                status,
+            ]),
+            Line::from(vec![
+                Span::styled("Sort keys: ", label),
+                Span::raw(format!(
+                    "{} .. {}",
+                    stats.min_sort_key.as_deref().unwrap_or("-"),
+                    stats.max_sort_key.as_deref().unwrap_or("-")
+                )),
+            ]),
+        ]
+    }
+
+    /// Prefixes with the most items first.
+    fn prefix_items(&self) -> Vec<ListItem<'static>> {
+        let mut prefixes: Vec<(&String, &usize)> = self.stats.prefixes.iter().collect();
+        prefixes.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+
+        let largest = prefixes.first().map(|(_, count)| **count).unwrap_or(1);
+        let theme = self.config.config.theme;
+
+        prefixes
+            .into_iter()
+            .map(|(prefix, count)| {
+                let prefix = if prefix.is_empty() {
+                    "(no prefix)"
+                } else {
+                    prefix.as_str()
+                };
+                let bar = "#".repeat((count * MAX_BAR_WIDTH).div_ceil(largest));
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("{prefix:<24} {count:>8}  ")),
+                    Span::styled(bar, Style::new().fg(theme.active_border)),
+                ]))
+            })
+            .collect()
+    }
+}
+
+impl Component for PartitionExplorerBox {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::GetPartitionStats(table, _, partition_key_value, _) => {
+                self.active = true;
+                self.stats = PartitionStats {
+                    table,
+                    partition_key_value,
+                    ..PartitionStats::default()
+                };
+                self.list_state.select_first();
+            }
+            // Stats of an explorer that was closed can still be in flight.
+            Action::TransmitPartitionStats(stats)
+                if self.active
+                    && stats.table == self.stats.table
+                    && stats.partition_key_value == self.stats.partition_key_value =>
+            {
+                self.stats = stats;
+            }
+            Action::ExitPartitionExplorer => self.active = false,
+            Action::PartitionExplorerScrollDown => self.list_state.select_next(),
+            Action::PartitionExplorerScrollUp => self.list_state.select_previous(),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Percentage(10),
+            Constraint::Percentage(80),
+            Constraint::Percentage(10),
+        ])
+        .areas(area);
+
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(15),
+            Constraint::Percentage(70),
+            Constraint::Percentage(15),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .padding(Padding::horizontal(1))
+            .title(format!("Partition Explorer - {}", self.stats.table))
+            .title_bottom(Line::from("<j/k> to scroll - <esc> to close").centered());
+
+        let inner = block.inner(middle);
+        frame.render_widget(Clear, middle);
+        frame.render_widget(block, middle);
+
+        let [summary, _, prefixes] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .areas(inner);
+
+        frame.render_widget(Paragraph::new(self.summary_lines()), summary);
+
+        let list = List::new(self.prefix_items())
+            .block(
+                Block::new()
+                    .borders(Borders::TOP)
+                    .border_style(Style::new().fg(theme.muted))
+                    .title("Items per sort key prefix"),
+            )
+            .highlight_style(theme.selected().remove_modifier(Modifier::BOLD));
+        frame.render_stateful_widget(list, prefixes, &mut self.list_state);
+
+        Ok(())
+    }
+}
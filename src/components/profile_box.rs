@@ -0,0 +1,137 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Padding, Paragraph},
+    Frame,
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{action::Action, config::Config, credentials::current_profile, sso::profile_names};
+
+use super::Component;
+
+/// Popup that switches to one of the profiles in the AWS config and credentials files, to
+/// browse the tables of another account without restarting.
+#[derive(Default)]
+pub struct ProfileBox {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    active: bool,
+    /// The profiles listed when the popup was opened.
+    profiles: Vec<String>,
+    current: String,
+    list_state: ListState,
+}
+
+impl ProfileBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Component for ProfileBox {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ViewProfiles => {
+                self.active = true;
+                self.profiles = profile_names();
+                self.current = current_profile();
+                let selected = self
+                    .profiles
+                    .iter()
+                    .position(|profile| *profile == self.current)
+                    .unwrap_or_default();
+                self.list_state.select(Some(selected));
+            }
+            Action::ProfileNext => self.list_state.select_next(),
+            Action::ProfilePrev => self.list_state.select_previous(),
+            Action::SwitchToSelectedProfile => {
+                let profile = self
+                    .list_state
+                    .selected()
+                    .and_then(|selected| self.profiles.get(selected))
+                    .cloned();
+                if let Some(profile) = profile {
+                    self.active = false;
+                    self.command_tx
+                        .as_ref()
+                        .unwrap()
+                        .send(Action::SwitchProfile(profile))?;
+                }
+            }
+            Action::ExitProfiles => self.active = false,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Percentage(25),
+            Constraint::Percentage(50),
+            Constraint::Percentage(25),
+        ])
+        .areas(area);
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(30),
+            Constraint::Percentage(40),
+            Constraint::Percentage(30),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .padding(Padding::horizontal(1))
+            .title("AWS Profiles")
+            .title_bottom(
+                Line::from("<j/k> to choose - <enter> to switch - <esc> to cancel").centered(),
+            );
+
+        frame.render_widget(Clear, middle);
+
+        if self.profiles.is_empty() {
+            let empty = Paragraph::new(Span::styled(
+                "No profiles in ~/.aws/config or ~/.aws/credentials",
+                Style::new().fg(theme.muted),
+            ))
+            .block(block);
+            frame.render_widget(empty, middle);
+            return Ok(());
+        }
+
+        let items: Vec<ListItem> = self
+            .profiles
+            .iter()
+            .map(|profile| {
+                let marker = if *profile == self.current { "* " } else { "  " };
+                ListItem::new(format!("{marker}{profile}"))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(theme.selected());
+        frame.render_stateful_widget(list, middle, &mut self.list_state);
+
+        Ok(())
+    }
+}
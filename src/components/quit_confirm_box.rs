@@ -0,0 +1,120 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{action::Action, config::Config, job::Job};
+
+use super::Component;
+
+/// Popup shown on quit while jobs that write items are running, to choose whether to wait for
+/// them, cancel them, or leave them finishing once the terminal is restored.
+#[derive(Default)]
+pub struct QuitConfirmBox {
+    config: Config,
+    active: bool,
+    /// Set once waiting for the jobs, or for them to be cancelled, before quitting.
+    waiting: bool,
+    jobs: Vec<Job>,
+}
+
+impl QuitConfirmBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn job_lines(&self) -> Vec<Line<'static>> {
+        self.jobs
+            .iter()
+            .filter(|job| job.is_running() && job.writes)
+            .map(|job| Line::from(format!("{}  {}", job.label, job.progress_label())))
+            .collect()
+    }
+}
+
+impl Component for QuitConfirmBox {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ConfirmQuit => {
+                self.active = true;
+                self.waiting = false;
+            }
+            Action::QuitWhenJobsFinish | Action::CancelJobsAndQuit => self.waiting = true,
+            Action::ExitQuitConfirm => self.active = false,
+            Action::TransmitJobs(jobs) => self.jobs = jobs,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+
+        let job_lines = self.job_lines();
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(job_lines.len() as u16 + 8),
+            Constraint::Min(0),
+        ])
+        .areas(area);
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(20),
+            Constraint::Percentage(60),
+            Constraint::Percentage(20),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let hint = if self.waiting {
+            "<esc> to stop waiting - <Ctrl-c> to quit now"
+        } else {
+            "<w> to wait - <c> to cancel them - <d> to detach - <esc> to go back"
+        };
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .padding(Padding::uniform(1))
+            .title("Quit")
+            .title_bottom(Line::from(hint).centered());
+
+        let heading = if self.waiting {
+            format!("Quitting once {} jobs have stopped...", job_lines.len())
+        } else {
+            format!("{} jobs are still writing items:", job_lines.len())
+        };
+        let mut lines = vec![
+            Line::styled(heading, Style::default().add_modifier(Modifier::BOLD)),
+            Line::from(""),
+        ];
+        lines.extend(job_lines);
+        if !self.waiting {
+            lines.push(Line::from(""));
+            lines.push(Line::styled(
+                "Detached jobs keep running after the terminal is restored, and report how they \
+                 ended before the app exits.",
+                Style::new().fg(theme.muted),
+            ));
+        }
+
+        frame.render_widget(Clear, middle);
+        frame.render_widget(
+            Paragraph::new(lines).block(block).wrap(Wrap { trim: true }),
+            middle,
+        );
+
+        Ok(())
+    }
+}
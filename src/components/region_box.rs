@@ -1,3 +1,5 @@
+use std::time::{Duration, SystemTime};
+
 use color_eyre::Result;
 use ratatui::layout::Alignment;
 use ratatui::prelude::Widget;
@@ -5,31 +7,120 @@ use ratatui::prelude::*;
 use ratatui::{
     layout::Rect,
     style::Style,
-    widgets::{Block, BorderType, Borders, Paragraph},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Padding, Paragraph},
 };
-use style::palette::tailwind::EMERALD;
 use tokio::sync::mpsc::UnboundedSender;
 
 use crate::action::Action;
-use crate::config::Config;
+use crate::config::{Config, ZoomedPane};
+use crate::credentials::remaining;
+use crate::table_search::DYNAMODB_REGIONS;
+use crate::workspace::Workspace;
 
 use super::Component;
 
+/// How long before the credentials expire the countdown turns red.
+const EXPIRY_WARNING: Duration = Duration::from_secs(10 * 60);
+
 #[derive(Default)]
 pub struct AWSRegionBox {
     command_tx: Option<UnboundedSender<Action>>,
     config: Config,
     active: bool,
     region: String,
+    /// The region shown when no workspace names one.
+    default_region: String,
+    workspace: Option<Workspace>,
+    /// The profile picked at runtime, until the next workspace switch.
+    profile: Option<String>,
+    account_id: Option<String>,
+    arn: Option<String>,
+    /// When the credentials stop working, if they expire.
+    credentials_expiry: Option<SystemTime>,
+    /// The region picked in the list of regions, while it is open.
+    list_state: ListState,
 }
 
 impl AWSRegionBox {
     pub fn new(region: &str) -> Self {
         Self {
             region: region.to_string(),
+            default_region: region.to_string(),
             ..Default::default()
         }
     }
+
+    fn select_current_region(&mut self) {
+        let selected = DYNAMODB_REGIONS
+            .iter()
+            .position(|region| *region == self.region)
+            .unwrap_or_default();
+        self.list_state.select(Some(selected));
+    }
+
+    fn switch_to_selected_region(&mut self) -> Result<()> {
+        let Some(region) = self
+            .list_state
+            .selected()
+            .and_then(|selected| DYNAMODB_REGIONS.get(selected))
+        else {
+            return Ok(());
+        };
+        self.active = false;
+        self.command_tx
+            .as_ref()
+            .unwrap()
+            .send(Action::SwitchRegion(region.to_string()))?;
+        Ok(())
+    }
+
+    fn draw_regions(&mut self, frame: &mut Frame, area: Rect) {
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Percentage(20),
+            Constraint::Percentage(60),
+            Constraint::Percentage(20),
+        ])
+        .areas(area);
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(35),
+            Constraint::Percentage(30),
+            Constraint::Percentage(35),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .padding(Padding::horizontal(1))
+            .title("Regions")
+            .title_bottom(Line::from("<enter> to switch - <esc> to cancel").centered());
+
+        let items: Vec<ListItem> = DYNAMODB_REGIONS
+            .iter()
+            .map(|region| {
+                let marker = if *region == self.region { "* " } else { "  " };
+                ListItem::new(format!("{marker}{region}"))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(theme.selected());
+
+        frame.render_widget(Clear, middle);
+        frame.render_stateful_widget(list, middle, &mut self.list_state);
+    }
+
+    fn is_production(&self) -> bool {
+        self.account_id.as_ref().is_some_and(|account_id| {
+            self.config
+                .config
+                .production_account_ids
+                .contains(account_id)
+        })
+    }
 }
 
 impl Component for AWSRegionBox {
@@ -51,14 +142,58 @@ impl Component for AWSRegionBox {
             Action::Render => {
                 // add any logic here that should run on every render
             }
+            Action::TransmitCallerIdentity(account_id, arn) => {
+                self.account_id = Some(account_id);
+                self.arn = Some(arn);
+            }
+            Action::TransmitCredentialsExpiry(expiry) => self.credentials_expiry = expiry,
+            Action::TransmitWorkspace(workspace) => {
+                self.region = workspace
+                    .as_ref()
+                    .and_then(|workspace| workspace.region.clone())
+                    .unwrap_or_else(|| self.default_region.clone());
+                self.workspace = workspace;
+                self.profile = None;
+                self.account_id = None;
+                self.arn = None;
+                self.credentials_expiry = None;
+            }
+            Action::TransmitLayout(layout) => self.config.config.layout = layout,
+            Action::SelectingRegion => {
+                self.active = true;
+                self.select_current_region();
+            }
+            Action::RegionNext => self.list_state.select_next(),
+            Action::RegionPrev => self.list_state.select_previous(),
+            Action::SwitchToSelectedRegion => self.switch_to_selected_region()?,
+            Action::ExitRegions => self.active = false,
+            Action::TransmitProfile(profile) => {
+                self.profile = Some(profile);
+                self.account_id = None;
+                self.arn = None;
+                self.credentials_expiry = None;
+            }
+            Action::TransmitRegion(region) => {
+                self.region = region;
+                self.account_id = None;
+                self.arn = None;
+            }
             _ => {}
         }
         Ok(None)
     }
 
     fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if self.config.config.layout.zoomed == Some(ZoomedPane::Tables) {
+            if self.active {
+                self.draw_regions(frame, area);
+            }
+            return Ok(());
+        }
+
         let [left, _] =
-            Layout::horizontal([Constraint::Percentage(30), Constraint::Min(0)]).areas(area);
+            Layout::horizontal([self.config.config.layout.sidebar(), Constraint::Min(0)])
+                .areas(area);
 
         let [top_left, _, _] = Layout::vertical([
             Constraint::Length(3),
@@ -72,15 +207,63 @@ impl Component for AWSRegionBox {
             .border_type(BorderType::Rounded)
             .title("AWS Region");
 
-        if self.active {
-            block = block.border_style(Style::default().fg(EMERALD.c300));
+        if let Some(workspace) = &self.workspace {
+            let mut title = format!(" {} ", workspace.name);
+            if workspace.read_only {
+                title.push_str("(read-only) ");
+            }
+            block = block.title(Line::from(title).right_aligned());
+        }
+        if let Some(profile) = &self.profile {
+            block = block.title(Line::from(format!(" {profile} ")).right_aligned());
+        }
+
+        if let Some(arn) = &self.arn {
+            block = block.title_bottom(Line::from(arn.clone()).right_aligned());
+        }
+
+        let theme = self.config.config.theme;
+        if let Some(expiry) = self.credentials_expiry {
+            let (text, color) = match expiry.duration_since(SystemTime::now()) {
+                Ok(left) if left < EXPIRY_WARNING => {
+                    (format!(" expires in {} ", remaining(left)), theme.error)
+                }
+                Ok(left) => (format!(" expires in {} ", remaining(left)), theme.muted),
+                Err(_) => (" credentials expired ".to_string(), theme.error),
+            };
+            block = block.title_bottom(Line::styled(text, Style::new().fg(color)).left_aligned());
+        }
+
+        let mut text_style = Style::default().fg(theme.text);
+
+        if self.is_production() {
+            block = block
+                .title(Line::from(" PRODUCTION ").right_aligned())
+                .border_style(
+                    Style::default()
+                        .fg(theme.error)
+                        .add_modifier(Modifier::BOLD),
+                );
+            text_style = text_style.fg(theme.error).add_modifier(Modifier::BOLD);
+        } else {
+            block = block.border_style(theme.border(self.active));
         }
 
-        Paragraph::new(self.region.clone())
+        let text = match &self.account_id {
+            Some(account_id) => format!("{} | {}", self.region, account_id),
+            None => self.region.clone(),
+        };
+
+        Paragraph::new(text)
             .alignment(Alignment::Center)
+            .style(text_style)
             .block(block)
             .render(top_left, frame.buffer_mut());
 
+        if self.active {
+            self.draw_regions(frame, area);
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,261 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Padding, Paragraph},
+    Frame,
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    action::Action,
+    config::Config,
+    replica::{diverging_attributes, RegionItem},
+};
+
+use super::Component;
+
+/// Popup that reads one item from two regions of a global table and shows the copies side by
+/// side, with the attributes that differ highlighted.
+#[derive(Default)]
+pub struct RegionCompareBox {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    /// The table and the key of the item, while the popup is open.
+    target: Option<(String, String)>,
+    /// `None` until the regions of the table are known.
+    regions: Option<Vec<String>>,
+    /// Indices into `regions` of the two regions to compare, in the order they were picked.
+    chosen: Vec<usize>,
+    list_state: ListState,
+    /// The item as read in each chosen region, once they have been read.
+    items: Option<Vec<RegionItem>>,
+}
+
+impl RegionCompareBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn toggle_selected(&mut self) {
+        let Some(selected) = self.list_state.selected() else {
+            return;
+        };
+        if let Some(position) = self.chosen.iter().position(|index| *index == selected) {
+            self.chosen.remove(position);
+        } else {
+            if self.chosen.len() == 2 {
+                self.chosen.remove(0);
+            }
+            self.chosen.push(selected);
+        }
+    }
+
+    fn submit(&mut self) -> Result<()> {
+        let (Some((table, key)), Some(regions)) = (&self.target, &self.regions) else {
+            return Ok(());
+        };
+        if self.items.is_some() || self.chosen.len() != 2 {
+            return Ok(());
+        }
+
+        let chosen = self
+            .chosen
+            .iter()
+            .map(|index| regions[*index].clone())
+            .collect();
+        self.command_tx
+            .as_ref()
+            .unwrap()
+            .send(Action::GetRegionItems(table.clone(), key.clone(), chosen))?;
+        Ok(())
+    }
+
+    fn close(&mut self) {
+        self.target = None;
+        self.regions = None;
+        self.items = None;
+        self.chosen.clear();
+    }
+
+    fn render_regions(&mut self, frame: &mut Frame, area: Rect, regions: &[String]) {
+        let theme = self.config.config.theme;
+        if regions.len() < 2 {
+            frame.render_widget(
+                Paragraph::new("This table has no replicas in other regions.")
+                    .style(Style::new().fg(theme.muted)),
+                area,
+            );
+            return;
+        }
+
+        let items: Vec<ListItem> = regions
+            .iter()
+            .enumerate()
+            .map(|(index, region)| {
+                let marker = if self.chosen.contains(&index) {
+                    "[x]"
+                } else {
+                    "[ ]"
+                };
+                ListItem::new(format!("{marker} {region}"))
+            })
+            .collect();
+        let list = List::new(items).highlight_style(theme.selected());
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    fn render_items(&self, frame: &mut Frame, area: Rect, items: &[RegionItem]) {
+        let theme = self.config.config.theme;
+        let [first, second] = [&items[0], &items[1]];
+        let (first_attributes, second_attributes) = (first.attributes(), second.attributes());
+        let diverging = diverging_attributes(&first_attributes, &second_attributes);
+
+        let [summary, _, columns] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .areas(area);
+
+        let summary_line = if first.error.is_some() || second.error.is_some() {
+            Line::styled("✗ The item could not be read in every region", theme.error)
+        } else if diverging.is_empty() {
+            Line::styled("✓ The replicas match", theme.active_border)
+        } else {
+            Line::styled(
+                format!("≠ {} attributes differ", diverging.len()),
+                Style::new().fg(theme.error).add_modifier(Modifier::BOLD),
+            )
+        };
+        frame.render_widget(Paragraph::new(summary_line), summary);
+
+        let halves = Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(columns);
+        for (item, attributes, half) in [
+            (first, &first_attributes, halves[0]),
+            (second, &second_attributes, halves[1]),
+        ] {
+            let mut lines = vec![Line::styled(
+                item.region.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )];
+            match (&item.error, &item.item) {
+                (Some(error), _) => lines.push(Line::styled(error.clone(), theme.error)),
+                (None, None) => lines.push(Line::styled("No item with this key", theme.error)),
+                (None, Some(_)) => lines.extend(attributes.iter().map(|(name, value)| {
+                    let style = if diverging.contains(name) {
+                        Style::new().fg(theme.error)
+                    } else {
+                        Style::new().fg(theme.text)
+                    };
+                    Line::from(vec![
+                        Span::styled(format!("{name}: "), style.add_modifier(Modifier::BOLD)),
+                        Span::styled(value.to_string(), style),
+                    ])
+                })),
+            }
+            frame.render_widget(
+                Paragraph::new(lines).block(Block::new().padding(Padding::right(1))),
+                half,
+            );
+        }
+    }
+}
+
+impl Component for RegionCompareBox {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ShowRegionComparison(table, key) => {
+                self.close();
+                self.target = Some((table, key));
+                self.list_state.select_first();
+            }
+            Action::TransmitReplicaRegions(table, regions)
+                if self
+                    .target
+                    .as_ref()
+                    .is_some_and(|(target, _)| *target == table) =>
+            {
+                // Start from the current region and the first replica.
+                self.chosen = (0..regions.len().min(2)).collect();
+                self.regions = Some(regions);
+            }
+            Action::TransmitRegionItems(table, items)
+                if self
+                    .target
+                    .as_ref()
+                    .is_some_and(|(target, _)| *target == table) =>
+            {
+                self.items = Some(items);
+            }
+            Action::RegionCompareNext if self.items.is_none() => self.list_state.select_next(),
+            Action::RegionComparePrev if self.items.is_none() => self.list_state.select_previous(),
+            Action::ToggleRegionCompareRegion if self.items.is_none() => self.toggle_selected(),
+            Action::SubmitRegionCompare => self.submit()?,
+            Action::ExitRegionCompare => self.close(),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let Some((table, key)) = self.target.clone() else {
+            return Ok(());
+        };
+
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Percentage(10),
+            Constraint::Percentage(80),
+            Constraint::Percentage(10),
+        ])
+        .areas(area);
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(10),
+            Constraint::Percentage(80),
+            Constraint::Percentage(10),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let hint = if self.items.is_some() {
+            "<esc> to close"
+        } else {
+            "<j/k> to move - <space> to pick two regions - <enter> to compare - <esc> to close"
+        };
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .padding(Padding::uniform(1))
+            .title(format!("Compare Regions - {table} {key}"))
+            .title_bottom(Line::from(hint).centered());
+
+        let inner = block.inner(middle);
+        frame.render_widget(Clear, middle);
+        frame.render_widget(block, middle);
+
+        match (self.regions.clone(), self.items.clone()) {
+            (_, Some(items)) if items.len() == 2 => self.render_items(frame, inner, &items),
+            (Some(regions), _) => self.render_regions(frame, inner, &regions),
+            _ => frame.render_widget(
+                Paragraph::new("Loading...").style(Style::new().fg(theme.loading)),
+                inner,
+            ),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,206 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Position, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph},
+    Frame,
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    action::Action,
+    config::Config,
+    scratchpad::{self, Notes},
+};
+
+use super::Component;
+
+/// Popup holding the notes of a table: keys pasted in, findings, and snippets stashed from items.
+/// The notes are written to the data directory while they change, so they outlive the session.
+#[derive(Default)]
+pub struct ScratchpadBox {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    /// The table whose notes are open, while the popup is open.
+    table: Option<String>,
+    notes: Notes,
+    /// Set when the notes changed since they were last written.
+    dirty: bool,
+    /// The first line and column shown, moved along to keep the cursor in view.
+    scroll: (usize, usize),
+    error: Option<String>,
+}
+
+impl ScratchpadBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn open(&mut self, table: String) {
+        match scratchpad::load(&table) {
+            Ok(text) => {
+                self.notes = Notes::new(&text);
+                self.error = None;
+            }
+            Err(err) => {
+                self.notes = Notes::default();
+                self.error = Some(format!("Could not read the notes: {err}"));
+            }
+        }
+        self.table = Some(table);
+        self.dirty = false;
+        self.scroll = (0, 0);
+    }
+
+    fn edit(&mut self, edit: impl FnOnce(&mut Notes)) {
+        if self.table.is_some() {
+            edit(&mut self.notes);
+            self.dirty = true;
+        }
+    }
+
+    fn save(&mut self) {
+        let Some(table) = &self.table else {
+            return;
+        };
+        if !self.dirty {
+            return;
+        }
+        self.dirty = false;
+        self.error = scratchpad::save(table, &self.notes.text())
+            .err()
+            .map(|err| format!("Could not write the notes: {err}"));
+    }
+
+    /// Moves the scroll offsets just enough for the cursor to fit in an area of `width` by
+    /// `height`.
+    fn scroll_to_cursor(&mut self, width: usize, height: usize) {
+        let (row, column) = self.notes.cursor();
+        let (top, left) = &mut self.scroll;
+        if row < *top {
+            *top = row;
+        } else if row >= *top + height {
+            *top = row + 1 - height;
+        }
+        if column < *left {
+            *left = column;
+        } else if column >= *left + width {
+            *left = column + 1 - width;
+        }
+    }
+}
+
+impl Component for ScratchpadBox {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ShowScratchpad(table) => self.open(table),
+            Action::NewScratchpadCharacter(c) => self.edit(|notes| notes.insert_char(c)),
+            Action::PasteIntoScratchpad(text) => self.edit(|notes| notes.insert_str(&text)),
+            Action::ScratchpadNewline => self.edit(Notes::newline),
+            Action::DeleteScratchpadCharacter => self.edit(Notes::backspace),
+            Action::ScratchpadCursorLeft => self.notes.move_left(),
+            Action::ScratchpadCursorRight => self.notes.move_right(),
+            Action::ScratchpadCursorUp => self.notes.move_up(),
+            Action::ScratchpadCursorDown => self.notes.move_down(),
+            Action::Tick => self.save(),
+            Action::ExitScratchpad => {
+                self.save();
+                self.table = None;
+            }
+            Action::StashInScratchpad(table, snippet) => {
+                if self.table.as_ref() == Some(&table) {
+                    self.edit(|notes| notes.append(&snippet));
+                } else if let Err(err) = scratchpad::stash(&table, &snippet) {
+                    self.command_tx
+                        .as_ref()
+                        .unwrap()
+                        .send(Action::Error(format!(
+                            "Could not stash in the notes: {err}"
+                        )))?;
+                }
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let Some(table) = self.table.clone() else {
+            return Ok(());
+        };
+
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Percentage(15),
+            Constraint::Percentage(70),
+            Constraint::Percentage(15),
+        ])
+        .areas(area);
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(15),
+            Constraint::Percentage(70),
+            Constraint::Percentage(15),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .padding(Padding::horizontal(1))
+            .title(format!("Notes - {table}"))
+            .title_bottom(Line::from("<esc> to close - notes are kept per table").centered());
+
+        let inner = block.inner(middle);
+        frame.render_widget(Clear, middle);
+        frame.render_widget(block, middle);
+
+        let [notes_area, error_area] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(u16::from(self.error.is_some())),
+        ])
+        .areas(inner);
+
+        self.scroll_to_cursor(
+            usize::from(notes_area.width.max(1)),
+            usize::from(notes_area.height.max(1)),
+        );
+        let (top, left) = self.scroll;
+        let lines: Vec<Line> = self
+            .notes
+            .lines()
+            .iter()
+            .skip(top)
+            .take(usize::from(notes_area.height))
+            .map(|line| Line::raw(line.chars().skip(left).collect::<String>()))
+            .collect();
+        frame.render_widget(Paragraph::new(lines), notes_area);
+
+        let (row, column) = self.notes.cursor();
+        frame.set_cursor_position(Position::new(
+            notes_area.x + (column - left) as u16,
+            notes_area.y + (row - top) as u16,
+        ));
+
+        if let Some(error) = &self.error {
+            frame.render_widget(
+                Paragraph::new(error.clone()).style(Style::new().fg(theme.error)),
+                error_area,
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,302 @@
+use std::time::SystemTime;
+
+use aws_sdk_dynamodb::primitives::{DateTime, DateTimeFormat};
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Padding, Paragraph},
+    Frame,
+};
+
+use crate::{
+    action::Action,
+    config::Config,
+    snapshot::{Snapshot, SnapshotDiff},
+};
+
+use super::{text_input::TextInput, Component};
+
+/// Popups to label a snapshot of the data pane and to compare two snapshots of a table.
+#[derive(Default)]
+pub struct SnapshotBox {
+    config: Config,
+    snapshots: Vec<Snapshot>,
+    /// The snapshot waiting for a label.
+    pending: Option<Snapshot>,
+    label_input: TextInput,
+    /// The table whose snapshots are being compared.
+    comparing: Option<String>,
+    /// Index, among the older snapshots of the table, of the one compared with the newest.
+    base: usize,
+    /// `base` compared with the newest snapshot, kept so it is not recomputed on every frame.
+    diff: Option<SnapshotDiff>,
+    list_state: ListState,
+}
+
+impl SnapshotBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn table_snapshots(&self, table: &str) -> Vec<&Snapshot> {
+        self.snapshots
+            .iter()
+            .filter(|snapshot| snapshot.table == table)
+            .collect()
+    }
+
+    /// The snapshot compared against and the newest one, if the table has two.
+    fn compared(&self) -> Option<(&Snapshot, &Snapshot)> {
+        let snapshots = self.table_snapshots(self.comparing.as_deref()?);
+        let (newest, older) = snapshots.split_last()?;
+        Some((older.get(self.base)?, newest))
+    }
+
+    fn select_base(&mut self, base: usize) {
+        self.base = base;
+        self.diff = self.compared().map(|(base, newest)| base.diff(newest));
+        self.list_state.select_first();
+    }
+
+    fn save_pending(&mut self) {
+        if let Some(mut snapshot) = self.pending.take() {
+            snapshot.label = if self.label_input.is_empty() {
+                self.default_label(&snapshot.table)
+            } else {
+                self.label_input.value().to_string()
+            };
+            self.snapshots.push(snapshot);
+        }
+        self.label_input.clear();
+    }
+
+    fn default_label(&self, table: &str) -> String {
+        format!("snapshot {}", self.table_snapshots(table).len() + 1)
+    }
+
+    fn describe(snapshot: &Snapshot) -> String {
+        // Timestamps are RFC 3339, so the time of day starts at the 12th character.
+        let time = snapshot.taken_at.get(11..19).unwrap_or(&snapshot.taken_at);
+        format!(
+            "{} ({time}, {} items)",
+            snapshot.label,
+            snapshot.records.len()
+        )
+    }
+
+    fn diff_lines(base: &Snapshot, newest: &Snapshot, diff: &SnapshotDiff) -> Vec<Line<'static>> {
+        let label = Style::default().add_modifier(Modifier::BOLD);
+
+        vec![
+            Line::from(vec![
+                Span::styled("Before: ", label),
+                Span::raw(Self::describe(base)),
+            ]),
+            Line::from(vec![
+                Span::styled("After:  ", label),
+                Span::raw(Self::describe(newest)),
+            ]),
+            Line::from(format!(
+                "{} added, {} removed, {} changed, {} unchanged",
+                diff.added.len(),
+                diff.removed.len(),
+                diff.changed.len(),
+                diff.unchanged
+            )),
+        ]
+    }
+
+    fn diff_items(&self, diff: &SnapshotDiff) -> Vec<ListItem<'static>> {
+        let theme = self.config.config.theme;
+        let entries = [
+            ("+", theme.active_border, &diff.added),
+            ("-", theme.error, &diff.removed),
+            ("~", theme.loading, &diff.changed),
+        ];
+
+        entries
+            .into_iter()
+            .flat_map(|(marker, color, keys)| {
+                keys.iter().map(move |key| {
+                    ListItem::new(Line::styled(
+                        format!("{marker} {key}"),
+                        Style::new().fg(color),
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    fn render_label_input(&self, frame: &mut Frame, area: Rect, pending: &Snapshot) {
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(5),
+            Constraint::Min(0),
+        ])
+        .areas(area);
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(30),
+            Constraint::Percentage(40),
+            Constraint::Percentage(30),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .padding(Padding::uniform(1))
+            .title(format!(
+                "Snapshot {} ({} items)",
+                pending.table,
+                pending.records.len()
+            ))
+            .title_bottom(Line::from("<enter> to save - <esc> to cancel").centered());
+
+        let inner = block.inner(middle);
+        frame.render_widget(Clear, middle);
+        frame.render_widget(block, middle);
+
+        let [label, input] =
+            Layout::horizontal([Constraint::Length(7), Constraint::Min(0)]).areas(inner);
+        frame.render_widget(
+            Paragraph::new("Label:").style(Style::new().fg(theme.muted)),
+            label,
+        );
+        self.label_input
+            .render(frame, input, Style::default().fg(theme.text), true);
+    }
+
+    fn render_diff(&mut self, frame: &mut Frame, area: Rect, table: &str) {
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Percentage(10),
+            Constraint::Percentage(80),
+            Constraint::Percentage(10),
+        ])
+        .areas(area);
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(15),
+            Constraint::Percentage(70),
+            Constraint::Percentage(15),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .padding(Padding::horizontal(1))
+            .title(format!("Compare Snapshots - {table}"))
+            .title_bottom(
+                Line::from(
+                    "<j/k> to scroll - <h/l> to change the earlier snapshot - <esc> to close",
+                )
+                .centered(),
+            );
+
+        let inner = block.inner(middle);
+        frame.render_widget(Clear, middle);
+        frame.render_widget(block, middle);
+
+        let (Some((base, newest)), Some(diff)) = (self.compared(), &self.diff) else {
+            let taken = self.table_snapshots(table).len();
+            frame.render_widget(
+                Paragraph::new(format!(
+                    "{taken} of 2 snapshots taken. Press <S> in the data pane to take another."
+                ))
+                .style(Style::new().fg(theme.muted)),
+                inner,
+            );
+            return;
+        };
+
+        let [summary, _, keys] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .areas(inner);
+
+        let lines = Self::diff_lines(base, newest, diff);
+        let items = self.diff_items(diff);
+
+        frame.render_widget(Paragraph::new(lines), summary);
+
+        let list = List::new(items)
+            .block(
+                Block::new()
+                    .borders(Borders::TOP)
+                    .border_style(Style::new().fg(theme.muted))
+                    .title("Items by key"),
+            )
+            .highlight_style(theme.selected().remove_modifier(Modifier::BOLD));
+        frame.render_stateful_widget(list, keys, &mut self.list_state);
+    }
+}
+
+impl Component for SnapshotBox {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::TransmitSnapshotRecords(table, records) => {
+                self.label_input = TextInput::new().placeholder(&self.default_label(&table));
+                self.pending = Some(Snapshot {
+                    label: String::new(),
+                    table,
+                    taken_at: DateTime::from(SystemTime::now())
+                        .fmt(DateTimeFormat::DateTime)
+                        .unwrap_or_default(),
+                    records,
+                });
+            }
+            Action::NewSnapshotLabelCharacter(c) => self.label_input.enter_char(c),
+            Action::DeleteSnapshotLabelCharacter => self.label_input.delete_char(),
+            Action::SubmitSnapshotLabel => self.save_pending(),
+            Action::ExitSnapshotLabel => {
+                self.pending = None;
+                self.label_input.clear();
+            }
+            Action::ShowSnapshotDiff(table) => {
+                let older = self.table_snapshots(&table).len().saturating_sub(1);
+                self.comparing = Some(table);
+                self.select_base(older.saturating_sub(1));
+            }
+            Action::SnapshotDiffPrevBase => self.select_base(self.base.saturating_sub(1)),
+            Action::SnapshotDiffNextBase => {
+                let older = self
+                    .comparing
+                    .as_deref()
+                    .map(|table| self.table_snapshots(table).len().saturating_sub(1))
+                    .unwrap_or_default();
+                self.select_base((self.base + 1).min(older.saturating_sub(1)));
+            }
+            Action::SnapshotDiffScrollDown => self.list_state.select_next(),
+            Action::SnapshotDiffScrollUp => self.list_state.select_previous(),
+            Action::ExitSnapshotDiff => {
+                self.comparing = None;
+                self.diff = None;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if let Some(pending) = &self.pending {
+            self.render_label_input(frame, area, pending);
+        } else if let Some(table) = self.comparing.clone() {
+            self.render_diff(frame, area, &table);
+        }
+        Ok(())
+    }
+}
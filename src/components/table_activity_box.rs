@@ -0,0 +1,162 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{
+        Block, BorderType, Borders, Clear, List, ListItem, ListState, Padding, Paragraph, Wrap,
+    },
+    Frame,
+};
+
+use crate::{action::Action, cloudtrail::TableEvent, config::Config};
+
+use super::Component;
+
+/// Popup listing the writes CloudTrail recorded to a table: who made them, when, and what they
+/// were.
+#[derive(Default)]
+pub struct TableActivityBox {
+    config: Config,
+    /// The table whose events are shown, while the popup is open.
+    table: Option<String>,
+    /// `None` until the lookup answers.
+    events: Option<Result<Vec<TableEvent>, String>>,
+    list_state: ListState,
+}
+
+impl TableActivityBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn event_items(&self, events: &[TableEvent]) -> Vec<ListItem<'static>> {
+        let theme = self.config.config.theme;
+        events
+            .iter()
+            .map(|event| {
+                // Times are RFC 3339: the date and the time of day, without the zone.
+                let time = event
+                    .time
+                    .get(..19)
+                    .unwrap_or(&event.time)
+                    .replace('T', " ");
+                let mut spans = vec![
+                    Span::styled(format!("{time}  "), Style::new().fg(theme.muted)),
+                    Span::raw(format!("{:<28} ", event.operation)),
+                    Span::raw(event.identity.clone()),
+                ];
+                if let Some(source_ip) = &event.source_ip {
+                    spans.push(Span::styled(
+                        format!("  from {source_ip}"),
+                        Style::new().fg(theme.muted),
+                    ));
+                }
+                if let Some(error) = &event.error {
+                    spans.push(Span::styled(
+                        format!("  {error}"),
+                        Style::new().fg(theme.error),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect()
+    }
+}
+
+impl Component for TableActivityBox {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ShowTableActivity(table) => {
+                self.table = Some(table);
+                self.events = None;
+                self.list_state.select_first();
+            }
+            // The events of a popup that was closed can still be in flight.
+            Action::TransmitTableEvents(table, events) if self.table.as_ref() == Some(&table) => {
+                self.events = Some(events);
+            }
+            Action::ExitTableActivity => self.table = None,
+            Action::TableActivityScrollDown => self.list_state.select_next(),
+            Action::TableActivityScrollUp => self.list_state.select_previous(),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let Some(table) = &self.table else {
+            return Ok(());
+        };
+
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Percentage(10),
+            Constraint::Percentage(80),
+            Constraint::Percentage(10),
+        ])
+        .areas(area);
+
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(10),
+            Constraint::Percentage(80),
+            Constraint::Percentage(10),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .padding(Padding::horizontal(1))
+            .title(format!("CloudTrail Activity - {table}"))
+            .title_bottom(Line::from("<j/k> to scroll - <esc> to close").centered());
+
+        let inner = block.inner(middle);
+        frame.render_widget(Clear, middle);
+        frame.render_widget(block, middle);
+
+        let [events_area, note_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(2)]).areas(inner);
+
+        match &self.events {
+            None => frame.render_widget(
+                Paragraph::new("Looking up events...").style(Style::new().fg(theme.loading)),
+                events_area,
+            ),
+            Some(Err(error)) => frame.render_widget(
+                Paragraph::new(error.clone())
+                    .style(Style::new().fg(theme.error))
+                    .wrap(Wrap { trim: true }),
+                events_area,
+            ),
+            Some(Ok(events)) if events.is_empty() => frame.render_widget(
+                Paragraph::new("No writes to the table in the event history."),
+                events_area,
+            ),
+            Some(Ok(events)) => {
+                let list = List::new(self.event_items(events))
+                    .highlight_style(theme.selected().remove_modifier(Modifier::BOLD));
+                frame.render_stateful_widget(list, events_area, &mut self.list_state);
+            }
+        }
+
+        frame.render_widget(
+            Paragraph::new(
+                "The event history holds table changes, not item writes: those are data events, \
+                 only kept by trails that log them.",
+            )
+            .style(Style::new().fg(theme.muted))
+            .wrap(Wrap { trim: true }),
+            note_area,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,186 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph, Wrap},
+    Frame,
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    action::Action,
+    config::Config,
+    copy::{parse_renames, CopyTarget},
+};
+
+use super::{text_input::TextInput, Component};
+
+const FIELDS: [&str; 5] = [
+    "Destination table (defaults to the same name):",
+    "Region (optional):",
+    "Endpoint URL (optional, e.g. http://localhost:8000):",
+    "Profile (optional):",
+    "Rename attributes (optional, e.g. pk=PK, sk=SK):",
+];
+
+/// Form that starts a job copying every item of a table into another table, which may live in
+/// another region, account, or in DynamoDB Local.
+#[derive(Default)]
+pub struct TableCopyBox {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    /// The table being copied, while the form is open.
+    source: Option<String>,
+    inputs: [TextInput; 5],
+    focus: usize,
+    error: Option<String>,
+}
+
+impl TableCopyBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn optional(&self, index: usize) -> Option<String> {
+        let value = self.inputs[index].value().trim();
+        (!value.is_empty()).then(|| value.to_string())
+    }
+
+    fn target(&self, source: &str) -> Result<CopyTarget, String> {
+        let target = CopyTarget {
+            table: self.optional(0).unwrap_or_else(|| source.to_string()),
+            region: self.optional(1),
+            endpoint_url: self.optional(2),
+            profile: self.optional(3),
+            renames: parse_renames(self.inputs[4].value())?,
+        };
+        if target.is_source(source) {
+            return Err(
+                "Choose another table, region, endpoint, or profile to copy to".to_string(),
+            );
+        }
+        Ok(target)
+    }
+
+    fn submit(&mut self) -> Result<()> {
+        let Some(source) = self.source.clone() else {
+            return Ok(());
+        };
+        match self.target(&source) {
+            Ok(target) => {
+                self.close();
+                self.command_tx
+                    .as_ref()
+                    .unwrap()
+                    .send(Action::StartTableCopy(source, target))?;
+            }
+            Err(error) => self.error = Some(error),
+        }
+        Ok(())
+    }
+
+    fn close(&mut self) {
+        self.source = None;
+        self.error = None;
+    }
+}
+
+impl Component for TableCopyBox {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ShowTableCopy(table) => {
+                // The rest of the destination is kept, so several tables can be copied to the
+                // same place in a row.
+                self.inputs[0] = TextInput::new().placeholder(&table);
+                self.source = Some(table);
+                self.focus = 0;
+                self.error = None;
+            }
+            Action::NewTableCopyCharacter(c) => {
+                self.inputs[self.focus].enter_char(c);
+                self.error = None;
+            }
+            Action::DeleteTableCopyCharacter => {
+                self.inputs[self.focus].delete_char();
+                self.error = None;
+            }
+            Action::NextTableCopyField => self.focus = (self.focus + 1) % FIELDS.len(),
+            Action::SubmitTableCopy => self.submit()?,
+            Action::ExitTableCopy => self.close(),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let Some(source) = &self.source else {
+            return Ok(());
+        };
+
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(2 * FIELDS.len() as u16 + 6),
+            Constraint::Min(0),
+        ])
+        .areas(area);
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(25),
+            Constraint::Percentage(50),
+            Constraint::Percentage(25),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .padding(Padding::uniform(1))
+            .title(format!("Copy Table - {source}"))
+            .title_bottom(
+                Line::from("<enter> to start - <tab> to switch fields - <esc> to cancel")
+                    .centered(),
+            );
+
+        let inner = block.inner(middle);
+        frame.render_widget(Clear, middle);
+        frame.render_widget(block, middle);
+
+        let mut constraints = vec![Constraint::Length(2); FIELDS.len()];
+        constraints.push(Constraint::Min(0));
+        let rows = Layout::vertical(constraints).split(inner);
+
+        for (index, (label, input)) in FIELDS.iter().zip(&self.inputs).enumerate() {
+            let [label_area, value_area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(rows[index]);
+            frame.render_widget(
+                Paragraph::new(*label).style(theme.popup().fg(theme.muted)),
+                label_area,
+            );
+            input.render(frame, value_area, theme.popup(), index == self.focus);
+        }
+
+        if let Some(error) = &self.error {
+            frame.render_widget(
+                Paragraph::new(error.clone())
+                    .style(Style::new().fg(theme.error))
+                    .wrap(Wrap { trim: true }),
+                rows[FIELDS.len()],
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,238 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    action::Action,
+    config::Config,
+    cost::Billing,
+    data::KeyAttribute,
+    table_info::{format_bytes, IndexInfo, TableInfo},
+};
+
+use super::Component;
+
+/// Popup describing the table highlighted in the tables pane: its keys, size, capacity,
+/// indexes, stream and time to live.
+#[derive(Default)]
+pub struct TableInfoBox {
+    config: Config,
+    /// The table described, while the popup is open.
+    table: Option<String>,
+    /// `None` while the table is being described.
+    info: Option<Result<TableInfo, String>>,
+    /// The first line shown, for descriptions longer than the popup.
+    scroll: u16,
+}
+
+impl TableInfoBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn field(&self, label: &str, value: impl Into<String>) -> Line<'static> {
+        Line::from(vec![
+            Span::styled(
+                format!("{label:<20}"),
+                Style::new().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(value.into()),
+        ])
+    }
+
+    fn heading(&self, heading: String) -> Line<'static> {
+        Line::styled(
+            heading,
+            Style::new()
+                .fg(self.config.config.theme.loading)
+                .add_modifier(Modifier::BOLD),
+        )
+    }
+
+    fn info_lines(&self, info: &TableInfo) -> Vec<Line<'static>> {
+        let theme = self.config.config.theme;
+        let key = |key: &Option<KeyAttribute>| {
+            key.as_ref().map_or("-".to_string(), |key| {
+                format!("{} ({:?})", key.name, key.attribute_type)
+            })
+        };
+
+        let mut lines = vec![
+            self.field("Status", info.status.clone()),
+            self.field("Created", info.created.clone().unwrap_or("-".to_string())),
+            self.field("Partition key", key(&info.partition_key)),
+            self.field("Sort key", key(&info.sort_key)),
+            self.field("Items", info.item_count.to_string()),
+            self.field("Size", format_bytes(info.size_bytes)),
+            self.field(
+                "Billing",
+                match info.billing {
+                    Billing::OnDemand => "On demand".to_string(),
+                    Billing::Provisioned(read, write) => {
+                        format!("Provisioned, {read} RCU / {write} WCU")
+                    }
+                },
+            ),
+            self.field(
+                "Stream",
+                info.stream.clone().unwrap_or("Disabled".to_string()),
+            ),
+        ];
+        lines.push(match &info.ttl {
+            Ok(ttl) => self.field("Time to live", ttl.label()),
+            Err(error) => Line::from(vec![
+                Span::styled(
+                    format!("{:<20}", "Time to live"),
+                    Style::new().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(error.clone(), Style::new().fg(theme.error)),
+            ]),
+        });
+        lines.push(self.field(
+            "Deletion protection",
+            if info.deletion_protection {
+                "Enabled"
+            } else {
+                "Disabled"
+            },
+        ));
+
+        for (heading, indexes) in [
+            ("Global indexes", &info.global_indexes),
+            ("Local indexes", &info.local_indexes),
+        ] {
+            if indexes.is_empty() {
+                continue;
+            }
+            lines.push(Line::default());
+            lines.push(self.heading(format!("{heading} ({})", indexes.len())));
+            for index in indexes {
+                lines.extend(self.index_lines(index));
+            }
+        }
+
+        lines.push(Line::default());
+        lines.push(Line::styled(
+            "Item counts and sizes are refreshed by DynamoDB about every six hours.",
+            Style::new().fg(theme.muted),
+        ));
+        lines
+    }
+
+    fn index_lines(&self, index: &IndexInfo) -> Vec<Line<'static>> {
+        let theme = self.config.config.theme;
+        let mut title = vec![Span::styled(
+            index.name.clone(),
+            Style::new().add_modifier(Modifier::BOLD),
+        )];
+        if let Some(status) = index.status.as_ref().filter(|status| *status != "ACTIVE") {
+            title.push(Span::styled(
+                format!("  {status}"),
+                Style::new().fg(theme.loading),
+            ));
+        }
+
+        let mut details = format!(
+            "  {} - {} - {} items, {}",
+            index.keys(),
+            index.projection,
+            index.item_count,
+            format_bytes(index.size_bytes)
+        );
+        if let Some((read, write)) = index.capacity {
+            details.push_str(&format!(" - {read} RCU / {write} WCU"));
+        }
+        vec![
+            Line::from(title),
+            Line::styled(details, Style::new().fg(theme.muted)),
+        ]
+    }
+}
+
+impl Component for TableInfoBox {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ShowTableInfo(table) => {
+                self.table = Some(table);
+                self.info = None;
+                self.scroll = 0;
+            }
+            // The description of a popup that was closed can still be in flight.
+            Action::TransmitTableInfo(table, info) if self.table.as_ref() == Some(&table) => {
+                self.info = Some(info);
+            }
+            Action::TableInfoScrollDown if self.table.is_some() => {
+                self.scroll = self.scroll.saturating_add(1);
+            }
+            Action::TableInfoScrollUp => self.scroll = self.scroll.saturating_sub(1),
+            Action::ExitTableInfo => self.table = None,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        let Some(table) = &self.table else {
+            return Ok(());
+        };
+
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Percentage(15),
+            Constraint::Percentage(70),
+            Constraint::Percentage(15),
+        ])
+        .areas(area);
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(15),
+            Constraint::Percentage(70),
+            Constraint::Percentage(15),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .padding(Padding::horizontal(1))
+            .title(format!("Table - {table}"))
+            .title_bottom(Line::from("<j/k> to scroll - <esc> to close").centered());
+
+        let inner = block.inner(middle);
+        frame.render_widget(Clear, middle);
+        frame.render_widget(block, middle);
+
+        match &self.info {
+            None => frame.render_widget(
+                Paragraph::new("Describing...").style(Style::new().fg(theme.loading)),
+                inner,
+            ),
+            Some(Err(error)) => frame.render_widget(
+                Paragraph::new(error.clone())
+                    .style(Style::new().fg(theme.error))
+                    .wrap(Wrap { trim: true }),
+                inner,
+            ),
+            Some(Ok(info)) => {
+                let lines = self.info_lines(info);
+                // Stops scrolling once the last line is at the bottom.
+                let last_top = (lines.len() as u16).saturating_sub(inner.height);
+                self.scroll = self.scroll.min(last_top);
+                frame.render_widget(Paragraph::new(lines).scroll((self.scroll, 0)), inner);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,206 @@
+use color_eyre::Result;
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::{
+    action::Action,
+    config::Config,
+    table_search::{merge, RegionTables},
+};
+
+use super::{text_input::TextInput, Component};
+
+/// Popup listing the tables of every region at once, for finding the region a table lives in.
+#[derive(Default)]
+pub struct TableSearchBox {
+    config: Config,
+    active: bool,
+    /// `None` until every region has answered.
+    results: Option<Vec<RegionTables>>,
+    /// Every table found, as `(table, region)` pairs.
+    tables: Vec<(String, String)>,
+    filter: TextInput,
+    list_state: ListState,
+}
+
+impl TableSearchBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn filtered_tables(&self) -> Vec<&(String, String)> {
+        if self.filter.is_empty() {
+            return self.tables.iter().collect();
+        }
+
+        let matcher = SkimMatcherV2::default();
+        self.tables
+            .iter()
+            .filter(|(table, region)| {
+                matcher
+                    .fuzzy_match(&format!("{table} {region}"), self.filter.value())
+                    .is_some()
+            })
+            .collect()
+    }
+
+    fn summary(&self, results: &[RegionTables]) -> Line<'static> {
+        let theme = self.config.config.theme;
+        let regions = results
+            .iter()
+            .filter(|result| !result.tables.is_empty())
+            .count();
+        let mut summary = vec![Span::styled(
+            format!("{} tables in {regions} regions", self.tables.len()),
+            Style::new().fg(theme.muted),
+        )];
+
+        let failed: Vec<&str> = results
+            .iter()
+            .filter(|result| result.error.is_some())
+            .map(|result| result.region.as_str())
+            .collect();
+        if !failed.is_empty() {
+            summary.push(Span::styled(
+                format!(
+                    " - not enabled or not allowed in {}: {}",
+                    failed.len(),
+                    failed.join(", ")
+                ),
+                Style::new().fg(theme.error),
+            ));
+        }
+
+        Line::from(summary)
+    }
+}
+
+impl Component for TableSearchBox {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::SearchAllRegions => {
+                self.active = true;
+                self.results = None;
+                self.tables.clear();
+                self.filter.clear();
+                self.list_state.select(None);
+            }
+            Action::TransmitTableSearch(results) if self.active => {
+                self.tables = merge(&results);
+                self.results = Some(results);
+                self.list_state.select_first();
+            }
+            Action::NewTableSearchCharacter(c) => {
+                self.filter.enter_char(c);
+                self.list_state.select_first();
+            }
+            Action::DeleteTableSearchCharacter => {
+                self.filter.delete_char();
+                self.list_state.select_first();
+            }
+            Action::TableSearchNext => self.list_state.select_next(),
+            Action::TableSearchPrev => self.list_state.select_previous(),
+            Action::ExitTableSearch => self.active = false,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Percentage(15),
+            Constraint::Percentage(70),
+            Constraint::Percentage(15),
+        ])
+        .areas(area);
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(20),
+            Constraint::Percentage(60),
+            Constraint::Percentage(20),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .title("Tables in All Regions")
+            .title_bottom(
+                Line::from("<type> to filter - <up/down> to move - <esc> to close").centered(),
+            );
+
+        let inner = block.inner(middle);
+        frame.render_widget(Clear, middle);
+        frame.render_widget(block, middle);
+
+        let [filter_area, summary_area, _, list_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .areas(inner);
+
+        let label = "Filter: ";
+        let [label_area, input_area] =
+            Layout::horizontal([Constraint::Length(label.len() as u16), Constraint::Min(0)])
+                .areas(filter_area);
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                label,
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+            label_area,
+        );
+        self.filter
+            .render(frame, input_area, Style::default().fg(theme.text), true);
+
+        let Some(results) = &self.results else {
+            frame.render_widget(
+                Paragraph::new("Listing tables in every region...")
+                    .style(Style::new().fg(theme.muted)),
+                summary_area,
+            );
+            return Ok(());
+        };
+        frame.render_widget(Paragraph::new(self.summary(results)), summary_area);
+
+        let width = self
+            .tables
+            .iter()
+            .map(|(table, _)| table.len())
+            .max()
+            .unwrap_or_default();
+        let items: Vec<ListItem> = self
+            .filtered_tables()
+            .into_iter()
+            .map(|(table, region)| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("{table:<width$}  "), Style::new().fg(theme.text)),
+                    Span::styled(region.clone(), Style::new().fg(theme.muted)),
+                ]))
+            })
+            .collect();
+        let list = List::new(items).highlight_style(theme.selected());
+        frame.render_stateful_widget(list, list_area, &mut self.list_state);
+
+        Ok(())
+    }
+}
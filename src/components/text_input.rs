@@ -0,0 +1,220 @@
+use ratatui::{
+    layout::{Position, Rect},
+    style::{Modifier, Style},
+    widgets::{Paragraph, Widget},
+    Frame,
+};
+
+type Validator = Box<dyn Fn(&str) -> Result<(), String>>;
+
+/// A single line text input with a cursor, shared by every prompt that accepts typed text.
+///
+/// The cursor is tracked as a character index rather than a byte index, so editing works with
+/// multi-byte characters.
+#[derive(Default)]
+pub struct TextInput {
+    value: String,
+    cursor: usize,
+    placeholder: String,
+    char_filter: Option<fn(char) -> bool>,
+    validator: Option<Validator>,
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Text shown, dimmed, while the input is empty.
+    pub fn placeholder(mut self, placeholder: &str) -> Self {
+        self.placeholder = placeholder.to_string();
+        self
+    }
+
+    /// Restricts which characters can be typed into the input.
+    pub fn char_filter(mut self, char_filter: fn(char) -> bool) -> Self {
+        self.char_filter = Some(char_filter);
+        self
+    }
+
+    pub fn set_char_filter(&mut self, char_filter: Option<fn(char) -> bool>) {
+        self.char_filter = char_filter;
+    }
+
+    pub fn set_validator<F>(&mut self, validator: F)
+    where
+        F: Fn(&str) -> Result<(), String> + 'static,
+    {
+        self.validator = Some(Box::new(validator));
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.value.clear();
+        self.cursor = 0;
+    }
+
+    /// Runs the validator, if any, against the current content.
+    pub fn validate(&self) -> Result<(), String> {
+        match &self.validator {
+            Some(validator) => validator(&self.value),
+            None => Ok(()),
+        }
+    }
+
+    /// The validation error for the current content. Empty inputs are not reported, so a form
+    /// does not start out covered in errors.
+    pub fn error(&self) -> Option<String> {
+        if self.is_empty() {
+            return None;
+        }
+        self.validate().err()
+    }
+
+    pub fn enter_char(&mut self, new_char: char) {
+        if self.char_filter.is_some_and(|allowed| !allowed(new_char)) {
+            return;
+        }
+        let index = self.byte_index();
+        self.value.insert(index, new_char);
+        self.move_cursor_right();
+    }
+
+    pub fn insert_str(&mut self, text: &str) {
+        text.chars().for_each(|c| self.enter_char(c));
+    }
+
+    /// Deletes the character before the cursor.
+    pub fn delete_char(&mut self) {
+        if self.cursor == 0 {
+            return;
+        }
+
+        // Method "remove" is not used on the saved text for deleting the selected char.
+        // Reason: Using remove on String works on bytes instead of the chars.
+        let before_char_to_delete = self.value.chars().take(self.cursor - 1);
+        let after_char_to_delete = self.value.chars().skip(self.cursor);
+
+        self.value = before_char_to_delete.chain(after_char_to_delete).collect();
+        self.move_cursor_left();
+    }
+
+    pub fn move_cursor_left(&mut self) {
+        self.cursor = self.clamp_cursor(self.cursor.saturating_sub(1));
+    }
+
+    pub fn move_cursor_right(&mut self) {
+        self.cursor = self.clamp_cursor(self.cursor.saturating_add(1));
+    }
+
+    /// Returns the byte index based on the character position.
+    ///
+    /// Since each character in a string can be contain multiple bytes, it's necessary to calculate
+    /// the byte index based on the index of the character.
+    fn byte_index(&self) -> usize {
+        self.value
+            .char_indices()
+            .map(|(i, _)| i)
+            .nth(self.cursor)
+            .unwrap_or(self.value.len())
+    }
+
+    fn clamp_cursor(&self, new_cursor_pos: usize) -> usize {
+        new_cursor_pos.clamp(0, self.value.chars().count())
+    }
+
+    /// Renders the content (or the placeholder) into `area` and, when `focused`, places the
+    /// terminal cursor at the editing position.
+    pub fn render(&self, frame: &mut Frame, area: Rect, style: Style, focused: bool) {
+        let paragraph = if self.value.is_empty() {
+            Paragraph::new(self.placeholder.clone()).style(style.add_modifier(Modifier::DIM))
+        } else {
+            Paragraph::new(self.value.clone()).style(style)
+        };
+        paragraph.render(area, frame.buffer_mut());
+
+        if focused {
+            frame.set_cursor_position(Position::new(area.x + self.cursor as u16, area.y));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::data::KeyAttributeType;
+
+    fn input_with(text: &str) -> TextInput {
+        let mut input = TextInput::new();
+        text.chars().for_each(|c| input.enter_char(c));
+        input
+    }
+
+    #[test]
+    fn test_enter_and_delete_multibyte_chars() {
+        let mut input = input_with("héllo");
+        input.move_cursor_left();
+        input.move_cursor_left();
+        input.delete_char();
+        assert_eq!(input.value(), "hélo");
+
+        input.enter_char('ü');
+        assert_eq!(input.value(), "héülo");
+    }
+
+    #[test]
+    fn test_delete_at_start_is_noop() {
+        let mut input = input_with("abc");
+        (0..5).for_each(|_| input.move_cursor_left());
+        input.delete_char();
+        assert_eq!(input.value(), "abc");
+
+        input.enter_char('x');
+        assert_eq!(input.value(), "xabc");
+    }
+
+    #[test]
+    fn test_char_filter() {
+        let mut input = TextInput::new().char_filter(|c| c.is_ascii_digit());
+        "1a2b3".chars().for_each(|c| input.enter_char(c));
+        assert_eq!(input.value(), "123");
+    }
+
+    #[test]
+    fn test_validator() {
+        let mut input = TextInput::new();
+        input.set_validator(|value| {
+            if value.is_empty() {
+                Err("required".to_string())
+            } else {
+                Ok(())
+            }
+        });
+        assert!(input.validate().is_err());
+        input.enter_char('x');
+        assert!(input.validate().is_ok());
+    }
+
+    #[test]
+    fn test_error_ignores_empty_input() {
+        let mut input = TextInput::new();
+        input.set_validator(|value| KeyAttributeType::N.validate(value));
+        assert_eq!(input.error(), None);
+        input.insert_str("4x");
+        assert_eq!(input.error(), Some("`4x` is not a number".to_string()));
+        input.delete_char();
+        assert_eq!(input.error(), None);
+        input.clear();
+        input.insert_str("inf");
+        assert_eq!(input.error(), Some("`inf` is not a number".to_string()));
+    }
+}
@@ -0,0 +1,166 @@
+use color_eyre::Result;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Padding},
+    Frame,
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{action::Action, config::Config, workspace::Workspace};
+
+use super::Component;
+
+/// Popup that switches to one of the workspaces in the config, or back to the environment's
+/// profile and region, which is listed first.
+#[derive(Default)]
+pub struct WorkspaceBox {
+    command_tx: Option<UnboundedSender<Action>>,
+    config: Config,
+    active: bool,
+    current: Option<String>,
+    list_state: ListState,
+}
+
+impl WorkspaceBox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn workspaces(&self) -> Vec<Option<&Workspace>> {
+        std::iter::once(None)
+            .chain(self.config.config.workspaces.iter().map(Some))
+            .collect()
+    }
+
+    fn render_workspace(&self, workspace: Option<&Workspace>) -> ListItem<'static> {
+        let theme = self.config.config.theme;
+        let name = workspace.map(|workspace| workspace.name.clone());
+        let marker = if name == self.current { "* " } else { "  " };
+
+        let mut spans = vec![Span::raw(marker)];
+        match workspace {
+            Some(workspace) => {
+                spans.push(Span::raw(workspace.name.clone()));
+                let details: Vec<String> = [
+                    workspace.profile.clone(),
+                    workspace.region.clone(),
+                    (!workspace.tables.is_empty())
+                        .then(|| format!("{} tables", workspace.tables.len())),
+                    workspace.read_only.then(|| "read-only".to_string()),
+                ]
+                .into_iter()
+                .flatten()
+                .collect();
+                if !details.is_empty() {
+                    spans.push(Span::styled(
+                        format!("  {}", details.join(", ")),
+                        Style::new().fg(theme.muted),
+                    ));
+                }
+            }
+            None => spans.push(Span::styled(
+                "No workspace (profile and region from the environment)",
+                Style::new().fg(theme.muted),
+            )),
+        }
+
+        ListItem::new(Line::from(spans))
+    }
+}
+
+impl Component for WorkspaceBox {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.command_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::ViewWorkspaces => {
+                self.active = true;
+                let selected = self
+                    .workspaces()
+                    .iter()
+                    .position(|workspace| {
+                        workspace.map(|workspace| &workspace.name) == self.current.as_ref()
+                    })
+                    .unwrap_or_default();
+                self.list_state.select(Some(selected));
+            }
+            Action::WorkspaceNext => self.list_state.select_next(),
+            Action::WorkspacePrev => self.list_state.select_previous(),
+            Action::SwitchToSelectedWorkspace => {
+                let workspace = self
+                    .list_state
+                    .selected()
+                    .and_then(|selected| self.workspaces().get(selected).copied())
+                    .map(|workspace| workspace.cloned());
+                if let Some(workspace) = workspace {
+                    self.active = false;
+                    self.command_tx
+                        .as_ref()
+                        .unwrap()
+                        .send(Action::SwitchWorkspace(workspace))?;
+                }
+            }
+            Action::TransmitWorkspace(workspace) => {
+                self.current = workspace.map(|workspace| workspace.name)
+            }
+            Action::ExitWorkspaces => self.active = false,
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, frame: &mut Frame, area: Rect) -> Result<()> {
+        if !self.active {
+            return Ok(());
+        }
+
+        let [_, y_middle, _] = Layout::vertical([
+            Constraint::Percentage(25),
+            Constraint::Percentage(50),
+            Constraint::Percentage(25),
+        ])
+        .areas(area);
+        let [_, middle, _] = Layout::horizontal([
+            Constraint::Percentage(20),
+            Constraint::Percentage(60),
+            Constraint::Percentage(20),
+        ])
+        .areas(y_middle);
+
+        let theme = self.config.config.theme;
+        let block = Block::new()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border(true))
+            .style(theme.popup())
+            .padding(Padding::horizontal(1))
+            .title("Workspaces")
+            .title_bottom(
+                Line::from("<j/k> to choose - <enter> to switch - <esc> to cancel").centered(),
+            );
+
+        let items: Vec<ListItem> = self
+            .workspaces()
+            .into_iter()
+            .map(|workspace| self.render_workspace(workspace))
+            .collect();
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(theme.selected());
+
+        frame.render_widget(Clear, middle);
+        frame.render_stateful_widget(list, middle, &mut self.list_state);
+
+        Ok(())
+    }
+}
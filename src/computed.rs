@@ -0,0 +1,281 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::pin;
+
+/// A column shown before every item of a table, computed from the item by an expression such as
+/// `concat(firstName, ' ', lastName)` or `len(items)`. Bare names are dotted attribute paths, as
+/// for pinned fields, and text is quoted with single or double quotes.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComputedColumn {
+    pub table: String,
+    pub name: String,
+    pub expression: String,
+}
+
+/// The columns computed for `table`, in the order they were defined.
+pub fn for_table<'a>(
+    columns: &'a [ComputedColumn],
+    table: &'a str,
+) -> impl Iterator<Item = &'a ComputedColumn> {
+    columns.iter().filter(move |column| column.table == table)
+}
+
+/// Reads a column typed as `name = expression`. A column typed without a name is named after its
+/// expression.
+pub fn parse_definition(table: &str, definition: &str) -> Result<ComputedColumn, String> {
+    let (name, expression) = match definition.split_once('=') {
+        Some((name, expression)) if is_name(name.trim()) => (name.trim(), expression.trim()),
+        _ => (definition.trim(), definition.trim()),
+    };
+    Expr::parse(expression)?;
+    Ok(ComputedColumn {
+        table: table.to_string(),
+        name: name.to_string(),
+        expression: expression.to_string(),
+    })
+}
+
+fn is_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(is_path_char)
+}
+
+fn is_path_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '#')
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Path(String),
+    Text(String),
+    Call(Function, Vec<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Function {
+    /// Joins its arguments as text, leaving out missing ones.
+    Concat,
+    /// The number of characters of a string, elements of a list, or attributes of a map.
+    Len,
+    Upper,
+    Lower,
+    /// The first argument the item has.
+    Coalesce,
+}
+
+impl Function {
+    fn named(name: &str) -> Option<Self> {
+        match name {
+            "concat" => Some(Function::Concat),
+            "len" => Some(Function::Len),
+            "upper" => Some(Function::Upper),
+            "lower" => Some(Function::Lower),
+            "coalesce" => Some(Function::Coalesce),
+            _ => None,
+        }
+    }
+
+    /// How many arguments the function takes, at least and at most.
+    fn arity(self) -> (usize, usize) {
+        match self {
+            Function::Concat | Function::Coalesce => (1, usize::MAX),
+            Function::Len | Function::Upper | Function::Lower => (1, 1),
+        }
+    }
+}
+
+impl Expr {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            chars: expression.chars().collect(),
+            position: 0,
+        };
+        let expr = parser.expr()?;
+        parser.skip_whitespace();
+        match parser.peek() {
+            None => Ok(expr),
+            Some(c) => Err(format!("Unexpected '{c}' at {}", parser.position + 1)),
+        }
+    }
+
+    /// The value of the expression for `item`, null when it reads an attribute the item does not
+    /// have.
+    pub fn eval(&self, item: &Value) -> Value {
+        match self {
+            Expr::Path(path) => pin::lookup(item, path).cloned().unwrap_or(Value::Null),
+            Expr::Text(text) => Value::String(text.clone()),
+            Expr::Call(function, arguments) => {
+                let mut values = arguments.iter().map(|argument| argument.eval(item));
+                match function {
+                    Function::Concat => {
+                        Value::String(values.map(|value| display(&value)).collect())
+                    }
+                    Function::Len => match values.next().unwrap_or_default() {
+                        Value::String(text) => text.chars().count().into(),
+                        Value::Array(list) => list.len().into(),
+                        Value::Object(map) => map.len().into(),
+                        _ => Value::Null,
+                    },
+                    Function::Upper => {
+                        Value::String(display(&values.next().unwrap_or_default()).to_uppercase())
+                    }
+                    Function::Lower => {
+                        Value::String(display(&values.next().unwrap_or_default()).to_lowercase())
+                    }
+                    Function::Coalesce => values.find(|value| !value.is_null()).unwrap_or_default(),
+                }
+            }
+        }
+    }
+}
+
+/// How a computed value is shown: text without its quotes, nothing for a missing value, and JSON
+/// for anything else.
+pub fn display(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            None => Err("Expected an attribute, text or function".to_string()),
+            Some(quote @ ('\'' | '"')) => {
+                self.position += 1;
+                let start = self.position;
+                while self.peek().is_some_and(|c| c != quote) {
+                    self.position += 1;
+                }
+                if self.peek().is_none() {
+                    return Err("Unterminated text".to_string());
+                }
+                let text = self.chars[start..self.position].iter().collect();
+                self.position += 1;
+                Ok(Expr::Text(text))
+            }
+            Some(c) if is_path_char(c) => {
+                let start = self.position;
+                while self.peek().is_some_and(is_path_char) {
+                    self.position += 1;
+                }
+                let name: String = self.chars[start..self.position].iter().collect();
+                self.skip_whitespace();
+                if self.peek() != Some('(') {
+                    return Ok(Expr::Path(name));
+                }
+                let function =
+                    Function::named(&name).ok_or_else(|| format!("Unknown function {name}"))?;
+                self.position += 1;
+                let arguments = self.arguments()?;
+                let (least, most) = function.arity();
+                if arguments.len() < least || arguments.len() > most {
+                    return Err(format!("Wrong number of arguments to {name}"));
+                }
+                Ok(Expr::Call(function, arguments))
+            }
+            Some(c) => Err(format!("Unexpected '{c}' at {}", self.position + 1)),
+        }
+    }
+
+    /// The arguments of a call, after its opening parenthesis.
+    fn arguments(&mut self) -> Result<Vec<Expr>, String> {
+        let mut arguments = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(')') {
+            self.position += 1;
+            return Ok(arguments);
+        }
+        loop {
+            arguments.push(self.expr()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(')') => {
+                    self.position += 1;
+                    return Ok(arguments);
+                }
+                _ => return Err("Expected ',' or ')'".to_string()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn eval(expression: &str, item: &Value) -> String {
+        display(&Expr::parse(expression).unwrap().eval(item))
+    }
+
+    #[test]
+    fn test_eval_expressions() {
+        let item = json!({
+            "firstName": "Ada",
+            "lastName": "Lovelace",
+            "items": [1, 2, 3],
+            "address": { "city": "London" },
+            "total": 12.5
+        });
+
+        assert_eq!(
+            eval("concat(firstName, ' ', lastName)", &item),
+            "Ada Lovelace"
+        );
+        assert_eq!(eval("len(items)", &item), "3");
+        assert_eq!(eval("len(firstName)", &item), "3");
+        assert_eq!(eval("upper(address.city)", &item), "LONDON");
+        assert_eq!(eval("coalesce(nickname, \"none\")", &item), "none");
+        assert_eq!(eval("concat(total, '€')", &item), "12.5€");
+        assert_eq!(eval("items.1", &item), "2");
+        assert_eq!(eval("nickname", &item), "");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Expr::parse("concat(a, 'b'").is_err());
+        assert!(Expr::parse("sum(a)").is_err());
+        assert!(Expr::parse("len(a, b)").is_err());
+        assert!(Expr::parse("'open").is_err());
+        assert!(Expr::parse("a b").is_err());
+    }
+
+    #[test]
+    fn test_parse_definitions() {
+        assert_eq!(
+            parse_definition("users", "name = concat(first, ' ', last)"),
+            Ok(ComputedColumn {
+                table: "users".to_string(),
+                name: "name".to_string(),
+                expression: "concat(first, ' ', last)".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_definition("users", "len(items)").unwrap().name,
+            "len(items)"
+        );
+        assert!(parse_definition("users", "count = len(").is_err());
+    }
+}
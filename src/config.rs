@@ -1,26 +1,380 @@
 #![allow(dead_code)] // Remove this once you start using the code
 
-use std::{collections::HashMap, env, path::PathBuf};
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
 
 use color_eyre::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use derive_deref::{Deref, DerefMut};
 use directories::ProjectDirs;
 use lazy_static::lazy_static;
-use ratatui::style::{Color, Modifier, Style};
-use serde::{de::Deserializer, Deserialize};
+use ratatui::{
+    layout::Constraint,
+    style::{Color, Modifier, Style},
+};
+use serde::{de::Deserializer, Deserialize, Serialize};
+use serde_json::json;
 use tracing::error;
 
-use crate::{action::Action, app::Mode};
+use crate::{
+    action::Action,
+    app::Mode,
+    computed::ComputedColumn,
+    data::DEFAULT_PAGE_SIZE,
+    encryption::EncryptedAttributes,
+    pin::PinnedFields,
+    proxy::ProxyConfig,
+    relation::Relation,
+    schema::TableSchema,
+    soft_delete::SoftDelete,
+    template::ItemTemplate,
+    theme::{Theme, ThemeConfig},
+    workspace::Workspace,
+};
 
 const CONFIG: &str = include_str!("../.config/config.json5");
 
-#[derive(Clone, Debug, Deserialize, Default)]
+/// Written by the app whenever the pane layout changes, and loaded on top of the user config.
+const LAYOUT_FILE: &str = "layout.json";
+
+/// Written by the app whenever a field is pinned or unpinned in the detail view.
+const PINNED_FIELDS_FILE: &str = "pinned_fields.json";
+
+const MIN_SIDEBAR_WIDTH: u16 = 10;
+const MAX_SIDEBAR_WIDTH: u16 = 70;
+const SIDEBAR_WIDTH_STEP: u16 = 5;
+
+#[derive(Clone, Debug, Deserialize)]
 pub struct AppConfig {
     #[serde(default)]
     pub data_dir: PathBuf,
     #[serde(default)]
     pub config_dir: PathBuf,
+    /// Account IDs that are highlighted as production when they are the active identity.
+    #[serde(default)]
+    pub production_account_ids: Vec<String>,
+    /// Regions searched for tables across the account. Every region DynamoDB is offered in when
+    /// empty.
+    #[serde(default)]
+    pub search_regions: Vec<String>,
+    /// Named combinations of profile, region, and tables that can be switched between.
+    #[serde(default)]
+    pub workspaces: Vec<Workspace>,
+    /// Items a scan reads per page. Smaller pages arrive sooner on a slow connection or when items
+    /// are large; DynamoDB also ends a page once it has read 1 MB.
+    #[serde(default = "default_page_size")]
+    pub page_size: i32,
+    /// Upper bound on the number of items kept for a single result set.
+    #[serde(default = "default_max_items")]
+    pub max_items: usize,
+    /// Bytes of records a result set keeps in memory; older pages past this are moved to a
+    /// temporary file and read back when they scroll into view.
+    #[serde(default = "default_records_memory_budget")]
+    pub records_memory_budget: usize,
+    /// Copying more than this many bytes of records to the clipboard asks for confirmation first.
+    #[serde(default = "default_copy_confirm_bytes")]
+    pub copy_confirm_bytes: usize,
+    /// Whether items read again with GetItem, to refresh a row or a bookmark, are read strongly
+    /// consistent. Eventually consistent reads cost half as much but may miss a recent write.
+    #[serde(default = "default_consistent_reads")]
+    pub consistent_reads: bool,
+    /// Probes which operations the credentials allow on a table when it is selected. The probes
+    /// read at most one item each and write nothing, but are still billed.
+    #[serde(default)]
+    pub probe_permissions: bool,
+    /// How long the table list, table descriptions, and scanned pages are reused before they are
+    /// fetched again.
+    #[serde(default = "default_cache_ttl_secs")]
+    pub cache_ttl_secs: u64,
+    /// How often the credentials are resolved again, which renews temporary credentials before
+    /// they expire and updates the countdown to their expiry. Never when zero.
+    #[serde(default = "default_credentials_refresh_secs")]
+    pub credentials_refresh_secs: u64,
+    /// How many matches a scan of the whole table for the find text stops at, unless a count is
+    /// typed before the key. Every item is scanned when zero.
+    #[serde(default = "default_deep_find_matches")]
+    pub deep_find_matches: usize,
+    /// How many segments a parallel scan reads the table in at once. More segments read a large
+    /// table faster, but use its read capacity faster too.
+    #[serde(default = "default_parallel_scan_segments")]
+    pub parallel_scan_segments: i32,
+    /// Where exports are written: `clipboard`, `-` for stdout once the app exits,
+    /// `s3://bucket/key` for an S3 object, `| command` to pipe into a shell command, or a file
+    /// path.
+    #[serde(default = "default_export_destination")]
+    pub export_destination: String,
+    /// A SQLite file the items of every scan and query are copied into, a table for each
+    /// DynamoDB table, to query with SQL or browse again with `--offline`. Nothing is copied when
+    /// unset.
+    #[serde(default)]
+    pub sqlite_mirror: Option<PathBuf>,
+    /// Starts with item values masked on screen and in copies, for sharing the screen.
+    #[serde(default)]
+    pub privacy_mode: bool,
+    /// Shows, copies and exports items with their key attributes first, ahead of the other
+    /// attributes, which are always sorted by name.
+    #[serde(default)]
+    pub key_attributes_first: bool,
+    /// How long the keys typed so far of a multi-key binding wait for the next one.
+    #[serde(default = "default_chord_timeout_ms")]
+    pub chord_timeout_ms: u64,
+    /// Item templates offered when creating an item in the table each one names.
+    #[serde(default)]
+    pub templates: Vec<ItemTemplate>,
+    /// Attribute paths shown first in the detail view of every item of a table.
+    #[serde(default)]
+    pub pinned_fields: Vec<PinnedFields>,
+    /// Attributes that reference items of another table, followed from the detail view.
+    #[serde(default)]
+    pub relations: Vec<Relation>,
+    /// JSON Schemas that items written to a table must follow, and that items read are checked
+    /// against.
+    #[serde(default)]
+    pub schemas: Vec<TableSchema>,
+    /// Columns computed from every item of a table and shown before it in the list.
+    #[serde(default)]
+    pub computed_columns: Vec<ComputedColumn>,
+    /// Attributes that mark the items of a table as deleted, which are hidden unless asked for.
+    #[serde(default)]
+    pub soft_delete: Vec<SoftDelete>,
+    /// Attributes encrypted on the client, shown with a lock and left out of fuzzy search.
+    #[serde(default)]
+    pub encrypted_attributes: Vec<EncryptedAttributes>,
+    #[serde(default)]
+    pub layout: LayoutConfig,
+    #[serde(default)]
+    pub scroll: ScrollConfig,
+    #[serde(default)]
+    pub proxy: ProxyConfig,
+    #[serde(default, rename = "theme")]
+    pub theme_config: ThemeConfig,
+    /// Resolved from `theme_config` once the config is loaded.
+    #[serde(skip)]
+    pub theme: Theme,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            data_dir: PathBuf::default(),
+            config_dir: PathBuf::default(),
+            production_account_ids: Vec::new(),
+            search_regions: Vec::new(),
+            workspaces: Vec::new(),
+            page_size: default_page_size(),
+            max_items: default_max_items(),
+            records_memory_budget: default_records_memory_budget(),
+            copy_confirm_bytes: default_copy_confirm_bytes(),
+            consistent_reads: default_consistent_reads(),
+            probe_permissions: false,
+            cache_ttl_secs: default_cache_ttl_secs(),
+            credentials_refresh_secs: default_credentials_refresh_secs(),
+            deep_find_matches: default_deep_find_matches(),
+            parallel_scan_segments: default_parallel_scan_segments(),
+            export_destination: default_export_destination(),
+            sqlite_mirror: None,
+            privacy_mode: false,
+            key_attributes_first: false,
+            chord_timeout_ms: default_chord_timeout_ms(),
+            templates: Vec::new(),
+            pinned_fields: Vec::new(),
+            relations: Vec::new(),
+            schemas: Vec::new(),
+            computed_columns: Vec::new(),
+            soft_delete: Vec::new(),
+            encrypted_attributes: Vec::new(),
+            layout: LayoutConfig::default(),
+            scroll: ScrollConfig::default(),
+            proxy: ProxyConfig::default(),
+            theme_config: ThemeConfig::default(),
+            theme: Theme::default(),
+        }
+    }
+}
+
+fn default_page_size() -> i32 {
+    DEFAULT_PAGE_SIZE
+}
+
+fn default_max_items() -> usize {
+    10_000
+}
+
+fn default_records_memory_budget() -> usize {
+    256 * 1024 * 1024
+}
+
+fn default_copy_confirm_bytes() -> usize {
+    1_000_000
+}
+
+fn default_consistent_reads() -> bool {
+    true
+}
+
+fn default_cache_ttl_secs() -> u64 {
+    300
+}
+
+fn default_credentials_refresh_secs() -> u64 {
+    300
+}
+
+fn default_deep_find_matches() -> usize {
+    1
+}
+
+fn default_parallel_scan_segments() -> i32 {
+    8
+}
+
+fn default_export_destination() -> String {
+    "clipboard".to_string()
+}
+
+fn default_chord_timeout_ms() -> u64 {
+    1000
+}
+
+/// How far the scroll keys and the mouse wheel move. Half-page and page scrolling follow the
+/// height of the pane instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScrollConfig {
+    /// Lines the table list and the data pane move by for their scroll keys.
+    #[serde(default = "default_list_lines")]
+    pub list_lines: u16,
+    /// Lines the item detail moves by for its scroll keys.
+    #[serde(default = "default_detail_lines")]
+    pub detail_lines: u16,
+    /// Lines the table list and the data pane move by for each notch of the mouse wheel.
+    #[serde(default = "default_wheel_lines")]
+    pub wheel_lines: u16,
+    /// Lines the item detail moves by for each notch of the mouse wheel.
+    #[serde(default = "default_detail_wheel_lines")]
+    pub detail_wheel_lines: u16,
+}
+
+impl Default for ScrollConfig {
+    fn default() -> Self {
+        Self {
+            list_lines: default_list_lines(),
+            detail_lines: default_detail_lines(),
+            wheel_lines: default_wheel_lines(),
+            detail_wheel_lines: default_detail_wheel_lines(),
+        }
+    }
+}
+
+fn default_list_lines() -> u16 {
+    5
+}
+
+fn default_detail_lines() -> u16 {
+    10
+}
+
+fn default_wheel_lines() -> u16 {
+    1
+}
+
+fn default_detail_wheel_lines() -> u16 {
+    3
+}
+
+/// How the panes are laid out. The size of the table list sidebar is a percentage of the
+/// terminal width.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayoutConfig {
+    #[serde(default = "default_sidebar_width")]
+    pub sidebar_width: u16,
+    #[serde(default)]
+    pub sidebar_collapsed: bool,
+    /// Numbers the records in the data pane by their position in the result set.
+    #[serde(default)]
+    pub row_numbers: bool,
+    /// Lists the tables in as many columns as fit the sidebar, like `ls`.
+    #[serde(default)]
+    pub table_columns: bool,
+    /// The pane expanded to the whole terminal, until it is zoomed out again. Not saved.
+    #[serde(skip)]
+    pub zoomed: Option<ZoomedPane>,
+}
+
+/// A pane that can be zoomed to fill the terminal, as with tmux's pane zoom.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ZoomedPane {
+    Tables,
+    Data,
+    Detail,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            sidebar_width: default_sidebar_width(),
+            sidebar_collapsed: false,
+            row_numbers: false,
+            table_columns: false,
+            zoomed: None,
+        }
+    }
+}
+
+fn default_sidebar_width() -> u16 {
+    30
+}
+
+impl LayoutConfig {
+    /// Horizontal constraint of the sidebar panes, which disappear when it is collapsed or the
+    /// data pane is zoomed, and take the whole width when the table list is zoomed.
+    pub fn sidebar(&self) -> Constraint {
+        match self.zoomed {
+            Some(ZoomedPane::Tables) => Constraint::Percentage(100),
+            Some(ZoomedPane::Data) => Constraint::Length(0),
+            _ if self.sidebar_collapsed => Constraint::Length(0),
+            _ => Constraint::Percentage(self.sidebar_width),
+        }
+    }
+
+    /// Horizontal constraint of the status line's left cell, which stays visible when the sidebar
+    /// is collapsed so the loading indicator is not hidden.
+    pub fn status(&self) -> Constraint {
+        Constraint::Percentage(self.sidebar_width)
+    }
+
+    pub fn grow(&mut self) {
+        self.sidebar_width = (self.sidebar_width + SIDEBAR_WIDTH_STEP).min(MAX_SIDEBAR_WIDTH);
+    }
+
+    pub fn shrink(&mut self) {
+        self.sidebar_width = self
+            .sidebar_width
+            .saturating_sub(SIDEBAR_WIDTH_STEP)
+            .max(MIN_SIDEBAR_WIDTH);
+    }
+
+    /// Writes the layout to the config directory so it is restored on the next start.
+    pub fn save(&self, config_dir: &Path) -> Result<()> {
+        fs::create_dir_all(config_dir)?;
+        fs::write(
+            config_dir.join(LAYOUT_FILE),
+            serde_json::to_string_pretty(&json!({ "layout": self }))?,
+        )?;
+        Ok(())
+    }
+}
+
+/// Writes the pinned fields to the config directory so they are restored on the next start.
+pub fn save_pinned_fields(config_dir: &Path, pinned_fields: &[PinnedFields]) -> Result<()> {
+    fs::create_dir_all(config_dir)?;
+    fs::write(
+        config_dir.join(PINNED_FIELDS_FILE),
+        serde_json::to_string_pretty(&json!({ "pinned_fields": pinned_fields }))?,
+    )?;
+    Ok(())
 }
 
 #[derive(Clone, Debug, Default, Deserialize)]
@@ -74,8 +428,16 @@ impl Config {
         if !found_config {
             error!("No configuration file found. Application may not behave as expected");
         }
+        for file in [LAYOUT_FILE, PINNED_FIELDS_FILE] {
+            builder = builder.add_source(
+                config::File::from(config_dir.join(file))
+                    .format(config::FileFormat::Json)
+                    .required(false),
+            );
+        }
 
         let mut cfg: Self = builder.build()?.try_deserialize()?;
+        cfg.config.theme = cfg.config.theme_config.resolve();
 
         for (mode, default_bindings) in default_config.keybindings.iter() {
             let user_bindings = cfg.keybindings.entry(*mode).or_default();
@@ -291,6 +653,13 @@ pub fn key_event_to_string(key_event: &KeyEvent) -> String {
     key
 }
 
+/// Whether `keys` begin a binding of `keymap` that needs more keys.
+pub fn is_chord_prefix(keymap: &HashMap<Vec<KeyEvent>, Action>, keys: &[KeyEvent]) -> bool {
+    keymap
+        .keys()
+        .any(|binding| binding.len() > keys.len() && binding.starts_with(keys))
+}
+
 pub fn parse_key_sequence(raw: &str) -> Result<Vec<KeyEvent>, String> {
     if raw.chars().filter(|c| *c == '>').count() != raw.chars().filter(|c| *c == '<').count() {
         return Err(format!("Unable to parse `{}`", raw));
@@ -494,6 +863,24 @@ mod tests {
         assert_eq!(color, Some(Color::Indexed(expected)));
     }
 
+    #[test]
+    fn test_is_chord_prefix() {
+        let keymap = HashMap::from([(parse_key_sequence("<g><g>").unwrap(), Action::Quit)]);
+
+        assert!(is_chord_prefix(
+            &keymap,
+            &parse_key_sequence("<g>").unwrap()
+        ));
+        assert!(!is_chord_prefix(
+            &keymap,
+            &parse_key_sequence("<g><g>").unwrap()
+        ));
+        assert!(!is_chord_prefix(
+            &keymap,
+            &parse_key_sequence("<x>").unwrap()
+        ));
+    }
+
     #[test]
     fn test_parse_color_unknown() {
         let color = parse_color("unknown");
@@ -514,6 +901,24 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_default_max_items() -> Result<()> {
+        let c = Config::new()?;
+        assert_eq!(c.config.max_items, 10_000);
+        assert_eq!(c.config.deep_find_matches, 1);
+        assert!(c.config.production_account_ids.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_default_scroll() -> Result<()> {
+        let c = Config::new()?;
+        assert_eq!(c.config.scroll, ScrollConfig::default());
+        assert_eq!(c.config.scroll.list_lines, 5);
+        assert_eq!(c.config.scroll.detail_lines, 10);
+        Ok(())
+    }
+
     #[test]
     fn test_simple_keys() {
         assert_eq!(
@@ -595,4 +1000,31 @@ mod tests {
             KeyEvent::new(KeyCode::Enter, KeyModifiers::ALT)
         );
     }
+
+    #[test]
+    fn test_sidebar_width_bounds() {
+        let mut layout = LayoutConfig::default();
+        (0..20).for_each(|_| layout.grow());
+        assert_eq!(layout.sidebar_width, MAX_SIDEBAR_WIDTH);
+        (0..20).for_each(|_| layout.shrink());
+        assert_eq!(layout.sidebar_width, MIN_SIDEBAR_WIDTH);
+    }
+
+    #[test]
+    fn test_zoomed_sidebar() {
+        let mut layout = LayoutConfig {
+            sidebar_collapsed: true,
+            ..LayoutConfig::default()
+        };
+        layout.zoomed = Some(ZoomedPane::Tables);
+        assert_eq!(layout.sidebar(), Constraint::Percentage(100));
+        layout.zoomed = Some(ZoomedPane::Detail);
+        assert_eq!(layout.sidebar(), Constraint::Length(0));
+
+        layout.sidebar_collapsed = false;
+        layout.zoomed = Some(ZoomedPane::Data);
+        assert_eq!(layout.sidebar(), Constraint::Length(0));
+        layout.zoomed = None;
+        assert_eq!(layout.sidebar(), Constraint::Percentage(30));
+    }
 }
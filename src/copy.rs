@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use aws_sdk_dynamodb::types::AttributeValue;
+use serde::{Deserialize, Serialize};
+
+/// Where the copy of a table is written. Unset fields fall back to what the app itself uses, so
+/// only the table name is needed to copy within the same account and region.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CopyTarget {
+    pub table: String,
+    pub region: Option<String>,
+    /// Endpoint to send the writes to instead of AWS, such as `http://localhost:8000` for
+    /// DynamoDB Local.
+    pub endpoint_url: Option<String>,
+    pub profile: Option<String>,
+    /// Attributes to rename on the way, as `(from, to)` pairs, for a destination whose keys are
+    /// named differently.
+    pub renames: Vec<(String, String)>,
+}
+
+impl CopyTarget {
+    /// Whether writing to the target would write the source table back onto itself.
+    pub fn is_source(&self, source: &str) -> bool {
+        self.table == source
+            && self.region.is_none()
+            && self.endpoint_url.is_none()
+            && self.profile.is_none()
+    }
+}
+
+/// Parses renames written as `from=to`, separated by commas.
+pub fn parse_renames(text: &str) -> Result<Vec<(String, String)>, String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|rename| !rename.is_empty())
+        .map(|rename| match rename.split_once('=') {
+            Some((from, to)) if !from.trim().is_empty() && !to.trim().is_empty() => {
+                Ok((from.trim().to_string(), to.trim().to_string()))
+            }
+            _ => Err(format!("`{rename}` is not of the form from=to")),
+        })
+        .collect()
+}
+
+pub fn rename_attributes(item: &mut HashMap<String, AttributeValue>, renames: &[(String, String)]) {
+    for (from, to) in renames {
+        if let Some(value) = item.remove(from) {
+            item.insert(to.clone(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_renames() {
+        assert_eq!(
+            parse_renames("pk=PK, sk = SK,").unwrap(),
+            vec![
+                ("pk".to_string(), "PK".to_string()),
+                ("sk".to_string(), "SK".to_string()),
+            ]
+        );
+        assert_eq!(parse_renames("").unwrap(), vec![]);
+        assert!(parse_renames("pk").is_err());
+        assert!(parse_renames("pk=").is_err());
+    }
+
+    #[test]
+    fn test_rename_attributes() {
+        let mut item = HashMap::from([
+            ("pk".to_string(), AttributeValue::S("1".to_string())),
+            ("name".to_string(), AttributeValue::S("one".to_string())),
+        ]);
+        rename_attributes(&mut item, &[("pk".to_string(), "id".to_string())]);
+
+        assert_eq!(
+            item,
+            HashMap::from([
+                ("id".to_string(), AttributeValue::S("1".to_string())),
+                ("name".to_string(), AttributeValue::S("one".to_string())),
+            ])
+        );
+    }
+}
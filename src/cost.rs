@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+
+/// On-demand prices in us-east-1, in dollars per million request units. Other regions charge
+/// somewhat more, so estimates are a lower bound there.
+const ON_DEMAND_READ_PRICE: f64 = 0.125;
+const ON_DEMAND_WRITE_PRICE: f64 = 0.625;
+
+/// A scan reads 4 KB per read unit, at half a unit when eventually consistent.
+const READ_UNIT_BYTES: f64 = 4096.0;
+const WRITE_UNIT_BYTES: f64 = 1024.0;
+
+/// What DescribeTable reports about the size of a table. DynamoDB refreshes the counts about
+/// every six hours, so they may lag behind recent writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableUsage {
+    pub item_count: u64,
+    pub size_bytes: u64,
+    pub billing: Billing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Billing {
+    OnDemand,
+    /// Read and write capacity units per second.
+    Provisioned(u64, u64),
+}
+
+/// The capacity an operation is expected to consume, in whole request units.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CostEstimate {
+    pub items: u64,
+    pub read_units: u64,
+    pub write_units: u64,
+}
+
+impl TableUsage {
+    fn average_item_bytes(&self) -> f64 {
+        if self.item_count == 0 {
+            return 0.0;
+        }
+        self.size_bytes as f64 / self.item_count as f64
+    }
+}
+
+impl CostEstimate {
+    /// Scanning the whole table, and writing every item elsewhere when `writes` is set.
+    pub fn scan(usage: &TableUsage, writes: bool) -> Self {
+        let write_units = if writes {
+            usage.item_count * write_units_per_item(usage.average_item_bytes())
+        } else {
+            0
+        };
+
+        Self {
+            items: usage.item_count,
+            read_units: (usage.size_bytes as f64 / READ_UNIT_BYTES / 2.0).ceil() as u64,
+            write_units,
+        }
+    }
+
+    /// Scanning the table until `max_items` items have been read, or all of them if it holds
+    /// fewer.
+    pub fn scan_up_to(usage: &TableUsage, max_items: u64) -> Self {
+        let whole = Self::scan(usage, false);
+        if usage.item_count <= max_items {
+            return whole;
+        }
+        let share = max_items as f64 / usage.item_count as f64;
+        Self {
+            items: max_items,
+            read_units: (whole.read_units as f64 * share).ceil() as u64,
+            write_units: 0,
+        }
+    }
+
+    /// Writing `items` new items of about `item_bytes` each.
+    pub fn writes(items: u64, item_bytes: usize) -> Self {
+        Self {
+            items,
+            read_units: 0,
+            write_units: items * write_units_per_item(item_bytes as f64),
+        }
+    }
+
+    /// The price of the units, had they been consumed by an on-demand table.
+    pub fn on_demand_dollars(&self) -> f64 {
+        (self.read_units as f64 * ON_DEMAND_READ_PRICE
+            + self.write_units as f64 * ON_DEMAND_WRITE_PRICE)
+            / 1_000_000.0
+    }
+
+    /// How long a provisioned table takes to serve the units when nothing else uses its
+    /// capacity, in seconds.
+    pub fn provisioned_seconds(&self, read_capacity: u64, write_capacity: u64) -> f64 {
+        let reads = self.read_units as f64 / read_capacity.max(1) as f64;
+        let writes = self.write_units as f64 / write_capacity.max(1) as f64;
+        reads.max(writes)
+    }
+}
+
+fn write_units_per_item(item_bytes: f64) -> u64 {
+    ((item_bytes / WRITE_UNIT_BYTES).ceil() as u64).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_estimate_scan_and_copy() {
+        let usage = TableUsage {
+            item_count: 1_000,
+            size_bytes: 1_000 * 1_500,
+            billing: Billing::OnDemand,
+        };
+
+        let estimate = CostEstimate::scan(&usage, true);
+        assert_eq!(estimate.read_units, 184);
+        assert_eq!(estimate.write_units, 2_000);
+        assert!((estimate.on_demand_dollars() - 0.001273).abs() < 0.000001);
+        assert_eq!(estimate.provisioned_seconds(10, 100), 20.0);
+    }
+
+    #[test]
+    fn test_estimate_scan_up_to() {
+        let usage = TableUsage {
+            item_count: 1_000,
+            size_bytes: 1_000 * 1_500,
+            billing: Billing::OnDemand,
+        };
+
+        let estimate = CostEstimate::scan_up_to(&usage, 250);
+        assert_eq!(estimate.items, 250);
+        assert_eq!(estimate.read_units, 46);
+        assert_eq!(estimate.write_units, 0);
+        assert_eq!(
+            CostEstimate::scan_up_to(&usage, 5_000),
+            CostEstimate::scan(&usage, false)
+        );
+    }
+
+    #[test]
+    fn test_estimate_writes() {
+        assert_eq!(CostEstimate::writes(10, 0).write_units, 10);
+        assert_eq!(CostEstimate::writes(10, 2_049).write_units, 30);
+    }
+}
@@ -0,0 +1,162 @@
+use std::{
+    env,
+    error::Error as StdError,
+    fs,
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use aws_sdk_dynamodb::{
+    error::ProvideErrorMetadata,
+    primitives::{DateTime, DateTimeFormat},
+    Error,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    sso::{sso_cache_dir, sso_profile, SsoProfile},
+    workspace,
+};
+
+/// Error codes DynamoDB returns when the request was signed with credentials that are no longer
+/// (or never were) valid.
+const CREDENTIAL_ERROR_CODES: [&str; 5] = [
+    "ExpiredTokenException",
+    "ExpiredToken",
+    "UnrecognizedClientException",
+    "InvalidClientTokenId",
+    "InvalidSignatureException",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CredentialsFailure {
+    pub profile: String,
+    pub reason: String,
+    pub sso_session_expiry: Option<String>,
+    pub sso_login_available: bool,
+}
+
+/// The profile clients are built from: the one picked or named by the active workspace, or else
+/// the one the environment points at.
+pub fn current_profile() -> String {
+    workspace::profile()
+        .or_else(|| env::var("AWS_PROFILE").ok())
+        .unwrap_or_else(|| "default".to_string())
+}
+
+/// Inspects an SDK error and, if it was caused by missing or expired credentials, describes the
+/// failure so it can be shown to the user.
+pub fn credentials_failure(err: &Error) -> Option<CredentialsFailure> {
+    let reason = if let Some(code) = err.code().filter(|c| CREDENTIAL_ERROR_CODES.contains(c)) {
+        match err.message() {
+            Some(message) => format!("{code}: {message}"),
+            None => code.to_string(),
+        }
+    } else {
+        let mut source: Option<&(dyn StdError + 'static)> = err.source();
+        let mut reason = None;
+
+        while let Some(current) = source {
+            let text = current.to_string();
+            let lower = text.to_lowercase();
+            if lower.contains("credential") || lower.contains("token") || lower.contains("sso") {
+                reason = Some(text);
+            }
+            source = current.source();
+        }
+
+        reason?
+    };
+
+    let profile = current_profile();
+    let sso = sso_profile(&profile);
+
+    Some(CredentialsFailure {
+        profile,
+        reason,
+        sso_session_expiry: sso
+            .as_ref()
+            .and_then(|sso| sso_session_expiry(&sso_cache_dir()?, sso)),
+        sso_login_available: sso.is_some(),
+    })
+}
+
+/// When the credentials of the current profile stop working. The role credentials of an SSO
+/// profile are renewed until its SSO session ends, so that is when they stop; other temporary
+/// credentials stop at their `expiry`.
+pub fn session_expiry(expiry: Option<SystemTime>) -> Option<SystemTime> {
+    let Some(sso) = sso_profile(&current_profile()) else {
+        return expiry;
+    };
+    sso_cache_dir()
+        .and_then(|cache_dir| sso_session_expiry(&cache_dir, &sso))
+        .and_then(|expires_at| DateTime::from_str(&expires_at, DateTimeFormat::DateTime).ok())
+        .and_then(|expires_at| SystemTime::try_from(expires_at).ok())
+        .or(expiry)
+}
+
+/// How long is left, to the minute once there is more than a minute left.
+pub fn remaining(duration: Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, 0) => format!("{}s", duration.as_secs()),
+        (0, minutes) => format!("{minutes}m"),
+        (hours, minutes) => format!("{hours}h {minutes:02}m"),
+    }
+}
+
+/// Returns the `expiresAt` timestamp of the token the AWS CLI cached for the SSO session of the
+/// profile. Other sessions have token files of their own in the same folder.
+fn sso_session_expiry(cache_dir: &Path, sso: &SsoProfile) -> Option<String> {
+    let contents = fs::read_to_string(cache_dir.join(format!("{}.json", sso.cache_key()))).ok()?;
+    let token = serde_json::from_str::<Value>(&contents).ok()?;
+    token.get("accessToken")?;
+    token.get("expiresAt")?.as_str().map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_remaining() {
+        assert_eq!(remaining(Duration::from_secs(42)), "42s");
+        assert_eq!(remaining(Duration::from_secs(25 * 60 + 10)), "25m");
+        assert_eq!(remaining(Duration::from_secs(3 * 3600 + 5 * 60)), "3h 05m");
+    }
+
+    #[test]
+    fn test_sso_session_expiry() {
+        let sso = |session: &str| SsoProfile {
+            session_name: Some(session.to_string()),
+            start_url: "https://example.awsapps.com/start".to_string(),
+            region: "us-east-1".to_string(),
+        };
+        let (work, personal) = (sso("work"), sso("personal"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let token = |expires_at: &str| {
+            format!(r#"{{"accessToken": "token", "expiresAt": "{expires_at}"}}"#)
+        };
+        fs::write(
+            dir.path().join(format!("{}.json", work.cache_key())),
+            token("2026-01-01T08:00:00Z"),
+        )
+        .unwrap();
+        // The other session expires later, which must not hide that this one has.
+        fs::write(
+            dir.path().join(format!("{}.json", personal.cache_key())),
+            token("2026-01-01T20:00:00Z"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            sso_session_expiry(dir.path(), &work),
+            Some("2026-01-01T08:00:00Z".to_string())
+        );
+        assert_eq!(sso_session_expiry(dir.path(), &sso("other")), None);
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::BTreeSet;
+
+use serde_json::{Map, Value};
+
+/// Whether an export destination asks for CSV rather than JSON.
+pub fn is_csv(destination: &str) -> bool {
+    destination.trim().to_ascii_lowercase().ends_with(".csv")
+}
+
+/// Items as CSV: a header row naming every attribute any of the items holds, then a line per
+/// item. Nested maps are flattened into a column per attribute, named by its dotted path, while
+/// lists are written as JSON. The attributes named in `first` lead, the others follow sorted by
+/// name.
+pub fn to_csv(items: &[Value], first: &[String]) -> String {
+    let rows: Vec<Map<String, Value>> = items
+        .iter()
+        .map(|item| {
+            let mut row = Map::new();
+            flatten(item, "", &mut row);
+            row
+        })
+        .collect();
+
+    let names: BTreeSet<&String> = rows.iter().flat_map(|row| row.keys()).collect();
+    let columns: Vec<&String> = first
+        .iter()
+        .filter(|name| names.contains(name))
+        .chain(names.iter().copied().filter(|name| !first.contains(name)))
+        .collect();
+
+    let mut csv = line(columns.iter().map(|name| name.to_string()));
+    for row in &rows {
+        csv.push_str(&line(
+            columns
+                .iter()
+                .map(|name| row.get(*name).map(cell).unwrap_or_default()),
+        ));
+    }
+    csv
+}
+
+/// Moves the attributes of `value` into `row`, named by their path under `prefix`.
+fn flatten(value: &Value, prefix: &str, row: &mut Map<String, Value>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (name, value) in map {
+                let path = if prefix.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{prefix}.{name}")
+                };
+                flatten(value, &path, row);
+            }
+        }
+        value => {
+            row.insert(prefix.to_string(), value.clone());
+        }
+    }
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// A row of fields, quoted where they hold a separator, a quote or a line break.
+fn line(fields: impl Iterator<Item = String>) -> String {
+    let fields: Vec<String> = fields
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field
+            }
+        })
+        .collect();
+    format!("{}\n", fields.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_to_csv() {
+        let items = [
+            json!({ "pk": "USER#1", "name": "Ada, Countess", "address": { "city": "London" } }),
+            json!({ "pk": "USER#2", "tags": ["a", "b"], "address": { "zip": 10115 }, "note": null }),
+        ];
+
+        assert_eq!(
+            to_csv(&items, &["pk".to_string()]),
+            "pk,address.city,address.zip,name,note,tags\n\
+             USER#1,London,,\"Ada, Countess\",,\n\
+             USER#2,,10115,,,\"[\"\"a\"\",\"\"b\"\"]\"\n"
+        );
+        assert!(is_csv("out/Users.CSV"));
+        assert!(!is_csv("users.json"));
+    }
+}
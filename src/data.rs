@@ -1,89 +1,591 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime},
+};
 
-use aws_config::{meta::region::RegionProviderChain, BehaviorVersion};
+use aws_config::{
+    meta::region::RegionProviderChain, BehaviorVersion, ConfigLoader, Region, SdkConfig,
+};
+use aws_sdk_cloudtrail::types::{LookupAttribute, LookupAttributeKey};
 use aws_sdk_dynamodb::{
-    types::{AttributeValue, KeyType},
+    config::ProvideCredentials,
+    error::ProvideErrorMetadata,
+    primitives::{Blob, DateTimeFormat},
+    types::{
+        AttributeValue, BillingMode, KeySchemaElement, KeyType, KeysAndAttributes, Projection,
+        PutRequest, ReturnConsumedCapacity, ReturnValue, ScalarAttributeType, Select,
+        TableDescription, WriteRequest,
+    },
     Client, Error,
 };
+use base64::{prelude::BASE64_STANDARD, Engine};
+use futures::{stream, Stream};
+use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
+use tokio::time::sleep;
+
+use crate::{
+    access::AccessDenied,
+    api_log::{ApiCall, ApiLog},
+    cloudtrail::{table_event, TableEvent, MAX_LOOKUP_PAGES, MAX_TABLE_EVENTS},
+    copy::{rename_attributes, CopyTarget},
+    cost::{Billing, TableUsage},
+    credentials::{session_expiry, CredentialsFailure},
+    failure_report::FailedItem,
+    import::{merge_expression, ImportMode, ImportPreview, ImportStatus},
+    job::JobState,
+    latency::{RegionLatency, LATENCY_SAMPLES},
+    permission::{Permission, Permissions, TableOperation, PROBE_KEY_VALUE},
+    proxy,
+    replica::RegionItem,
+    soft_delete::SoftDeleteFilter,
+    table_info::{IndexInfo, TableInfo, TimeToLive},
+    table_search::RegionTables,
+    time_range::{after_bound, split_range},
+    util::{dynamodb_to_json, json_to_dynamodb},
+    workspace::{self, Workspace},
+};
+
+/// The scalar types DynamoDB allows for key attributes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyAttributeType {
+    S,
+    N,
+    B,
+}
+
+impl KeyAttributeType {
+    /// Checks that `value` can be sent as an attribute of this type.
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        match self {
+            KeyAttributeType::S => Ok(()),
+            KeyAttributeType::N if is_number(value.trim()) => Ok(()),
+            KeyAttributeType::N => Err(format!("`{value}` is not a number")),
+            KeyAttributeType::B => BASE64_STANDARD
+                .decode(value.trim())
+                .map(|_| ())
+                .map_err(|_| format!("`{value}` is not valid base64")),
+        }
+    }
+
+    /// Builds the `AttributeValue` for `value`, which is expected to have passed `validate`.
+    pub fn attribute_value(&self, value: &str) -> AttributeValue {
+        match self {
+            KeyAttributeType::S => AttributeValue::S(value.to_string()),
+            KeyAttributeType::N => AttributeValue::N(value.trim().to_string()),
+            KeyAttributeType::B => AttributeValue::B(Blob::new(
+                BASE64_STANDARD.decode(value.trim()).unwrap_or_default(),
+            )),
+        }
+    }
+}
+
+/// Whether `text` is a number as DynamoDB reads one: an optional sign, digits with an optional
+/// fraction, then an optional exponent. Unlike for `f64`, `inf` and `NaN` are not numbers.
+pub fn is_number(text: &str) -> bool {
+    fn unsigned(text: &str) -> &str {
+        text.strip_prefix(['+', '-']).unwrap_or(text)
+    }
+    let is_digits = |text: &str| !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit());
+
+    let (mantissa, exponent) = match text.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (text, None),
+    };
+    let (whole, fraction) = match unsigned(mantissa).split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (unsigned(mantissa), None),
+    };
+    is_digits(whole)
+        && fraction.is_none_or(is_digits)
+        && exponent.is_none_or(|exponent| is_digits(unsigned(exponent)))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyAttribute {
+    pub name: String,
+    pub attribute_type: KeyAttributeType,
+}
+
+/// The attributes of an item read as JSON, with its key attributes of the types the table
+/// declares: JSON loses the difference between a binary value and its base64 text.
+pub fn key_typed_item(
+    item: Map<String, Value>,
+    key_attributes: &[KeyAttribute],
+) -> HashMap<String, AttributeValue> {
+    item.into_iter()
+        .map(|(name, value)| {
+            let key_attribute = key_attributes.iter().find(|key| key.name == name);
+            let value = match (key_attribute, &value) {
+                (Some(key), Value::String(text)) => key.attribute_type.attribute_value(text),
+                (Some(key), Value::Number(number)) => {
+                    key.attribute_type.attribute_value(&number.to_string())
+                }
+                _ => json_to_dynamodb(value),
+            };
+            (name, value)
+        })
+        .collect()
+}
+
+/// A key condition on the partition key, and optionally the sort key, of a table.
+#[derive(Debug, Clone)]
+pub struct KeyQuery {
+    pub partition_key: KeyAttribute,
+    pub partition_key_value: String,
+    pub sort_key: Option<(KeyAttribute, String)>,
+    pub limit: Option<i32>,
+}
 
-use crate::util::dynamodb_to_json;
+/// Items a scan reads per page unless configured otherwise.
+pub const DEFAULT_PAGE_SIZE: i32 = 100;
 
 #[derive(Debug)]
 pub enum FetchRequest {
     Tables,
-    TableData(String),
-    NextBatchTableData(String, Option<HashMap<String, AttributeValue>>),
+    /// Scans the first page of the table, of up to the given number of items.
+    TableData(String, i32),
+    NextBatchTableData(String, Option<HashMap<String, AttributeValue>>, i32),
     GetApproximateItemCount(String),
     DescribeTable(String),
-    QueryTableByPk(String, String, String),
-    QueryTableByPkSk(String, String, String, String, String),
+    QueryTableData(String, KeyQuery),
+    NextBatchQueryTableData(String, KeyQuery, Option<HashMap<String, AttributeValue>>),
+    /// Queries the items written since the last time a followed query was run.
+    FollowQuery(String, KeyQuery),
+    ExplorePartition(String, KeyAttribute, String, Option<KeyAttribute>),
+    StopPartitionExplorer,
+    /// Writes an item, refused if an item with the same value of the named partition key exists.
+    PutItem(String, HashMap<String, AttributeValue>, Option<String>),
+    SsoLogin(String),
+    CallerIdentity,
+    CancelJob(RequestId),
+    ReplicaRegions(String),
+    /// Describes the table for the table info popup.
+    TableInfo(String),
+    /// Reads the item with the given key, strongly consistent when the flag is set.
+    GetItem(String, HashMap<String, AttributeValue>, bool),
+    /// Reads the item with the given key with the types of its attributes, for the item editor to
+    /// write it back with them.
+    ItemTypes(String, HashMap<String, AttributeValue>),
+    /// Tries each operation on the table to tell which ones the credentials allow.
+    ProbePermissions(String),
+    /// Reads the item with the given key from each of the regions.
+    CompareRegions(String, HashMap<String, AttributeValue>, Vec<String>),
+    /// Times calls to the table from the current region, and from its replicas if `true`.
+    ProbeLatency(String, bool),
+    CopyTable(String, CopyTarget),
+    /// Writes the given number of items made from a template, as JSON.
+    SeedItems(String, Value, usize),
+    /// Reads the items of a file and compares them with the items of the table with their keys.
+    PreviewImport(String, PathBuf),
+    /// Scans the table for items holding the text, stopping after the given number of matches
+    /// unless it is zero.
+    DeepFind(String, String, usize),
+    /// Scans the whole table in pages of up to the given number of items, appending each item to
+    /// the file as a line of JSON.
+    DumpTable(String, PathBuf, i32),
+    /// Scans the table in the given number of segments at once, in pages of up to the given
+    /// number of items, until it has been read or the given number of items has.
+    ParallelScan(String, i32, usize, i32),
+    /// Writes the items of an import file, as JSON.
+    ImportItems(String, Vec<Value>, ImportMode),
+    TableUsage(String),
+    /// Lists the tables in each of the regions at the same time.
+    SearchTables(Vec<String>),
+    /// Builds every client from now on for the workspace, or for the environment if `None`.
+    SwitchWorkspace(Option<Workspace>),
+    /// Builds every client from now on for the region, keeping the workspace's profile.
+    SwitchRegion(String),
+    /// Builds every client from now on with the profile's credentials.
+    SwitchProfile(String),
+    /// Looks up the writes to the table in the CloudTrail event history.
+    TableEvents(String),
+    /// Resolves the credentials again, renewing them if they can be, to tell when they expire.
+    RefreshCredentials,
+    /// Scans and queries the table from now on for the items of the view, or for every item if
+    /// `None`.
+    FilterSoftDeleted(String, Option<SoftDeleteFilter>),
 }
 
+impl FetchRequest {
+    /// What the jobs popup calls the request, for the requests that run as jobs.
+    pub fn job_label(&self) -> Option<String> {
+        match self {
+            FetchRequest::ExplorePartition(table, _, value, _) => {
+                Some(format!("Explore partition {value} of {table}"))
+            }
+            FetchRequest::SsoLogin(profile) => Some(format!("SSO login for {profile}")),
+            FetchRequest::CopyTable(source, target) => {
+                Some(format!("Copy {source} to {}", target.table))
+            }
+            FetchRequest::SeedItems(table, _, count) => {
+                Some(format!("Seed {count} items into {table}"))
+            }
+            FetchRequest::DeepFind(table, needle, _) => {
+                Some(format!("Find \"{needle}\" in {table}"))
+            }
+            FetchRequest::ImportItems(table, items, _) => {
+                Some(format!("Import {} items into {table}", items.len()))
+            }
+            FetchRequest::DumpTable(table, path, _) => {
+                Some(format!("Dump {table} to {}", path.display()))
+            }
+            FetchRequest::ParallelScan(table, segments, ..) => {
+                Some(format!("Scan {table} in {segments} segments"))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the request writes items as it goes, rather than all at once or not at all.
+    pub fn writes_items(&self) -> bool {
+        matches!(
+            self,
+            FetchRequest::CopyTable(..)
+                | FetchRequest::SeedItems(..)
+                | FetchRequest::ImportItems(..)
+        )
+    }
+}
+
+/// Identifies a request sent to the fetch worker, so the UI can tell when it has finished.
+pub type RequestId = u64;
+
 #[derive(Debug)]
 pub enum FetchResponse {
-    Tables(Vec<String>),
-    TableData(Vec<String>, bool, Option<HashMap<String, AttributeValue>>),
-    NextBatchTableData(Vec<String>, bool, Option<HashMap<String, AttributeValue>>),
-    ApproximateTableDataCount(i64),
-    TableDescription((Option<String>, Option<String>)),
+    /// `None` when the tables could not be listed. The same holds for the item count and the
+    /// description below.
+    Tables(Option<Vec<String>>),
+    TableData(
+        String,
+        Vec<String>,
+        bool,
+        Option<HashMap<String, AttributeValue>>,
+    ),
+    NextBatchTableData(
+        String,
+        Vec<String>,
+        bool,
+        Option<HashMap<String, AttributeValue>>,
+    ),
+    ApproximateTableDataCount(String, Option<i64>),
+    TableDescription(String, Option<(Option<KeyAttribute>, Option<KeyAttribute>)>),
+    CredentialsFailure(CredentialsFailure),
+    AccessDenied(AccessDenied),
+    SsoDeviceAuthorization(String, String),
+    SsoLoginComplete,
+    SsoLoginFailed(String),
+    CallerIdentity(String, String),
+    ApiCall(ApiCall),
+    PartitionStats(PartitionStats),
+    /// `None` when the table could not be described, as for the usage below.
+    ReplicaRegions(String, Option<Vec<String>>),
+    TableSearch(Vec<RegionTables>),
+    /// The items a followed query found, empty if it failed.
+    FollowedTableData(String, Vec<String>),
+    WorkspaceSwitched(Option<Workspace>),
+    RegionSwitched(String),
+    ProfileSwitched(String),
+    TableUsage(String, Option<TableUsage>),
+    RegionItems(String, Vec<RegionItem>),
+    /// The round trips to each region probed, or the reason the table could not be reached.
+    Latencies(String, Result<Vec<RegionLatency>, String>),
+    Permissions(Permissions),
+    /// The description of a table, or the reason it could not be read.
+    TableInfo(String, Result<TableInfo, String>),
+    /// The key an item was read with, and the item, `None` if there is none, or the reason it
+    /// could not be read.
+    Item(
+        String,
+        HashMap<String, AttributeValue>,
+        Result<Option<String>, String>,
+    ),
+    /// The item read for the item editor in the DynamoDB JSON format, an empty map if there is
+    /// none, or the reason it could not be read.
+    ItemTypes(String, Result<Value, String>),
+    DeepFindResult(String, DeepFindResult),
+    /// The table a query was sent to and the reason it failed.
+    QueryFailed(String, String),
+    /// The table an item was written to, with the reason it was not if the write failed.
+    ItemPut(String, Option<String>),
+    /// What importing a file would do, with the items of the file and what writing each would
+    /// do, or the reason the file could not be compared.
+    ImportPreview(
+        String,
+        Result<(ImportPreview, Vec<Value>, Vec<ImportStatus>), String>,
+    ),
+    /// How many items a job has processed, and how many there are if that is known.
+    JobProgress(RequestId, usize, Option<usize>),
+    /// How many items a job reading page by page has processed, and how many pages it fetched.
+    JobPageProgress(RequestId, usize, usize),
+    JobFinished(RequestId, JobState),
+    /// The writes CloudTrail recorded to a table, or the reason they could not be looked up.
+    TableEvents(String, Result<Vec<TableEvent>, String>),
+    /// When the credentials stop working, `None` if they do not expire.
+    CredentialsExpiry(Option<SystemTime>),
+    /// A failure that belongs to no response of its own, shown in the error banner.
+    Error(String),
+    /// Sent once the worker is done with a request, after any response it produced.
+    RequestFinished(RequestId),
+}
+
+/// The items a scan for a text found, and how far it got.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeepFindResult {
+    pub matches: Vec<String>,
+    pub scanned: usize,
+    /// Whether every item was scanned, rather than stopping at the last match.
+    pub complete: bool,
+    pub error: Option<String>,
+}
+
+/// Characters that end the prefix of a sort key, as in `ORDER#2024-01-01`.
+const SORT_KEY_SEPARATORS: [char; 2] = ['#', '|'];
+
+/// How the items of a single partition are spread over its sort keys.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartitionStats {
+    pub table: String,
+    pub partition_key_value: String,
+    pub items: usize,
+    pub pages: usize,
+    pub min_sort_key: Option<String>,
+    pub max_sort_key: Option<String>,
+    /// Number of items per sort key prefix. Keys without a separator are counted under "".
+    pub prefixes: BTreeMap<String, usize>,
+    pub complete: bool,
+    pub error: Option<String>,
+}
+
+impl PartitionStats {
+    /// Counts an item. Queries return items in sort key order, so the first key seen is the
+    /// smallest and the last one the largest.
+    fn record(&mut self, sort_key: Option<String>) {
+        self.items += 1;
+
+        if let Some(sort_key) = sort_key {
+            let prefix = sort_key
+                .split_once(SORT_KEY_SEPARATORS)
+                .map(|(prefix, _)| prefix)
+                .unwrap_or_default();
+            *self.prefixes.entry(prefix.to_string()).or_default() += 1;
+
+            if self.min_sort_key.is_none() {
+                self.min_sort_key = Some(sort_key.clone());
+            }
+            self.max_sort_key = Some(sort_key);
+        }
+    }
+}
+
+/// The SDK defaults, pointed at the picked profile or the one the active workspace names, if any.
+fn workspace_defaults() -> ConfigLoader {
+    let loader = aws_config::defaults(BehaviorVersion::v2024_03_28());
+    match workspace::profile() {
+        Some(profile) => loader.profile_name(profile),
+        None => loader,
+    }
+}
+
+/// The region of the active workspace, falling back to the one the environment points at.
+fn workspace_region() -> RegionProviderChain {
+    let region = workspace::region()
+        .or_else(|| workspace::active().and_then(|workspace| workspace.region))
+        .map(Region::new);
+    RegionProviderChain::first_try(region)
+        .or_default_provider()
+        .or_else("us-east-1")
+}
+
+async fn load_sdk_config() -> SdkConfig {
+    proxy::apply(workspace_defaults().region(workspace_region()))
+        .load()
+        .await
 }
 
 pub async fn get_client() -> Client {
-    let region = "us-east-1";
-    let region_provider = RegionProviderChain::default_provider().or_else(region);
-    let config = aws_config::defaults(BehaviorVersion::v2024_03_28())
-        .region(region_provider)
+    Client::new(&load_sdk_config().await)
+}
+
+/// Writes `data` to the object `key` of `bucket`, with the credentials and region the tables are
+/// read with.
+pub async fn put_s3_object(bucket: &str, key: &str, data: Vec<u8>) -> Result<(), String> {
+    aws_sdk_s3::Client::new(&load_sdk_config().await)
+        .put_object()
+        .bucket(bucket)
+        .key(key)
+        .body(aws_sdk_s3::primitives::ByteStream::from(data))
+        .send()
+        .await
+        .map(|_| ())
+        .map_err(|err| aws_sdk_s3::error::DisplayErrorContext(err).to_string())
+}
+
+/// A client for `region` rather than the one the profile points at, to read the replicas of a
+/// global table or to look for tables across regions.
+async fn get_regional_client(region: &str) -> Client {
+    let config = proxy::apply(workspace_defaults().region(Region::new(region.to_string())))
         .load()
         .await;
-
     Client::new(&config)
 }
 
-pub async fn load_collections() -> Vec<String> {
-    let client = get_client().await;
+/// Resolves the credentials clients are built with, which renews the temporary credentials the
+/// provider can renew, and returns when they stop working, or `None` if they do not expire.
+pub async fn refresh_credentials() -> Result<Option<SystemTime>, String> {
+    let config = load_sdk_config().await;
+    let provider = config
+        .credentials_provider()
+        .ok_or_else(|| "No credentials provider is configured".to_string())?;
+    let credentials = provider
+        .provide_credentials()
+        .await
+        .map_err(|err| err.to_string())?;
+    Ok(session_expiry(credentials.expiry()))
+}
+
+pub async fn get_caller_identity() -> Result<(String, String), aws_sdk_sts::Error> {
+    let client = aws_sdk_sts::Client::new(&load_sdk_config().await);
+    let identity = client.get_caller_identity().send().await?;
+
+    Ok((
+        identity.account().unwrap_or_default().to_string(),
+        identity.arn().unwrap_or_default().to_string(),
+    ))
+}
+
+pub async fn load_collections(log: &ApiLog) -> Result<Vec<String>, Error> {
+    list_table_names(log, &get_client().await).await
+}
 
+/// The tables in `region`, whichever region the profile points at.
+pub async fn load_collections_in_region(log: &ApiLog, region: &str) -> Result<Vec<String>, Error> {
+    list_table_names(log, &get_regional_client(region).await).await
+}
+
+async fn list_table_names(log: &ApiLog, client: &Client) -> Result<Vec<String>, Error> {
     let mut last_evaluated_table_name = None;
 
     let mut collections = Vec::new();
 
     loop {
-        let request = client
+        let started = Instant::now();
+        let output = client
             .list_tables()
-            .set_exclusive_start_table_name(last_evaluated_table_name.clone());
-
-        match request.send().await {
-            Ok(output) => {
-                let table_names = output.table_names();
+            .set_exclusive_start_table_name(last_evaluated_table_name.clone())
+            .send()
+            .await;
+        log.record("ListTables", None, started, &output, |output| {
+            (None, Some(output.table_names().len()))
+        })
+        .await;
+        let output = output?;
 
-                for name in table_names {
-                    collections.push(name.clone());
-                }
+        for name in output.table_names() {
+            collections.push(name.clone());
+        }
 
-                last_evaluated_table_name =
-                    output.last_evaluated_table_name().map(|s| s.to_string());
+        last_evaluated_table_name = output.last_evaluated_table_name().map(|s| s.to_string());
 
-                if last_evaluated_table_name.is_none() {
-                    break;
-                }
-            }
-            Err(_) => {
-                collections.push("Error loading collections.".to_string());
-                break;
-            }
+        if last_evaluated_table_name.is_none() {
+            break;
         }
     }
 
-    collections
+    Ok(collections)
 }
 
 pub async fn load_data(
+    log: &ApiLog,
     collection_name: &str,
     last_evaluated_key: Option<HashMap<String, AttributeValue>>,
+    soft_delete: Option<&SoftDeleteFilter>,
+    page_size: i32,
 ) -> Result<(Vec<String>, bool, Option<HashMap<String, AttributeValue>>), Error> {
     let client = get_client().await;
 
-    let mut request = client.scan().table_name(collection_name).limit(100);
+    let (records, new_last_evaluated_key) = scan_page(
+        log,
+        &client,
+        collection_name,
+        None,
+        last_evaluated_key,
+        soft_delete,
+        page_size,
+    )
+    .await?;
+
+    let has_more = new_last_evaluated_key.is_some();
+
+    Ok((records, has_more, new_last_evaluated_key))
+}
+
+/// Scans the table in `segments` segments at once, in pages of up to `page_size` items, yielding
+/// the items of each page as it arrives from any of them. Each segment ends after its last page,
+/// or after a page that failed.
+pub async fn parallel_scan<'a>(
+    log: &'a ApiLog,
+    table_name: &'a str,
+    segments: i32,
+    page_size: i32,
+    soft_delete: Option<&'a SoftDeleteFilter>,
+) -> impl Stream<Item = Result<Vec<String>, Error>> + 'a {
+    let client = get_client().await;
+
+    stream::select_all((0..segments).map(move |segment| {
+        let client = client.clone();
+        // `None` once the segment has been read to the end.
+        Box::pin(stream::unfold(Some(None), move |start| {
+            let client = client.clone();
+            async move {
+                let page = scan_page(
+                    log,
+                    &client,
+                    table_name,
+                    Some((segment, segments)),
+                    start?,
+                    soft_delete,
+                    page_size,
+                )
+                .await;
+                Some(match page {
+                    Ok((records, next)) => (Ok(records), next.map(Some)),
+                    Err(err) => (Err(err), None),
+                })
+            }
+        }))
+    }))
+}
+
+/// Reads a page of up to `page_size` items of the table, or of one of its segments given as the
+/// segment and the number of segments.
+async fn scan_page(
+    log: &ApiLog,
+    client: &Client,
+    table_name: &str,
+    segment: Option<(i32, i32)>,
+    last_evaluated_key: Option<HashMap<String, AttributeValue>>,
+    soft_delete: Option<&SoftDeleteFilter>,
+    page_size: i32,
+) -> Result<(Vec<String>, Option<HashMap<String, AttributeValue>>), Error> {
+    let mut request = client
+        .scan()
+        .table_name(table_name)
+        .limit(page_size)
+        .return_consumed_capacity(ReturnConsumedCapacity::Total);
+
+    if let Some((segment, total_segments)) = segment {
+        request = request.segment(segment).total_segments(total_segments);
+    }
+
+    if let Some((filter, expression)) = soft_delete_expression(soft_delete) {
+        request = request
+            .filter_expression(expression)
+            .expression_attribute_names("#sd", &filter.attribute)
+            .expression_attribute_values(":sdnull", AttributeValue::S("NULL".to_string()))
+            .expression_attribute_values(":sdfalse", AttributeValue::Bool(false));
+    }
 
     if let Some(ref key) = last_evaluated_key {
         for (k, v) in key.iter() {
@@ -91,7 +593,16 @@ pub async fn load_data(
         }
     }
 
-    let response = request.send().await?;
+    let started = Instant::now();
+    let response = request.send().await;
+    log.record("Scan", Some(table_name), started, &response, |output| {
+        (
+            output.consumed_capacity().and_then(|c| c.capacity_units()),
+            Some(output.count() as usize),
+        )
+    })
+    .await;
+    let response = response?;
 
     let records = if let Some(items) = response.items {
         hashmap_to_json(items)
@@ -103,18 +614,30 @@ pub async fn load_data(
         .last_evaluated_key
         .map(|key| key.into_iter().collect::<HashMap<String, AttributeValue>>());
 
-    let has_more = new_last_evaluated_key.is_some();
+    Ok((records, new_last_evaluated_key))
+}
 
-    Ok((records, has_more, new_last_evaluated_key))
+/// The filter leaving soft-deleted items in or out of a scan or query, if it leaves any out.
+fn soft_delete_expression(
+    filter: Option<&SoftDeleteFilter>,
+) -> Option<(&SoftDeleteFilter, &'static str)> {
+    let filter = filter?;
+    filter.expression().map(|expression| (filter, expression))
 }
 
-pub async fn get_approximate_item_count(table_name: &str) -> Result<i64, Error> {
+pub async fn get_approximate_item_count(log: &ApiLog, table_name: &str) -> Result<i64, Error> {
     let client = get_client().await;
-    let response = client
-        .describe_table()
-        .table_name(table_name)
-        .send()
-        .await?;
+    let started = Instant::now();
+    let response = client.describe_table().table_name(table_name).send().await;
+    log.record(
+        "DescribeTable",
+        Some(table_name),
+        started,
+        &response,
+        |_| (None, None),
+    )
+    .await;
+    let response = response?;
     if let Some(table) = response.table {
         Ok(table.item_count.unwrap_or(0))
     } else {
@@ -122,54 +645,307 @@ pub async fn get_approximate_item_count(table_name: &str) -> Result<i64, Error>
     }
 }
 
-pub async fn describe_table_key_schema(
+/// The description DescribeTable gives of a table, if any.
+async fn table_description(
+    log: &ApiLog,
     table_name: &str,
-) -> Result<(Option<String>, Option<String>), Error> {
+) -> Result<Option<TableDescription>, Error> {
     let client = get_client().await;
+    let started = Instant::now();
+    let response = client.describe_table().table_name(table_name).send().await;
+    log.record(
+        "DescribeTable",
+        Some(table_name),
+        started,
+        &response,
+        |_| (None, None),
+    )
+    .await;
+    Ok(response?.table)
+}
 
-    let table_info = client
-        .describe_table()
+pub async fn describe_table_usage(log: &ApiLog, table_name: &str) -> Result<TableUsage, Error> {
+    let table = table_description(log, table_name)
+        .await?
+        .unwrap_or_else(|| TableDescription::builder().build());
+    Ok(table_usage(&table))
+}
+
+fn table_usage(table: &TableDescription) -> TableUsage {
+    let on_demand = table
+        .billing_mode_summary()
+        .and_then(|summary| summary.billing_mode())
+        == Some(&BillingMode::PayPerRequest);
+    let billing = match table.provisioned_throughput() {
+        Some(throughput) if !on_demand => Billing::Provisioned(
+            throughput.read_capacity_units().unwrap_or_default().max(0) as u64,
+            throughput.write_capacity_units().unwrap_or_default().max(0) as u64,
+        ),
+        _ => Billing::OnDemand,
+    };
+
+    TableUsage {
+        item_count: table.item_count().unwrap_or_default().max(0) as u64,
+        size_bytes: table.table_size_bytes().unwrap_or_default().max(0) as u64,
+        billing,
+    }
+}
+
+pub async fn describe_table_key_schema(
+    log: &ApiLog,
+    table_name: &str,
+) -> Result<(Option<KeyAttribute>, Option<KeyAttribute>), Error> {
+    Ok(table_description(log, table_name)
+        .await?
+        .map(|table| table_key_schema(&table))
+        .unwrap_or_default())
+}
+
+/// Everything the table info popup shows: the keys, size and capacity of the table, its
+/// indexes, its stream and its time to live. Time to live is read with a call of its own, whose
+/// failure is shown in its place.
+pub async fn describe_table(log: &ApiLog, table_name: &str) -> Result<TableInfo, Error> {
+    let table = table_description(log, table_name)
+        .await?
+        .unwrap_or_else(|| TableDescription::builder().build());
+    let (partition_key, sort_key) = table_key_schema(&table);
+    let usage = table_usage(&table);
+
+    let client = get_client().await;
+    let started = Instant::now();
+    let response = client
+        .describe_time_to_live()
         .table_name(table_name)
         .send()
-        .await?;
+        .await;
+    log.record(
+        "DescribeTimeToLive",
+        Some(table_name),
+        started,
+        &response,
+        |_| (None, None),
+    )
+    .await;
+    let ttl = match response {
+        Ok(output) => Ok(output
+            .time_to_live_description()
+            .map(|ttl| TimeToLive {
+                status: ttl
+                    .time_to_live_status()
+                    .map_or("DISABLED", |status| status.as_str())
+                    .to_string(),
+                attribute: ttl.attribute_name().map(str::to_string),
+            })
+            .unwrap_or_default()),
+        Err(err) => Err(err
+            .message()
+            .map(str::to_string)
+            .unwrap_or_else(|| err.to_string())),
+    };
 
-    let table = table_info.table();
+    let global_indexes = table
+        .global_secondary_indexes()
+        .iter()
+        .map(|index| IndexInfo {
+            name: index.index_name().unwrap_or_default().to_string(),
+            status: index
+                .index_status()
+                .map(|status| status.as_str().to_string()),
+            capacity: index
+                .provisioned_throughput()
+                .filter(|_| matches!(usage.billing, Billing::Provisioned(..)))
+                .map(|throughput| {
+                    (
+                        throughput.read_capacity_units().unwrap_or_default().max(0) as u64,
+                        throughput.write_capacity_units().unwrap_or_default().max(0) as u64,
+                    )
+                }),
+            item_count: index.item_count().unwrap_or_default().max(0) as u64,
+            size_bytes: index.index_size_bytes().unwrap_or_default().max(0) as u64,
+            ..index_keys(index.key_schema(), index.projection())
+        })
+        .collect();
+    let local_indexes = table
+        .local_secondary_indexes()
+        .iter()
+        .map(|index| IndexInfo {
+            name: index.index_name().unwrap_or_default().to_string(),
+            item_count: index.item_count().unwrap_or_default().max(0) as u64,
+            size_bytes: index.index_size_bytes().unwrap_or_default().max(0) as u64,
+            ..index_keys(index.key_schema(), index.projection())
+        })
+        .collect();
+
+    Ok(TableInfo {
+        status: table
+            .table_status()
+            .map_or("UNKNOWN", |status| status.as_str())
+            .to_string(),
+        created: table
+            .creation_date_time()
+            .and_then(|created| created.fmt(DateTimeFormat::DateTime).ok()),
+        partition_key,
+        sort_key,
+        item_count: usage.item_count,
+        size_bytes: usage.size_bytes,
+        billing: usage.billing,
+        global_indexes,
+        local_indexes,
+        stream: table
+            .stream_specification()
+            .filter(|stream| stream.stream_enabled())
+            .map(|stream| {
+                stream
+                    .stream_view_type()
+                    .map_or("ENABLED", |view| view.as_str())
+                    .to_string()
+            }),
+        ttl,
+        deletion_protection: table.deletion_protection_enabled().unwrap_or_default(),
+    })
+}
 
-    if table.is_none() {
-        return Ok((None, None));
+/// The key attributes and the projection of an index.
+fn index_keys(key_schema: &[KeySchemaElement], projection: Option<&Projection>) -> IndexInfo {
+    let key = |key_type: KeyType| {
+        key_schema
+            .iter()
+            .find(|element| *element.key_type() == key_type)
+            .map(|element| element.attribute_name().to_string())
+    };
+    let projection = match projection {
+        Some(projection) if !projection.non_key_attributes().is_empty() => format!(
+            "{} ({})",
+            projection
+                .projection_type()
+                .map_or("INCLUDE", |projection| projection.as_str()),
+            projection.non_key_attributes().join(", ")
+        ),
+        Some(projection) => projection
+            .projection_type()
+            .map_or("ALL", |projection| projection.as_str())
+            .to_string(),
+        None => "ALL".to_string(),
+    };
+    IndexInfo {
+        partition_key: key(KeyType::Hash),
+        sort_key: key(KeyType::Range),
+        projection,
+        ..IndexInfo::default()
     }
+}
 
-    let key_schema = table.unwrap().key_schema();
+fn table_key_schema(table: &TableDescription) -> (Option<KeyAttribute>, Option<KeyAttribute>) {
+    let key_schema = table.key_schema();
 
     let mut partition_key = None;
     let mut sort_key = None;
 
     for key_element in key_schema {
+        let attribute_type = table
+            .attribute_definitions()
+            .iter()
+            .find(|definition| definition.attribute_name() == key_element.attribute_name())
+            .map(|definition| match definition.attribute_type() {
+                ScalarAttributeType::N => KeyAttributeType::N,
+                ScalarAttributeType::B => KeyAttributeType::B,
+                _ => KeyAttributeType::S,
+            })
+            .unwrap_or(KeyAttributeType::S);
+
+        let key = Some(KeyAttribute {
+            name: key_element.attribute_name().to_string(),
+            attribute_type,
+        });
+
         match key_element.key_type() {
-            KeyType::Hash => partition_key = Some(key_element.attribute_name().to_string()),
-            KeyType::Range => sort_key = Some(key_element.attribute_name().to_string()),
+            KeyType::Hash => partition_key = key,
+            KeyType::Range => sort_key = key,
             _ => (),
         }
     }
 
-    Ok((partition_key, sort_key))
+    (partition_key, sort_key)
 }
 
-pub async fn query_by_partition_key(
+pub async fn query_table_data(
+    log: &ApiLog,
     table_name: &str,
-    partition_key_name: &str,
-    partition_key_value: &str,
-) -> Result<Vec<String>, Error> {
+    query: &KeyQuery,
+    last_evaluated_key: Option<HashMap<String, AttributeValue>>,
+    soft_delete: Option<&SoftDeleteFilter>,
+) -> Result<(Vec<String>, bool, Option<HashMap<String, AttributeValue>>), Error> {
     let client = get_client().await;
 
-    let response = client
+    let sort_key_range = query
+        .sort_key
+        .as_ref()
+        .and_then(|(_, value)| split_range(value));
+    let sort_key_after = query
+        .sort_key
+        .as_ref()
+        .and_then(|(_, value)| after_bound(value));
+    let key_condition = match (&query.sort_key, sort_key_range, sort_key_after) {
+        (Some(_), Some(_), _) => "#pk = :pkval AND #sk BETWEEN :skfrom AND :skto",
+        (Some(_), None, Some(_)) => "#pk = :pkval AND #sk > :skfrom",
+        (Some(_), None, None) => "#pk = :pkval AND #sk = :skval",
+        (None, ..) => "#pk = :pkval",
+    };
+
+    let mut request = client
         .query()
         .table_name(table_name)
-        .key_condition_expression("#pk = :pkval")
-        .expression_attribute_names("#pk", partition_key_name)
-        .expression_attribute_values(":pkval", AttributeValue::S(partition_key_value.to_string()))
-        .send()
-        .await?;
+        .key_condition_expression(key_condition)
+        .expression_attribute_names("#pk", &query.partition_key.name)
+        .expression_attribute_values(
+            ":pkval",
+            query
+                .partition_key
+                .attribute_type
+                .attribute_value(&query.partition_key_value),
+        )
+        .set_limit(query.limit)
+        .set_exclusive_start_key(last_evaluated_key)
+        .return_consumed_capacity(ReturnConsumedCapacity::Total);
+
+    if let Some((sort_key, sort_key_value)) = &query.sort_key {
+        request = request.expression_attribute_names("#sk", &sort_key.name);
+        request = match (sort_key_range, sort_key_after) {
+            (Some((from, to)), _) => request
+                .expression_attribute_values(
+                    ":skfrom",
+                    sort_key.attribute_type.attribute_value(from),
+                )
+                .expression_attribute_values(":skto", sort_key.attribute_type.attribute_value(to)),
+            (None, Some(from)) => request.expression_attribute_values(
+                ":skfrom",
+                sort_key.attribute_type.attribute_value(from),
+            ),
+            (None, None) => request.expression_attribute_values(
+                ":skval",
+                sort_key.attribute_type.attribute_value(sort_key_value),
+            ),
+        };
+    }
+
+    if let Some((filter, expression)) = soft_delete_expression(soft_delete) {
+        request = request
+            .filter_expression(expression)
+            .expression_attribute_names("#sd", &filter.attribute)
+            .expression_attribute_values(":sdnull", AttributeValue::S("NULL".to_string()))
+            .expression_attribute_values(":sdfalse", AttributeValue::Bool(false));
+    }
+
+    let started = Instant::now();
+    let response = request.send().await;
+    log.record("Query", Some(table_name), started, &response, |output| {
+        (
+            output.consumed_capacity().and_then(|c| c.capacity_units()),
+            Some(output.count() as usize),
+        )
+    })
+    .await;
+    let response = response?;
 
     let records = if let Some(items) = response.items {
         hashmap_to_json(items)
@@ -177,36 +953,1057 @@ pub async fn query_by_partition_key(
         Vec::new()
     };
 
-    Ok(records)
+    // An explicit limit asks for exactly that many items, so only unbounded queries keep paging.
+    let has_more = response.last_evaluated_key.is_some() && query.limit.is_none();
+
+    Ok((records, has_more, response.last_evaluated_key))
 }
 
-pub async fn query_by_partition_and_sort_key(
+/// A client for the destination of a table copy, which may be another account, region, or a
+/// local endpoint.
+async fn get_target_client(target: &CopyTarget) -> Client {
+    let mut loader = workspace_defaults();
+    loader = match &target.region {
+        Some(region) => loader.region(Region::new(region.clone())),
+        None => loader.region(workspace_region()),
+    };
+    if let Some(endpoint_url) = &target.endpoint_url {
+        loader = loader.endpoint_url(endpoint_url);
+    }
+    if let Some(profile) = &target.profile {
+        loader = loader.profile_name(profile);
+    }
+    Client::new(&proxy::apply(loader).load().await)
+}
+
+/// The most items a single BatchWriteItem call accepts.
+const BATCH_WRITE_SIZE: usize = 25;
+const MAX_BATCH_WRITE_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Scans the table for items whose JSON holds `needle`, ignoring ASCII case, as the find in the
+/// data pane does. The scan stops as soon as `max_matches` items are found, so no more pages are
+/// read than needed, or goes through the whole table when it is zero. `on_page` is called with
+/// the number of items scanned so far after each page.
+pub async fn deep_find<F>(
+    log: &ApiLog,
+    table_name: &str,
+    needle: &str,
+    max_matches: usize,
+    mut on_page: F,
+) -> Result<DeepFindResult, Error>
+where
+    F: FnMut(usize),
+{
+    let client = get_client().await;
+    let needle = needle.to_ascii_lowercase();
+
+    let mut result = DeepFindResult::default();
+    let mut last_evaluated_key = None;
+    loop {
+        let started = Instant::now();
+        let response = client
+            .scan()
+            .table_name(table_name)
+            .set_exclusive_start_key(last_evaluated_key)
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
+            .send()
+            .await;
+        log.record("Scan", Some(table_name), started, &response, |output| {
+            (
+                output.consumed_capacity().and_then(|c| c.capacity_units()),
+                Some(output.count() as usize),
+            )
+        })
+        .await;
+        let response = response?;
+
+        let records = hashmap_to_json(response.items.unwrap_or_default());
+        result.scanned += records.len();
+        result.matches.extend(
+            records
+                .into_iter()
+                .filter(|record| record.to_ascii_lowercase().contains(&needle)),
+        );
+        on_page(result.scanned);
+
+        if max_matches > 0 && result.matches.len() >= max_matches {
+            result.matches.truncate(max_matches);
+            return Ok(result);
+        }
+        last_evaluated_key = response.last_evaluated_key;
+        if last_evaluated_key.is_none() {
+            result.complete = true;
+            return Ok(result);
+        }
+    }
+}
+
+/// Scans every item of `source` and writes it to `target`, renaming attributes on the way.
+/// `on_written` is called with each item written, and `on_progress` with the number of items
+/// written so far after each batch.
+pub async fn copy_table<W, F>(
+    log: &ApiLog,
+    source: &str,
+    target: &CopyTarget,
+    failures: &mut Vec<FailedItem>,
+    mut on_written: W,
+    mut on_progress: F,
+) -> Result<usize, Error>
+where
+    W: FnMut(&HashMap<String, AttributeValue>),
+    F: FnMut(usize),
+{
+    let source_client = get_client().await;
+    let target_client = get_target_client(target).await;
+
+    let mut copied = 0;
+    let mut last_evaluated_key = None;
+    loop {
+        let started = Instant::now();
+        let response = source_client
+            .scan()
+            .table_name(source)
+            .set_exclusive_start_key(last_evaluated_key)
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
+            .send()
+            .await;
+        log.record("Scan", Some(source), started, &response, |output| {
+            (
+                output.consumed_capacity().and_then(|c| c.capacity_units()),
+                Some(output.count() as usize),
+            )
+        })
+        .await;
+        let response = response?;
+
+        let mut items = response.items.unwrap_or_default();
+        for item in items.iter_mut() {
+            rename_attributes(item, &target.renames);
+        }
+        for batch in items.chunks(BATCH_WRITE_SIZE) {
+            failures.extend(
+                write_batch_or_items(log, &target_client, &target.table, batch, &mut on_written)
+                    .await?,
+            );
+            copied += batch.len();
+            on_progress(copied);
+        }
+
+        last_evaluated_key = response.last_evaluated_key;
+        if last_evaluated_key.is_none() {
+            return Ok(copied);
+        }
+    }
+}
+
+/// Writes `count` items made by `make_item` to the table, in batches. `on_written` is called
+/// with each item written, and `on_progress` with the number of items written so far after each
+/// batch.
+pub async fn seed_items<F, W, P>(
+    log: &ApiLog,
+    table_name: &str,
+    count: usize,
+    failures: &mut Vec<FailedItem>,
+    mut make_item: F,
+    mut on_written: W,
+    mut on_progress: P,
+) -> Result<(), Error>
+where
+    F: FnMut() -> HashMap<String, AttributeValue>,
+    W: FnMut(&HashMap<String, AttributeValue>),
+    P: FnMut(usize),
+{
+    let client = get_client().await;
+
+    let mut written = 0;
+    while written < count {
+        let batch: Vec<_> = (0..BATCH_WRITE_SIZE.min(count - written))
+            .map(|_| make_item())
+            .collect();
+        failures
+            .extend(write_batch_or_items(log, &client, table_name, &batch, &mut on_written).await?);
+        written += batch.len();
+        on_progress(written);
+    }
+
+    Ok(())
+}
+
+/// Writes the items in batches. `on_written` is called with each item written, and
+/// `on_progress` with the number of items written so far after each batch.
+pub async fn write_items<W, P>(
+    log: &ApiLog,
+    table_name: &str,
+    items: &[HashMap<String, AttributeValue>],
+    failures: &mut Vec<FailedItem>,
+    mut on_written: W,
+    mut on_progress: P,
+) -> Result<(), Error>
+where
+    W: FnMut(&HashMap<String, AttributeValue>),
+    P: FnMut(usize),
+{
+    let client = get_client().await;
+
+    let mut written = 0;
+    for batch in items.chunks(BATCH_WRITE_SIZE) {
+        failures
+            .extend(write_batch_or_items(log, &client, table_name, batch, &mut on_written).await?);
+        written += batch.len();
+        on_progress(written);
+    }
+
+    Ok(())
+}
+
+/// Writes the items one at a time, each only if no item has its partition key. Items whose key
+/// is taken are reported as conflicts rather than stopping the import. `on_written` is called
+/// with each item written.
+pub async fn put_items_if_absent<W, P>(
+    log: &ApiLog,
+    table_name: &str,
+    partition_key: &str,
+    items: &[HashMap<String, AttributeValue>],
+    failures: &mut Vec<FailedItem>,
+    mut on_written: W,
+    mut on_progress: P,
+) -> Result<(), Error>
+where
+    W: FnMut(&HashMap<String, AttributeValue>),
+    P: FnMut(usize),
+{
+    let client = get_client().await;
+
+    for (written, item) in items.iter().enumerate() {
+        let started = Instant::now();
+        let response = client
+            .put_item()
+            .table_name(table_name)
+            .set_item(Some(item.clone()))
+            .condition_expression("attribute_not_exists(#pk)")
+            .expression_attribute_names("#pk", partition_key)
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
+            .send()
+            .await;
+        log.record("PutItem", Some(table_name), started, &response, |output| {
+            (
+                output.consumed_capacity().and_then(|c| c.capacity_units()),
+                Some(1),
+            )
+        })
+        .await;
+        match response {
+            Ok(_) => on_written(item),
+            Err(err) => {
+                let err = Error::from(err);
+                let Some(reason) = item_failure_reason(&err) else {
+                    return Err(err);
+                };
+                failures.push(FailedItem {
+                    item: dynamodb_to_json(AttributeValue::M(item.clone())),
+                    reason,
+                });
+            }
+        }
+        on_progress(written + 1);
+    }
+
+    Ok(())
+}
+
+/// Sets the attributes of each item on the item with its key, creating it if there is none.
+/// Attributes the item does not have are left as they are. `on_written` is called with each
+/// item as it is once merged.
+pub async fn merge_items<W, P>(
+    log: &ApiLog,
+    table_name: &str,
+    key_names: &[String],
+    items: &[HashMap<String, AttributeValue>],
+    failures: &mut Vec<FailedItem>,
+    mut on_written: W,
+    mut on_progress: P,
+) -> Result<(), Error>
+where
+    W: FnMut(&HashMap<String, AttributeValue>),
+    P: FnMut(usize),
+{
+    let client = get_client().await;
+
+    for (written, item) in items.iter().enumerate() {
+        let (key, attributes): (HashMap<_, _>, Vec<_>) = {
+            let mut key = HashMap::new();
+            let mut attributes = Vec::new();
+            for (name, value) in item {
+                if key_names.contains(name) {
+                    key.insert(name.clone(), value.clone());
+                } else {
+                    attributes.push((name.clone(), value.clone()));
+                }
+            }
+            (key, attributes)
+        };
+
+        let mut request = client
+            .update_item()
+            .table_name(table_name)
+            .set_key(Some(key))
+            .return_values(ReturnValue::AllNew)
+            .return_consumed_capacity(ReturnConsumedCapacity::Total);
+        // Without attributes to set, the update only creates the item if it is missing.
+        if !attributes.is_empty() {
+            request = request.update_expression(merge_expression(attributes.len()));
+        }
+        for (position, (name, value)) in attributes.into_iter().enumerate() {
+            request = request
+                .expression_attribute_names(format!("#a{position}"), name)
+                .expression_attribute_values(format!(":v{position}"), value);
+        }
+
+        let started = Instant::now();
+        let response = request.send().await;
+        log.record(
+            "UpdateItem",
+            Some(table_name),
+            started,
+            &response,
+            |output| {
+                (
+                    output.consumed_capacity().and_then(|c| c.capacity_units()),
+                    Some(1),
+                )
+            },
+        )
+        .await;
+        match response {
+            Ok(output) => on_written(output.attributes.as_ref().unwrap_or(item)),
+            Err(err) => {
+                let err = Error::from(err);
+                let Some(reason) = item_failure_reason(&err) else {
+                    return Err(err);
+                };
+                failures.push(FailedItem {
+                    item: dynamodb_to_json(AttributeValue::M(item.clone())),
+                    reason,
+                });
+            }
+        }
+        on_progress(written + 1);
+    }
+
+    Ok(())
+}
+
+/// Why DynamoDB refused a single item, when it did so for its key or what it holds. Any other
+/// error stops the operation.
+fn item_failure_reason(err: &Error) -> Option<String> {
+    match err {
+        Error::ConditionalCheckFailedException(_) => {
+            Some("Conflict: an item with this key already exists".to_string())
+        }
+        err if is_item_error(err) => {
+            Some(err.message().unwrap_or("ValidationException").to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Writes a batch, and when DynamoDB rejects it for what an item holds, each of its items on its
+/// own to tell which ones. `on_written` is called with each item written. Returns the items that
+/// could not be written; any other error stops the operation.
+async fn write_batch_or_items(
+    log: &ApiLog,
+    client: &Client,
+    table_name: &str,
+    items: &[HashMap<String, AttributeValue>],
+    on_written: &mut impl FnMut(&HashMap<String, AttributeValue>),
+) -> Result<Vec<FailedItem>, Error> {
+    match batch_write(log, client, table_name, items).await {
+        Err(err) if is_item_error(&err) => {}
+        result => {
+            result?;
+            items.iter().for_each(on_written);
+            return Ok(Vec::new());
+        }
+    }
+
+    let mut failures = Vec::new();
+    for item in items {
+        match batch_write(log, client, table_name, std::slice::from_ref(item)).await {
+            Err(err) if is_item_error(&err) => failures.push(FailedItem {
+                item: dynamodb_to_json(AttributeValue::M(item.clone())),
+                reason: err.message().unwrap_or("ValidationException").to_string(),
+            }),
+            result => {
+                result?;
+                on_written(item);
+            }
+        }
+    }
+    Ok(failures)
+}
+
+/// DynamoDB refuses a whole batch with a validation error when one of its items is too large,
+/// misses a key attribute, or has one of the wrong type.
+fn is_item_error(err: &Error) -> bool {
+    err.code() == Some("ValidationException")
+}
+
+/// Writes the items, retrying the ones DynamoDB leaves unprocessed when the destination is
+/// throttled. Retries back off but never give up; the job can be cancelled instead.
+async fn batch_write(
+    log: &ApiLog,
+    client: &Client,
+    table_name: &str,
+    items: &[HashMap<String, AttributeValue>],
+) -> Result<(), Error> {
+    let mut requests: Vec<WriteRequest> = items
+        .iter()
+        // Building a put request only fails when it has no item.
+        .filter_map(|item| {
+            PutRequest::builder()
+                .set_item(Some(item.clone()))
+                .build()
+                .ok()
+        })
+        .map(|put_request| WriteRequest::builder().put_request(put_request).build())
+        .collect();
+    let mut backoff = Duration::from_millis(100);
+
+    while !requests.is_empty() {
+        let started = Instant::now();
+        let response = client
+            .batch_write_item()
+            .request_items(table_name, requests)
+            .return_consumed_capacity(ReturnConsumedCapacity::Total)
+            .send()
+            .await;
+        log.record(
+            "BatchWriteItem",
+            Some(table_name),
+            started,
+            &response,
+            |output| {
+                let capacity = output
+                    .consumed_capacity()
+                    .iter()
+                    .filter_map(|capacity| capacity.capacity_units())
+                    .sum();
+                (Some(capacity), None)
+            },
+        )
+        .await;
+
+        requests = response?
+            .unprocessed_items
+            .and_then(|mut unprocessed| unprocessed.remove(table_name))
+            .unwrap_or_default();
+        if !requests.is_empty() {
+            sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BATCH_WRITE_BACKOFF);
+        }
+    }
+
+    Ok(())
+}
+
+/// The most keys a single BatchGetItem call accepts.
+const BATCH_GET_SIZE: usize = 100;
+
+/// Reads the items with the given keys, as JSON. Keys without an item are left out, and keys
+/// DynamoDB leaves unprocessed are asked for again.
+pub async fn batch_get_items(
+    log: &ApiLog,
+    table_name: &str,
+    keys: Vec<HashMap<String, AttributeValue>>,
+) -> Result<Vec<Value>, Error> {
+    let client = get_client().await;
+
+    let mut items = Vec::new();
+    for chunk in keys.chunks(BATCH_GET_SIZE) {
+        let mut keys = chunk.to_vec();
+        let mut backoff = Duration::from_millis(100);
+        while !keys.is_empty() {
+            // Building the request only fails without keys.
+            let Ok(request) = KeysAndAttributes::builder()
+                .set_keys(Some(keys))
+                .consistent_read(true)
+                .build()
+            else {
+                break;
+            };
+
+            let started = Instant::now();
+            let response = client
+                .batch_get_item()
+                .request_items(table_name, request)
+                .return_consumed_capacity(ReturnConsumedCapacity::Total)
+                .send()
+                .await;
+            log.record(
+                "BatchGetItem",
+                Some(table_name),
+                started,
+                &response,
+                |output| {
+                    let capacity = output
+                        .consumed_capacity()
+                        .iter()
+                        .filter_map(|capacity| capacity.capacity_units())
+                        .sum();
+                    let count = output
+                        .responses()
+                        .and_then(|responses| responses.get(table_name))
+                        .map(Vec::len);
+                    (Some(capacity), count)
+                },
+            )
+            .await;
+            let mut response = response?;
+
+            if let Some(found) = response
+                .responses
+                .as_mut()
+                .and_then(|responses| responses.remove(table_name))
+            {
+                items.extend(
+                    found
+                        .into_iter()
+                        .map(|item| dynamodb_to_json(AttributeValue::M(item))),
+                );
+            }
+            keys = response
+                .unprocessed_keys
+                .and_then(|mut unprocessed| unprocessed.remove(table_name))
+                .map(|unprocessed| unprocessed.keys)
+                .unwrap_or_default();
+            if !keys.is_empty() {
+                sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BATCH_WRITE_BACKOFF);
+            }
+        }
+    }
+
+    Ok(items)
+}
+
+/// The writes CloudTrail recorded to `table_name` in its event history, newest first. Only
+/// management events, such as `UpdateTable`, are in the history: writes to items are data events,
+/// which CloudTrail only keeps in trails that log them.
+pub async fn lookup_table_events(
+    log: &ApiLog,
+    table_name: &str,
+) -> Result<Vec<TableEvent>, String> {
+    let client = aws_sdk_cloudtrail::Client::new(&load_sdk_config().await);
+    let source = LookupAttribute::builder()
+        .attribute_key(LookupAttributeKey::EventSource)
+        .attribute_value("dynamodb.amazonaws.com")
+        .build()
+        .map_err(|err| err.to_string())?;
+
+    let mut events = Vec::new();
+    let mut next_token = None;
+    for _ in 0..MAX_LOOKUP_PAGES {
+        let started = Instant::now();
+        let response = client
+            .lookup_events()
+            .lookup_attributes(source.clone())
+            .set_next_token(next_token)
+            .send()
+            .await;
+        log.record(
+            "LookupEvents",
+            Some(table_name),
+            started,
+            &response,
+            |output| (None, Some(output.events().len())),
+        )
+        .await;
+        let response = response.map_err(|err| match (err.code(), err.message()) {
+            (Some(code), Some(message)) => format!("{code}: {message}"),
+            _ => err.to_string(),
+        })?;
+
+        events.extend(
+            response
+                .events()
+                .iter()
+                .filter_map(|event| table_event(table_name, event.cloud_trail_event()?)),
+        );
+        next_token = response.next_token;
+        if events.len() >= MAX_TABLE_EVENTS || next_token.is_none() {
+            break;
+        }
+    }
+    events.truncate(MAX_TABLE_EVENTS);
+    Ok(events)
+}
+
+/// The regions a table can be read in: the current one, followed by those of its replicas when
+/// it is a global table.
+pub async fn describe_replica_regions(
+    log: &ApiLog,
     table_name: &str,
-    partition_key_name: &str,
-    partition_key_value: &str,
-    sort_key_name: &str,
-    sort_key_value: &str,
 ) -> Result<Vec<String>, Error> {
     let client = get_client().await;
 
+    let started = Instant::now();
+    let table_info = client.describe_table().table_name(table_name).send().await;
+    log.record(
+        "DescribeTable",
+        Some(table_name),
+        started,
+        &table_info,
+        |_| (None, None),
+    )
+    .await;
+    let table_info = table_info?;
+
+    let mut regions: Vec<String> = client
+        .config()
+        .region()
+        .map(|region| region.to_string())
+        .into_iter()
+        .collect();
+    for replica in table_info
+        .table()
+        .map(|table| table.replicas())
+        .unwrap_or_default()
+    {
+        if let Some(region) = replica.region_name() {
+            if !regions.iter().any(|known| known == region) {
+                regions.push(region.to_string());
+            }
+        }
+    }
+
+    Ok(regions)
+}
+
+/// Reads the latest version of one item.
+pub async fn get_item(
+    log: &ApiLog,
+    table_name: &str,
+    key: HashMap<String, AttributeValue>,
+    consistent: bool,
+) -> Result<Option<String>, Error> {
+    Ok(get_item_attributes(log, table_name, key, consistent)
+        .await?
+        .and_then(|item| hashmap_to_json(vec![item]).pop()))
+}
+
+/// Reads the latest version of one item, with the attributes as DynamoDB returns them.
+pub async fn get_item_attributes(
+    log: &ApiLog,
+    table_name: &str,
+    key: HashMap<String, AttributeValue>,
+    consistent: bool,
+) -> Result<Option<HashMap<String, AttributeValue>>, Error> {
+    let client = get_client().await;
+
+    let started = Instant::now();
     let response = client
+        .get_item()
+        .table_name(table_name)
+        .set_key(Some(key))
+        .consistent_read(consistent)
+        .return_consumed_capacity(ReturnConsumedCapacity::Total)
+        .send()
+        .await;
+    log.record("GetItem", Some(table_name), started, &response, |output| {
+        (
+            output.consumed_capacity().and_then(|c| c.capacity_units()),
+            Some(usize::from(output.item().is_some())),
+        )
+    })
+    .await;
+    Ok(response?.item)
+}
+
+/// Tries each operation the app performs on a table with requests that read at most one item and
+/// write nothing: the write is conditioned on the item both existing and not. Operations that
+/// need a key are not probed when the table cannot be described.
+pub async fn probe_permissions(log: &ApiLog, table_name: &str) -> Permissions {
+    let client = get_client().await;
+    let mut operations = Vec::new();
+
+    let started = Instant::now();
+    let description = client.describe_table().table_name(table_name).send().await;
+    log.record(
+        "DescribeTable",
+        Some(table_name),
+        started,
+        &description,
+        |_| (None, None),
+    )
+    .await;
+    let key_schema = description
+        .as_ref()
+        .ok()
+        .and_then(|output| output.table())
+        .map(table_key_schema);
+    operations.push((TableOperation::DescribeTable, probe_outcome(description)));
+
+    let started = Instant::now();
+    let scan = client
+        .scan()
+        .table_name(table_name)
+        .limit(1)
+        .return_consumed_capacity(ReturnConsumedCapacity::Total)
+        .send()
+        .await;
+    log.record("Scan", Some(table_name), started, &scan, |output| {
+        (
+            output.consumed_capacity().and_then(|c| c.capacity_units()),
+            Some(output.items().len()),
+        )
+    })
+    .await;
+    operations.push((TableOperation::Scan, probe_outcome(scan)));
+
+    let Some((Some(partition_key), sort_key)) = key_schema else {
+        return Permissions {
+            table: table_name.to_string(),
+            operations,
+        };
+    };
+    let probe_value = |attribute: &KeyAttribute| match attribute.attribute_type {
+        KeyAttributeType::S => AttributeValue::S(PROBE_KEY_VALUE.to_string()),
+        KeyAttributeType::N => AttributeValue::N("0".to_string()),
+        KeyAttributeType::B => AttributeValue::B(Blob::new(PROBE_KEY_VALUE)),
+    };
+    let mut key = HashMap::from([(partition_key.name.clone(), probe_value(&partition_key))]);
+    if let Some(sort_key) = &sort_key {
+        key.insert(sort_key.name.clone(), probe_value(sort_key));
+    }
+
+    let started = Instant::now();
+    let query = client
         .query()
         .table_name(table_name)
-        .key_condition_expression("#pk = :pkval AND #sk = :skval")
-        .expression_attribute_names("#pk", partition_key_name)
-        .expression_attribute_names("#sk", sort_key_name)
-        .expression_attribute_values(":pkval", AttributeValue::S(partition_key_value.to_string()))
-        .expression_attribute_values(":skval", AttributeValue::S(sort_key_value.to_string()))
+        .key_condition_expression("#pk = :pk")
+        .expression_attribute_names("#pk", &partition_key.name)
+        .expression_attribute_values(":pk", probe_value(&partition_key))
+        .limit(1)
+        .return_consumed_capacity(ReturnConsumedCapacity::Total)
         .send()
-        .await?;
+        .await;
+    log.record("Query", Some(table_name), started, &query, |output| {
+        (
+            output.consumed_capacity().and_then(|c| c.capacity_units()),
+            Some(output.items().len()),
+        )
+    })
+    .await;
+    operations.push((TableOperation::Query, probe_outcome(query)));
 
-    let records = if let Some(items) = response.items {
-        hashmap_to_json(items)
-    } else {
-        Vec::new()
+    let started = Instant::now();
+    let item = client
+        .get_item()
+        .table_name(table_name)
+        .set_key(Some(key.clone()))
+        .return_consumed_capacity(ReturnConsumedCapacity::Total)
+        .send()
+        .await;
+    log.record("GetItem", Some(table_name), started, &item, |output| {
+        (
+            output.consumed_capacity().and_then(|c| c.capacity_units()),
+            Some(usize::from(output.item().is_some())),
+        )
+    })
+    .await;
+    operations.push((TableOperation::GetItem, probe_outcome(item)));
+
+    let put = match workspace::refuse_write() {
+        Some(reason) => Permission::Denied(reason),
+        None => {
+            let started = Instant::now();
+            // The condition can never hold, on purpose: DynamoDB checks that the caller may
+            // write before it evaluates the condition, so a failed condition proves the write
+            // is allowed without ever writing the probe item.
+            let put = client
+                .put_item()
+                .table_name(table_name)
+                .set_item(Some(key))
+                .condition_expression("attribute_exists(#pk) AND attribute_not_exists(#pk)")
+                .expression_attribute_names("#pk", &partition_key.name)
+                .return_consumed_capacity(ReturnConsumedCapacity::Total)
+                .send()
+                .await;
+            log.record("PutItem", Some(table_name), started, &put, |output| {
+                (
+                    output.consumed_capacity().and_then(|c| c.capacity_units()),
+                    Some(1),
+                )
+            })
+            .await;
+            probe_outcome(put)
+        }
     };
+    operations.push((TableOperation::PutItem, put));
+
+    Permissions {
+        table: table_name.to_string(),
+        operations,
+    }
+}
+
+fn probe_outcome<T, E>(result: Result<T, E>) -> Permission
+where
+    Error: From<E>,
+{
+    match result.map_err(Error::from) {
+        Ok(_) => Permission::Allowed,
+        Err(err) => {
+            let message = err
+                .message()
+                .map(str::to_string)
+                .unwrap_or_else(|| err.to_string());
+            Permission::from_error(err.code(), &message)
+        }
+    }
+}
+
+/// Times DescribeTable calls on the table from the current region, and from each of its replica
+/// regions if `replicas`. The first call to a region is not timed, so setting up the connection
+/// is left out; in the current region it also names the replicas.
+pub async fn probe_latency(
+    log: &ApiLog,
+    table_name: &str,
+    replicas: bool,
+) -> Result<Vec<RegionLatency>, Error> {
+    let client = get_client().await;
+
+    let started = Instant::now();
+    let table_info = client.describe_table().table_name(table_name).send().await;
+    log.record(
+        "DescribeTable",
+        Some(table_name),
+        started,
+        &table_info,
+        |_| (None, None),
+    )
+    .await;
+    let table_info = table_info?;
+
+    let current = client
+        .config()
+        .region()
+        .map(|region| region.to_string())
+        .unwrap_or_default();
+    let mut latencies = vec![RegionLatency {
+        samples: time_describe_table(log, &client, table_name, LATENCY_SAMPLES).await?,
+        region: current.clone(),
+        error: None,
+    }];
+    if !replicas {
+        return Ok(latencies);
+    }
+
+    let regions = table_info
+        .table()
+        .map(|table| table.replicas())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|replica| replica.region_name())
+        .filter(|region| *region != current);
+    for region in regions {
+        let client = get_regional_client(region).await;
+        let (samples, error) =
+            match time_describe_table(log, &client, table_name, 1 + LATENCY_SAMPLES).await {
+                Ok(samples) => (samples.into_iter().skip(1).collect(), None),
+                Err(err) => (
+                    Vec::new(),
+                    Some(
+                        err.message()
+                            .map(str::to_string)
+                            .unwrap_or_else(|| err.to_string()),
+                    ),
+                ),
+            };
+        latencies.push(RegionLatency {
+            region: region.to_string(),
+            samples,
+            error,
+        });
+    }
+
+    Ok(latencies)
+}
+
+/// The round trip of each of `count` DescribeTable calls made one after the other.
+async fn time_describe_table(
+    log: &ApiLog,
+    client: &Client,
+    table_name: &str,
+    count: usize,
+) -> Result<Vec<Duration>, Error> {
+    let mut samples = Vec::new();
+    for _ in 0..count {
+        let started = Instant::now();
+        let response = client.describe_table().table_name(table_name).send().await;
+        let elapsed = started.elapsed();
+        log.record(
+            "DescribeTable",
+            Some(table_name),
+            started,
+            &response,
+            |_| (None, None),
+        )
+        .await;
+        response?;
+        samples.push(elapsed);
+    }
+    Ok(samples)
+}
+
+/// Reads one item from the replica in `region`. The read is strongly consistent, so only
+/// replication lag shows up as a difference between regions.
+pub async fn get_item_in_region(
+    log: &ApiLog,
+    table_name: &str,
+    region: &str,
+    key: HashMap<String, AttributeValue>,
+) -> Result<Option<String>, Error> {
+    let client = get_regional_client(region).await;
+
+    let started = Instant::now();
+    let response = client
+        .get_item()
+        .table_name(table_name)
+        .set_key(Some(key))
+        .consistent_read(true)
+        .return_consumed_capacity(ReturnConsumedCapacity::Total)
+        .send()
+        .await;
+    log.record("GetItem", Some(table_name), started, &response, |output| {
+        (
+            output.consumed_capacity().and_then(|c| c.capacity_units()),
+            Some(usize::from(output.item().is_some())),
+        )
+    })
+    .await;
+    let response = response?;
+
+    Ok(response
+        .item
+        .and_then(|item| hashmap_to_json(vec![item]).pop()))
+}
+
+/// Writes the item, returning the one it replaced if there was one.
+pub async fn put_item(
+    log: &ApiLog,
+    table_name: &str,
+    item: HashMap<String, AttributeValue>,
+    new_partition_key: Option<&str>,
+) -> Result<Option<HashMap<String, AttributeValue>>, Error> {
+    let client = get_client().await;
+
+    let mut request = client
+        .put_item()
+        .table_name(table_name)
+        .set_item(Some(item))
+        .return_values(ReturnValue::AllOld)
+        .return_consumed_capacity(ReturnConsumedCapacity::Total);
+    if let Some(partition_key) = new_partition_key {
+        request = request
+            .condition_expression("attribute_not_exists(#pk)")
+            .expression_attribute_names("#pk", partition_key);
+    }
 
-    Ok(records)
+    let started = Instant::now();
+    let response = request.send().await;
+    log.record("PutItem", Some(table_name), started, &response, |output| {
+        (
+            output.consumed_capacity().and_then(|c| c.capacity_units()),
+            Some(1),
+        )
+    })
+    .await;
+    Ok(response?.attributes)
+}
+
+/// Pages through every item of a partition, reading only the sort key. `on_page` is called with
+/// the running totals after each page.
+pub async fn explore_partition<F>(
+    log: &ApiLog,
+    table_name: &str,
+    partition_key: &KeyAttribute,
+    partition_key_value: &str,
+    sort_key: Option<&KeyAttribute>,
+    mut on_page: F,
+) -> Result<PartitionStats, Error>
+where
+    F: FnMut(&PartitionStats),
+{
+    let client = get_client().await;
+
+    let mut stats = PartitionStats {
+        table: table_name.to_string(),
+        partition_key_value: partition_key_value.to_string(),
+        ..PartitionStats::default()
+    };
+    let mut last_evaluated_key = None;
+
+    loop {
+        let mut request = client
+            .query()
+            .table_name(table_name)
+            .key_condition_expression("#pk = :pkval")
+            .expression_attribute_names("#pk", &partition_key.name)
+            .expression_attribute_values(
+                ":pkval",
+                partition_key
+                    .attribute_type
+                    .attribute_value(partition_key_value),
+            )
+            .set_exclusive_start_key(last_evaluated_key)
+            .return_consumed_capacity(ReturnConsumedCapacity::Total);
+
+        request = match sort_key {
+            Some(sort_key) => request
+                .projection_expression("#sk")
+                .expression_attribute_names("#sk", &sort_key.name),
+            None => request.select(Select::Count),
+        };
+
+        let started = Instant::now();
+        let response = request.send().await;
+        log.record("Query", Some(table_name), started, &response, |output| {
+            (
+                output.consumed_capacity().and_then(|c| c.capacity_units()),
+                Some(output.count() as usize),
+            )
+        })
+        .await;
+        let response = response?;
+
+        stats.pages += 1;
+        match sort_key {
+            Some(sort_key) => {
+                for item in response.items() {
+                    stats.record(item.get(&sort_key.name).map(attribute_to_string));
+                }
+            }
+            None => stats.items += response.count() as usize,
+        }
+
+        last_evaluated_key = response.last_evaluated_key;
+        if last_evaluated_key.is_none() {
+            break;
+        }
+        on_page(&stats);
+    }
+
+    stats.complete = true;
+    Ok(stats)
+}
+
+fn attribute_to_string(value: &AttributeValue) -> String {
+    match value {
+        AttributeValue::S(value) | AttributeValue::N(value) => value.clone(),
+        AttributeValue::B(blob) => BASE64_STANDARD.encode(blob.as_ref()),
+        other => format!("{other:?}"),
+    }
 }
 
 fn hashmap_to_json(items: Vec<HashMap<String, AttributeValue>>) -> Vec<String> {
@@ -221,3 +2018,135 @@ fn hashmap_to_json(items: Vec<HashMap<String, AttributeValue>>) -> Vec<String> {
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_validate_number_key() {
+        assert!(KeyAttributeType::N.validate("42").is_ok());
+        assert!(KeyAttributeType::N.validate("-1.5e3").is_ok());
+        assert!(KeyAttributeType::N.validate("USER#42").is_err());
+        for text in ["inf", "infinity", "-Infinity", "NaN"] {
+            assert!(KeyAttributeType::N.validate(text).is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_is_number() {
+        for text in ["0", "-7", "+3.25", "1.5E-3", "6e+10", "007"] {
+            assert!(is_number(text), "{text}");
+        }
+        for text in [
+            "", "-", "1.", ".5", "1e", "1e+", "1.2.3", "0x10", "1_000", "inf", "NaN",
+        ] {
+            assert!(!is_number(text), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_probe_outcome() {
+        use aws_sdk_dynamodb::{
+            error::ErrorMetadata,
+            types::error::{ConditionalCheckFailedException, ResourceNotFoundException},
+        };
+
+        let meta = |code: &str, message: &str| {
+            ErrorMetadata::builder().code(code).message(message).build()
+        };
+        let failed_condition = Error::ConditionalCheckFailedException(
+            ConditionalCheckFailedException::builder()
+                .meta(meta(
+                    "ConditionalCheckFailedException",
+                    "The conditional request failed",
+                ))
+                .build(),
+        );
+        assert_eq!(
+            probe_outcome::<(), _>(Err(failed_condition)),
+            Permission::Allowed
+        );
+        let missing_table = Error::ResourceNotFoundException(
+            ResourceNotFoundException::builder()
+                .meta(meta(
+                    "ResourceNotFoundException",
+                    "Requested resource not found",
+                ))
+                .build(),
+        );
+        assert_eq!(
+            probe_outcome::<(), _>(Err(missing_table)),
+            Permission::Unknown("Requested resource not found".to_string())
+        );
+        assert_eq!(probe_outcome::<(), Error>(Ok(())), Permission::Allowed);
+    }
+
+    #[test]
+    fn test_validate_binary_key() {
+        assert!(KeyAttributeType::B.validate("aGVsbG8=").is_ok());
+        assert!(KeyAttributeType::B.validate("not base64!").is_err());
+    }
+
+    #[test]
+    fn test_partition_stats_record() {
+        let mut stats = PartitionStats::default();
+        for sort_key in ["ORDER#001", "ORDER#002", "PROFILE", "RETURN|7"] {
+            stats.record(Some(sort_key.to_string()));
+        }
+
+        assert_eq!(stats.items, 4);
+        assert_eq!(stats.min_sort_key.as_deref(), Some("ORDER#001"));
+        assert_eq!(stats.max_sort_key.as_deref(), Some("RETURN|7"));
+        assert_eq!(
+            stats.prefixes,
+            BTreeMap::from([
+                (String::new(), 1),
+                ("ORDER".to_string(), 2),
+                ("RETURN".to_string(), 1),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_attribute_value_by_type() {
+        assert_eq!(
+            KeyAttributeType::S.attribute_value("42"),
+            AttributeValue::S("42".to_string())
+        );
+        assert_eq!(
+            KeyAttributeType::N.attribute_value(" 42 "),
+            AttributeValue::N("42".to_string())
+        );
+        assert_eq!(
+            KeyAttributeType::B.attribute_value("aGVsbG8="),
+            AttributeValue::B(Blob::new("hello"))
+        );
+    }
+
+    #[test]
+    fn test_key_typed_item() {
+        let keys = [
+            KeyAttribute {
+                name: "id".to_string(),
+                attribute_type: KeyAttributeType::B,
+            },
+            KeyAttribute {
+                name: "version".to_string(),
+                attribute_type: KeyAttributeType::N,
+            },
+        ];
+        let item = serde_json::json!({ "id": "aGVsbG8=", "version": 3, "note": "aGVsbG8=" });
+        let Value::Object(item) = item else {
+            unreachable!()
+        };
+
+        let item = key_typed_item(item, &keys);
+
+        assert_eq!(item["id"], AttributeValue::B(Blob::new("hello")));
+        assert_eq!(item["version"], AttributeValue::N("3".to_string()));
+        assert_eq!(item["note"], AttributeValue::S("aGVsbG8=".to_string()));
+    }
+}
@@ -0,0 +1,153 @@
+use std::{
+    borrow::Cow,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Shown in place of, or next to, the value of an encrypted attribute.
+pub const LOCK_BADGE: &str = "🔒";
+
+/// Attributes of a table that are encrypted on the client, such as by the DynamoDB Encryption
+/// SDK. Their values are ciphertext, so they are shown as a badge and left out of fuzzy search.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedAttributes {
+    pub table: String,
+    pub attributes: Vec<String>,
+    /// A shell command that decrypts a value for the detail view. It reads the value on stdin,
+    /// with binary values as base64, and writes the plaintext to stdout. The table and attribute
+    /// are named by `DYNOTUI_TABLE` and `DYNOTUI_ATTRIBUTE`.
+    #[serde(default)]
+    pub decrypt_command: Option<String>,
+}
+
+pub fn for_table<'a>(
+    encrypted: &'a [EncryptedAttributes],
+    table: &str,
+) -> Option<&'a EncryptedAttributes> {
+    encrypted.iter().find(|encrypted| encrypted.table == table)
+}
+
+/// The names of the attributes of `table` that are encrypted.
+pub fn attributes_of<'a>(encrypted: &'a [EncryptedAttributes], table: &str) -> &'a [String] {
+    for_table(encrypted, table).map_or(&[], |encrypted| encrypted.attributes.as_slice())
+}
+
+/// The item with the value of each encrypted attribute it has replaced by a lock badge.
+pub fn badge(item: &mut Value, attributes: &[String]) {
+    let Value::Object(map) = item else {
+        return;
+    };
+    for attribute in attributes {
+        if let Some(value) = map.get_mut(attribute) {
+            *value = Value::String(LOCK_BADGE.to_string());
+        }
+    }
+}
+
+/// A record written again with a lock badge for each encrypted attribute. Records that are not
+/// JSON, and every record when no attribute is encrypted, are left as they are.
+pub fn badge_record<'a>(record: Cow<'a, str>, attributes: &[String]) -> Cow<'a, str> {
+    if attributes.is_empty() {
+        return record;
+    }
+    match serde_json::from_str::<Value>(&record) {
+        Ok(mut item) => {
+            badge(&mut item, attributes);
+            Cow::Owned(item.to_string())
+        }
+        Err(_) => record,
+    }
+}
+
+/// The item without its encrypted attributes, whose ciphertext would match searches at random.
+pub fn without_encrypted(item: &Value, attributes: &[String]) -> Value {
+    match item {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .filter(|(name, _)| !attributes.contains(name))
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect(),
+        ),
+        item => item.clone(),
+    }
+}
+
+/// Runs the decrypt command of a table on the value of one of its attributes.
+pub fn decrypt(
+    command: &str,
+    table: &str,
+    attribute: &str,
+    value: &Value,
+) -> Result<String, String> {
+    let input = match value {
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    };
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("DYNOTUI_TABLE", table)
+        .env("DYNOTUI_ATTRIBUTE", attribute)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| err.to_string())?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input.as_bytes())
+            .map_err(|err| err.to_string())?;
+    }
+    let output = child.wait_with_output().map_err(|err| err.to_string())?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_badge_and_hide_encrypted_attributes() {
+        let attributes = names(&["ssn", "card"]);
+
+        assert_eq!(
+            badge_record(Cow::Borrowed(r#"{"id":"1","ssn":"AQIDBA=="}"#), &attributes),
+            r#"{"id":"1","ssn":"🔒"}"#
+        );
+        assert_eq!(
+            badge_record(Cow::Borrowed("not json"), &attributes),
+            "not json"
+        );
+        assert_eq!(
+            without_encrypted(&json!({ "id": "1", "ssn": "AQIDBA==" }), &attributes),
+            json!({ "id": "1" })
+        );
+    }
+
+    #[test]
+    fn test_decrypt_command() {
+        assert_eq!(
+            decrypt(
+                "echo \"$DYNOTUI_TABLE.$DYNOTUI_ATTRIBUTE=$(cat)\"",
+                "users",
+                "ssn",
+                &json!("plain")
+            ),
+            Ok("users.ssn=plain".to_string())
+        );
+        assert!(decrypt("echo nope >&2; exit 1", "users", "ssn", &json!("x")).is_err());
+    }
+}
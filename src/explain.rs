@@ -0,0 +1,138 @@
+use crate::{
+    data::KeyQuery,
+    time_range::{after_bound, split_range},
+};
+
+/// Explains, in a few lines, what submitting the query form would ask of DynamoDB: the kind of
+/// request, its key condition, how many items it reads, where the filter runs, and how
+/// consistent the result is. `query` is `None` while no partition key value has been entered.
+pub fn explain(table: &str, query: Option<&KeyQuery>, filter: &str) -> Vec<String> {
+    let Some(query) = query else {
+        return vec![
+            format!("Scan of {table}: without a partition key value there is no key condition."),
+            "A scan reads every item of the table and is billed for all of them, however few \
+             are shown."
+                .to_string(),
+            "Enter a partition key value to query a single partition instead.".to_string(),
+        ];
+    };
+
+    let partition_key = &query.partition_key;
+    let mut condition = format!(
+        "{} = {}",
+        partition_key.name,
+        quote(&query.partition_key_value)
+    );
+    let mut reads = "every item of the partition".to_string();
+    if let Some((sort_key, value)) = &query.sort_key {
+        match (split_range(value), after_bound(value)) {
+            (Some((from, to)), _) => {
+                condition.push_str(&format!(
+                    " AND {} BETWEEN {} AND {}",
+                    sort_key.name,
+                    quote(from),
+                    quote(to)
+                ));
+                reads = format!("the items of the partition with {} in range", sort_key.name);
+            }
+            (None, Some(from)) => {
+                condition.push_str(&format!(" AND {} > {}", sort_key.name, quote(from)));
+                reads = format!("the items of the partition after {}", quote(from));
+            }
+            (None, None) => {
+                condition.push_str(&format!(" AND {} = {}", sort_key.name, quote(value)));
+                reads = "the single item with this key".to_string();
+            }
+        }
+    }
+
+    let mut lines = vec![
+        format!("Query on the base table {table}, not an index."),
+        format!("Key condition: {condition}"),
+        match query.limit {
+            Some(limit) => format!("Reads {reads}, stopping after {limit} items."),
+            None => format!("Reads {reads}, a page at a time, in sort key order."),
+        },
+    ];
+    lines.push(match filter.trim() {
+        "" => "No filter: every item read is shown.".to_string(),
+        filter => format!(
+            "The filter \"{filter}\" runs here, on the items read, so it does not lower the cost."
+        ),
+    });
+    lines.push(
+        "Eventually consistent: a write from the last second may be missing, at half the cost \
+         of a strongly consistent read."
+            .to_string(),
+    );
+    lines
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{value}\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::data::{KeyAttribute, KeyAttributeType};
+
+    fn key(name: &str) -> KeyAttribute {
+        KeyAttribute {
+            name: name.to_string(),
+            attribute_type: KeyAttributeType::S,
+        }
+    }
+
+    #[test]
+    fn test_explain_scan() {
+        let lines = explain("orders", None, "");
+
+        assert!(lines[0].starts_with("Scan of orders"));
+    }
+
+    #[test]
+    fn test_explain_query() {
+        let query = KeyQuery {
+            partition_key: key("pk"),
+            partition_key_value: "USER#1".to_string(),
+            sort_key: Some((key("sk"), "2024-01..2024-02".to_string())),
+            limit: Some(10),
+        };
+
+        assert_eq!(
+            explain("orders", Some(&query), "paid"),
+            vec![
+                "Query on the base table orders, not an index.",
+                "Key condition: pk = \"USER#1\" AND sk BETWEEN \"2024-01\" AND \"2024-02\"",
+                "Reads the items of the partition with sk in range, stopping after 10 items.",
+                "The filter \"paid\" runs here, on the items read, so it does not lower the cost.",
+                "Eventually consistent: a write from the last second may be missing, at half \
+                 the cost of a strongly consistent read.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_explain_query_after() {
+        let query = KeyQuery {
+            partition_key: key("pk"),
+            partition_key_value: "USER#1".to_string(),
+            sort_key: Some((key("sk"), ">ORDER#0042".to_string())),
+            limit: None,
+        };
+
+        let lines = explain("orders", Some(&query), "");
+        assert_eq!(
+            lines[1],
+            "Key condition: pk = \"USER#1\" AND sk > \"ORDER#0042\""
+        );
+        assert_eq!(
+            lines[2],
+            "Reads the items of the partition after \"ORDER#0042\", a page at a time, in sort \
+             key order."
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::config::get_data_dir;
+
+/// An item a bulk operation could not write, as JSON, and the reason DynamoDB gave.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FailedItem {
+    pub item: Value,
+    pub reason: String,
+}
+
+/// The report of an operation: what it was, and every item that failed with its reason.
+fn report(operation: &str, table: &str, failures: &[FailedItem]) -> Value {
+    json!({
+        "operation": operation,
+        "table": table,
+        "failed": failures.len(),
+        "failures": failures,
+    })
+}
+
+/// Just the failed items, one per line, ready to be written again once fixed.
+fn retry_ndjson(failures: &[FailedItem]) -> String {
+    failures
+        .iter()
+        .map(|failure| format!("{}\n", failure.item))
+        .collect()
+}
+
+/// Writes the report and the items to retry to the `reports` folder of the data directory,
+/// returning the path of the report. The items are next to it, with the `.ndjson` extension.
+fn write(operation: &str, table: &str, failures: &[FailedItem]) -> io::Result<PathBuf> {
+    let dir = get_data_dir().join("reports");
+    fs::create_dir_all(&dir)?;
+
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let report_path = dir.join(format!("{table}-{seconds}.json"));
+    write_files(&report_path, operation, table, failures)?;
+    Ok(report_path)
+}
+
+fn write_files(
+    report_path: &Path,
+    operation: &str,
+    table: &str,
+    failures: &[FailedItem],
+) -> io::Result<()> {
+    let report = serde_json::to_string_pretty(&report(operation, table, failures))?;
+    fs::write(report_path, report)?;
+    fs::write(report_path.with_extension("ndjson"), retry_ndjson(failures))
+}
+
+/// The outcome of a bulk operation as its job reports it. Any items that failed are written to a
+/// report first, so a partial failure is not lost once the job is dropped from the list.
+pub fn finish(
+    operation: &str,
+    table: &str,
+    failures: &[FailedItem],
+    result: Result<(), String>,
+) -> Result<(), String> {
+    if failures.is_empty() {
+        return result;
+    }
+
+    let report = match write(operation, table, failures) {
+        Ok(path) => format!("report written to {}", path.display()),
+        Err(err) => format!("the report could not be written: {err}"),
+    };
+    let failed = format!("{} items failed, {report}", failures.len());
+    Err(match result {
+        Ok(()) => failed,
+        Err(reason) => format!("{reason} ({failed})"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn failures() -> Vec<FailedItem> {
+        vec![
+            FailedItem {
+                item: json!({ "pk": "a" }),
+                reason: "Item size has exceeded the maximum allowed size".to_string(),
+            },
+            FailedItem {
+                item: json!({ "pk": "b" }),
+                reason: "Type mismatch for key".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_failure_report() {
+        assert_eq!(
+            report("Seed 100 items into orders", "orders", &failures()),
+            json!({
+                "operation": "Seed 100 items into orders",
+                "table": "orders",
+                "failed": 2,
+                "failures": [
+                    { "item": { "pk": "a" }, "reason": "Item size has exceeded the maximum allowed size" },
+                    { "item": { "pk": "b" }, "reason": "Type mismatch for key" },
+                ],
+            })
+        );
+        assert_eq!(
+            retry_ndjson(&failures()),
+            "{\"pk\":\"a\"}\n{\"pk\":\"b\"}\n"
+        );
+    }
+
+    #[test]
+    fn test_write_failure_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let report_path = dir.path().join("orders-1.json");
+        write_files(&report_path, "Copy orders", "orders", &failures()).unwrap();
+
+        let ndjson = fs::read_to_string(report_path.with_extension("ndjson")).unwrap();
+        assert_eq!(ndjson.lines().count(), 2);
+        let report: Value =
+            serde_json::from_str(&fs::read_to_string(&report_path).unwrap()).unwrap();
+        assert_eq!(report["failed"], 2);
+    }
+}
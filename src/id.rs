@@ -0,0 +1,110 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand::RngCore;
+
+use crate::action::Action;
+
+const CROCKFORD_BASE32: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const BASE62: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// KSUID timestamps count seconds from 2014-05-13 rather than from the Unix epoch.
+const KSUID_EPOCH_SECS: u64 = 1_400_000_000;
+
+/// The kinds of identifiers that can be typed into an input with a single key.
+#[derive(Debug, Clone, Copy)]
+enum IdKind {
+    Uuid,
+    Ulid,
+    Ksuid,
+}
+
+/// A fresh identifier for one of the `Insert*` actions, which insert it at the cursor.
+pub fn generate_for(action: &Action) -> Option<String> {
+    let kind = match action {
+        Action::InsertUuid => IdKind::Uuid,
+        Action::InsertUlid => IdKind::Ulid,
+        Action::InsertKsuid => IdKind::Ksuid,
+        _ => return None,
+    };
+    Some(generate(kind))
+}
+
+fn generate(kind: IdKind) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let mut random = [0; 16];
+    rand::thread_rng().fill_bytes(&mut random);
+
+    match kind {
+        IdKind::Uuid => uuid::Uuid::new_v4().to_string(),
+        IdKind::Ulid => {
+            let mut entropy = [0; 10];
+            entropy.copy_from_slice(&random[..10]);
+            encode_ulid(now.as_millis() as u64, entropy)
+        }
+        IdKind::Ksuid => encode_ksuid(
+            now.as_secs().saturating_sub(KSUID_EPOCH_SECS) as u32,
+            random,
+        ),
+    }
+}
+
+/// A 48 bit millisecond timestamp followed by 80 random bits, as 26 Crockford base32 digits.
+fn encode_ulid(timestamp_ms: u64, entropy: [u8; 10]) -> String {
+    let mut value = u128::from(timestamp_ms & 0xFFFF_FFFF_FFFF) << 80;
+    for (index, byte) in entropy.iter().enumerate() {
+        value |= u128::from(*byte) << (72 - 8 * index);
+    }
+
+    (0..26)
+        .rev()
+        .map(|digit| CROCKFORD_BASE32[((value >> (5 * digit)) & 0x1F) as usize] as char)
+        .collect()
+}
+
+/// A 32 bit second timestamp followed by 128 random bits, as 27 base62 digits.
+fn encode_ksuid(timestamp: u32, payload: [u8; 16]) -> String {
+    let mut bytes = [0; 20];
+    bytes[..4].copy_from_slice(&timestamp.to_be_bytes());
+    bytes[4..].copy_from_slice(&payload);
+
+    // The 160 bit value does not fit in an integer type, so divide it as big-endian bytes.
+    let mut digits = Vec::with_capacity(27);
+    let mut number = bytes.to_vec();
+    while digits.len() < 27 {
+        let mut remainder = 0u32;
+        for byte in number.iter_mut() {
+            let accumulator = (remainder << 8) | u32::from(*byte);
+            *byte = (accumulator / 62) as u8;
+            remainder = accumulator % 62;
+        }
+        digits.push(BASE62[remainder as usize] as char);
+    }
+
+    digits.iter().rev().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_encode_ulid() {
+        assert_eq!(encode_ulid(0, [0; 10]), "00000000000000000000000000");
+        assert_eq!(encode_ulid(1_469_918_176_385, [0; 10])[..10], *"01ARYZ6S41");
+        assert_eq!(encode_ulid(0, [0xFF; 10]), "0000000000ZZZZZZZZZZZZZZZZ");
+    }
+
+    #[test]
+    fn test_encode_ksuid() {
+        assert_eq!(encode_ksuid(0, [0; 16]), "000000000000000000000000000");
+        assert_eq!(
+            encode_ksuid(u32::MAX, [0xFF; 16]),
+            "aWgEPTl1tmebfsQzFP4bxwgy80V"
+        );
+        assert_eq!(generate_for(&Action::InsertKsuid).unwrap().len(), 27);
+    }
+}
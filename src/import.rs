@@ -0,0 +1,255 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// How many of the items that would overwrite another are shown with their changes.
+const SAMPLE_DIFFS: usize = 5;
+
+/// What writing an item of an import file would do to the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportStatus {
+    Create,
+    Overwrite,
+    /// The table already holds the same item, so it is not written.
+    Identical,
+}
+
+/// How the items of an import are written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImportMode {
+    /// Replaces any item with the same key.
+    #[default]
+    Overwrite,
+    /// Writes only the items whose key is not taken, reporting the others as conflicts.
+    InsertOnly,
+    /// Sets the attributes the file provides, keeping the other attributes of existing items.
+    Merge,
+}
+
+impl ImportMode {
+    pub fn next(self) -> Self {
+        match self {
+            ImportMode::Overwrite => ImportMode::InsertOnly,
+            ImportMode::InsertOnly => ImportMode::Merge,
+            ImportMode::Merge => ImportMode::Overwrite,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ImportMode::Overwrite => "overwrite",
+            ImportMode::InsertOnly => "insert only",
+            ImportMode::Merge => "merge",
+        }
+    }
+}
+
+/// The update expression that sets the attributes at the given positions of a merged item, named
+/// `#a<position>` and valued `:v<position>`.
+pub fn merge_expression(positions: usize) -> String {
+    let assignments: Vec<String> = (0..positions)
+        .map(|position| format!("#a{position} = :v{position}"))
+        .collect();
+    format!("SET {}", assignments.join(", "))
+}
+
+/// An item of the file that would overwrite a different one, with what would change.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportSample {
+    /// The key attributes of the item, as a JSON object.
+    pub key: String,
+    pub changes: Vec<String>,
+}
+
+/// What importing a file would do, shown before anything is written.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportPreview {
+    pub table: String,
+    pub path: String,
+    pub creates: usize,
+    pub overwrites: usize,
+    pub identical: usize,
+    pub samples: Vec<ImportSample>,
+}
+
+/// Reads the items of an import file: a JSON array of objects, a single object, or one object
+/// per line (NDJSON).
+pub fn read_items(path: &Path) -> Result<Vec<Value>, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+    parse_items(&text)
+}
+
+pub fn parse_items(text: &str) -> Result<Vec<Value>, String> {
+    let items = match serde_json::from_str::<Value>(text) {
+        Ok(Value::Array(items)) => items,
+        Ok(item @ Value::Object(_)) => vec![item],
+        Ok(_) => return Err("The file must hold JSON objects".to_string()),
+        Err(_) => text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line).map_err(|err| format!("Line {}: {err}", index + 1))
+            })
+            .collect::<Result<_, _>>()?,
+    };
+
+    match items.iter().position(|item| !item.is_object()) {
+        Some(index) => Err(format!("Item {} is not a JSON object", index + 1)),
+        None => Ok(items),
+    }
+}
+
+/// The key attributes of an item, or `None` when it misses one of them.
+pub fn item_key(item: &Value, key_names: &[String]) -> Option<Map<String, Value>> {
+    key_names
+        .iter()
+        .map(|name| Some((name.clone(), item.get(name)?.clone())))
+        .collect()
+}
+
+/// Compares each incoming item with the existing item of the same key, if any.
+pub fn classify(
+    table: &str,
+    path: &str,
+    items: &[Value],
+    existing: &[Value],
+    key_names: &[String],
+) -> (ImportPreview, Vec<ImportStatus>) {
+    let mut preview = ImportPreview {
+        table: table.to_string(),
+        path: path.to_string(),
+        ..ImportPreview::default()
+    };
+
+    let statuses = items
+        .iter()
+        .map(|item| {
+            let key = item_key(item, key_names);
+            let current = key.as_ref().and_then(|key| {
+                existing
+                    .iter()
+                    .find(|existing| item_key(existing, key_names).as_ref() == Some(key))
+            });
+            match current {
+                None => {
+                    preview.creates += 1;
+                    ImportStatus::Create
+                }
+                Some(current) if current == item => {
+                    preview.identical += 1;
+                    ImportStatus::Identical
+                }
+                Some(current) => {
+                    preview.overwrites += 1;
+                    if preview.samples.len() < SAMPLE_DIFFS {
+                        preview.samples.push(ImportSample {
+                            key: Value::Object(key.unwrap_or_default()).to_string(),
+                            changes: diff(current, item),
+                        });
+                    }
+                    ImportStatus::Overwrite
+                }
+            }
+        })
+        .collect();
+
+    (preview, statuses)
+}
+
+/// The top-level attributes that differ between two versions of an item.
+pub fn diff(old: &Value, new: &Value) -> Vec<String> {
+    let empty = Map::new();
+    let old = old.as_object().unwrap_or(&empty);
+    let new = new.as_object().unwrap_or(&empty);
+
+    let mut changes = Vec::new();
+    for (name, value) in new {
+        match old.get(name) {
+            None => changes.push(format!("+ {name}: {value}")),
+            Some(previous) if previous != value => {
+                changes.push(format!("~ {name}: {previous} → {value}"))
+            }
+            Some(_) => {}
+        }
+    }
+    for (name, value) in old {
+        if !new.contains_key(name) {
+            changes.push(format!("- {name}: {value}"));
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_items() {
+        assert_eq!(
+            parse_items(r#"[{"pk":"a"},{"pk":"b"}]"#).unwrap(),
+            vec![json!({ "pk": "a" }), json!({ "pk": "b" })]
+        );
+        assert_eq!(
+            parse_items("{\"pk\":\"a\"}\n\n{\"pk\":\"b\"}\n").unwrap(),
+            vec![json!({ "pk": "a" }), json!({ "pk": "b" })]
+        );
+        assert_eq!(
+            parse_items(r#"[{"pk":"a"},1]"#),
+            Err("Item 2 is not a JSON object".to_string())
+        );
+        assert!(parse_items("{\"pk\":\"a\"}\nnot json").is_err());
+    }
+
+    #[test]
+    fn test_merge_expression() {
+        assert_eq!(merge_expression(1), "SET #a0 = :v0");
+        assert_eq!(merge_expression(3), "SET #a0 = :v0, #a1 = :v1, #a2 = :v2");
+    }
+
+    #[test]
+    fn test_classify_items() {
+        let keys = vec!["pk".to_string()];
+        let items = [
+            json!({ "pk": "new", "total": 1 }),
+            json!({ "pk": "same", "total": 2 }),
+            json!({ "pk": "changed", "total": 3, "note": "x" }),
+        ];
+        let existing = [
+            json!({ "pk": "same", "total": 2 }),
+            json!({ "pk": "changed", "total": 4, "status": "paid" }),
+        ];
+
+        let (preview, statuses) = classify("orders", "orders.json", &items, &existing, &keys);
+
+        assert_eq!(
+            statuses,
+            vec![
+                ImportStatus::Create,
+                ImportStatus::Identical,
+                ImportStatus::Overwrite
+            ]
+        );
+        assert_eq!(
+            (preview.creates, preview.overwrites, preview.identical),
+            (1, 1, 1)
+        );
+        assert_eq!(
+            preview.samples,
+            vec![ImportSample {
+                key: r#"{"pk":"changed"}"#.to_string(),
+                changes: vec![
+                    "+ note: \"x\"".to_string(),
+                    "~ total: 4 → 3".to_string(),
+                    "- status: \"paid\"".to_string(),
+                ],
+            }]
+        );
+    }
+}
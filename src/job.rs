@@ -0,0 +1,261 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::{sync::mpsc, task};
+
+use crate::data::{FetchResponse, RequestId};
+
+/// A long-running request, listed in the jobs popup until it is dropped to make room for newer
+/// ones. Jobs share the ID of the request that started them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Job {
+    pub id: RequestId,
+    pub label: String,
+    pub state: JobState,
+    /// Writes items, so stopping it part way leaves some of them written.
+    pub writes: bool,
+    /// Items processed so far, and how many there are in all when that is known up front.
+    pub progress: Option<(usize, Option<usize>)>,
+    /// Pages fetched so far, for jobs that read a table page by page.
+    pub pages: Option<usize>,
+    #[serde(skip, default = "Instant::now")]
+    pub started_at: Instant,
+    #[serde(skip)]
+    pub finished_at: Option<Instant>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JobState {
+    Running,
+    Finished,
+    Failed(String),
+    Cancelled,
+}
+
+impl Job {
+    pub fn new(id: RequestId, label: String, writes: bool) -> Self {
+        Self {
+            id,
+            label,
+            state: JobState::Running,
+            writes,
+            progress: None,
+            pages: None,
+            started_at: Instant::now(),
+            finished_at: None,
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.state == JobState::Running
+    }
+
+    /// How long the job ran, or has been running so far.
+    pub fn elapsed(&self) -> Duration {
+        self.finished_at
+            .unwrap_or_else(Instant::now)
+            .duration_since(self.started_at)
+    }
+
+    pub fn finish(&mut self, state: JobState) {
+        self.state = state;
+        self.finished_at = Some(Instant::now());
+    }
+
+    /// Such as `40/120 (33%)` or `300 items, 3 pages`, or empty before the first report.
+    pub fn progress_label(&self) -> String {
+        let progress = match self.progress {
+            Some((done, Some(total))) if total > 0 => {
+                format!("{done}/{total} ({}%)", done * 100 / total)
+            }
+            Some((done, _)) => format!("{done} items"),
+            None => String::new(),
+        };
+        match self.pages {
+            Some(pages) if !progress.is_empty() => format!("{progress}, {pages} pages"),
+            _ => progress,
+        }
+    }
+
+    /// A line on how the job ended, printed once the app has quit.
+    pub fn summary(&self) -> String {
+        let state = match &self.state {
+            JobState::Running => "still running".to_string(),
+            JobState::Finished => "done".to_string(),
+            JobState::Failed(reason) => format!("failed: {reason}"),
+            JobState::Cancelled => "cancelled".to_string(),
+        };
+        match self.progress_label() {
+            progress if progress.is_empty() => format!("{}: {state}", self.label),
+            progress => format!("{}: {state} after {progress}", self.label),
+        }
+    }
+}
+
+/// Runs jobs for the fetch worker, each on its own task so it can be cancelled and does not hold
+/// up other requests.
+pub struct JobRunner {
+    tasks: HashMap<RequestId, task::JoinHandle<()>>,
+    response_tx: mpsc::Sender<FetchResponse>,
+}
+
+impl JobRunner {
+    pub fn new(response_tx: mpsc::Sender<FetchResponse>) -> Self {
+        Self {
+            tasks: HashMap::new(),
+            response_tx,
+        }
+    }
+
+    /// Starts the job made by `job`. Once it is done, its outcome is reported, followed by the
+    /// end of its request.
+    pub fn spawn<F, Fut>(&mut self, id: RequestId, job: F)
+    where
+        F: FnOnce(JobProgress) -> Fut,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.tasks.retain(|_, handle| !handle.is_finished());
+
+        let future = job(JobProgress {
+            id,
+            response_tx: self.response_tx.clone(),
+        });
+        let response_tx = self.response_tx.clone();
+        let handle = task::spawn(async move {
+            let state = match future.await {
+                Ok(()) => JobState::Finished,
+                Err(reason) => JobState::Failed(reason),
+            };
+            let _ = response_tx
+                .send(FetchResponse::JobFinished(id, state))
+                .await;
+            let _ = response_tx.send(FetchResponse::RequestFinished(id)).await;
+        });
+        self.tasks.insert(id, handle);
+    }
+
+    /// Aborts a job that is still running and reports it as cancelled.
+    pub async fn cancel(&mut self, id: RequestId) {
+        let Some(handle) = self.tasks.remove(&id) else {
+            return;
+        };
+        if handle.is_finished() {
+            return;
+        }
+
+        handle.abort();
+        let _ = self
+            .response_tx
+            .send(FetchResponse::JobFinished(id, JobState::Cancelled))
+            .await;
+        let _ = self
+            .response_tx
+            .send(FetchResponse::RequestFinished(id))
+            .await;
+    }
+}
+
+/// Handed to a job so it can report how far along it is.
+#[derive(Clone)]
+pub struct JobProgress {
+    id: RequestId,
+    response_tx: mpsc::Sender<FetchResponse>,
+}
+
+impl JobProgress {
+    /// Progress updates are dropped rather than waited on when the UI is behind; the next one
+    /// catches it up.
+    pub fn report(&self, done: usize, total: Option<usize>) {
+        let _ = self
+            .response_tx
+            .try_send(FetchResponse::JobProgress(self.id, done, total));
+    }
+
+    /// Like `report`, for jobs that read page by page and cannot tell how many items are left.
+    pub fn report_pages(&self, done: usize, pages: usize) {
+        let _ = self
+            .response_tx
+            .try_send(FetchResponse::JobPageProgress(self.id, done, pages));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_job_summary() {
+        let mut job = Job::new(3, "Seed 120 items into orders".to_string(), true);
+        assert_eq!(job.summary(), "Seed 120 items into orders: still running");
+
+        job.progress = Some((40, Some(120)));
+        job.finish(JobState::Cancelled);
+        assert_eq!(
+            job.summary(),
+            "Seed 120 items into orders: cancelled after 40/120 (33%)"
+        );
+
+        let mut job = Job::new(4, "Dump orders to orders.ndjson".to_string(), false);
+        job.progress = Some((250, None));
+        job.pages = Some(3);
+        job.finish(JobState::Finished);
+        assert_eq!(
+            job.summary(),
+            "Dump orders to orders.ndjson: done after 250 items, 3 pages"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancel_job() {
+        let (response_tx, mut response_rx) = mpsc::channel(10);
+        let mut jobs = JobRunner::new(response_tx);
+
+        jobs.spawn(7, |_| future::pending());
+        jobs.cancel(7).await;
+
+        assert!(matches!(
+            response_rx.recv().await,
+            Some(FetchResponse::JobFinished(7, JobState::Cancelled))
+        ));
+        assert!(matches!(
+            response_rx.recv().await,
+            Some(FetchResponse::RequestFinished(7))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_failed_job() {
+        let (response_tx, mut response_rx) = mpsc::channel(10);
+        let mut jobs = JobRunner::new(response_tx);
+
+        jobs.spawn(1, |progress| async move {
+            progress.report(3, Some(4));
+            Err("throttled".to_string())
+        });
+
+        let mut responses = Vec::new();
+        while let Some(response) = response_rx.recv().await {
+            let finished = matches!(response, FetchResponse::RequestFinished(_));
+            responses.push(format!("{response:?}"));
+            if finished {
+                break;
+            }
+        }
+        assert_eq!(
+            responses,
+            vec![
+                "JobProgress(1, 3, Some(4))",
+                "JobFinished(1, Failed(\"throttled\"))",
+                "RequestFinished(1)",
+            ]
+        );
+    }
+}
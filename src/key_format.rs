@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{
+    data::{KeyAttribute, KeyAttributeType},
+    key_order,
+    time_range::sort_key_text,
+};
+
+/// The ways the key of an item can be copied, to be pasted into another tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyFormat {
+    /// The key attributes as a JSON object, `{"pk":"USER#1"}`.
+    Json,
+    /// A `--key` argument of the AWS CLI, with typed attribute values.
+    CliArgument,
+    /// A PartiQL `WHERE` clause selecting the item.
+    PartiqlWhere,
+}
+
+impl KeyFormat {
+    pub const ALL: [KeyFormat; 3] = [
+        KeyFormat::Json,
+        KeyFormat::CliArgument,
+        KeyFormat::PartiqlWhere,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            KeyFormat::Json => "JSON",
+            KeyFormat::CliArgument => "AWS CLI --key",
+            KeyFormat::PartiqlWhere => "PartiQL WHERE",
+        }
+    }
+
+    /// The key of an item, given as its key attributes with their values read from the item.
+    pub fn format(self, key: &[(KeyAttribute, Value)]) -> String {
+        // The partition key leads, whatever the names of the attributes.
+        let names: Vec<String> = key
+            .iter()
+            .map(|(attribute, _)| attribute.name.clone())
+            .collect();
+        let texts = key
+            .iter()
+            .map(|(attribute, value)| (attribute, sort_key_text(value).unwrap_or_default()));
+        match self {
+            KeyFormat::Json => {
+                let map: Map<String, Value> = texts
+                    .map(|(attribute, text)| {
+                        let value = match attribute.attribute_type {
+                            KeyAttributeType::N => {
+                                serde_json::from_str(&text).unwrap_or(Value::String(text))
+                            }
+                            KeyAttributeType::S | KeyAttributeType::B => Value::String(text),
+                        };
+                        (attribute.name.clone(), value)
+                    })
+                    .collect();
+                key_order::to_string(&Value::Object(map), &names)
+            }
+            KeyFormat::CliArgument => {
+                let map: Map<String, Value> = texts
+                    .map(|(attribute, text)| {
+                        let type_name = format!("{:?}", attribute.attribute_type);
+                        let mut typed = Map::new();
+                        typed.insert(type_name, Value::String(text));
+                        (attribute.name.clone(), Value::Object(typed))
+                    })
+                    .collect();
+                let json = key_order::to_string(&Value::Object(map), &names);
+                format!("--key '{}'", json.replace('\'', r"'\''"))
+            }
+            KeyFormat::PartiqlWhere => {
+                let conditions: Vec<String> = texts
+                    .map(|(attribute, text)| {
+                        let name = attribute.name.replace('"', "\"\"");
+                        match attribute.attribute_type {
+                            KeyAttributeType::N => format!("\"{name}\" = {text}"),
+                            // PartiQL has no binary literals, so binary keys are written as their
+                            // base64 text, to be swapped for a parameter.
+                            KeyAttributeType::S | KeyAttributeType::B => {
+                                format!("\"{name}\" = '{}'", text.replace('\'', "''"))
+                            }
+                        }
+                    })
+                    .collect();
+                format!("WHERE {}", conditions.join(" AND "))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn key(attribute_type: KeyAttributeType, name: &str, value: Value) -> (KeyAttribute, Value) {
+        (
+            KeyAttribute {
+                name: name.to_string(),
+                attribute_type,
+            },
+            value,
+        )
+    }
+
+    #[test]
+    fn test_key_formats() {
+        let key = [
+            key(KeyAttributeType::S, "user", json!("USER#O'Brien")),
+            key(KeyAttributeType::N, "created", json!(3.0)),
+        ];
+
+        assert_eq!(
+            KeyFormat::Json.format(&key),
+            r#"{"user":"USER#O'Brien","created":3}"#
+        );
+        assert_eq!(
+            KeyFormat::CliArgument.format(&key),
+            r#"--key '{"user":{"S":"USER#O'\''Brien"},"created":{"N":"3"}}'"#
+        );
+        assert_eq!(
+            KeyFormat::PartiqlWhere.format(&key),
+            r#"WHERE "user" = 'USER#O''Brien' AND "created" = 3"#
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use std::borrow::Cow;
+
+use serde::{ser::SerializeMap, ser::SerializeSeq, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+/// The attributes of an item in the order they are shown: the attributes named in `first`, in
+/// that order, then the others sorted by name.
+pub fn ordered<'a>(map: &'a Map<String, Value>, first: &[String]) -> Vec<(&'a String, &'a Value)> {
+    let mut attributes: Vec<_> = first
+        .iter()
+        .filter_map(|name| map.get_key_value(name))
+        .collect();
+    attributes.extend(map.iter().filter(|(name, _)| !first.contains(name)));
+    attributes
+}
+
+/// An item, or a list of items, written with the attributes named in `first` leading each item.
+/// Maps nested in an item keep their attributes sorted by name.
+struct KeysFirst<'a> {
+    value: &'a Value,
+    first: &'a [String],
+}
+
+impl Serialize for KeysFirst<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.value {
+            Value::Object(map) => {
+                let mut state = serializer.serialize_map(Some(map.len()))?;
+                for (name, value) in ordered(map, self.first) {
+                    state.serialize_entry(name, value)?;
+                }
+                state.end()
+            }
+            Value::Array(items) => {
+                let mut state = serializer.serialize_seq(Some(items.len()))?;
+                for value in items {
+                    state.serialize_element(&KeysFirst {
+                        value,
+                        first: self.first,
+                    })?;
+                }
+                state.end()
+            }
+            value => value.serialize(serializer),
+        }
+    }
+}
+
+pub fn to_string(value: &Value, first: &[String]) -> String {
+    serde_json::to_string(&KeysFirst { value, first }).unwrap_or_default()
+}
+
+pub fn to_string_pretty(value: &Value, first: &[String]) -> String {
+    serde_json::to_string_pretty(&KeysFirst { value, first }).unwrap_or_default()
+}
+
+/// A record written again with the attributes named in `first` leading it. Records that are not
+/// JSON, and every record when `first` is empty, are left as they are.
+pub fn reorder<'a>(record: Cow<'a, str>, first: &[String]) -> Cow<'a, str> {
+    if first.is_empty() {
+        return record;
+    }
+    match serde_json::from_str::<Value>(&record) {
+        Ok(item) => Cow::Owned(to_string(&item, first)),
+        Err(_) => record,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_keys_first() {
+        let item = json!({ "amount": 3, "sk": "2024", "pk": "USER#1", "note": { "z": 1, "a": 2 } });
+
+        assert_eq!(
+            to_string(&item, &names(&["pk", "sk"])),
+            r#"{"pk":"USER#1","sk":"2024","amount":3,"note":{"a":2,"z":1}}"#
+        );
+        assert_eq!(
+            to_string(
+                &json!([item, { "pk": "USER#2", "b": 1 }]),
+                &names(&["pk", "sk"])
+            ),
+            r#"[{"pk":"USER#1","sk":"2024","amount":3,"note":{"a":2,"z":1}},{"pk":"USER#2","b":1}]"#
+        );
+    }
+
+    #[test]
+    fn test_reorder_records() {
+        let first = names(&["pk"]);
+
+        assert_eq!(
+            reorder(Cow::Borrowed(r#"{"a":1,"pk":"x"}"#), &first),
+            r#"{"pk":"x","a":1}"#
+        );
+        assert_eq!(reorder(Cow::Borrowed("not json"), &first), "not json");
+        assert_eq!(
+            reorder(Cow::Borrowed(r#"{"a":1,"pk":"x"}"#), &[]),
+            r#"{"a":1,"pk":"x"}"#
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Calls timed in each region after the first one, which sets up the connection.
+pub const LATENCY_SAMPLES: usize = 5;
+
+/// The round trips of the calls made to a region, to tell which one is the fastest to work from.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegionLatency {
+    pub region: String,
+    pub samples: Vec<Duration>,
+    pub error: Option<String>,
+}
+
+impl RegionLatency {
+    pub fn median(&self) -> Option<Duration> {
+        let mut samples = self.samples.clone();
+        samples.sort();
+        samples.get(samples.len() / 2).copied()
+    }
+
+    pub fn fastest(&self) -> Option<Duration> {
+        self.samples.iter().min().copied()
+    }
+
+    pub fn slowest(&self) -> Option<Duration> {
+        self.samples.iter().max().copied()
+    }
+}
+
+/// The region with the lowest median round trip, among those that answered.
+pub fn fastest_region(latencies: &[RegionLatency]) -> Option<&str> {
+    latencies
+        .iter()
+        .filter_map(|latency| Some((latency.median()?, latency.region.as_str())))
+        .min()
+        .map(|(_, region)| region)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn latency(region: &str, millis: &[u64]) -> RegionLatency {
+        RegionLatency {
+            region: region.to_string(),
+            samples: millis.iter().copied().map(Duration::from_millis).collect(),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_latency_stats() {
+        let home = latency("us-east-1", &[90, 30, 40, 35, 200]);
+        assert_eq!(home.median(), Some(Duration::from_millis(40)));
+        assert_eq!(home.fastest(), Some(Duration::from_millis(30)));
+        assert_eq!(home.slowest(), Some(Duration::from_millis(200)));
+
+        let latencies = [
+            home,
+            latency("eu-west-1", &[12, 15, 11]),
+            RegionLatency {
+                region: "ap-south-1".to_string(),
+                error: Some("AccessDenied".to_string()),
+                ..RegionLatency::default()
+            },
+        ];
+        assert_eq!(fastest_region(&latencies), Some("eu-west-1"));
+        assert_eq!(fastest_region(&latencies[2..]), None);
+    }
+}
@@ -1,110 +1,1237 @@
+use std::{collections::HashMap, path::Path};
+
+use access::access_denied;
+use aws_sdk_dynamodb::{
+    error::ProvideErrorMetadata, operation::RequestId as _, types::AttributeValue,
+};
 use clap::Parser;
 use cli::Cli;
-use color_eyre::Result;
+use color_eyre::{eyre::eyre, Result};
+use credentials::credentials_failure;
 use data::{
-    describe_table_key_schema, get_approximate_item_count, load_collections, load_data,
-    query_by_partition_and_sort_key, query_by_partition_key, FetchRequest, FetchResponse,
+    batch_get_items, copy_table, deep_find, describe_replica_regions, describe_table,
+    describe_table_key_schema, describe_table_usage, explore_partition, get_approximate_item_count,
+    get_caller_identity, get_item, get_item_attributes, get_item_in_region, load_collections,
+    load_collections_in_region, load_data, lookup_table_events, merge_items, parallel_scan,
+    probe_latency, probe_permissions, put_item, put_items_if_absent, query_table_data,
+    refresh_credentials, seed_items, write_items, DeepFindResult, FetchRequest, FetchResponse,
+    PartitionStats, RequestId,
+};
+use futures::StreamExt;
+use import::{ImportMode, ImportPreview, ImportStatus};
+use job::{JobProgress, JobRunner};
+use replica::RegionItem;
+use script::Command;
+use serde_json::Value;
+use sso::sso_profile;
+use table_search::RegionTables;
+use template::fill_generated;
+use tokio::{
+    fs::File,
+    io::{AsyncWriteExt, BufWriter},
+    sync::mpsc,
+    task,
 };
-use tokio::{sync::mpsc, task};
 
-use crate::app::App;
+use crate::{
+    api_log::ApiLog,
+    app::App,
+    audit::{AuditEntry, BulkAudit},
+    config::Config,
+    mirror::Mirror,
+    soft_delete::SoftDeleteFilter,
+    util::{dynamodb_to_json, json_to_dynamodb},
+};
 
+mod access;
 mod action;
+mod api_log;
 mod app;
+mod attribute_filter;
+mod audit;
+mod bookmark;
+mod cache;
 mod cli;
+mod cloudtrail;
 mod components;
+mod computed;
 mod config;
-mod constants;
+mod copy;
+mod cost;
+mod credentials;
+mod csv_export;
 mod data;
+mod encryption;
 mod errors;
+mod explain;
+mod failure_report;
+mod id;
+mod import;
+mod job;
+mod key_format;
+mod key_order;
+mod latency;
 mod logging;
+mod metrics;
+mod mirror;
+mod parquet_export;
+mod permission;
+mod pin;
+mod proxy;
+mod record_store;
+mod relation;
+mod relevance;
+mod replica;
+mod schema;
+mod scratchpad;
+mod script;
+mod session;
+mod sink;
+mod snapshot;
+mod soft_delete;
+mod sso;
+mod table_info;
+mod table_search;
+mod tear_out;
+mod template;
+mod theme;
+mod time_range;
 mod tui;
+mod typed_json;
 mod util;
+mod workspace;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     crate::errors::init()?;
     crate::logging::init()?;
 
+    let mut config = Config::new()?;
+    crate::proxy::init(&config.config.proxy)?;
+
+    let args = Cli::parse();
+    if let Some(page_size) = args.page_size {
+        config.config.page_size = page_size;
+    }
+    let mirror_path = args.mirror.clone().or(config.config.sqlite_mirror.clone());
+    // Neither replaying nor browsing offline reads anything new to mirror.
+    let worker_mirror_path = mirror_path
+        .clone()
+        .filter(|_| args.replay.is_none() && !args.offline);
+
     // Set up channels
     let (fetch_tx, mut fetch_rx) = mpsc::channel(10);
     let (response_tx, response_rx) = mpsc::channel(10);
 
     // Spawn the background task
+    let api_log = ApiLog::new(response_tx.clone());
     task::spawn(async move {
-        while let Some(request) = fetch_rx.recv().await {
-            match request {
-                FetchRequest::Tables => {
-                    let collections = load_collections().await;
-                    let _ = response_tx.send(FetchResponse::Tables(collections)).await;
-                }
-                FetchRequest::TableData(collection_name) => {
-                    if let Ok(result) = load_data(&collection_name, None).await {
-                        let (data, has_more, last_evaluated_key) = result;
+        let mut jobs = JobRunner::new(response_tx.clone());
+        // Only one partition is explored at a time, so a new one stops the last.
+        let mut partition_explorer: Option<RequestId> = None;
+        // Read on the first write and for each workspace, for the audit log.
+        let mut identity: Option<String> = None;
+        let mut key_names: HashMap<String, Vec<String>> = HashMap::new();
+        // The views of the tables whose soft-deleted items are left out by DynamoDB.
+        let mut soft_deleted: HashMap<String, SoftDeleteFilter> = HashMap::new();
+        let mut mirror = match worker_mirror_path.as_deref().map(Mirror::open) {
+            Some(Ok(mirror)) => Some(mirror),
+            Some(Err(err)) => {
+                let _ = response_tx
+                    .send(FetchResponse::Error(format!(
+                        "Could not open the SQLite mirror: {err}"
+                    )))
+                    .await;
+                None
+            }
+            None => None,
+        };
 
+        while let Some((id, request)) = fetch_rx.recv().await {
+            match request {
+                FetchRequest::Tables => match load_collections(&api_log).await {
+                    Ok(collections) => {
                         let _ = response_tx
-                            .send(FetchResponse::TableData(data, has_more, last_evaluated_key))
+                            .send(FetchResponse::Tables(Some(collections)))
+                            .await;
+                    }
+                    Err(err) => {
+                        if !report_failure(&response_tx, &err, "ListTables").await {
+                            let _ = response_tx.send(FetchResponse::Tables(None)).await;
+                        }
+                    }
+                },
+                FetchRequest::TableData(collection_name, page_size) => {
+                    match load_data(
+                        &api_log,
+                        &collection_name,
+                        None,
+                        soft_deleted.get(&collection_name),
+                        page_size,
+                    )
+                    .await
+                    {
+                        Ok((data, has_more, last_evaluated_key)) => {
+                            mirror_items(
+                                &api_log,
+                                &response_tx,
+                                &mut mirror,
+                                &collection_name,
+                                &data,
+                            )
                             .await;
+                            let _ = response_tx
+                                .send(FetchResponse::TableData(
+                                    collection_name,
+                                    data,
+                                    has_more,
+                                    last_evaluated_key,
+                                ))
+                                .await;
+                        }
+                        Err(err) => {
+                            report_failure(&response_tx, &err, "Scan").await;
+                        }
                     }
                 }
-                FetchRequest::NextBatchTableData(collection_name, last_evaluated_key) => {
-                    if let Ok(result) = load_data(&collection_name, last_evaluated_key).await {
-                        let (data, has_more, last_evaluated_key) = result;
-
-                        let _ = response_tx
-                            .send(FetchResponse::NextBatchTableData(
-                                data,
-                                has_more,
-                                last_evaluated_key,
-                            ))
+                FetchRequest::NextBatchTableData(
+                    collection_name,
+                    last_evaluated_key,
+                    page_size,
+                ) => {
+                    match load_data(
+                        &api_log,
+                        &collection_name,
+                        last_evaluated_key,
+                        soft_deleted.get(&collection_name),
+                        page_size,
+                    )
+                    .await
+                    {
+                        Ok((data, has_more, last_evaluated_key)) => {
+                            mirror_items(
+                                &api_log,
+                                &response_tx,
+                                &mut mirror,
+                                &collection_name,
+                                &data,
+                            )
                             .await;
+                            let _ = response_tx
+                                .send(FetchResponse::NextBatchTableData(
+                                    collection_name,
+                                    data,
+                                    has_more,
+                                    last_evaluated_key,
+                                ))
+                                .await;
+                        }
+                        Err(err) => {
+                            report_failure(&response_tx, &err, "Scan").await;
+                        }
                     }
                 }
                 FetchRequest::GetApproximateItemCount(collection_name) => {
-                    if let Ok(result) = get_approximate_item_count(&collection_name).await {
-                        let _ = response_tx
-                            .send(FetchResponse::ApproximateTableDataCount(result))
-                            .await;
-                    } else {
-                        let _ = response_tx
-                            .send(FetchResponse::ApproximateTableDataCount(0))
-                            .await;
+                    match get_approximate_item_count(&api_log, &collection_name).await {
+                        Ok(result) => {
+                            let _ = response_tx
+                                .send(FetchResponse::ApproximateTableDataCount(
+                                    collection_name,
+                                    Some(result),
+                                ))
+                                .await;
+                        }
+                        Err(err) => {
+                            report_failure(&response_tx, &err, "DescribeTable").await;
+                            let _ = response_tx
+                                .send(FetchResponse::ApproximateTableDataCount(
+                                    collection_name,
+                                    None,
+                                ))
+                                .await;
+                        }
                     }
                 }
                 FetchRequest::DescribeTable(table_name) => {
-                    if let Ok(result) = describe_table_key_schema(&table_name).await {
+                    match describe_table_key_schema(&api_log, &table_name).await {
+                        Ok(result) => {
+                            let _ = response_tx
+                                .send(FetchResponse::TableDescription(table_name, Some(result)))
+                                .await;
+                        }
+                        Err(err) => {
+                            report_failure(&response_tx, &err, "DescribeTable").await;
+                            let _ = response_tx
+                                .send(FetchResponse::TableDescription(table_name, None))
+                                .await;
+                        }
+                    }
+                }
+                FetchRequest::QueryTableData(table_name, query) => {
+                    match query_table_data(
+                        &api_log,
+                        &table_name,
+                        &query,
+                        None,
+                        soft_deleted.get(&table_name),
+                    )
+                    .await
+                    {
+                        Ok((data, has_more, last_evaluated_key)) => {
+                            mirror_items(&api_log, &response_tx, &mut mirror, &table_name, &data)
+                                .await;
+                            let _ = response_tx
+                                .send(FetchResponse::TableData(
+                                    table_name,
+                                    data,
+                                    has_more,
+                                    last_evaluated_key,
+                                ))
+                                .await;
+                        }
+                        Err(err) => {
+                            report_failure(&response_tx, &err, "Query").await;
+                            let _ = response_tx
+                                .send(FetchResponse::QueryFailed(table_name, error_message(&err)))
+                                .await;
+                        }
+                    }
+                }
+                FetchRequest::FollowQuery(table_name, query) => {
+                    let records = match query_table_data(
+                        &api_log,
+                        &table_name,
+                        &query,
+                        None,
+                        soft_deleted.get(&table_name),
+                    )
+                    .await
+                    {
+                        Ok((records, ..)) => records,
+                        Err(err) => {
+                            report_failure(&response_tx, &err, "Query").await;
+                            Vec::new()
+                        }
+                    };
+                    mirror_items(&api_log, &response_tx, &mut mirror, &table_name, &records).await;
+                    let _ = response_tx
+                        .send(FetchResponse::FollowedTableData(table_name, records))
+                        .await;
+                }
+                FetchRequest::NextBatchQueryTableData(table_name, query, last_evaluated_key) => {
+                    match query_table_data(
+                        &api_log,
+                        &table_name,
+                        &query,
+                        last_evaluated_key,
+                        soft_deleted.get(&table_name),
+                    )
+                    .await
+                    {
+                        Ok((data, has_more, last_evaluated_key)) => {
+                            mirror_items(&api_log, &response_tx, &mut mirror, &table_name, &data)
+                                .await;
+                            let _ = response_tx
+                                .send(FetchResponse::NextBatchTableData(
+                                    table_name,
+                                    data,
+                                    has_more,
+                                    last_evaluated_key,
+                                ))
+                                .await;
+                        }
+                        Err(err) => {
+                            report_failure(&response_tx, &err, "Query").await;
+                        }
+                    }
+                }
+                FetchRequest::RefreshCredentials => {
+                    let response = match refresh_credentials().await {
+                        Ok(expiry) => FetchResponse::CredentialsExpiry(expiry),
+                        Err(err) => FetchResponse::Error(format!(
+                            "Failed to refresh the credentials: {err}"
+                        )),
+                    };
+                    let _ = response_tx.send(response).await;
+                }
+                FetchRequest::CallerIdentity => {
+                    if let Ok((account, arn)) = get_caller_identity().await {
+                        identity = Some(arn.clone());
                         let _ = response_tx
-                            .send(FetchResponse::TableDescription(result))
+                            .send(FetchResponse::CallerIdentity(account, arn))
+                            .await;
+                    }
+                }
+                FetchRequest::ExplorePartition(table_name, partition_key, value, sort_key) => {
+                    if let Some(running) = partition_explorer.take() {
+                        jobs.cancel(running).await;
+                    }
+
+                    let response_tx = response_tx.clone();
+                    let api_log = api_log.clone();
+                    jobs.spawn(id, |progress| async move {
+                        let result = explore_partition(
+                            &api_log,
+                            &table_name,
+                            &partition_key,
+                            &value,
+                            sort_key.as_ref(),
+                            |stats| {
+                                progress.report(stats.items, None);
+                                let _ = response_tx
+                                    .try_send(FetchResponse::PartitionStats(stats.clone()));
+                            },
+                        )
+                        .await;
+
+                        let (stats, outcome) = match result {
+                            Ok(stats) => (stats, Ok(())),
+                            Err(err) => {
+                                report_failure(&response_tx, &err, "Query").await;
+                                let stats = PartitionStats {
+                                    table: table_name,
+                                    partition_key_value: value,
+                                    error: Some(err.to_string()),
+                                    ..PartitionStats::default()
+                                };
+                                (stats, Err(error_message(&err)))
+                            }
+                        };
+                        let _ = response_tx.send(FetchResponse::PartitionStats(stats)).await;
+                        outcome
+                    });
+                    partition_explorer = Some(id);
+
+                    // The job reports when the partition has been read.
+                    continue;
+                }
+                FetchRequest::StopPartitionExplorer => {
+                    if let Some(running) = partition_explorer.take() {
+                        jobs.cancel(running).await;
+                    }
+                }
+                FetchRequest::CancelJob(job) => jobs.cancel(job).await,
+                FetchRequest::DeepFind(table_name, needle, max_matches) => {
+                    let response_tx = response_tx.clone();
+                    let api_log = api_log.clone();
+                    jobs.spawn(id, |progress| async move {
+                        let result =
+                            deep_find(&api_log, &table_name, &needle, max_matches, |scanned| {
+                                progress.report(scanned, None)
+                            })
                             .await;
-                    } else {
+
+                        let (result, outcome) = match result {
+                            Ok(result) => (result, Ok(())),
+                            Err(err) => {
+                                report_failure(&response_tx, &err, "Scan").await;
+                                let result = DeepFindResult {
+                                    error: Some(error_message(&err)),
+                                    ..DeepFindResult::default()
+                                };
+                                (result, Err(error_message(&err)))
+                            }
+                        };
                         let _ = response_tx
-                            .send(FetchResponse::TableDescription((None, None)))
+                            .send(FetchResponse::DeepFindResult(table_name, result))
                             .await;
-                    }
+                        outcome
+                    });
+
+                    // The job reports when the scan has stopped.
+                    continue;
+                }
+                FetchRequest::ParallelScan(table_name, segments, max_items, page_size) => {
+                    let soft_delete = soft_deleted.get(&table_name).cloned();
+                    let response_tx = response_tx.clone();
+                    let api_log = api_log.clone();
+                    jobs.spawn(id, |progress| async move {
+                        let mut pages = parallel_scan(
+                            &api_log,
+                            &table_name,
+                            segments,
+                            page_size,
+                            soft_delete.as_ref(),
+                        )
+                        .await;
+                        let (mut items, mut count) = (0, 0);
+                        while let Some(page) = pages.next().await {
+                            let records = match page {
+                                Ok(records) => records,
+                                Err(err) => {
+                                    report_failure(&response_tx, &err, "Scan").await;
+                                    return Err(error_message(&err));
+                                }
+                            };
+                            items += records.len();
+                            count += 1;
+                            progress.report_pages(items, count);
+                            // The first page replaces the records shown, the others add to them.
+                            // There is no key to go on from, so no page offers more: loading
+                            // more would scan the table again from its start.
+                            let response = if count == 1 {
+                                FetchResponse::TableData(table_name.clone(), records, false, None)
+                            } else {
+                                FetchResponse::NextBatchTableData(
+                                    table_name.clone(),
+                                    records,
+                                    false,
+                                    None,
+                                )
+                            };
+                            let _ = response_tx.send(response).await;
+                            if items >= max_items {
+                                break;
+                            }
+                        }
+                        Ok(())
+                    });
+
+                    // The job reports when every segment has been read.
+                    continue;
+                }
+                FetchRequest::DumpTable(table_name, path, page_size) => {
+                    let response_tx = response_tx.clone();
+                    let api_log = api_log.clone();
+                    jobs.spawn(id, |progress| async move {
+                        dump_table(
+                            &api_log,
+                            &response_tx,
+                            &table_name,
+                            &path,
+                            page_size,
+                            &progress,
+                        )
+                        .await
+                    });
+
+                    // The job reports when the last page has been written.
+                    continue;
                 }
-                FetchRequest::QueryTableByPk(table_name, pk, pk_value) => {
-                    if let Ok(data) = query_by_partition_key(&table_name, &pk, &pk_value).await {
+                FetchRequest::SeedItems(table_name, item, count) => {
+                    let audit = BulkAudit::new(
+                        AuditEntry::bulk(
+                            caller_arn(&mut identity).await,
+                            "SeedItems",
+                            &table_name,
+                            Some(count),
+                        ),
+                        key_attribute_names(&api_log, &table_name, &mut key_names).await,
+                    );
+                    let response_tx = response_tx.clone();
+                    let api_log = api_log.clone();
+                    jobs.spawn(id, |progress| async move {
+                        if let Some(reason) = workspace::refuse_write() {
+                            audit.discard();
+                            return Err(reason);
+                        }
+                        let make_item = || match fill_generated(&item) {
+                            Value::Object(item) => item
+                                .into_iter()
+                                .map(|(name, value)| (name, json_to_dynamodb(value)))
+                                .collect(),
+                            _ => HashMap::new(),
+                        };
+                        let mut failures = Vec::new();
+                        let result = seed_items(
+                            &api_log,
+                            &table_name,
+                            count,
+                            &mut failures,
+                            make_item,
+                            |item| audit.written(item),
+                            |written| progress.report(written, Some(count)),
+                        )
+                        .await;
+                        if let Err(err) = &result {
+                            report_failure(&response_tx, err, "BatchWriteItem").await;
+                        }
+                        let result = failure_report::finish(
+                            &format!("Seed {count} items into {table_name}"),
+                            &table_name,
+                            &failures,
+                            result.map_err(|err| error_message(&err)),
+                        );
+                        audit.finish(&result);
+                        result
+                    });
+
+                    // The job reports when the items have been written.
+                    continue;
+                }
+                FetchRequest::PreviewImport(table_name, path) => {
+                    let key = key_attribute_names(&api_log, &table_name, &mut key_names).await;
+                    let response_tx = response_tx.clone();
+                    let api_log = api_log.clone();
+                    // Comparing reads back every item of the file, so it runs as a job to keep
+                    // serving other requests in the meantime.
+                    jobs.spawn(id, |_| async move {
+                        let preview =
+                            preview_import(&api_log, &response_tx, &table_name, &path, &key).await;
                         let _ = response_tx
-                            .send(FetchResponse::TableData(data, false, None))
+                            .send(FetchResponse::ImportPreview(table_name, preview))
                             .await;
+                        Ok(())
+                    });
+
+                    // The job reports when the file has been compared.
+                    continue;
+                }
+                FetchRequest::ImportItems(table_name, items, mode) => {
+                    let count = items.len();
+                    // Conditional puts and updates need the key of the table, as does the audit
+                    // log to tell the items apart.
+                    let key = key_attribute_names(&api_log, &table_name, &mut key_names).await;
+                    let audit = BulkAudit::new(
+                        AuditEntry::bulk(
+                            caller_arn(&mut identity).await,
+                            &format!("ImportItems ({})", mode.label()),
+                            &table_name,
+                            Some(count),
+                        ),
+                        key.clone(),
+                    );
+                    let response_tx = response_tx.clone();
+                    let api_log = api_log.clone();
+                    jobs.spawn(id, |progress| async move {
+                        if let Some(reason) = workspace::refuse_write() {
+                            audit.discard();
+                            return Err(reason);
+                        }
+                        let items: Vec<HashMap<_, _>> = items
+                            .into_iter()
+                            .map(|item| match json_to_dynamodb(item) {
+                                AttributeValue::M(item) => item,
+                                _ => HashMap::new(),
+                            })
+                            .collect();
+                        if mode != ImportMode::Overwrite && key.is_empty() {
+                            let result = Err(format!("Failed to describe the key of {table_name}"));
+                            audit.finish(&result);
+                            return result;
+                        }
+                        let mut failures = Vec::new();
+                        let on_written = |item: &HashMap<_, _>| audit.written(item);
+                        let on_progress = |written| progress.report(written, Some(count));
+                        let (result, operation) = match mode {
+                            ImportMode::Overwrite => (
+                                write_items(
+                                    &api_log,
+                                    &table_name,
+                                    &items,
+                                    &mut failures,
+                                    on_written,
+                                    on_progress,
+                                )
+                                .await,
+                                "BatchWriteItem",
+                            ),
+                            ImportMode::InsertOnly => (
+                                put_items_if_absent(
+                                    &api_log,
+                                    &table_name,
+                                    &key[0],
+                                    &items,
+                                    &mut failures,
+                                    on_written,
+                                    on_progress,
+                                )
+                                .await,
+                                "PutItem",
+                            ),
+                            ImportMode::Merge => (
+                                merge_items(
+                                    &api_log,
+                                    &table_name,
+                                    &key,
+                                    &items,
+                                    &mut failures,
+                                    on_written,
+                                    on_progress,
+                                )
+                                .await,
+                                "UpdateItem",
+                            ),
+                        };
+                        if let Err(err) = &result {
+                            report_failure(&response_tx, err, operation).await;
+                        }
+                        let result = failure_report::finish(
+                            &format!("Import {count} items into {table_name}"),
+                            &table_name,
+                            &failures,
+                            result.map_err(|err| error_message(&err)),
+                        );
+                        audit.finish(&result);
+                        result
+                    });
+
+                    // The job reports when the items have been written.
+                    continue;
+                }
+                FetchRequest::CopyTable(source, target) => {
+                    // The copies are keyed like the source, under the names they are renamed to.
+                    let copy_key = key_attribute_names(&api_log, &source, &mut key_names)
+                        .await
+                        .into_iter()
+                        .map(
+                            |name| match target.renames.iter().find(|(from, _)| *from == name) {
+                                Some((_, to)) => to.clone(),
+                                None => name,
+                            },
+                        )
+                        .collect();
+                    let audit = BulkAudit::new(
+                        AuditEntry::bulk(
+                            caller_arn(&mut identity).await,
+                            &format!("CopyTable from {source}"),
+                            &target.table,
+                            None,
+                        ),
+                        copy_key,
+                    );
+                    let response_tx = response_tx.clone();
+                    let api_log = api_log.clone();
+                    jobs.spawn(id, |progress| async move {
+                        if let Some(reason) = workspace::refuse_write() {
+                            audit.discard();
+                            return Err(reason);
+                        }
+                        let mut failures = Vec::new();
+                        let result = copy_table(
+                            &api_log,
+                            &source,
+                            &target,
+                            &mut failures,
+                            |item| audit.written(item),
+                            |copied| progress.report(copied, None),
+                        )
+                        .await;
+                        if let Err(err) = &result {
+                            report_failure(&response_tx, err, "BatchWriteItem").await;
+                        }
+                        let result = failure_report::finish(
+                            &format!("Copy {source} to {}", target.table),
+                            &target.table,
+                            &failures,
+                            result.map(|_| ()).map_err(|err| error_message(&err)),
+                        );
+                        audit.finish(&result);
+                        result
+                    });
+
+                    // The job reports when the table has been copied.
+                    continue;
+                }
+                FetchRequest::TableUsage(table_name) => {
+                    let usage = match describe_table_usage(&api_log, &table_name).await {
+                        Ok(usage) => Some(usage),
+                        Err(err) => {
+                            report_failure(&response_tx, &err, "DescribeTable").await;
+                            None
+                        }
+                    };
+                    let _ = response_tx
+                        .send(FetchResponse::TableUsage(table_name, usage))
+                        .await;
+                }
+                FetchRequest::SearchTables(regions) => {
+                    let response_tx = response_tx.clone();
+                    let api_log = api_log.clone();
+                    // A region that is slow to answer would hold up every request queued behind
+                    // the search, so it runs as a job.
+                    jobs.spawn(id, |_| async move {
+                        let api_log = &api_log;
+                        let results = futures::future::join_all(regions.into_iter().map(
+                            |region| async move {
+                                match load_collections_in_region(api_log, &region).await {
+                                    Ok(tables) => RegionTables {
+                                        region,
+                                        tables,
+                                        error: None,
+                                    },
+                                    Err(err) => RegionTables {
+                                        region,
+                                        tables: Vec::new(),
+                                        error: Some(error_message(&err)),
+                                    },
+                                }
+                            },
+                        ))
+                        .await;
+                        let _ = response_tx.send(FetchResponse::TableSearch(results)).await;
+                        Ok(())
+                    });
+
+                    // The job reports when every region has answered.
+                    continue;
+                }
+                FetchRequest::SwitchWorkspace(workspace) => {
+                    // Requests are served in order, so the ones sent before the switch still go
+                    // to the old workspace and the ones after it to the new one.
+                    workspace::activate(workspace.clone());
+                    identity = None;
+                    key_names.clear();
+                    let _ = response_tx
+                        .send(FetchResponse::WorkspaceSwitched(workspace))
+                        .await;
+                }
+                FetchRequest::FilterSoftDeleted(table_name, filter) => match filter {
+                    Some(filter) => {
+                        soft_deleted.insert(table_name, filter);
+                    }
+                    None => {
+                        soft_deleted.remove(&table_name);
                     }
+                },
+                FetchRequest::SwitchRegion(region) => {
+                    workspace::set_region(Some(region.clone()));
+                    identity = None;
+                    key_names.clear();
+                    let _ = response_tx
+                        .send(FetchResponse::RegionSwitched(region))
+                        .await;
                 }
-                FetchRequest::QueryTableByPkSk(table_name, pk, pk_value, sk, sk_value) => {
-                    if let Ok(data) =
-                        query_by_partition_and_sort_key(&table_name, &pk, &pk_value, &sk, &sk_value)
-                            .await
+                FetchRequest::SwitchProfile(profile) => {
+                    workspace::set_profile(Some(profile.clone()));
+                    identity = None;
+                    key_names.clear();
+                    let _ = response_tx
+                        .send(FetchResponse::ProfileSwitched(profile))
+                        .await;
+                }
+                FetchRequest::TableEvents(table_name) => {
+                    let events = lookup_table_events(&api_log, &table_name).await;
+                    let _ = response_tx
+                        .send(FetchResponse::TableEvents(table_name, events))
+                        .await;
+                }
+                FetchRequest::TableInfo(table_name) => {
+                    let info = match describe_table(&api_log, &table_name).await {
+                        Ok(info) => Ok(info),
+                        Err(err) => {
+                            report_failure(&response_tx, &err, "DescribeTable").await;
+                            Err(error_message(&err))
+                        }
+                    };
+                    let _ = response_tx
+                        .send(FetchResponse::TableInfo(table_name, info))
+                        .await;
+                }
+                FetchRequest::ReplicaRegions(table_name) => {
+                    let regions = match describe_replica_regions(&api_log, &table_name).await {
+                        Ok(regions) => Some(regions),
+                        Err(err) => {
+                            report_failure(&response_tx, &err, "DescribeTable").await;
+                            None
+                        }
+                    };
+                    let _ = response_tx
+                        .send(FetchResponse::ReplicaRegions(table_name, regions))
+                        .await;
+                }
+                FetchRequest::GetItem(table_name, key, consistent) => {
+                    let item = match get_item(&api_log, &table_name, key.clone(), consistent).await
                     {
+                        Ok(item) => Ok(item),
+                        Err(err) => {
+                            report_failure(&response_tx, &err, "GetItem").await;
+                            Err(error_message(&err))
+                        }
+                    };
+                    let _ = response_tx
+                        .send(FetchResponse::Item(table_name, key, item))
+                        .await;
+                }
+                FetchRequest::ItemTypes(table_name, key) => {
+                    let item = match get_item_attributes(&api_log, &table_name, key, true).await {
+                        Ok(item) => Ok(typed_json::item_to_typed(&item.unwrap_or_default())),
+                        Err(err) => {
+                            report_failure(&response_tx, &err, "GetItem").await;
+                            Err(error_message(&err))
+                        }
+                    };
+                    let _ = response_tx
+                        .send(FetchResponse::ItemTypes(table_name, item))
+                        .await;
+                }
+                FetchRequest::ProbePermissions(table_name) => {
+                    let permissions = probe_permissions(&api_log, &table_name).await;
+                    let _ = response_tx
+                        .send(FetchResponse::Permissions(permissions))
+                        .await;
+                }
+                FetchRequest::CompareRegions(table_name, key, regions) => {
+                    let response_tx = response_tx.clone();
+                    let api_log = api_log.clone();
+                    // Each replica region is read in turn, so it runs as a job like the search.
+                    jobs.spawn(id, |_| async move {
+                        let mut items = Vec::new();
+                        for region in regions {
+                            let result =
+                                get_item_in_region(&api_log, &table_name, &region, key.clone())
+                                    .await;
+                            items.push(match result {
+                                Ok(item) => RegionItem {
+                                    region,
+                                    item,
+                                    error: None,
+                                },
+                                Err(err) => RegionItem {
+                                    region,
+                                    item: None,
+                                    error: Some(error_message(&err)),
+                                },
+                            });
+                        }
                         let _ = response_tx
-                            .send(FetchResponse::TableData(data, false, None))
+                            .send(FetchResponse::RegionItems(table_name, items))
                             .await;
-                    }
+                        Ok(())
+                    });
+
+                    // The job reports when every region has been read.
+                    continue;
+                }
+                FetchRequest::ProbeLatency(table_name, replicas) => {
+                    let latencies = match probe_latency(&api_log, &table_name, replicas).await {
+                        Ok(latencies) => Ok(latencies),
+                        Err(err) => {
+                            report_failure(&response_tx, &err, "DescribeTable").await;
+                            Err(error_message(&err))
+                        }
+                    };
+                    let _ = response_tx
+                        .send(FetchResponse::Latencies(table_name, latencies))
+                        .await;
+                }
+                FetchRequest::PutItem(table_name, item, new_partition_key) => {
+                    let error = match workspace::refuse_write() {
+                        Some(reason) => Some(reason),
+                        None => {
+                            let result = put_item(
+                                &api_log,
+                                &table_name,
+                                item.clone(),
+                                new_partition_key.as_deref(),
+                            )
+                            .await;
+                            match result {
+                                Ok(old_item) => {
+                                    let names =
+                                        key_attribute_names(&api_log, &table_name, &mut key_names)
+                                            .await;
+                                    audit::append(&AuditEntry::put_item(
+                                        caller_arn(&mut identity).await,
+                                        &table_name,
+                                        &names,
+                                        &item,
+                                        old_item,
+                                    ));
+                                    None
+                                }
+                                Err(err) => {
+                                    report_failure(&response_tx, &err, "PutItem").await;
+                                    Some(error_message(&err))
+                                }
+                            }
+                        }
+                    };
+                    let _ = response_tx
+                        .send(FetchResponse::ItemPut(table_name, error))
+                        .await;
+                }
+                FetchRequest::SsoLogin(profile) => {
+                    let response_tx = response_tx.clone();
+
+                    // The device flow polls until the user approves it in the browser, so it runs
+                    // as a job to keep serving other requests in the meantime.
+                    jobs.spawn(id, |_| async move {
+                        let result = match sso_profile(&profile) {
+                            Some(sso) => sso::login(&sso, |authorization| {
+                                let _ =
+                                    response_tx.try_send(FetchResponse::SsoDeviceAuthorization(
+                                        authorization.verification_uri,
+                                        authorization.user_code,
+                                    ));
+                            })
+                            .await
+                            .map_err(|err| err.to_string()),
+                            None => Err(format!("Profile `{profile}` is not configured for SSO")),
+                        };
+
+                        let response = match &result {
+                            Ok(()) => FetchResponse::SsoLoginComplete,
+                            Err(reason) => FetchResponse::SsoLoginFailed(reason.clone()),
+                        };
+                        let _ = response_tx.send(response).await;
+                        result
+                    });
+
+                    // The job reports when the login is finished.
+                    continue;
                 }
             }
+
+            let _ = response_tx.send(FetchResponse::RequestFinished(id)).await;
         }
     });
 
-    let args = Cli::parse();
-    let mut app = App::new(args.tick_rate, args.frame_rate, fetch_tx, response_rx)?;
+    workspace::set_default_profile(args.profile.clone());
+    let commands = args
+        .commands
+        .iter()
+        .map(|command| Command::parse(command))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| eyre!(err))?;
+    let recorder = args
+        .record
+        .as_deref()
+        .map(session::SessionRecorder::create)
+        .transpose()?;
+    let mut replayed_events = None;
+    // Replacing the channels drops the ones the worker listens on, so it stops before sending
+    // anything to AWS.
+    let (fetch_tx, response_rx) = match args.replay.as_deref() {
+        Some(path) => {
+            let entries = session::load(path)?;
+            let (replay_fetch_tx, replay_fetch_rx) = mpsc::channel(10);
+            let (replay_response_tx, replay_response_rx) = mpsc::channel(10);
+            let (event_tx, event_rx) = mpsc::unbounded_channel();
+            session::replay(entries, event_tx, replay_response_tx, replay_fetch_rx);
+            replayed_events = Some(event_rx);
+            (replay_fetch_tx, replay_response_rx)
+        }
+        None if args.offline => {
+            let path = mirror_path.ok_or_else(|| {
+                eyre!("--offline browses the SQLite mirror: set `sqlite_mirror` or pass --mirror")
+            })?;
+            let mirror = Mirror::open(&path)?;
+            let (offline_fetch_tx, offline_fetch_rx) = mpsc::channel(10);
+            let (offline_response_tx, offline_response_rx) = mpsc::channel(10);
+            mirror::serve(mirror, offline_response_tx, offline_fetch_rx);
+            (offline_fetch_tx, offline_response_rx)
+        }
+        None => (fetch_tx, response_rx),
+    };
+    let mut app = App::new(
+        config,
+        args.tick_rate,
+        args.frame_rate,
+        fetch_tx,
+        response_rx,
+    )?
+    .fps_debug(args.fps_debug)
+    .record(recorder)
+    .replay(replayed_events)
+    .metrics_file(args.metrics_file)
+    .commands(commands);
     app.run().await?;
+    sink::flush_stdout()?;
     Ok(())
 }
+
+/// Copies the items of a scan or query into the SQLite mirror, if one is kept. The table is
+/// described the first time its items are copied, for its key columns.
+async fn mirror_items(
+    api_log: &ApiLog,
+    response_tx: &mpsc::Sender<FetchResponse>,
+    mirror: &mut Option<Mirror>,
+    table_name: &str,
+    records: &[String],
+) {
+    let Some(mirror) = mirror else {
+        return;
+    };
+    if records.is_empty() {
+        return;
+    }
+    if mirror.key_schema(table_name).is_none() {
+        let Ok((Some(partition_key), sort_key)) =
+            describe_table_key_schema(api_log, table_name).await
+        else {
+            return;
+        };
+        if let Err(err) = mirror.add_table(table_name, (partition_key, sort_key)) {
+            let _ = response_tx
+                .send(FetchResponse::Error(format!(
+                    "Could not mirror {table_name}: {err}"
+                )))
+                .await;
+            return;
+        }
+    }
+    if let Err(err) = mirror.store(table_name, records) {
+        let _ = response_tx
+            .send(FetchResponse::Error(format!(
+                "Could not mirror the items of {table_name}: {err}"
+            )))
+            .await;
+    }
+}
+
+/// The names of the key attributes of a table, described once per workspace.
+async fn key_attribute_names(
+    api_log: &ApiLog,
+    table_name: &str,
+    known: &mut HashMap<String, Vec<String>>,
+) -> Vec<String> {
+    if let Some(names) = known.get(table_name) {
+        return names.clone();
+    }
+
+    let names: Vec<String> = match describe_table_key_schema(api_log, table_name).await {
+        Ok((partition_key, sort_key)) => partition_key
+            .into_iter()
+            .chain(sort_key)
+            .map(|key| key.name)
+            .collect(),
+        // Not remembered, so the next write tries again.
+        Err(_) => return Vec::new(),
+    };
+    known.insert(table_name.to_string(), names.clone());
+    names
+}
+
+/// Scans the whole table into a file, one item per line, in pages of up to `page_size` items,
+/// reporting the items written and the pages fetched after each page. Each page is flushed once
+/// written, so a dump that is cancelled leaves a file of whole lines.
+async fn dump_table(
+    api_log: &ApiLog,
+    response_tx: &mpsc::Sender<FetchResponse>,
+    table_name: &str,
+    path: &Path,
+    page_size: i32,
+    progress: &JobProgress,
+) -> Result<(), String> {
+    let file = File::create(path)
+        .await
+        .map_err(|err| format!("Failed to create {}: {err}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+
+    let (mut items, mut pages) = (0, 0);
+    let mut last_evaluated_key = None;
+    loop {
+        let (records, _, next_key) =
+            match load_data(api_log, table_name, last_evaluated_key, None, page_size).await {
+                Ok(page) => page,
+                Err(err) => {
+                    report_failure(response_tx, &err, "Scan").await;
+                    return Err(error_message(&err));
+                }
+            };
+
+        for record in &records {
+            writer
+                .write_all(format!("{record}\n").as_bytes())
+                .await
+                .map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+        }
+        writer
+            .flush()
+            .await
+            .map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+        items += records.len();
+        pages += 1;
+        progress.report_pages(items, pages);
+
+        match next_key {
+            Some(key) => last_evaluated_key = Some(key),
+            None => return Ok(()),
+        }
+    }
+}
+
+/// Reads the items of an import file and the items of the table with the same keys, to tell
+/// which ones the import would create, overwrite, or leave as they are.
+async fn preview_import(
+    api_log: &ApiLog,
+    response_tx: &mpsc::Sender<FetchResponse>,
+    table_name: &str,
+    path: &Path,
+    key_names: &[String],
+) -> Result<(ImportPreview, Vec<Value>, Vec<ImportStatus>), String> {
+    let items = import::read_items(path)?;
+    if key_names.is_empty() {
+        return Err(format!("Failed to describe the key of {table_name}"));
+    }
+
+    // Numbers read back from DynamoDB are floats, so the items are compared the same way.
+    let comparable: Vec<Value> = items
+        .iter()
+        .map(|item| dynamodb_to_json(json_to_dynamodb(item.clone())))
+        .collect();
+    let mut keys = Vec::new();
+    for item in &comparable {
+        if let Some(key) = import::item_key(item, key_names) {
+            let key: HashMap<_, _> = key
+                .into_iter()
+                .map(|(name, value)| (name, json_to_dynamodb(value)))
+                .collect();
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+    }
+
+    let existing = match batch_get_items(api_log, table_name, keys).await {
+        Ok(existing) => existing,
+        Err(err) => {
+            report_failure(response_tx, &err, "BatchGetItem").await;
+            return Err(error_message(&err));
+        }
+    };
+    let (preview, statuses) = import::classify(
+        table_name,
+        &path.display().to_string(),
+        &comparable,
+        &existing,
+        key_names,
+    );
+    Ok((preview, items, statuses))
+}
+
+/// The ARN of the caller, read once and kept until the workspace changes.
+async fn caller_arn(identity: &mut Option<String>) -> Option<String> {
+    if identity.is_none() {
+        *identity = get_caller_identity().await.ok().map(|(_, arn)| arn);
+    }
+    identity.clone()
+}
+
+/// The reason a request failed, worded to be shown next to the input that caused it, with the
+/// ID AWS gave the request when it was answered.
+fn error_message(err: &aws_sdk_dynamodb::Error) -> String {
+    let message = match err {
+        aws_sdk_dynamodb::Error::ConditionalCheckFailedException(_) => {
+            "An item with this key already exists".to_string()
+        }
+        err => match (err.code(), err.message()) {
+            (Some(code), Some(message)) => format!("{code}: {message}"),
+            _ => err.to_string(),
+        },
+    };
+    match err.request_id() {
+        Some(request_id) => format!("{message} (request ID {request_id})"),
+        None => message,
+    }
+}
+
+/// Forwards a credentials failure, or a request IAM denied, to the UI. Returns `false` if the
+/// error was neither and nothing was sent.
+async fn report_failure(
+    response_tx: &mpsc::Sender<FetchResponse>,
+    err: &aws_sdk_dynamodb::Error,
+    operation: &str,
+) -> bool {
+    let response = if let Some(failure) = credentials_failure(err) {
+        FetchResponse::CredentialsFailure(failure)
+    } else if let Some(denied) = access_denied(err, operation) {
+        FetchResponse::AccessDenied(denied)
+    } else {
+        return false;
+    };
+
+    let _ = response_tx.send(response).await;
+    true
+}
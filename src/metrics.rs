@@ -0,0 +1,170 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use crate::api_log::ApiCall;
+
+/// Error codes DynamoDB answers with when a request is throttled.
+const THROTTLING_CODES: [&str; 3] = [
+    "ThrottlingException",
+    "ProvisionedThroughputExceededException",
+    "RequestLimitExceeded",
+];
+
+/// Counters kept over the session, written in the Prometheus text format on exit with
+/// `--metrics-file`.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    /// Calls per operation, and those that failed or were throttled.
+    api_calls: BTreeMap<String, u64>,
+    api_errors: BTreeMap<String, u64>,
+    throttles: BTreeMap<String, u64>,
+    items_fetched: u64,
+    /// The size of the fetched items as JSON, which is close to, but not, what DynamoDB bills.
+    bytes_fetched: u64,
+    cache_hits: u64,
+    cache_misses: u64,
+}
+
+impl Metrics {
+    pub fn record_call(&mut self, call: &ApiCall) {
+        *self.api_calls.entry(call.operation.clone()).or_default() += 1;
+
+        let Some(error) = &call.error else {
+            return;
+        };
+        *self.api_errors.entry(call.operation.clone()).or_default() += 1;
+        if THROTTLING_CODES.iter().any(|code| error.starts_with(code)) {
+            *self.throttles.entry(call.operation.clone()).or_default() += 1;
+        }
+    }
+
+    pub fn record_items(&mut self, records: &[String]) {
+        self.items_fetched += records.len() as u64;
+        self.bytes_fetched += records
+            .iter()
+            .map(|record| record.len() as u64)
+            .sum::<u64>();
+    }
+
+    /// Adds the lookups of a cache, as counted by the cache itself.
+    pub fn record_cache_lookups(&mut self, (hits, misses): (u64, u64)) {
+        self.cache_hits += hits;
+        self.cache_misses += misses;
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        write_by_operation(
+            &mut out,
+            "dynotui_api_calls_total",
+            "DynamoDB requests sent, by operation.",
+            &self.api_calls,
+        );
+        write_by_operation(
+            &mut out,
+            "dynotui_api_errors_total",
+            "DynamoDB requests that failed, by operation.",
+            &self.api_errors,
+        );
+        write_by_operation(
+            &mut out,
+            "dynotui_api_throttles_total",
+            "DynamoDB requests that were throttled, by operation.",
+            &self.throttles,
+        );
+        write_counter(
+            &mut out,
+            "dynotui_items_fetched_total",
+            "Items received from scans and queries.",
+            self.items_fetched,
+        );
+        write_counter(
+            &mut out,
+            "dynotui_bytes_fetched_total",
+            "Size of the items received, as JSON.",
+            self.bytes_fetched,
+        );
+        write_counter(
+            &mut out,
+            "dynotui_cache_hits_total",
+            "Lookups answered from the response caches.",
+            self.cache_hits,
+        );
+        write_counter(
+            &mut out,
+            "dynotui_cache_misses_total",
+            "Lookups the response caches could not answer.",
+            self.cache_misses,
+        );
+        out
+    }
+}
+
+fn write_header(out: &mut String, name: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} counter");
+}
+
+fn write_counter(out: &mut String, name: &str, help: &str, value: u64) {
+    write_header(out, name, help);
+    let _ = writeln!(out, "{name} {value}");
+}
+
+fn write_by_operation(out: &mut String, name: &str, help: &str, values: &BTreeMap<String, u64>) {
+    write_header(out, name, help);
+    for (operation, value) in values {
+        let _ = writeln!(out, "{name}{{operation=\"{operation}\"}} {value}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn call(operation: &str, error: Option<&str>) -> ApiCall {
+        ApiCall {
+            timestamp: String::new(),
+            operation: operation.to_string(),
+            table: None,
+            duration_ms: 0,
+            consumed_capacity: None,
+            items: None,
+            error: error.map(str::to_string),
+            request_id: None,
+        }
+    }
+
+    #[test]
+    fn test_render_metrics() {
+        let mut metrics = Metrics::default();
+        metrics.record_call(&call("Scan", None));
+        metrics.record_call(&call(
+            "Scan",
+            Some("ProvisionedThroughputExceededException: slow down"),
+        ));
+        metrics.record_call(&call("Query", Some("ValidationException: bad key")));
+        metrics.record_items(&[r#"{"pk":"a"}"#.to_string()]);
+        metrics.record_cache_lookups((3, 1));
+
+        let rendered = metrics.render();
+        let samples: Vec<&str> = rendered
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .collect();
+        assert_eq!(
+            samples,
+            vec![
+                "dynotui_api_calls_total{operation=\"Query\"} 1",
+                "dynotui_api_calls_total{operation=\"Scan\"} 2",
+                "dynotui_api_errors_total{operation=\"Query\"} 1",
+                "dynotui_api_errors_total{operation=\"Scan\"} 1",
+                "dynotui_api_throttles_total{operation=\"Scan\"} 1",
+                "dynotui_items_fetched_total 1",
+                "dynotui_bytes_fetched_total 10",
+                "dynotui_cache_hits_total 3",
+                "dynotui_cache_misses_total 1",
+            ]
+        );
+    }
+}
@@ -0,0 +1,423 @@
+use std::{collections::HashMap, path::Path};
+
+use rusqlite::{params_from_iter, types::Value as SqlValue, Connection, OptionalExtension};
+use serde_json::Value;
+use tokio::{sync::mpsc, task};
+
+use crate::{
+    data::{FetchRequest, FetchResponse, KeyAttribute, KeyAttributeType, KeyQuery, RequestId},
+    time_range::{after_bound, split_range},
+};
+
+/// Lists the mirrored tables with their key attributes, which name the key columns of each.
+const TABLES: &str = "_dynotui_tables";
+
+/// The key attributes of a table: its partition key and its sort key, if it has one.
+type KeySchema = (KeyAttribute, Option<KeyAttribute>);
+
+/// A SQLite file holding a copy of the items scanned and queried, a table for each DynamoDB
+/// table. Each item is kept as JSON in an `item` column, next to a column for each key
+/// attribute, so `SELECT json_extract(item, '$.total') FROM orders WHERE pk = 'USER#1'` works.
+/// Items are replaced when they are read again, but never removed: an item deleted from
+/// DynamoDB stays in the mirror.
+pub struct Mirror {
+    connection: Connection,
+    /// The key attributes of the tables mirrored so far, read from the file when it opens.
+    key_schemas: HashMap<String, KeySchema>,
+}
+
+impl Mirror {
+    pub fn open(path: &Path) -> rusqlite::Result<Self> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            // A missing folder fails to open below with a clearer error than creating it would.
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let connection = Connection::open(path)?;
+        connection.execute_batch(&format!(
+            "CREATE TABLE IF NOT EXISTS {TABLES} (
+                name TEXT PRIMARY KEY,
+                partition_key TEXT NOT NULL,
+                partition_key_type TEXT NOT NULL,
+                sort_key TEXT,
+                sort_key_type TEXT
+            )"
+        ))?;
+
+        let key_schemas = {
+            let mut statement = connection.prepare(&format!(
+                "SELECT name, partition_key, partition_key_type, sort_key, sort_key_type \
+                 FROM {TABLES}"
+            ))?;
+            let rows = statement.query_map([], |row| {
+                let partition_key = key_attribute(row.get(1)?, row.get(2)?);
+                let sort_key = match (row.get(3)?, row.get::<_, Option<String>>(4)?) {
+                    (Some(name), Some(attribute_type)) => key_attribute(name, attribute_type),
+                    _ => None,
+                };
+                Ok((row.get::<_, String>(0)?, partition_key, sort_key))
+            })?;
+            rows.filter_map(Result::ok)
+                .filter_map(|(name, partition_key, sort_key)| {
+                    Some((name, (partition_key?, sort_key)))
+                })
+                .collect()
+        };
+
+        Ok(Self {
+            connection,
+            key_schemas,
+        })
+    }
+
+    pub fn key_schema(&self, table: &str) -> Option<&KeySchema> {
+        self.key_schemas.get(table)
+    }
+
+    pub fn tables(&self) -> Vec<String> {
+        let mut tables: Vec<String> = self.key_schemas.keys().cloned().collect();
+        tables.sort();
+        tables
+    }
+
+    /// Creates the table an item of `table` is copied into, with a column for each key
+    /// attribute.
+    pub fn add_table(&mut self, table: &str, key_schema: KeySchema) -> rusqlite::Result<()> {
+        let (partition_key, sort_key) = &key_schema;
+        let mut columns = vec![key_column(partition_key)];
+        let mut primary_key = vec![quote(&partition_key.name)];
+        if let Some(sort_key) = sort_key {
+            columns.push(key_column(sort_key));
+            primary_key.push(quote(&sort_key.name));
+        }
+        columns.push(format!("{} TEXT NOT NULL", quote(item_column(&key_schema))));
+
+        let transaction = self.connection.transaction()?;
+        transaction.execute(
+            &format!(
+                "CREATE TABLE IF NOT EXISTS {} ({}, PRIMARY KEY ({}))",
+                quote(table),
+                columns.join(", "),
+                primary_key.join(", ")
+            ),
+            [],
+        )?;
+        transaction.execute(
+            &format!("INSERT OR REPLACE INTO {TABLES} VALUES (?1, ?2, ?3, ?4, ?5)"),
+            (
+                table,
+                &partition_key.name,
+                type_name(partition_key.attribute_type),
+                sort_key.as_ref().map(|sort_key| &sort_key.name),
+                sort_key
+                    .as_ref()
+                    .map(|sort_key| type_name(sort_key.attribute_type)),
+            ),
+        )?;
+        transaction.commit()?;
+        self.key_schemas.insert(table.to_string(), key_schema);
+        Ok(())
+    }
+
+    /// Copies items of `table` into its mirror, replacing the ones with the same key. The table
+    /// must have been added first; items missing a key attribute are skipped.
+    pub fn store(&mut self, table: &str, records: &[String]) -> rusqlite::Result<()> {
+        let Some(key_schema) = self.key_schemas.get(table) else {
+            return Ok(());
+        };
+        let (partition_key, sort_key) = key_schema;
+        let keys: Vec<&KeyAttribute> = std::iter::once(partition_key).chain(sort_key).collect();
+        let mut columns: Vec<String> = keys.iter().map(|key| quote(&key.name)).collect();
+        columns.push(quote(item_column(key_schema)));
+        let insert = format!(
+            "INSERT OR REPLACE INTO {} ({}) VALUES ({})",
+            quote(table),
+            columns.join(", "),
+            vec!["?"; columns.len()].join(", ")
+        );
+
+        let transaction = self.connection.transaction()?;
+        {
+            let mut statement = transaction.prepare(&insert)?;
+            for record in records {
+                let Ok(item) = serde_json::from_str::<Value>(record) else {
+                    continue;
+                };
+                let Some(mut values) = keys
+                    .iter()
+                    .map(|key| item.get(&key.name).and_then(sql_value))
+                    .collect::<Option<Vec<SqlValue>>>()
+                else {
+                    continue;
+                };
+                values.push(SqlValue::Text(record.clone()));
+                statement.execute(params_from_iter(values))?;
+            }
+        }
+        transaction.commit()
+    }
+
+    /// The mirrored items of `table` in key order, only those of the queried partition and sort
+    /// key values when a query is given.
+    pub fn items(&self, table: &str, query: Option<&KeyQuery>) -> rusqlite::Result<Vec<String>> {
+        let Some(key_schema) = self.key_schemas.get(table) else {
+            return Ok(Vec::new());
+        };
+        let (partition_key, sort_key) = key_schema;
+        let mut conditions = Vec::new();
+        let mut values = Vec::new();
+        if let Some(query) = query {
+            conditions.push(format!("{} = ?", quote(&partition_key.name)));
+            values.push(query.partition_key_value.clone());
+            if let Some((sort_key, value)) = &query.sort_key {
+                let column = quote(&sort_key.name);
+                match (split_range(value), after_bound(value)) {
+                    (Some((from, to)), _) => {
+                        conditions.push(format!("{column} BETWEEN ? AND ?"));
+                        values.extend([from.to_string(), to.to_string()]);
+                    }
+                    (None, Some(from)) => {
+                        conditions.push(format!("{column} > ?"));
+                        values.push(from.to_string());
+                    }
+                    (None, None) => {
+                        conditions.push(format!("{column} = ?"));
+                        values.push(value.clone());
+                    }
+                }
+            }
+        }
+
+        let mut sql = format!(
+            "SELECT {} FROM {}",
+            quote(item_column(key_schema)),
+            quote(table)
+        );
+        if !conditions.is_empty() {
+            sql.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+        }
+        let order: Vec<String> = std::iter::once(partition_key)
+            .chain(sort_key)
+            .map(|key| quote(&key.name))
+            .collect();
+        sql.push_str(&format!(" ORDER BY {}", order.join(", ")));
+        if let Some(limit) = query.and_then(|query| query.limit) {
+            sql.push_str(&format!(" LIMIT {limit}"));
+        }
+
+        let mut statement = self.connection.prepare(&sql)?;
+        let items = statement
+            .query_map(params_from_iter(values), |row| row.get(0))?
+            .collect();
+        items
+    }
+
+    pub fn count(&self, table: &str) -> rusqlite::Result<i64> {
+        if !self.key_schemas.contains_key(table) {
+            return Ok(0);
+        }
+        self.connection
+            .query_row(
+                &format!("SELECT COUNT(*) FROM {}", quote(table)),
+                [],
+                |row| row.get(0),
+            )
+            .optional()
+            .map(Option::unwrap_or_default)
+    }
+}
+
+/// Answers the requests of the UI from the mirror instead of AWS, for browsing the items
+/// mirrored before without a connection. Only listing tables, scanning and querying are
+/// answered; any other request fails.
+pub fn serve(
+    mirror: Mirror,
+    response_tx: mpsc::Sender<FetchResponse>,
+    mut fetch_rx: mpsc::Receiver<(RequestId, FetchRequest)>,
+) {
+    task::spawn(async move {
+        while let Some((id, request)) = fetch_rx.recv().await {
+            for response in answer(&mirror, request) {
+                let _ = response_tx.send(response).await;
+            }
+            let _ = response_tx.send(FetchResponse::RequestFinished(id)).await;
+        }
+    });
+}
+
+fn answer(mirror: &Mirror, request: FetchRequest) -> Vec<FetchResponse> {
+    let response = match request {
+        FetchRequest::Tables => FetchResponse::Tables(Some(mirror.tables())),
+        FetchRequest::DescribeTable(table) => {
+            let key_schema = mirror
+                .key_schema(&table)
+                .map(|(partition_key, sort_key)| (Some(partition_key.clone()), sort_key.clone()));
+            FetchResponse::TableDescription(table, key_schema)
+        }
+        FetchRequest::GetApproximateItemCount(table) => {
+            let count = mirror.count(&table).ok();
+            FetchResponse::ApproximateTableDataCount(table, count)
+        }
+        FetchRequest::TableData(table, _) => match mirror.items(&table, None) {
+            Ok(items) => FetchResponse::TableData(table, items, false, None),
+            Err(err) => FetchResponse::Error(format!("Could not read the mirror: {err}")),
+        },
+        FetchRequest::QueryTableData(table, query) => match mirror.items(&table, Some(&query)) {
+            Ok(items) => FetchResponse::TableData(table, items, false, None),
+            Err(err) => FetchResponse::QueryFailed(table, err.to_string()),
+        },
+        FetchRequest::FollowQuery(table, query) => {
+            let items = mirror.items(&table, Some(&query)).unwrap_or_default();
+            FetchResponse::FollowedTableData(table, items)
+        }
+        // Every item is read at once, so there are no more pages.
+        FetchRequest::NextBatchTableData(table, ..)
+        | FetchRequest::NextBatchQueryTableData(table, ..) => {
+            FetchResponse::NextBatchTableData(table, Vec::new(), false, None)
+        }
+        // Sent in the background on their own, so they are not worth an error.
+        FetchRequest::CallerIdentity
+        | FetchRequest::RefreshCredentials
+        | FetchRequest::TableUsage(_)
+        | FetchRequest::FilterSoftDeleted(..)
+        | FetchRequest::CancelJob(_)
+        | FetchRequest::StopPartitionExplorer => return Vec::new(),
+        _ => FetchResponse::Error(
+            "Offline: only the mirrored items can be listed, scanned and queried".to_string(),
+        ),
+    };
+    vec![response]
+}
+
+fn key_attribute(name: String, attribute_type: String) -> Option<KeyAttribute> {
+    let attribute_type = match attribute_type.as_str() {
+        "S" => KeyAttributeType::S,
+        "N" => KeyAttributeType::N,
+        "B" => KeyAttributeType::B,
+        _ => return None,
+    };
+    Some(KeyAttribute {
+        name,
+        attribute_type,
+    })
+}
+
+fn type_name(attribute_type: KeyAttributeType) -> &'static str {
+    match attribute_type {
+        KeyAttributeType::S => "S",
+        KeyAttributeType::N => "N",
+        KeyAttributeType::B => "B",
+    }
+}
+
+/// A key column is compared as a number for a number key, so `pk = '42'` finds the item.
+fn key_column(key: &KeyAttribute) -> String {
+    let affinity = match key.attribute_type {
+        KeyAttributeType::N => "NUMERIC",
+        KeyAttributeType::S | KeyAttributeType::B => "TEXT",
+    };
+    format!("{} {affinity} NOT NULL", quote(&key.name))
+}
+
+/// The column holding the items as JSON, unless a key attribute is already called `item`.
+fn item_column(key_schema: &KeySchema) -> &'static str {
+    let (partition_key, sort_key) = key_schema;
+    if std::iter::once(partition_key)
+        .chain(sort_key)
+        .any(|key| key.name == "item")
+    {
+        "item_json"
+    } else {
+        "item"
+    }
+}
+
+fn sql_value(value: &Value) -> Option<SqlValue> {
+    match value {
+        Value::String(text) => Some(SqlValue::Text(text.clone())),
+        Value::Number(number) => Some(match number.as_i64() {
+            Some(integer) => SqlValue::Integer(integer),
+            None => SqlValue::Real(number.as_f64()?),
+        }),
+        _ => None,
+    }
+}
+
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn key(name: &str, attribute_type: KeyAttributeType) -> KeyAttribute {
+        KeyAttribute {
+            name: name.to_string(),
+            attribute_type,
+        }
+    }
+
+    #[test]
+    fn test_mirror() -> rusqlite::Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mirror.sqlite");
+        let mut mirror = Mirror::open(&path)?;
+        mirror.add_table(
+            "orders",
+            (
+                key("pk", KeyAttributeType::S),
+                Some(key("sk", KeyAttributeType::N)),
+            ),
+        )?;
+        mirror.store(
+            "orders",
+            &[
+                r#"{"pk":"USER#1","sk":2,"total":5}"#.to_string(),
+                r#"{"pk":"USER#1","sk":10,"total":7}"#.to_string(),
+                r#"{"pk":"USER#2","sk":1}"#.to_string(),
+                r#"{"sk":3}"#.to_string(),
+            ],
+        )?;
+        // Read again, an item replaces the one with its key.
+        mirror.store(
+            "orders",
+            &[r#"{"pk":"USER#1","sk":2,"total":6}"#.to_string()],
+        )?;
+
+        // Opened again, the mirror knows its tables.
+        let mirror = Mirror::open(&path)?;
+        assert_eq!(mirror.tables(), ["orders"]);
+        assert_eq!(mirror.count("orders")?, 3);
+
+        let query = |value: &str| KeyQuery {
+            partition_key: key("pk", KeyAttributeType::S),
+            partition_key_value: "USER#1".to_string(),
+            sort_key: Some((key("sk", KeyAttributeType::N), value.to_string())),
+            limit: None,
+        };
+        assert_eq!(
+            mirror.items("orders", Some(&query("1..5")))?,
+            [r#"{"pk":"USER#1","sk":2,"total":6}"#]
+        );
+        // Number keys are compared as numbers, so 10 comes after 2.
+        assert_eq!(
+            mirror.items("orders", Some(&query(">2")))?,
+            [r#"{"pk":"USER#1","sk":10,"total":7}"#]
+        );
+        assert_eq!(mirror.items("orders", None)?.len(), 3);
+
+        let connection = Connection::open(&path)?;
+        let total: i64 = connection.query_row(
+            "SELECT json_extract(item, '$.total') FROM orders WHERE pk = 'USER#1' AND sk = 10",
+            [],
+            |row| row.get(0),
+        )?;
+        assert_eq!(total, 7);
+        Ok(())
+    }
+}
@@ -0,0 +1,227 @@
+use std::{collections::BTreeSet, sync::Arc};
+
+use arrow::{
+    array::{
+        ArrayRef, BooleanArray, Float64Array, Int64Array, RecordBatch, RecordBatchOptions,
+        StringArray,
+    },
+    datatypes::{DataType, Field, Schema},
+};
+use parquet::{
+    arrow::ArrowWriter, basic::Compression, errors::ParquetError,
+    file::properties::WriterProperties,
+};
+use serde_json::Value;
+
+/// Whether an export destination asks for Parquet rather than JSON.
+pub fn is_parquet(destination: &str) -> bool {
+    destination
+        .trim()
+        .to_ascii_lowercase()
+        .ends_with(".parquet")
+}
+
+/// The type of a column, inferred from the values the items hold for its attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnType {
+    Boolean,
+    Int64,
+    Float64,
+    Utf8,
+    /// Maps, lists, and attributes holding values of different types, written as JSON text.
+    Json,
+}
+
+impl ColumnType {
+    fn of(value: &Value) -> Option<Self> {
+        match value {
+            Value::Null => None,
+            Value::Bool(_) => Some(Self::Boolean),
+            Value::Number(number) if number.is_i64() => Some(Self::Int64),
+            Value::Number(_) => Some(Self::Float64),
+            Value::String(_) => Some(Self::Utf8),
+            Value::Array(_) | Value::Object(_) => Some(Self::Json),
+        }
+    }
+
+    /// The type that holds the values of both types.
+    fn widen(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Self::Int64, Self::Float64) | (Self::Float64, Self::Int64) => Self::Float64,
+            _ => Self::Json,
+        }
+    }
+
+    fn data_type(self) -> DataType {
+        match self {
+            Self::Boolean => DataType::Boolean,
+            Self::Int64 => DataType::Int64,
+            Self::Float64 => DataType::Float64,
+            Self::Utf8 | Self::Json => DataType::Utf8,
+        }
+    }
+
+    fn array(self, values: Vec<Option<&Value>>) -> ArrayRef {
+        match self {
+            Self::Boolean => Arc::new(BooleanArray::from(
+                values
+                    .into_iter()
+                    .map(|value| value.and_then(Value::as_bool))
+                    .collect::<Vec<_>>(),
+            )),
+            Self::Int64 => Arc::new(Int64Array::from(
+                values
+                    .into_iter()
+                    .map(|value| value.and_then(Value::as_i64))
+                    .collect::<Vec<_>>(),
+            )),
+            Self::Float64 => Arc::new(Float64Array::from(
+                values
+                    .into_iter()
+                    .map(|value| value.and_then(Value::as_f64))
+                    .collect::<Vec<_>>(),
+            )),
+            Self::Utf8 => Arc::new(StringArray::from(
+                values
+                    .into_iter()
+                    .map(|value| value.and_then(Value::as_str))
+                    .collect::<Vec<_>>(),
+            )),
+            Self::Json => Arc::new(StringArray::from(
+                values
+                    .into_iter()
+                    .map(|value| value.map(Value::to_string))
+                    .collect::<Vec<_>>(),
+            )),
+        }
+    }
+}
+
+/// Items as a Parquet file with a nullable column per top-level attribute, for loading into
+/// DuckDB or Pandas. Booleans, whole numbers, other numbers and strings get columns of their
+/// type, while maps, lists and attributes holding values of several types are written as JSON
+/// text. The attributes named in `first` lead, the others follow sorted by name.
+pub fn to_parquet(items: &[Value], first: &[String]) -> Result<Vec<u8>, ParquetError> {
+    let names: BTreeSet<&String> = items
+        .iter()
+        .filter_map(Value::as_object)
+        .flat_map(|item| item.keys())
+        .collect();
+    let columns: Vec<&String> = first
+        .iter()
+        .filter(|name| names.contains(name))
+        .chain(names.iter().copied().filter(|name| !first.contains(name)))
+        .collect();
+
+    let mut fields = Vec::new();
+    let mut arrays = Vec::new();
+    for name in columns {
+        let values: Vec<Option<&Value>> = items
+            .iter()
+            .map(|item| item.get(name).filter(|value| !value.is_null()))
+            .collect();
+        // A column holding only nulls is typed as text.
+        let column_type = values
+            .iter()
+            .flatten()
+            .filter_map(|value| ColumnType::of(value))
+            .reduce(ColumnType::widen)
+            .unwrap_or(ColumnType::Utf8);
+        fields.push(Field::new(name, column_type.data_type(), true));
+        arrays.push(column_type.array(values));
+    }
+
+    let schema = Arc::new(Schema::new(fields));
+    // The row count is given for items without any attribute, which make no columns.
+    let batch = RecordBatch::try_new_with_options(
+        schema.clone(),
+        arrays,
+        &RecordBatchOptions::new().with_row_count(Some(items.len())),
+    )?;
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+
+    let mut buffer = Vec::new();
+    let mut writer = ArrowWriter::try_new(&mut buffer, schema, Some(properties))?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use arrow::array::Array;
+
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_to_parquet() {
+        let items = [
+            json!({"id": "b", "count": 2, "price": 1.5, "paid": true, "tags": ["x"]}),
+            json!({"id": "a", "count": 3, "price": 2, "mixed": "text"}),
+            json!({"id": "c", "mixed": 7, "missing": null}),
+        ];
+        let bytes = to_parquet(&items, &["id".to_string()]).unwrap();
+
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(&bytes).unwrap();
+        let reader = ParquetRecordBatchReaderBuilder::try_new(file)
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches: Vec<RecordBatch> = reader.map(Result::unwrap).collect();
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 3);
+
+        let schema = batch.schema();
+        let types: Vec<(&str, &DataType)> = schema
+            .fields()
+            .iter()
+            .map(|field| (field.name().as_str(), field.data_type()))
+            .collect();
+        assert_eq!(
+            types,
+            [
+                ("id", &DataType::Utf8),
+                ("count", &DataType::Int64),
+                ("missing", &DataType::Utf8),
+                ("mixed", &DataType::Utf8),
+                ("paid", &DataType::Boolean),
+                ("price", &DataType::Float64),
+                ("tags", &DataType::Utf8),
+            ]
+        );
+
+        let column = |name: &str| batch.column_by_name(name).unwrap().clone();
+        let mixed = column("mixed");
+        let mixed = mixed.as_any().downcast_ref::<StringArray>().unwrap();
+        assert!(mixed.is_null(0));
+        assert_eq!(mixed.value(1), "\"text\"");
+        assert_eq!(mixed.value(2), "7");
+        let price = column("price");
+        let price = price.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(price.value(1), 2.0);
+        let tags = column("tags");
+        let tags = tags.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(tags.value(0), "[\"x\"]");
+    }
+
+    #[test]
+    fn test_to_parquet_without_items() {
+        assert!(to_parquet(&[], &[]).is_ok_and(|bytes| !bytes.is_empty()));
+    }
+
+    #[test]
+    fn test_is_parquet() {
+        assert!(is_parquet("out/items.PARQUET"));
+        assert!(!is_parquet("items.json"));
+    }
+}
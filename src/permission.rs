@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+
+use crate::access::ACCESS_DENIED_CODES;
+
+/// Sent as the key of the probes that need one. No item is expected to have it, and the probes
+/// that write are conditioned so they never do.
+pub const PROBE_KEY_VALUE: &str = "dynotui-permission-probe";
+
+/// The operations the app performs on a table, in the order they are probed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TableOperation {
+    DescribeTable,
+    Scan,
+    Query,
+    GetItem,
+    PutItem,
+}
+
+impl TableOperation {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TableOperation::DescribeTable => "DescribeTable",
+            TableOperation::Scan => "Scan",
+            TableOperation::Query => "Query",
+            TableOperation::GetItem => "GetItem",
+            TableOperation::PutItem => "PutItem",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Permission {
+    Allowed,
+    /// Denied by IAM or by the workspace, for the reason given.
+    Denied(String),
+    /// The probe failed for another reason, so it told nothing.
+    Unknown(String),
+}
+
+impl Permission {
+    /// Reads the outcome of a probe from the error code it failed with, if any. A failed
+    /// condition means the request was allowed and only its condition stopped it.
+    pub fn from_error(code: Option<&str>, message: &str) -> Self {
+        match code {
+            Some(code) if ACCESS_DENIED_CODES.contains(&code) => {
+                Permission::Denied(message.to_string())
+            }
+            Some("ConditionalCheckFailedException") => Permission::Allowed,
+            _ => Permission::Unknown(message.to_string()),
+        }
+    }
+}
+
+/// What the current credentials may do with a table, as found by probing each operation.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Permissions {
+    pub table: String,
+    pub operations: Vec<(TableOperation, Permission)>,
+}
+
+impl Permissions {
+    pub fn is_denied(&self, operation: TableOperation) -> bool {
+        self.operations.iter().any(|(probed, permission)| {
+            *probed == operation && matches!(permission, Permission::Denied(_))
+        })
+    }
+
+    /// A line such as `3 of 5 operations allowed`, counting only the probes that told.
+    pub fn summary(&self) -> String {
+        let told: Vec<&Permission> = self
+            .operations
+            .iter()
+            .map(|(_, permission)| permission)
+            .filter(|permission| !matches!(permission, Permission::Unknown(_)))
+            .collect();
+        let allowed = told
+            .iter()
+            .filter(|permission| ***permission == Permission::Allowed)
+            .count();
+        format!("{allowed} of {} operations allowed", told.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_permission_from_error() {
+        assert_eq!(
+            Permission::from_error(Some("AccessDeniedException"), "not authorized"),
+            Permission::Denied("not authorized".to_string())
+        );
+        assert_eq!(
+            Permission::from_error(Some("ConditionalCheckFailedException"), ""),
+            Permission::Allowed
+        );
+        assert_eq!(
+            Permission::from_error(None, "timed out"),
+            Permission::Unknown("timed out".to_string())
+        );
+    }
+
+    #[test]
+    fn test_permissions_summary() {
+        let permissions = Permissions {
+            table: "orders".to_string(),
+            operations: vec![
+                (TableOperation::Scan, Permission::Allowed),
+                (TableOperation::PutItem, Permission::Denied(String::new())),
+                (TableOperation::Query, Permission::Unknown(String::new())),
+            ],
+        };
+
+        assert!(permissions.is_denied(TableOperation::PutItem));
+        assert!(!permissions.is_denied(TableOperation::Query));
+        assert_eq!(permissions.summary(), "1 of 2 operations allowed");
+    }
+}
@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Attribute paths shown at the top of the detail view for every item of a table. A path names
+/// the attributes leading to a value, joined by dots, with list elements named by their index.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PinnedFields {
+    pub table: String,
+    #[serde(default)]
+    pub paths: Vec<String>,
+}
+
+/// The paths pinned for `table`, in the order they were pinned.
+pub fn paths_for<'a>(pins: &'a [PinnedFields], table: &str) -> &'a [String] {
+    pins.iter()
+        .find(|pin| pin.table == table)
+        .map(|pin| pin.paths.as_slice())
+        .unwrap_or_default()
+}
+
+/// Pins `path` for `table`, or unpins it when it already is. Returns whether it is now pinned.
+pub fn toggle(pins: &mut Vec<PinnedFields>, table: &str, path: &str) -> bool {
+    let index = match pins.iter().position(|pin| pin.table == table) {
+        Some(index) => index,
+        None => {
+            pins.push(PinnedFields {
+                table: table.to_string(),
+                paths: Vec::new(),
+            });
+            pins.len() - 1
+        }
+    };
+
+    let paths = &mut pins[index].paths;
+    let pinned = match paths.iter().position(|pinned| pinned == path) {
+        Some(position) => {
+            paths.remove(position);
+            false
+        }
+        None => {
+            paths.push(path.to_string());
+            true
+        }
+    };
+
+    if pins[index].paths.is_empty() {
+        pins.remove(index);
+    }
+    pinned
+}
+
+/// The dotted form of a path through an item.
+pub fn join_path(path: &[String]) -> String {
+    path.join(".")
+}
+
+/// The value `path` leads to in `item`, if the item has it.
+pub fn lookup<'a>(item: &'a Value, path: &str) -> Option<&'a Value> {
+    lookup_segments(item, path.split('.'))
+}
+
+/// The value a path, given as its attribute names and list indexes, leads to in `item`. Unlike
+/// [`lookup`], names holding a dot are found.
+pub fn lookup_segments<S: AsRef<str>>(
+    item: &Value,
+    path: impl IntoIterator<Item = S>,
+) -> Option<&Value> {
+    path.into_iter()
+        .try_fold(item, |value, segment| match value {
+            Value::Object(map) => map.get(segment.as_ref()),
+            Value::Array(list) => segment
+                .as_ref()
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| list.get(i)),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_toggle_pins() {
+        let mut pins = Vec::new();
+
+        assert!(toggle(&mut pins, "orders", "status"));
+        assert!(toggle(&mut pins, "orders", "address.city"));
+        assert!(toggle(&mut pins, "users", "email"));
+        assert_eq!(paths_for(&pins, "orders"), ["status", "address.city"]);
+
+        assert!(!toggle(&mut pins, "orders", "status"));
+        assert_eq!(paths_for(&pins, "orders"), ["address.city"]);
+
+        assert!(!toggle(&mut pins, "users", "email"));
+        assert!(paths_for(&pins, "users").is_empty());
+        assert_eq!(pins.len(), 1);
+    }
+
+    #[test]
+    fn test_lookup_path() {
+        let item = json!({ "pk": "a", "address": { "city": "Oslo" }, "tags": ["x", "y"] });
+
+        assert_eq!(lookup(&item, "pk"), Some(&json!("a")));
+        assert_eq!(lookup(&item, "address.city"), Some(&json!("Oslo")));
+        assert_eq!(lookup(&item, "tags.1"), Some(&json!("y")));
+        assert_eq!(lookup(&item, "tags.2"), None);
+        assert_eq!(lookup(&item, "address.zip"), None);
+
+        let dotted = json!({ "a.b": { "c": 1 } });
+        assert_eq!(lookup(&dotted, "a.b.c"), None);
+        assert_eq!(lookup_segments(&dotted, ["a.b", "c"]), Some(&json!(1)));
+    }
+}
@@ -0,0 +1,302 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fs::File,
+    io::{self, Read, Seek, SeekFrom, Write},
+};
+
+use tracing::error;
+
+/// The records of one result set. Once they take more than `memory_budget` bytes, the oldest are
+/// appended to an anonymous temp file as NDJSON and read back whenever they are needed again.
+pub struct RecordStore {
+    records: Vec<Slot>,
+    memory_budget: usize,
+    resident_bytes: usize,
+    /// Records before this index are on disk.
+    spilled: usize,
+    file: Option<File>,
+    file_len: u64,
+    /// The index of the record with each primary key, for the records merged with theirs.
+    keys: HashMap<String, usize>,
+}
+
+enum Slot {
+    Memory(String),
+    Disk { offset: u64, len: usize },
+}
+
+impl Default for RecordStore {
+    fn default() -> Self {
+        Self::new(usize::MAX)
+    }
+}
+
+impl RecordStore {
+    pub fn new(memory_budget: usize) -> Self {
+        Self {
+            records: Vec::new(),
+            memory_budget,
+            resident_bytes: 0,
+            spilled: 0,
+            file: None,
+            file_len: 0,
+            keys: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Bytes of records held in memory.
+    pub fn resident_bytes(&self) -> usize {
+        self.resident_bytes
+    }
+
+    /// Number of records that were moved to disk.
+    pub fn spilled(&self) -> usize {
+        self.spilled
+    }
+
+    pub fn get(&self, index: usize) -> Option<Cow<'_, str>> {
+        match self.records.get(index)? {
+            Slot::Memory(record) => Some(Cow::Borrowed(record)),
+            Slot::Disk { offset, len } => match self.read(*offset, *len) {
+                Ok(record) => Some(Cow::Owned(record)),
+                Err(err) => {
+                    error!("Failed to read spilled record {index}: {err}");
+                    None
+                }
+            },
+        }
+    }
+
+    /// Appends a page of records, spilling the oldest ones if the budget is exceeded.
+    pub fn extend(&mut self, records: Vec<String>) {
+        self.resident_bytes += records.iter().map(String::len).sum::<usize>();
+        self.records.extend(records.into_iter().map(Slot::Memory));
+
+        if self.resident_bytes > self.memory_budget {
+            if let Err(err) = self.spill() {
+                // Keeping the records in memory is better than losing them.
+                error!("Failed to spill records to disk: {err}");
+            }
+        }
+    }
+
+    /// Appends a page of records paired with their primary keys, putting a record whose key is
+    /// already held in place of the older version instead. Records without a key are always
+    /// appended. Returns how many records were appended.
+    pub fn merge(&mut self, records: Vec<(Option<String>, String)>) -> usize {
+        let mut appended: Vec<String> = Vec::new();
+        for (key, record) in records {
+            let Some(key) = key else {
+                appended.push(record);
+                continue;
+            };
+            match self.keys.get(&key) {
+                // Pages can repeat an item of their own, which is not in the store yet.
+                Some(&index) if index >= self.records.len() => {
+                    appended[index - self.records.len()] = record;
+                }
+                Some(&index) => self.replace(index, record),
+                None => {
+                    self.keys.insert(key, self.records.len() + appended.len());
+                    appended.push(record);
+                }
+            }
+        }
+
+        let count = appended.len();
+        self.extend(appended);
+        count
+    }
+
+    /// Puts a newer version of the record at `index` in its place. A record on disk is written
+    /// again at the end of the spill file, so the records before `spilled` stay on disk.
+    pub fn replace(&mut self, index: usize, record: String) {
+        match self.records.get(index) {
+            None => {}
+            Some(Slot::Memory(old)) => {
+                self.resident_bytes = self.resident_bytes - old.len() + record.len();
+                self.records[index] = Slot::Memory(record);
+            }
+            Some(Slot::Disk { .. }) => match self.append_to_file(&record) {
+                Ok(offset) => {
+                    self.records[index] = Slot::Disk {
+                        offset,
+                        len: record.len(),
+                    }
+                }
+                Err(err) => error!("Failed to replace spilled record {index}: {err}"),
+            },
+        }
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        for slot in self.records.drain(len.min(self.records.len())..) {
+            if let Slot::Memory(record) = slot {
+                self.resident_bytes -= record.len();
+            }
+        }
+        self.spilled = self.spilled.min(len);
+        self.keys.retain(|_, index| *index < len);
+    }
+
+    /// Writes the oldest records in memory to the spill file until the rest fit in the budget.
+    fn spill(&mut self) -> io::Result<()> {
+        let file = match &mut self.file {
+            Some(file) => file,
+            None => self.file.insert(tempfile::tempfile()?),
+        };
+
+        let mut buffer = Vec::new();
+        let mut spilled = self.spilled;
+        let mut resident_bytes = self.resident_bytes;
+        let mut offsets = Vec::new();
+        while resident_bytes > self.memory_budget && spilled < self.records.len() {
+            let Slot::Memory(record) = &self.records[spilled] else {
+                unreachable!("records after the spilled ones are in memory");
+            };
+            offsets.push((self.file_len + buffer.len() as u64, record.len()));
+            buffer.extend_from_slice(record.as_bytes());
+            buffer.push(b'\n');
+            resident_bytes -= record.len();
+            spilled += 1;
+        }
+
+        file.seek(SeekFrom::Start(self.file_len))?;
+        file.write_all(&buffer)?;
+
+        // Only give up the records in memory once they are safely on disk.
+        for (index, (offset, len)) in (self.spilled..spilled).zip(offsets) {
+            self.records[index] = Slot::Disk { offset, len };
+        }
+        self.file_len += buffer.len() as u64;
+        self.spilled = spilled;
+        self.resident_bytes = resident_bytes;
+        Ok(())
+    }
+
+    fn append_to_file(&mut self, record: &str) -> io::Result<u64> {
+        let file = self
+            .file
+            .as_mut()
+            .ok_or_else(|| io::Error::other("no spill file"))?;
+        let offset = self.file_len;
+        file.seek(SeekFrom::Start(offset))?;
+        file.write_all(record.as_bytes())?;
+        file.write_all(b"\n")?;
+        self.file_len += record.len() as u64 + 1;
+        Ok(offset)
+    }
+
+    fn read(&self, offset: u64, len: usize) -> io::Result<String> {
+        let mut file = self
+            .file
+            .as_ref()
+            .ok_or_else(|| io::Error::other("no spill file"))?;
+        file.seek(SeekFrom::Start(offset))?;
+
+        let mut record = vec![0; len];
+        file.read_exact(&mut record)?;
+        String::from_utf8(record).map_err(io::Error::other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn page(records: &[&str]) -> Vec<String> {
+        records.iter().map(|record| record.to_string()).collect()
+    }
+
+    #[test]
+    fn test_spills_oldest_records_past_budget() {
+        let mut store = RecordStore::new(10);
+        store.extend(page(&[r#"{"id":1}"#, r#"{"id":2}"#]));
+        store.extend(page(&[r#"{"id":3}"#]));
+
+        assert_eq!(store.len(), 3);
+        assert_eq!(store.spilled(), 2);
+        assert_eq!(store.resident_bytes(), 8);
+        assert_eq!(
+            (0..store.len())
+                .filter_map(|index| store.get(index))
+                .collect::<Vec<_>>(),
+            vec![r#"{"id":1}"#, r#"{"id":2}"#, r#"{"id":3}"#]
+        );
+    }
+
+    #[test]
+    fn test_truncate_drops_spilled_and_resident_records() {
+        let mut store = RecordStore::new(10);
+        store.extend(page(&[r#"{"id":1}"#, r#"{"id":2}"#, r#"{"id":3}"#]));
+
+        store.truncate(1);
+        assert_eq!(store.len(), 1);
+        assert_eq!(store.spilled(), 1);
+        assert_eq!(store.resident_bytes(), 0);
+        assert_eq!(store.get(0).as_deref(), Some(r#"{"id":1}"#));
+
+        store.extend(page(&[r#"{"id":4}"#]));
+        assert_eq!(store.get(1).as_deref(), Some(r#"{"id":4}"#));
+    }
+
+    #[test]
+    fn test_merge_records_by_key() {
+        let keyed = |records: &[(&str, &str)]| -> Vec<(Option<String>, String)> {
+            records
+                .iter()
+                .map(|(key, record)| (Some(key.to_string()), record.to_string()))
+                .collect()
+        };
+        let mut store = RecordStore::new(10);
+
+        assert_eq!(
+            store.merge(keyed(&[("1", r#"{"id":1}"#), ("2", r#"{"id":2}"#)])),
+            2
+        );
+        assert_eq!(
+            store.merge(keyed(&[
+                ("2", r#"{"id":2,"v":2}"#),
+                ("3", r#"{"id":3}"#),
+                ("3", r#"{"id":3,"v":2}"#)
+            ])),
+            1
+        );
+        assert_eq!(store.merge(vec![(None, r#"{"id":1}"#.to_string())]), 1);
+        assert_eq!(
+            (0..store.len())
+                .filter_map(|index| store.get(index))
+                .collect::<Vec<_>>(),
+            vec![
+                r#"{"id":1}"#,
+                r#"{"id":2,"v":2}"#,
+                r#"{"id":3,"v":2}"#,
+                r#"{"id":1}"#
+            ]
+        );
+
+        store.truncate(1);
+        assert_eq!(store.merge(keyed(&[("2", r#"{"id":2}"#)])), 1);
+        assert_eq!(store.len(), 2);
+    }
+
+    #[test]
+    fn test_replace_spilled_and_resident_records() {
+        let mut store = RecordStore::new(10);
+        store.extend(page(&[r#"{"id":1}"#, r#"{"id":2}"#]));
+
+        store.replace(0, r#"{"id":1,"v":2}"#.to_string());
+        store.replace(1, r#"{"id":2,"v":2}"#.to_string());
+        assert_eq!(store.spilled(), 1);
+        assert_eq!(store.resident_bytes(), 14);
+        assert_eq!(store.get(0).as_deref(), Some(r#"{"id":1,"v":2}"#));
+        assert_eq!(store.get(1).as_deref(), Some(r#"{"id":2,"v":2}"#));
+    }
+}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// An entry of the `relations` config section: the attribute of `table` at the dotted path
+/// `attribute` holds the partition key value of an item in `target_table`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Relation {
+    pub table: String,
+    pub attribute: String,
+    pub target_table: String,
+}
+
+/// The relation declared for the attribute of `table` at `path`.
+pub fn find<'a>(relations: &'a [Relation], table: &str, path: &str) -> Option<&'a Relation> {
+    relations
+        .iter()
+        .find(|relation| relation.table == table && relation.attribute == path)
+}
+
+/// The partition key value to look up in the target table, for a value that can be a key.
+pub fn key_value(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn relation(table: &str, attribute: &str, target_table: &str) -> Relation {
+        Relation {
+            table: table.to_string(),
+            attribute: attribute.to_string(),
+            target_table: target_table.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_find_relation() {
+        let relations = [
+            relation("orders", "userId", "users"),
+            relation("orders", "shipping.addressId", "addresses"),
+        ];
+
+        assert_eq!(
+            find(&relations, "orders", "shipping.addressId").map(|r| r.target_table.as_str()),
+            Some("addresses")
+        );
+        assert_eq!(find(&relations, "users", "userId"), None);
+        assert_eq!(find(&relations, "orders", "total"), None);
+    }
+
+    #[test]
+    fn test_key_value() {
+        assert_eq!(key_value(&json!("u-1")), Some("u-1".to_string()));
+        assert_eq!(key_value(&json!(42)), Some("42".to_string()));
+        assert_eq!(key_value(&json!({ "id": 1 })), None);
+    }
+}
@@ -0,0 +1,100 @@
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+use serde_json::Value;
+
+/// How much more a match on the value of a key attribute counts than one on another value.
+const KEY_VALUE_WEIGHT: i64 = 4;
+/// How much more a match on the name of an attribute counts than one on a value.
+const ATTRIBUTE_NAME_WEIGHT: i64 = 3;
+/// Values longer than this count for less the longer they are, so a word buried in a long
+/// description ranks below the same word making up a short value.
+const SHORT_VALUE_LENGTH: usize = 32;
+
+/// How well a filter keyword matches an item, or `None` if nothing in the item matches it. The
+/// best match counts: matches on the values of the key attributes named in `key_names` rank
+/// highest, then matches on attribute names, then matches on other values, short ones first.
+pub fn keyword_score(
+    matcher: &SkimMatcherV2,
+    keyword: &str,
+    item: &Value,
+    key_names: &[String],
+) -> Option<i64> {
+    match item {
+        Value::Object(map) => map
+            .iter()
+            .filter_map(|(name, value)| {
+                let name_score = matcher
+                    .fuzzy_match(name, keyword)
+                    .map(|score| score * ATTRIBUTE_NAME_WEIGHT);
+                let value_score = value_score(matcher, keyword, value).map(|score| {
+                    if key_names.contains(name) {
+                        score * KEY_VALUE_WEIGHT
+                    } else {
+                        score
+                    }
+                });
+                name_score.max(value_score)
+            })
+            .max(),
+        value => value_score(matcher, keyword, value),
+    }
+}
+
+/// The best match in a value, looking through nested maps and lists.
+fn value_score(matcher: &SkimMatcherV2, keyword: &str, value: &Value) -> Option<i64> {
+    let text = match value {
+        Value::Object(map) => {
+            return map
+                .iter()
+                .filter_map(|(name, value)| {
+                    let name_score = matcher
+                        .fuzzy_match(name, keyword)
+                        .map(|score| score * ATTRIBUTE_NAME_WEIGHT);
+                    name_score.max(value_score(matcher, keyword, value))
+                })
+                .max();
+        }
+        Value::Array(values) => {
+            return values
+                .iter()
+                .filter_map(|value| value_score(matcher, keyword, value))
+                .max();
+        }
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(flag) => flag.to_string(),
+        Value::Null => return None,
+    };
+
+    let score = matcher.fuzzy_match(&text, keyword)?;
+    let length = text.chars().count().max(SHORT_VALUE_LENGTH) as i64;
+    // Any match still counts for something, however long the value.
+    Some((score * SHORT_VALUE_LENGTH as i64 / length).max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_keyword_scores() {
+        let matcher = SkimMatcherV2::default();
+        let keys = vec!["pk".to_string()];
+        let score = |item: Value| keyword_score(&matcher, "alice", &item, &keys);
+
+        let in_key = score(json!({ "pk": "alice", "note": "" }));
+        let in_name = score(json!({ "pk": "USER#2", "alice": true }));
+        let in_short_value = score(json!({ "pk": "USER#3", "name": "alice" }));
+        let in_long_value = score(json!({
+            "pk": "USER#4",
+            "bio": "Met with the team in the morning, then wrote to alice about the quarterly report",
+        }));
+
+        assert!(in_key > in_name);
+        assert!(in_name > in_short_value);
+        assert!(in_short_value > in_long_value);
+        assert!(in_long_value.is_some());
+        assert_eq!(score(json!({ "pk": "USER#5", "tags": [{ "n": 1 }] })), None);
+    }
+}
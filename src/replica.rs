@@ -0,0 +1,63 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// An item as it was read from one region of a global table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegionItem {
+    pub region: String,
+    /// The item as JSON, or `None` if the region has no item with the key.
+    pub item: Option<String>,
+    pub error: Option<String>,
+}
+
+impl RegionItem {
+    /// The attributes of the item, empty if it is missing or could not be read.
+    pub fn attributes(&self) -> Map<String, Value> {
+        match self.item.as_deref().map(serde_json::from_str) {
+            Some(Ok(Value::Object(attributes))) => attributes,
+            _ => Map::new(),
+        }
+    }
+}
+
+/// The names of the attributes that are missing from one of the items or hold different values.
+pub fn diverging_attributes(a: &Map<String, Value>, b: &Map<String, Value>) -> BTreeSet<String> {
+    a.keys()
+        .chain(b.keys())
+        .filter(|name| a.get(*name) != b.get(*name))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn attributes(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(attributes) => attributes,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_diverging_attributes() {
+        let primary = attributes(json!({ "id": 1, "status": "paid", "total": 10 }));
+        let replica = attributes(json!({ "id": 1, "status": "new", "note": "late" }));
+
+        assert_eq!(
+            diverging_attributes(&primary, &replica),
+            BTreeSet::from([
+                "note".to_string(),
+                "status".to_string(),
+                "total".to_string(),
+            ])
+        );
+        assert!(diverging_attributes(&primary, &primary).is_empty());
+    }
+}
@@ -0,0 +1,247 @@
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// An entry of the `schemas` config section: a JSON Schema the items of `table` must follow.
+///
+/// The keywords checked are `type`, `enum`, `const`, `required`, `properties`,
+/// `additionalProperties`, `items`, `minItems`, `maxItems`, `minLength`, `maxLength`, `minimum`
+/// and `maximum`. Other keywords are ignored.
+#[derive(Clone, Debug, Deserialize)]
+pub struct TableSchema {
+    pub table: String,
+    pub schema: Value,
+}
+
+/// The schema registered for `table`, if any.
+pub fn for_table<'a>(schemas: &'a [TableSchema], table: &str) -> Option<&'a Value> {
+    schemas
+        .iter()
+        .find(|schema| schema.table == table)
+        .map(|schema| &schema.schema)
+}
+
+/// The ways `value` breaks `schema`, each prefixed with the dotted path of the offending value.
+/// Empty when the value conforms.
+pub fn validate(schema: &Value, value: &Value) -> Vec<String> {
+    let mut violations = Vec::new();
+    check(schema, value, "", &mut violations);
+    violations
+}
+
+fn check(schema: &Value, value: &Value, path: &str, violations: &mut Vec<String>) {
+    let Value::Object(schema) = schema else {
+        // `true` and `false` accept and refuse everything.
+        if *schema == Value::Bool(false) {
+            violations.push(violation(path, "is not allowed"));
+        }
+        return;
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|name| has_type(value, name)) {
+            violations.push(violation(
+                path,
+                &format!("should be {}, not {}", types.join(" or "), type_name(value)),
+            ));
+            return;
+        }
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            violations.push(violation(path, "is not one of the allowed values"));
+        }
+    }
+    if let Some(expected) = schema.get("const") {
+        if expected != value {
+            violations.push(violation(path, &format!("should be {expected}")));
+        }
+    }
+
+    match value {
+        Value::Object(object) => check_object(schema, object, path, violations),
+        Value::Array(list) => {
+            if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+                if (list.len() as u64) < min {
+                    violations.push(violation(
+                        path,
+                        &format!("should have at least {min} items"),
+                    ));
+                }
+            }
+            if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+                if list.len() as u64 > max {
+                    violations.push(violation(path, &format!("should have at most {max} items")));
+                }
+            }
+            if let Some(items) = schema.get("items") {
+                for (index, item) in list.iter().enumerate() {
+                    check(items, item, &join(path, &index.to_string()), violations);
+                }
+            }
+        }
+        Value::String(text) => {
+            let length = text.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                if length < min {
+                    violations.push(violation(
+                        path,
+                        &format!("should be at least {min} characters"),
+                    ));
+                }
+            }
+            if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+                if length > max {
+                    violations.push(violation(
+                        path,
+                        &format!("should be at most {max} characters"),
+                    ));
+                }
+            }
+        }
+        Value::Number(number) => {
+            let number = number.as_f64().unwrap_or_default();
+            if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+                if number < min {
+                    violations.push(violation(path, &format!("should be at least {min}")));
+                }
+            }
+            if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+                if number > max {
+                    violations.push(violation(path, &format!("should be at most {max}")));
+                }
+            }
+        }
+        Value::Bool(_) | Value::Null => {}
+    }
+}
+
+fn check_object(
+    schema: &Map<String, Value>,
+    object: &Map<String, Value>,
+    path: &str,
+    violations: &mut Vec<String>,
+) {
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for name in required.iter().filter_map(Value::as_str) {
+            if !object.contains_key(name) {
+                violations.push(violation(&join(path, name), "is missing"));
+            }
+        }
+    }
+
+    let properties = schema.get("properties").and_then(Value::as_object);
+    for (name, value) in object {
+        match properties.and_then(|properties| properties.get(name)) {
+            Some(property) => check(property, value, &join(path, name), violations),
+            None => {
+                if let Some(additional) = schema.get("additionalProperties") {
+                    check(additional, value, &join(path, name), violations);
+                }
+            }
+        }
+    }
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "integer" => value.as_i64().is_some() || value.as_u64().is_some(),
+        "number" => value.is_number(),
+        other => type_name(value) == other,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn join(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{path}.{name}")
+    }
+}
+
+fn violation(path: &str, problem: &str) -> String {
+    if path.is_empty() {
+        format!("The item {problem}")
+    } else {
+        format!("{path} {problem}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["pk", "status"],
+            "properties": {
+                "pk": { "type": "string", "minLength": 1 },
+                "status": { "enum": ["new", "paid"] },
+                "total": { "type": "number", "minimum": 0 },
+                "lines": {
+                    "type": "array",
+                    "minItems": 1,
+                    "items": { "type": "object", "required": ["quantity"] },
+                },
+            },
+            "additionalProperties": false,
+        })
+    }
+
+    #[test]
+    fn test_valid_item() {
+        let item = json!({ "pk": "a", "status": "new", "total": 3, "lines": [{ "quantity": 1 }] });
+
+        assert!(validate(&schema(), &item).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_item() {
+        let item = json!({
+            "pk": "",
+            "total": -1,
+            "lines": [{}],
+            "note": "x",
+        });
+
+        assert_eq!(
+            validate(&schema(), &item),
+            vec![
+                "status is missing",
+                "lines.0.quantity is missing",
+                "note is not allowed",
+                "pk should be at least 1 characters",
+                "total should be at least 0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_mismatch() {
+        assert_eq!(
+            validate(&json!({ "type": "integer" }), &json!(1.5)),
+            vec!["The item should be integer, not number"]
+        );
+        assert!(validate(&json!({ "type": ["string", "null"] }), &json!(null)).is_empty());
+    }
+}
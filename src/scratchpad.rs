@@ -0,0 +1,220 @@
+use std::{fs, io, path::PathBuf};
+
+use crate::config::get_data_dir;
+
+/// Where the notes of a table are kept, in the `notes` folder of the data directory.
+pub fn path(table: &str) -> PathBuf {
+    let name: String = table
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    get_data_dir().join("notes").join(format!("{name}.md"))
+}
+
+/// The notes of a table, empty if none were written yet.
+pub fn load(table: &str) -> io::Result<String> {
+    match fs::read_to_string(path(table)) {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        text => text,
+    }
+}
+
+pub fn save(table: &str, text: &str) -> io::Result<()> {
+    let path = path(table);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, text)
+}
+
+/// Adds a snippet on a line of its own at the end of the notes of a table.
+pub fn stash(table: &str, snippet: &str) -> io::Result<()> {
+    let mut notes = Notes::new(&load(table)?);
+    notes.append(snippet);
+    save(table, &notes.text())
+}
+
+/// Text being written in the scratchpad, as lines with a cursor in them. The column of the cursor
+/// counts characters rather than bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notes {
+    lines: Vec<String>,
+    row: usize,
+    column: usize,
+}
+
+impl Default for Notes {
+    fn default() -> Self {
+        Self {
+            lines: vec![String::new()],
+            row: 0,
+            column: 0,
+        }
+    }
+}
+
+impl Notes {
+    /// The notes with the cursor at their end, where writing usually resumes.
+    pub fn new(text: &str) -> Self {
+        let mut notes = Self {
+            lines: text.lines().map(str::to_string).collect(),
+            ..Self::default()
+        };
+        if notes.lines.is_empty() {
+            notes.lines.push(String::new());
+        }
+        notes.row = notes.lines.len() - 1;
+        notes.column = notes.line_length();
+        notes
+    }
+
+    pub fn text(&self) -> String {
+        let mut text = self.lines.join("\n");
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// The line and the column of the cursor.
+    pub fn cursor(&self) -> (usize, usize) {
+        (self.row, self.column)
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        let index = self.byte_index();
+        self.lines[self.row].insert(index, c);
+        self.column += 1;
+    }
+
+    /// Inserts pasted text at the cursor, starting a line at each line break.
+    pub fn insert_str(&mut self, text: &str) {
+        for c in text.chars() {
+            match c {
+                '\n' => self.newline(),
+                '\r' => {}
+                '\t' => (0..4).for_each(|_| self.insert_char(' ')),
+                c => self.insert_char(c),
+            }
+        }
+    }
+
+    /// Splits the line at the cursor.
+    pub fn newline(&mut self) {
+        let index = self.byte_index();
+        let rest = self.lines[self.row].split_off(index);
+        self.row += 1;
+        self.lines.insert(self.row, rest);
+        self.column = 0;
+    }
+
+    /// Deletes the character before the cursor, joining the line to the one above at its start.
+    pub fn backspace(&mut self) {
+        if self.column > 0 {
+            self.column -= 1;
+            let index = self.byte_index();
+            self.lines[self.row].remove(index);
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.column = self.line_length();
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    pub fn move_left(&mut self) {
+        if self.column > 0 {
+            self.column -= 1;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.column = self.line_length();
+        }
+    }
+
+    pub fn move_right(&mut self) {
+        if self.column < self.line_length() {
+            self.column += 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.column = 0;
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        self.row = self.row.saturating_sub(1);
+        self.column = self.column.min(self.line_length());
+    }
+
+    pub fn move_down(&mut self) {
+        self.row = (self.row + 1).min(self.lines.len() - 1);
+        self.column = self.column.min(self.line_length());
+    }
+
+    /// Adds a snippet after the last line, and moves the cursor to its end.
+    pub fn append(&mut self, snippet: &str) {
+        if !self.lines.last().is_some_and(String::is_empty) {
+            self.lines.push(String::new());
+        }
+        self.row = self.lines.len() - 1;
+        self.column = self.line_length();
+        self.insert_str(snippet);
+    }
+
+    fn line_length(&self) -> usize {
+        self.lines[self.row].chars().count()
+    }
+
+    fn byte_index(&self) -> usize {
+        let line = &self.lines[self.row];
+        line.char_indices()
+            .map(|(i, _)| i)
+            .nth(self.column)
+            .unwrap_or(line.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_edit_notes() {
+        let mut notes = Notes::new("pk: USER#1\n");
+        assert_eq!(notes.cursor(), (0, 10));
+
+        notes.newline();
+        notes.insert_str("sk: ORDER#7\r\nthrottled é");
+        assert_eq!(notes.cursor(), (2, 11));
+        notes.backspace();
+        notes.move_up();
+        notes.insert_char('8');
+        assert_eq!(notes.text(), "pk: USER#1\nsk: ORDER#87\nthrottled \n");
+
+        notes.move_down();
+        notes.move_right();
+        notes.move_right();
+        assert_eq!(notes.cursor(), (2, 10));
+        (0..11).for_each(|_| notes.backspace());
+        assert_eq!(notes.cursor(), (1, 12));
+        assert_eq!(notes.lines(), ["pk: USER#1", "sk: ORDER#87"]);
+        notes.move_up();
+        (0..11).for_each(|_| notes.move_left());
+        assert_eq!(notes.cursor(), (0, 0));
+
+        notes.append("{\"id\":\"1\"}");
+        assert_eq!(notes.text(), "pk: USER#1\nsk: ORDER#87\n{\"id\":\"1\"}\n");
+        assert_eq!(Notes::new("").text(), "");
+    }
+}
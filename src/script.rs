@@ -0,0 +1,154 @@
+use crate::action::Action;
+
+/// A command given with `--cmd`, run once the app has started by sending the actions its keys
+/// would. Each command runs once the requests of the one before it have finished.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// `table <name>`: shows the items of a table.
+    Table(String),
+    /// `query <pk> [<sk>]`: queries the table shown for a partition key value, and optionally a
+    /// sort key value or range. Values may be written `name=value`.
+    Query(String, Option<String>),
+    /// `filter <text>`: filters the records shown, as typed after `f`.
+    Filter(String),
+    /// `find <text>`: jumps to the records holding the text, as typed after `/`.
+    Find(String),
+    /// `export <destination>`: writes the records shown to a file or another export destination,
+    /// as exported after `X`.
+    Export(String),
+    /// `import <path>`: compares a JSON or NDJSON file with the table shown, to be written once
+    /// the preview is confirmed, as imported after `I`.
+    Import(String),
+}
+
+impl Command {
+    pub fn parse(command: &str) -> Result<Self, String> {
+        let command = command.trim();
+        let (name, argument) = command.split_once(' ').unwrap_or((command, ""));
+        let argument = argument.trim();
+        if argument.is_empty() {
+            return Err(format!("`{command}` is missing its argument"));
+        }
+
+        match name {
+            "table" => Ok(Command::Table(argument.to_string())),
+            "query" => {
+                let mut values = argument.split_whitespace().map(key_value);
+                let partition_key = values.next().unwrap_or_default();
+                let sort_key = values.next();
+                match values.next() {
+                    Some(_) => Err(format!(
+                        "`{command}` takes a partition key value and at most a sort key value"
+                    )),
+                    None => Ok(Command::Query(partition_key, sort_key)),
+                }
+            }
+            "filter" => Ok(Command::Filter(argument.to_string())),
+            "find" => Ok(Command::Find(argument.to_string())),
+            "export" => Ok(Command::Export(argument.to_string())),
+            "import" => Ok(Command::Import(argument.to_string())),
+            _ => Err(format!(
+                "Unknown command `{name}`; expected table, query, filter, find, export or import"
+            )),
+        }
+    }
+
+    /// The actions the command is run with.
+    pub fn actions(&self) -> Vec<Action> {
+        match self {
+            Command::Table(table) => vec![
+                Action::TransmitSelectedTable(table.clone()),
+                Action::FetchTableData(table.clone()),
+            ],
+            Command::Query(partition_key, sort_key) => {
+                vec![Action::RunQuery(partition_key.clone(), sort_key.clone())]
+            }
+            Command::Filter(text) => std::iter::once(Action::ClearTableDataFilter)
+                .chain(std::iter::once(Action::FilterTableData))
+                .chain(text.chars().map(Action::NewFilterDataCharacter))
+                .chain(std::iter::once(Action::SubmitFilterDataText))
+                .collect(),
+            Command::Find(text) => std::iter::once(Action::FindInTableData)
+                .chain(text.chars().map(Action::NewFindCharacter))
+                .chain(std::iter::once(Action::SubmitFind))
+                .collect(),
+            Command::Export(destination) => std::iter::once(Action::ExportResults)
+                .chain(destination.chars().map(Action::NewExportCharacter))
+                .chain(std::iter::once(Action::SubmitExport))
+                .collect(),
+            Command::Import(path) => std::iter::once(Action::ImportFile)
+                .chain(path.chars().map(Action::NewImportCharacter))
+                .chain(std::iter::once(Action::SubmitImportPath))
+                .collect(),
+        }
+    }
+}
+
+/// The value of a `name=value` argument, or the argument itself when it names nothing.
+fn key_value(argument: &str) -> String {
+    match argument.split_once('=') {
+        Some((name, value))
+            if !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')) =>
+        {
+            value.to_string()
+        }
+        _ => argument.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(
+            Command::parse("table users"),
+            Ok(Command::Table("users".to_string()))
+        );
+        assert_eq!(
+            Command::parse("query pk=USER#42"),
+            Ok(Command::Query("USER#42".to_string(), None))
+        );
+        assert_eq!(
+            Command::parse("query USER#42 sk=2024-01..2024-02"),
+            Ok(Command::Query(
+                "USER#42".to_string(),
+                Some("2024-01..2024-02".to_string())
+            ))
+        );
+        assert_eq!(
+            Command::parse(" filter  has:email status"),
+            Ok(Command::Filter("has:email status".to_string()))
+        );
+        assert_eq!(
+            Command::parse("export out/users.json"),
+            Ok(Command::Export("out/users.json".to_string()))
+        );
+        assert_eq!(
+            Command::parse("import seed/users.ndjson"),
+            Ok(Command::Import("seed/users.ndjson".to_string()))
+        );
+        assert!(Command::parse("table").is_err());
+        assert!(Command::parse("query a b c").is_err());
+        assert!(Command::parse("delete users").is_err());
+    }
+
+    #[test]
+    fn test_find_actions() {
+        assert_eq!(
+            Command::Find("ab".to_string()).actions(),
+            vec![
+                Action::FindInTableData,
+                Action::NewFindCharacter('a'),
+                Action::NewFindCharacter('b'),
+                Action::SubmitFind,
+            ]
+        );
+    }
+}
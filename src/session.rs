@@ -0,0 +1,244 @@
+use std::{
+    fs::{self, File},
+    io::{LineWriter, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use color_eyre::Result;
+use crossterm::event::KeyEvent;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::{
+    sync::mpsc,
+    task,
+    time::{sleep_until, Instant as TokioInstant},
+};
+use tracing::debug;
+
+use crate::{
+    data::{FetchRequest, FetchResponse, KeyAttribute, RequestId},
+    tui::Event,
+};
+
+/// Something that happened during a recorded session, `at_ms` milliseconds after it started.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionEntry {
+    pub at_ms: u64,
+    pub event: SessionEvent,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionEvent {
+    Key(KeyEvent),
+    Resize(u16, u16),
+    /// Only the name of the action, since many of them carry items.
+    Action(String),
+    Response(RecordedResponse),
+}
+
+/// A fetch response with the values in its items redacted. Responses that would reveal more
+/// than the shape of the data are kept by name only, and are not replayed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecordedResponse {
+    Tables(Option<Vec<String>>),
+    TableData(String, Vec<String>, bool),
+    NextBatchTableData(String, Vec<String>, bool),
+    ApproximateTableDataCount(String, Option<i64>),
+    TableDescription(String, Option<(Option<KeyAttribute>, Option<KeyAttribute>)>),
+    FollowedTableData(String, Vec<String>),
+    QueryFailed(String, String),
+    RequestFinished(RequestId),
+    Other(String),
+}
+
+impl RecordedResponse {
+    pub fn new(response: &FetchResponse) -> Self {
+        match response {
+            FetchResponse::Tables(tables) => RecordedResponse::Tables(tables.clone()),
+            FetchResponse::TableData(table, data, has_more, _) => {
+                RecordedResponse::TableData(table.clone(), redact_records(data), *has_more)
+            }
+            FetchResponse::NextBatchTableData(table, data, has_more, _) => {
+                RecordedResponse::NextBatchTableData(table.clone(), redact_records(data), *has_more)
+            }
+            FetchResponse::ApproximateTableDataCount(table, count) => {
+                RecordedResponse::ApproximateTableDataCount(table.clone(), *count)
+            }
+            FetchResponse::TableDescription(table, description) => {
+                RecordedResponse::TableDescription(table.clone(), description.clone())
+            }
+            FetchResponse::FollowedTableData(table, data) => {
+                RecordedResponse::FollowedTableData(table.clone(), redact_records(data))
+            }
+            FetchResponse::QueryFailed(table, error) => {
+                RecordedResponse::QueryFailed(table.clone(), error.clone())
+            }
+            FetchResponse::RequestFinished(id) => RecordedResponse::RequestFinished(*id),
+            other => {
+                let name = format!("{other:?}");
+                let end = name.find(['(', ' ', '{']).unwrap_or(name.len());
+                RecordedResponse::Other(name[..end].to_string())
+            }
+        }
+    }
+
+    /// The response to send again when replaying, if it was recorded in full. Pages after a
+    /// replayed one are never actually requested, so they carry no key to continue from.
+    fn replayed(self) -> Option<FetchResponse> {
+        Some(match self {
+            RecordedResponse::Tables(tables) => FetchResponse::Tables(tables),
+            RecordedResponse::TableData(table, data, has_more) => {
+                FetchResponse::TableData(table, data, has_more, None)
+            }
+            RecordedResponse::NextBatchTableData(table, data, has_more) => {
+                FetchResponse::NextBatchTableData(table, data, has_more, None)
+            }
+            RecordedResponse::ApproximateTableDataCount(table, count) => {
+                FetchResponse::ApproximateTableDataCount(table, count)
+            }
+            RecordedResponse::TableDescription(table, description) => {
+                FetchResponse::TableDescription(table, description)
+            }
+            RecordedResponse::FollowedTableData(table, data) => {
+                FetchResponse::FollowedTableData(table, data)
+            }
+            RecordedResponse::QueryFailed(table, error) => FetchResponse::QueryFailed(table, error),
+            RecordedResponse::RequestFinished(id) => FetchResponse::RequestFinished(id),
+            RecordedResponse::Other(_) => return None,
+        })
+    }
+}
+
+fn redact_records(records: &[String]) -> Vec<String> {
+    records.iter().map(|record| redact_record(record)).collect()
+}
+
+/// Keeps the attribute names and the shape of an item, but replaces every string with as many
+/// `*` and every number with zero, so a glitch that depends on how wide values are still shows.
+pub fn redact_record(record: &str) -> String {
+    match serde_json::from_str::<Value>(record) {
+        Ok(value) => redact_value(value).to_string(),
+        Err(_) => "*".repeat(record.chars().count()),
+    }
+}
+
+/// The value with its strings and numbers hidden, as [`redact_record`] does for a whole item.
+pub fn redact_value(value: Value) -> Value {
+    match value {
+        Value::String(text) => Value::String("*".repeat(text.chars().count())),
+        Value::Number(_) => Value::from(0),
+        Value::Array(values) => Value::Array(values.into_iter().map(redact_value).collect()),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(name, value)| (name, redact_value(value)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// Writes a session to a file as it happens, one JSON entry per line, so the recording survives
+/// the app crashing.
+pub struct SessionRecorder {
+    file: LineWriter<File>,
+    started_at: Instant,
+}
+
+impl SessionRecorder {
+    pub fn create(path: &Path) -> Result<Self> {
+        Ok(Self {
+            file: LineWriter::new(File::create(path)?),
+            started_at: Instant::now(),
+        })
+    }
+
+    pub fn record(&mut self, event: SessionEvent) -> Result<()> {
+        let entry = SessionEntry {
+            at_ms: self.started_at.elapsed().as_millis() as u64,
+            event,
+        };
+        writeln!(self.file, "{}", serde_json::to_string(&entry)?)?;
+        Ok(())
+    }
+}
+
+pub fn load(path: &Path) -> Result<Vec<SessionEntry>> {
+    fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Ok(serde_json::from_str(line)?))
+        .collect()
+}
+
+/// Plays a recorded session back in place of the fetch worker: the key presses and resizes go
+/// to `event_tx` and the responses to `response_tx`, each as long after the start as it was
+/// recorded. Requests are answered only by what was recorded, so nothing reaches AWS.
+pub fn replay(
+    entries: Vec<SessionEntry>,
+    event_tx: mpsc::UnboundedSender<Event>,
+    response_tx: mpsc::Sender<FetchResponse>,
+    mut fetch_rx: mpsc::Receiver<(RequestId, FetchRequest)>,
+) {
+    task::spawn(async move {
+        while let Some((id, request)) = fetch_rx.recv().await {
+            debug!("Replay skipped request {id}: {request:?}");
+        }
+    });
+
+    task::spawn(async move {
+        let started_at = TokioInstant::now();
+        for entry in entries {
+            sleep_until(started_at + Duration::from_millis(entry.at_ms)).await;
+            let sent = match entry.event {
+                SessionEvent::Key(key) => event_tx.send(Event::Key(key)).is_ok(),
+                SessionEvent::Resize(x, y) => event_tx.send(Event::Resize(x, y)).is_ok(),
+                SessionEvent::Response(response) => match response.replayed() {
+                    Some(response) => response_tx.send(response).await.is_ok(),
+                    None => true,
+                },
+                SessionEvent::Action(_) => true,
+            };
+            if !sent {
+                break;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_redact_record() {
+        assert_eq!(
+            redact_record(r#"{"pk":"user#1","total":12.5,"tags":["a"],"active":true}"#),
+            r#"{"active":true,"pk":"******","tags":["*"],"total":0}"#
+        );
+    }
+
+    #[test]
+    fn test_recorded_response() {
+        let response = FetchResponse::TableData(
+            "orders".to_string(),
+            vec![r#"{"pk":"a"}"#.to_string()],
+            true,
+            None,
+        );
+        assert_eq!(
+            RecordedResponse::new(&response),
+            RecordedResponse::TableData(
+                "orders".to_string(),
+                vec![r#"{"pk":"*"}"#.to_string()],
+                true
+            )
+        );
+        assert_eq!(
+            RecordedResponse::new(&FetchResponse::SsoLoginComplete),
+            RecordedResponse::Other("SsoLoginComplete".to_string())
+        );
+    }
+}
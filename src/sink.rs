@@ -0,0 +1,221 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::Mutex,
+};
+
+use clipboard::{ClipboardContext, ClipboardProvider};
+use color_eyre::{eyre::eyre, Result};
+use tokio::runtime::Handle;
+
+use crate::data::put_s3_object;
+
+/// Output written to stdout while the terminal belongs to the TUI, printed once it exits.
+static PENDING_STDOUT: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+
+/// Where an export is written.
+pub trait ExportSink {
+    /// Names the destination for status messages, such as `orders.json`.
+    fn describe(&self) -> String;
+
+    fn write(&mut self, data: &[u8]) -> Result<()>;
+}
+
+/// Picks the sink for a destination written as `clipboard`, `-` for stdout, `s3://bucket/key`
+/// for an S3 object, `| command` to pipe into a shell command, or a file path.
+pub fn parse(destination: &str) -> Result<Box<dyn ExportSink>, String> {
+    let destination = destination.trim();
+    match destination {
+        "" => Err("Enter a destination".to_string()),
+        "clipboard" => Ok(Box::new(ClipboardSink)),
+        "-" | "stdout" => Ok(Box::new(StdoutSink)),
+        _ if destination.starts_with("s3://") => {
+            S3Sink::parse(destination).map(|sink| Box::new(sink) as Box<dyn ExportSink>)
+        }
+        _ => match destination.strip_prefix('|') {
+            Some(command) if command.trim().is_empty() => {
+                Err("Enter a command after |".to_string())
+            }
+            Some(command) => Ok(Box::new(CommandSink(command.trim().to_string()))),
+            None => Ok(Box::new(FileSink(PathBuf::from(destination)))),
+        },
+    }
+}
+
+pub struct ClipboardSink;
+
+impl ExportSink for ClipboardSink {
+    fn describe(&self) -> String {
+        "the clipboard".to_string()
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<()> {
+        let mut ctx: ClipboardContext =
+            ClipboardProvider::new().map_err(|err| eyre!("Failed to access clipboard: {err}"))?;
+        ctx.set_contents(String::from_utf8_lossy(data).into_owned())
+            .map_err(|err| eyre!("Failed to copy to clipboard: {err}"))
+    }
+}
+
+pub struct FileSink(pub PathBuf);
+
+impl ExportSink for FileSink {
+    fn describe(&self) -> String {
+        self.0.display().to_string()
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<()> {
+        if let Some(parent) = self
+            .0
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.0, data)?;
+        Ok(())
+    }
+}
+
+/// Holds the output back until the TUI gives the terminal back, see [`flush_stdout`].
+pub struct StdoutSink;
+
+impl ExportSink for StdoutSink {
+    fn describe(&self) -> String {
+        "stdout, on exit".to_string()
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<()> {
+        PENDING_STDOUT
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .extend_from_slice(data);
+        Ok(())
+    }
+}
+
+/// Prints what was exported to stdout during the session.
+pub fn flush_stdout() -> io::Result<()> {
+    let pending =
+        std::mem::take(&mut *PENDING_STDOUT.lock().unwrap_or_else(|err| err.into_inner()));
+    if pending.is_empty() {
+        return Ok(());
+    }
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(&pending)?;
+    stdout.flush()
+}
+
+/// Uploads the output as an S3 object, with the credentials and region the tables are read with.
+pub struct S3Sink {
+    bucket: String,
+    key: String,
+}
+
+impl S3Sink {
+    fn parse(destination: &str) -> Result<Self, String> {
+        let path = destination.strip_prefix("s3://").unwrap_or(destination);
+        match path.split_once('/') {
+            Some((bucket, key)) if !bucket.is_empty() && !key.is_empty() => Ok(Self {
+                bucket: bucket.to_string(),
+                key: key.to_string(),
+            }),
+            _ => Err("Enter the bucket and the key, as s3://bucket/key".to_string()),
+        }
+    }
+}
+
+impl ExportSink for S3Sink {
+    fn describe(&self) -> String {
+        format!("s3://{}/{}", self.bucket, self.key)
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<()> {
+        // Exports are written from the UI, which waits for the upload like it does for a file,
+        // on a thread of its own since it cannot block on the runtime it runs on.
+        let runtime = Handle::current();
+        let data = data.to_vec();
+        std::thread::scope(|scope| {
+            scope
+                .spawn(|| runtime.block_on(put_s3_object(&self.bucket, &self.key, data)))
+                .join()
+                .map_err(|_| eyre!("The upload to {} panicked", self.describe()))?
+                .map_err(|err| eyre!(err))
+        })
+    }
+}
+
+/// Pipes the output into a shell command, which must not read from the terminal.
+pub struct CommandSink(pub String);
+
+impl ExportSink for CommandSink {
+    fn describe(&self) -> String {
+        format!("| {}", self.0)
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<()> {
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(&self.0)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(data)?;
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(eyre!(
+                "`{}` failed: {}",
+                self.0,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_destination() {
+        assert_eq!(parse("clipboard").unwrap().describe(), "the clipboard");
+        assert_eq!(parse("-").unwrap().describe(), "stdout, on exit");
+        assert_eq!(parse("| jq .").unwrap().describe(), "| jq .");
+        assert_eq!(
+            parse("out/items.json").unwrap().describe(),
+            "out/items.json"
+        );
+        assert_eq!(
+            parse("s3://exports/orders/items.json").unwrap().describe(),
+            "s3://exports/orders/items.json"
+        );
+        assert!(parse("s3://exports").is_err());
+        assert!(parse("s3:///items.json").is_err());
+        assert!(parse("|").is_err());
+        assert!(parse(" ").is_err());
+    }
+
+    #[test]
+    fn test_file_and_command_sinks() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("nested").join("items.json");
+
+        FileSink(path.clone()).write(b"[]")?;
+        assert_eq!(fs::read_to_string(&path)?, "[]");
+
+        let copy = dir.path().join("copy.json");
+        CommandSink(format!("cat > '{}'", copy.display())).write(b"[1]")?;
+        assert_eq!(fs::read_to_string(&copy)?, "[1]");
+
+        assert!(CommandSink("exit 3".to_string()).write(b"").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A labelled copy of a result set, keyed by the primary key of each record.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub label: String,
+    pub table: String,
+    pub taken_at: String,
+    pub records: BTreeMap<String, String>,
+}
+
+/// The keys that differ between two snapshots of the same table.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SnapshotDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+    pub unchanged: usize,
+}
+
+impl Snapshot {
+    /// Compares this snapshot with one taken later. Records are compared as JSON, so attributes
+    /// listed in a different order do not count as a change.
+    pub fn diff(&self, newer: &Snapshot) -> SnapshotDiff {
+        let mut diff = SnapshotDiff::default();
+
+        for (key, record) in &self.records {
+            match newer.records.get(key) {
+                None => diff.removed.push(key.clone()),
+                Some(newer_record) if !same_record(record, newer_record) => {
+                    diff.changed.push(key.clone())
+                }
+                Some(_) => diff.unchanged += 1,
+            }
+        }
+        diff.added = newer
+            .records
+            .keys()
+            .filter(|key| !self.records.contains_key(*key))
+            .cloned()
+            .collect();
+
+        diff
+    }
+}
+
+/// Keys a snapshot record by its partition key value, followed by its sort key value if any.
+pub fn snapshot_key(partition_key: &Value, sort_key: Option<&Value>) -> String {
+    match sort_key {
+        Some(sort_key) => format!("{partition_key} / {sort_key}"),
+        None => partition_key.to_string(),
+    }
+}
+
+fn same_record(a: &str, b: &str) -> bool {
+    match (
+        serde_json::from_str::<Value>(a),
+        serde_json::from_str::<Value>(b),
+    ) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn snapshot(records: &[(&str, &str)]) -> Snapshot {
+        Snapshot {
+            records: records
+                .iter()
+                .map(|(key, record)| (key.to_string(), record.to_string()))
+                .collect(),
+            ..Snapshot::default()
+        }
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let before = snapshot(&[
+            ("1", r#"{"id":1,"status":"new"}"#),
+            ("2", r#"{"id":2,"status":"new"}"#),
+            ("3", r#"{"id":3,"status":"new"}"#),
+        ]);
+        let after = snapshot(&[
+            ("1", r#"{"status":"new","id":1}"#),
+            ("2", r#"{"id":2,"status":"done"}"#),
+            ("4", r#"{"id":4,"status":"new"}"#),
+        ]);
+
+        assert_eq!(
+            before.diff(&after),
+            SnapshotDiff {
+                added: vec!["4".to_string()],
+                removed: vec!["3".to_string()],
+                changed: vec!["2".to_string()],
+                unchanged: 1,
+            }
+        );
+    }
+}
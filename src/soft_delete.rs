@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// An attribute that marks the items of a table as deleted without removing them, such as a
+/// `deletedAt` timestamp. An item is deleted when it holds the attribute with any value but null
+/// or false.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SoftDelete {
+    pub table: String,
+    pub attribute: String,
+    /// Leaves the items out of the view in the scan or query itself, rather than only on screen,
+    /// so pages are filled with the items that are shown. Items read and left out still count
+    /// against the capacity used.
+    #[serde(default)]
+    pub server_side: bool,
+}
+
+pub fn for_table<'a>(soft_deletes: &'a [SoftDelete], table: &str) -> Option<&'a SoftDelete> {
+    soft_deletes
+        .iter()
+        .find(|soft_delete| soft_delete.table == table)
+}
+
+/// Which items of a table with a soft-delete attribute are shown.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SoftDeleteView {
+    #[default]
+    HideDeleted,
+    OnlyDeleted,
+    All,
+}
+
+impl SoftDeleteView {
+    pub fn next(self) -> Self {
+        match self {
+            SoftDeleteView::HideDeleted => SoftDeleteView::OnlyDeleted,
+            SoftDeleteView::OnlyDeleted => SoftDeleteView::All,
+            SoftDeleteView::All => SoftDeleteView::HideDeleted,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SoftDeleteView::HideDeleted => "deleted hidden",
+            SoftDeleteView::OnlyDeleted => "deleted only",
+            SoftDeleteView::All => "deleted shown",
+        }
+    }
+
+    /// Whether an item is shown, given whether it is deleted.
+    pub fn shows(self, deleted: bool) -> bool {
+        match self {
+            SoftDeleteView::HideDeleted => !deleted,
+            SoftDeleteView::OnlyDeleted => deleted,
+            SoftDeleteView::All => true,
+        }
+    }
+}
+
+pub fn is_deleted(item: &Value, attribute: &str) -> bool {
+    !matches!(
+        item.get(attribute),
+        None | Some(Value::Null) | Some(Value::Bool(false))
+    )
+}
+
+/// The view of a table that scans and queries of it apply, for tables filtered server side.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SoftDeleteFilter {
+    pub attribute: String,
+    pub view: SoftDeleteView,
+}
+
+impl SoftDeleteFilter {
+    /// The filter expression of the view, naming the attribute `#sd`, null `:sdnull` and false
+    /// `:sdfalse`. The view showing every item has none.
+    pub fn expression(&self) -> Option<&'static str> {
+        match self.view {
+            SoftDeleteView::HideDeleted => {
+                Some("attribute_not_exists(#sd) OR attribute_type(#sd, :sdnull) OR #sd = :sdfalse")
+            }
+            SoftDeleteView::OnlyDeleted => Some(
+                "attribute_exists(#sd) AND NOT attribute_type(#sd, :sdnull) AND #sd <> :sdfalse",
+            ),
+            SoftDeleteView::All => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_deleted_items() {
+        assert!(is_deleted(
+            &json!({ "deletedAt": "2024-11-02T10:15:00Z" }),
+            "deletedAt"
+        ));
+        assert!(is_deleted(&json!({ "deleted": true }), "deleted"));
+        assert!(is_deleted(&json!({ "deleted": 0 }), "deleted"));
+        assert!(!is_deleted(&json!({ "deleted": false }), "deleted"));
+        assert!(!is_deleted(&json!({ "deletedAt": null }), "deletedAt"));
+        assert!(!is_deleted(&json!({ "name": "Ada" }), "deletedAt"));
+    }
+
+    #[test]
+    fn test_views() {
+        let views: Vec<(bool, bool)> = [
+            SoftDeleteView::HideDeleted,
+            SoftDeleteView::OnlyDeleted,
+            SoftDeleteView::All,
+        ]
+        .into_iter()
+        .map(|view| (view.shows(false), view.shows(true)))
+        .collect();
+        assert_eq!(views, vec![(true, false), (false, true), (true, true)]);
+
+        assert_eq!(
+            SoftDeleteView::default().next().next().next(),
+            SoftDeleteView::HideDeleted
+        );
+    }
+}
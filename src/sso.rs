@@ -0,0 +1,342 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    env, fs,
+    path::PathBuf,
+    process::{Command, Stdio},
+    time::{Duration, SystemTime},
+};
+
+use aws_config::{BehaviorVersion, Region};
+use aws_sdk_dynamodb::primitives::{DateTime, DateTimeFormat};
+use aws_sdk_ssooidc::{operation::create_token::CreateTokenError, Client};
+use color_eyre::{eyre::eyre, Result};
+use directories::BaseDirs;
+use serde_json::json;
+use tokio::time::sleep;
+
+use crate::proxy;
+
+const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+
+/// The SSO settings of a profile, resolved from `~/.aws/config`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SsoProfile {
+    pub session_name: Option<String>,
+    pub start_url: String,
+    pub region: String,
+}
+
+impl SsoProfile {
+    /// The AWS CLI keys cached tokens by the SHA-1 of the session name, or of the start URL for
+    /// legacy profiles that configure SSO inline.
+    pub(crate) fn cache_key(&self) -> String {
+        let key = self.session_name.as_ref().unwrap_or(&self.start_url);
+        sha1_smol::Sha1::from(key).digest().to_string()
+    }
+}
+
+/// The device authorization the user has to confirm in the browser.
+#[derive(Debug, Clone)]
+pub struct DeviceAuthorization {
+    pub verification_uri: String,
+    pub user_code: String,
+}
+
+fn aws_config_file() -> Option<PathBuf> {
+    if let Ok(path) = env::var("AWS_CONFIG_FILE") {
+        return Some(PathBuf::from(path));
+    }
+    BaseDirs::new().map(|dirs| dirs.home_dir().join(".aws").join("config"))
+}
+
+fn aws_credentials_file() -> Option<PathBuf> {
+    if let Ok(path) = env::var("AWS_SHARED_CREDENTIALS_FILE") {
+        return Some(PathBuf::from(path));
+    }
+    BaseDirs::new().map(|dirs| dirs.home_dir().join(".aws").join("credentials"))
+}
+
+pub fn sso_cache_dir() -> Option<PathBuf> {
+    BaseDirs::new().map(|dirs| dirs.home_dir().join(".aws").join("sso").join("cache"))
+}
+
+/// Parses an AWS shared config file into `section name -> (key -> value)`.
+fn parse_sections(contents: &str) -> HashMap<String, HashMap<String, String>> {
+    let mut sections: HashMap<String, HashMap<String, String>> = HashMap::new();
+    let mut current = None;
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let name = name.trim().to_string();
+            sections.entry(name.clone()).or_default();
+            current = Some(name);
+        } else if let (Some(section), Some((key, value))) = (&current, line.split_once('=')) {
+            sections
+                .entry(section.clone())
+                .or_default()
+                .insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+
+    sections
+}
+
+fn resolve_sso_profile(
+    sections: &HashMap<String, HashMap<String, String>>,
+    profile: &str,
+) -> Option<SsoProfile> {
+    let section_name = if profile == "default" {
+        "default".to_string()
+    } else {
+        format!("profile {profile}")
+    };
+    let section = sections.get(&section_name)?;
+
+    if let Some(session_name) = section.get("sso_session") {
+        let session = sections.get(&format!("sso-session {session_name}"))?;
+        return Some(SsoProfile {
+            session_name: Some(session_name.clone()),
+            start_url: session.get("sso_start_url")?.clone(),
+            region: session.get("sso_region")?.clone(),
+        });
+    }
+
+    Some(SsoProfile {
+        session_name: None,
+        start_url: section.get("sso_start_url")?.clone(),
+        region: section.get("sso_region")?.clone(),
+    })
+}
+
+/// Returns the SSO settings of `profile`, or `None` if it does not authenticate through SSO.
+pub fn sso_profile(profile: &str) -> Option<SsoProfile> {
+    let contents = fs::read_to_string(aws_config_file()?).ok()?;
+    resolve_sso_profile(&parse_sections(&contents), profile)
+}
+
+/// The profiles of a config file and a credentials file. Config sections are named
+/// `profile <name>`, except for `default`, while credentials sections are named after the
+/// profile.
+fn section_profiles(
+    config: &HashMap<String, HashMap<String, String>>,
+    credentials: &HashMap<String, HashMap<String, String>>,
+) -> Vec<String> {
+    let profiles: BTreeSet<String> = config
+        .keys()
+        .filter_map(|section| match section.as_str() {
+            "default" => Some(section.as_str()),
+            section => section.strip_prefix("profile "),
+        })
+        .map(|profile| profile.trim().to_string())
+        .chain(credentials.keys().cloned())
+        .collect();
+    profiles.into_iter().collect()
+}
+
+/// The profiles in `~/.aws/config` and `~/.aws/credentials`, sorted by name.
+pub fn profile_names() -> Vec<String> {
+    let read = |path: Option<PathBuf>| {
+        path.and_then(|path| fs::read_to_string(path).ok())
+            .map(|contents| parse_sections(&contents))
+            .unwrap_or_default()
+    };
+    section_profiles(&read(aws_config_file()), &read(aws_credentials_file()))
+}
+
+/// Best-effort attempt to open the verification page in the user's browser.
+fn open_in_browser(url: &str) {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(target_os = "windows") {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+
+    let _ = Command::new(opener)
+        .arg(url)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+}
+
+/// Runs the OIDC device authorization flow for `profile`.
+///
+/// `on_authorization` is called once the user code is known, so the UI can display it while this
+/// function polls for the token. The resulting token is written to the AWS CLI SSO cache, where
+/// the SDK credential provider picks it up.
+pub async fn login<F>(sso: &SsoProfile, on_authorization: F) -> Result<()>
+where
+    F: FnOnce(DeviceAuthorization),
+{
+    let config = proxy::apply(
+        aws_config::defaults(BehaviorVersion::v2024_03_28())
+            .region(Region::new(sso.region.clone()))
+            .no_credentials(),
+    )
+    .load()
+    .await;
+    let client = Client::new(&config);
+
+    let registration = client
+        .register_client()
+        .client_name(env!("CARGO_PKG_NAME"))
+        .client_type("public")
+        .send()
+        .await?;
+    let client_id = registration
+        .client_id()
+        .ok_or_else(|| eyre!("SSO client registration returned no client id"))?;
+    let client_secret = registration
+        .client_secret()
+        .ok_or_else(|| eyre!("SSO client registration returned no client secret"))?;
+
+    let authorization = client
+        .start_device_authorization()
+        .client_id(client_id)
+        .client_secret(client_secret)
+        .start_url(&sso.start_url)
+        .send()
+        .await?;
+    let device_code = authorization
+        .device_code()
+        .ok_or_else(|| eyre!("SSO device authorization returned no device code"))?;
+    let verification_uri = authorization
+        .verification_uri_complete()
+        .or(authorization.verification_uri())
+        .unwrap_or_default()
+        .to_string();
+
+    open_in_browser(&verification_uri);
+    on_authorization(DeviceAuthorization {
+        verification_uri,
+        user_code: authorization.user_code().unwrap_or_default().to_string(),
+    });
+
+    let deadline =
+        SystemTime::now() + Duration::from_secs(authorization.expires_in().max(0) as u64);
+    let mut interval = Duration::from_secs(authorization.interval().max(1) as u64);
+
+    let token = loop {
+        if SystemTime::now() > deadline {
+            return Err(eyre!(
+                "SSO device authorization expired before it was approved"
+            ));
+        }
+
+        sleep(interval).await;
+
+        match client
+            .create_token()
+            .client_id(client_id)
+            .client_secret(client_secret)
+            .grant_type(DEVICE_CODE_GRANT_TYPE)
+            .device_code(device_code)
+            .send()
+            .await
+        {
+            Ok(token) => break token,
+            Err(err) => match err.into_service_error() {
+                CreateTokenError::AuthorizationPendingException(_) => continue,
+                CreateTokenError::SlowDownException(_) => interval += Duration::from_secs(5),
+                err => return Err(err.into()),
+            },
+        }
+    };
+
+    let expires_at = SystemTime::now() + Duration::from_secs(token.expires_in().max(0) as u64);
+    let registration_expires_at = DateTime::from_secs(registration.client_secret_expires_at())
+        .fmt(DateTimeFormat::DateTime)?;
+
+    let mut cached = json!({
+        "startUrl": sso.start_url,
+        "region": sso.region,
+        "accessToken": token.access_token().unwrap_or_default(),
+        "expiresAt": DateTime::from(expires_at).fmt(DateTimeFormat::DateTime)?,
+    });
+
+    if sso.session_name.is_some() {
+        cached["clientId"] = json!(client_id);
+        cached["clientSecret"] = json!(client_secret);
+        cached["registrationExpiresAt"] = json!(registration_expires_at);
+        if let Some(refresh_token) = token.refresh_token() {
+            cached["refreshToken"] = json!(refresh_token);
+        }
+    }
+
+    let cache_dir = sso_cache_dir().ok_or_else(|| eyre!("Unable to locate the home directory"))?;
+    fs::create_dir_all(&cache_dir)?;
+    fs::write(
+        cache_dir.join(format!("{}.json", sso.cache_key())),
+        serde_json::to_string_pretty(&cached)?,
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const CONFIG: &str = "
+[default]
+region = us-east-1
+
+[profile legacy]
+sso_start_url = https://legacy.awsapps.com/start
+sso_region = eu-west-1
+sso_account_id = 111111111111
+
+[profile modern]
+sso_session = corp
+sso_account_id = 222222222222
+
+[sso-session corp]
+sso_start_url = https://corp.awsapps.com/start
+sso_region = us-west-2
+";
+
+    #[test]
+    fn test_resolve_legacy_sso_profile() {
+        let profile = resolve_sso_profile(&parse_sections(CONFIG), "legacy").unwrap();
+        assert_eq!(profile.session_name, None);
+        assert_eq!(profile.start_url, "https://legacy.awsapps.com/start");
+        assert_eq!(profile.region, "eu-west-1");
+    }
+
+    #[test]
+    fn test_resolve_sso_session_profile() {
+        let profile = resolve_sso_profile(&parse_sections(CONFIG), "modern").unwrap();
+        assert_eq!(profile.session_name, Some("corp".to_string()));
+        assert_eq!(profile.start_url, "https://corp.awsapps.com/start");
+        assert_eq!(profile.region, "us-west-2");
+    }
+
+    #[test]
+    fn test_section_profiles() {
+        let credentials = parse_sections("[ci]\naws_access_key_id = x\n[default]\n");
+        assert_eq!(
+            section_profiles(&parse_sections(CONFIG), &credentials),
+            vec!["ci", "default", "legacy", "modern"]
+        );
+    }
+
+    #[test]
+    fn test_resolve_non_sso_profile() {
+        assert_eq!(
+            resolve_sso_profile(&parse_sections(CONFIG), "default"),
+            None
+        );
+        assert_eq!(
+            resolve_sso_profile(&parse_sections(CONFIG), "missing"),
+            None
+        );
+    }
+}
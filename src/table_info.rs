@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{cost::Billing, data::KeyAttribute};
+
+/// What DescribeTable and DescribeTimeToLive tell about a table. DynamoDB updates item counts and
+/// sizes about every six hours, so they may lag behind recent writes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableInfo {
+    /// Such as `ACTIVE`, or `UPDATING` while the capacity or the indexes change.
+    pub status: String,
+    /// When the table was created, in RFC 3339.
+    pub created: Option<String>,
+    pub partition_key: Option<KeyAttribute>,
+    pub sort_key: Option<KeyAttribute>,
+    pub item_count: u64,
+    pub size_bytes: u64,
+    pub billing: Billing,
+    pub global_indexes: Vec<IndexInfo>,
+    pub local_indexes: Vec<IndexInfo>,
+    /// What the stream of the table holds for each change, when it has one enabled.
+    pub stream: Option<String>,
+    /// Whether items expire, or the reason it could not be read.
+    pub ttl: Result<TimeToLive, String>,
+    pub deletion_protection: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexInfo {
+    pub name: String,
+    pub partition_key: Option<String>,
+    pub sort_key: Option<String>,
+    /// Which attributes the index holds: `ALL`, `KEYS_ONLY`, or the attributes it includes.
+    pub projection: String,
+    /// The status of a global index, such as `CREATING` while it is backfilled.
+    pub status: Option<String>,
+    /// The read and write capacity units of a global index of a provisioned table.
+    pub capacity: Option<(u64, u64)>,
+    pub item_count: u64,
+    pub size_bytes: u64,
+}
+
+impl IndexInfo {
+    /// The key attributes of the index, the sort key after a slash.
+    pub fn keys(&self) -> String {
+        let partition_key = self.partition_key.as_deref().unwrap_or("?");
+        match &self.sort_key {
+            Some(sort_key) => format!("{partition_key} / {sort_key}"),
+            None => partition_key.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeToLive {
+    /// Such as `ENABLED`, `DISABLED`, or `ENABLING` for up to an hour after it is turned on.
+    pub status: String,
+    /// The attribute holding the time each item expires at, in seconds since the epoch.
+    pub attribute: Option<String>,
+}
+
+impl TimeToLive {
+    pub fn label(&self) -> String {
+        match &self.attribute {
+            Some(attribute) if self.status != "DISABLED" => {
+                format!("{} on {attribute}", self.status)
+            }
+            _ => self.status.clone(),
+        }
+    }
+}
+
+/// A size in bytes, in the largest unit that keeps it at or above one.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1000 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1000.0;
+    let mut unit = 0;
+    while size >= 1000.0 && unit + 1 < UNITS.len() {
+        size /= 1000.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(999), "999 B");
+        assert_eq!(format_bytes(1_500), "1.5 KB");
+        assert_eq!(format_bytes(2_345_678), "2.3 MB");
+        assert_eq!(format_bytes(7_000_000_000_000_000), "7000.0 TB");
+    }
+
+    #[test]
+    fn test_labels() {
+        let index = IndexInfo {
+            partition_key: Some("status".to_string()),
+            sort_key: Some("created_at".to_string()),
+            ..IndexInfo::default()
+        };
+        assert_eq!(index.keys(), "status / created_at");
+
+        let ttl = TimeToLive {
+            status: "ENABLED".to_string(),
+            attribute: Some("expires_at".to_string()),
+        };
+        assert_eq!(ttl.label(), "ENABLED on expires_at");
+        assert_eq!(
+            TimeToLive {
+                status: "DISABLED".to_string(),
+                ..ttl
+            }
+            .label(),
+            "DISABLED"
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+
+/// The regions DynamoDB is offered in, searched when `search_regions` is not set in the config.
+/// Opt-in regions that are not enabled for the account fail to list and are reported as such.
+pub const DYNAMODB_REGIONS: &[&str] = &[
+    "us-east-1",
+    "us-east-2",
+    "us-west-1",
+    "us-west-2",
+    "af-south-1",
+    "ap-east-1",
+    "ap-south-1",
+    "ap-south-2",
+    "ap-southeast-1",
+    "ap-southeast-2",
+    "ap-southeast-3",
+    "ap-southeast-4",
+    "ap-northeast-1",
+    "ap-northeast-2",
+    "ap-northeast-3",
+    "ca-central-1",
+    "ca-west-1",
+    "eu-central-1",
+    "eu-central-2",
+    "eu-west-1",
+    "eu-west-2",
+    "eu-west-3",
+    "eu-south-1",
+    "eu-south-2",
+    "eu-north-1",
+    "il-central-1",
+    "me-south-1",
+    "me-central-1",
+    "sa-east-1",
+];
+
+/// The tables listed in one region, or why they could not be.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegionTables {
+    pub region: String,
+    pub tables: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// Every table found, as `(table, region)` pairs ordered by table name, so a table that exists in
+/// several regions shows up as neighbouring rows.
+pub fn merge(results: &[RegionTables]) -> Vec<(String, String)> {
+    let mut tables: Vec<(String, String)> = results
+        .iter()
+        .flat_map(|result| {
+            result
+                .tables
+                .iter()
+                .map(|table| (table.clone(), result.region.clone()))
+        })
+        .collect();
+    tables.sort();
+    tables
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_merge() {
+        let results = vec![
+            RegionTables {
+                region: "us-west-2".to_string(),
+                tables: vec!["orders".to_string(), "users".to_string()],
+                error: None,
+            },
+            RegionTables {
+                region: "ap-east-1".to_string(),
+                tables: Vec::new(),
+                error: Some("UnrecognizedClientException".to_string()),
+            },
+            RegionTables {
+                region: "eu-west-1".to_string(),
+                tables: vec!["users".to_string()],
+                error: None,
+            },
+        ];
+
+        assert_eq!(
+            merge(&results),
+            vec![
+                ("orders".to_string(), "us-west-2".to_string()),
+                ("users".to_string(), "eu-west-1".to_string()),
+                ("users".to_string(), "us-west-2".to_string()),
+            ]
+        );
+    }
+}
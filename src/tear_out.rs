@@ -0,0 +1,118 @@
+use std::{fs, path::PathBuf};
+
+use serde_json::Value;
+
+use crate::{bookmark::record_has_key, key_order};
+
+/// An item written to a file so it can be kept open in another editor or pane, and written
+/// again whenever a newer version of it is read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TearOut {
+    pub table: String,
+    /// The key attributes of the item, as a JSON object.
+    pub key: String,
+    pub path: PathBuf,
+    /// What the file was last written with, so an unchanged item is not written again.
+    written: String,
+}
+
+impl TearOut {
+    pub fn new(table: &str, key: &str) -> Self {
+        Self {
+            table: table.to_string(),
+            key: key.to_string(),
+            path: std::env::temp_dir()
+                .join(env!("CARGO_PKG_NAME"))
+                .join(file_name(table, key)),
+            written: String::new(),
+        }
+    }
+
+    /// Whether `record` of `table` is the torn out item.
+    pub fn matches(&self, table: &str, record: &str) -> bool {
+        self.table == table && record_has_key(record, &self.key)
+    }
+
+    /// Writes the item to the file, pretty-printed with the attributes named in `first` leading
+    /// it, unless the file already holds it. The file is replaced in one step, so a watcher never
+    /// reads it half written. Returns whether it was written.
+    pub fn write(&mut self, record: &str, first: &[String]) -> Result<bool, String> {
+        let contents = match serde_json::from_str::<Value>(record) {
+            Ok(item) => key_order::to_string_pretty(&item, first),
+            Err(_) => record.to_string(),
+        };
+        if contents == self.written {
+            return Ok(false);
+        }
+
+        let fail = |err: std::io::Error| format!("Failed to write {}: {err}", self.path.display());
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(fail)?;
+        }
+        let partial = self.path.with_extension("json.partial");
+        fs::write(&partial, format!("{contents}\n")).map_err(fail)?;
+        fs::rename(&partial, &self.path).map_err(fail)?;
+        self.written = contents;
+        Ok(true)
+    }
+}
+
+/// The table name and key values, with anything that does not belong in a file name replaced.
+fn file_name(table: &str, key: &str) -> String {
+    let values: Vec<String> = match serde_json::from_str::<Value>(key) {
+        Ok(Value::Object(key)) => key
+            .values()
+            .map(|value| match value {
+                Value::String(value) => value.clone(),
+                value => value.to_string(),
+            })
+            .collect(),
+        _ => vec![key.to_string()],
+    };
+    let name: String = std::iter::once(table.to_string())
+        .chain(values)
+        .collect::<Vec<_>>()
+        .join("-")
+        .chars()
+        .map(|c| match c {
+            c if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') => c,
+            _ => '_',
+        })
+        .collect();
+    format!("{name}.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(
+            file_name("orders", r#"{"pk":"USER#1","sk":"2024/01"}"#),
+            "orders-USER_1-2024_01.json"
+        );
+        assert_eq!(file_name("counts", r#"{"id":7}"#), "counts-7.json");
+    }
+
+    #[test]
+    fn test_write_only_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut tear_out = TearOut {
+            path: dir.path().join("orders-a.json"),
+            ..TearOut::new("orders", r#"{"pk":"a"}"#)
+        };
+
+        assert_eq!(tear_out.write(r#"{"pk":"a","total":1}"#, &[]), Ok(true));
+        assert_eq!(tear_out.write(r#"{"pk":"a","total":1}"#, &[]), Ok(false));
+        assert_eq!(tear_out.write(r#"{"pk":"a","total":2}"#, &[]), Ok(true));
+        assert_eq!(
+            fs::read_to_string(&tear_out.path).unwrap(),
+            "{\n  \"pk\": \"a\",\n  \"total\": 2\n}\n"
+        );
+        assert!(tear_out.matches("orders", r#"{"pk":"a","total":3}"#));
+        assert!(!tear_out.matches("users", r#"{"pk":"a"}"#));
+    }
+}
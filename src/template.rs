@@ -0,0 +1,297 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use aws_sdk_dynamodb::primitives::{DateTime, DateTimeFormat};
+use rand::{seq::SliceRandom, Rng};
+use serde::Deserialize;
+use serde_json::Value;
+
+const FIRST_NAMES: [&str; 12] = [
+    "Ada", "Alan", "Barbara", "Dennis", "Edsger", "Frances", "Grace", "Guido", "Ken", "Linus",
+    "Margaret", "Radia",
+];
+const LAST_NAMES: [&str; 12] = [
+    "Allen", "Dijkstra", "Hamilton", "Hopper", "Lamport", "Liskov", "Lovelace", "Perlman",
+    "Ritchie", "Thompson", "Torvalds", "Turing",
+];
+
+/// An item from the `templates` config section. String values may contain `{{name}}`
+/// placeholders, which are prompted for, or `{{name:default}}` to prefill the prompt.
+///
+/// `{{uuid}}`, `{{now}}` and `{{epoch}}` are filled in without asking, as are the random values
+/// used to seed tables: `{{random.int:1-100}}`, `{{random.float:0-1}}`, `{{random.bool}}`,
+/// `{{random.name}}`, `{{random.email}}`, `{{random.timestamp}}` (within the last year) and
+/// `{{random.choice:new|paid|shipped}}`. A string that is nothing but a number or boolean
+/// placeholder becomes a number or boolean.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ItemTemplate {
+    pub table: String,
+    pub name: String,
+    pub item: Value,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Placeholder {
+    pub name: String,
+    pub default: Option<String>,
+}
+
+impl ItemTemplate {
+    /// The placeholders in the item, each listed once.
+    pub fn placeholders(&self) -> Vec<Placeholder> {
+        let mut placeholders: Vec<Placeholder> = Vec::new();
+        visit_strings(&self.item, &mut |text| {
+            for placeholder in parse_placeholders(text) {
+                if !placeholders
+                    .iter()
+                    .any(|existing| existing.name == placeholder.name)
+                {
+                    placeholders.push(placeholder);
+                }
+            }
+        });
+        placeholders
+    }
+
+    /// The item with every placeholder found in `values` replaced. Others are left as they are.
+    pub fn fill(&self, values: &HashMap<String, String>) -> Value {
+        let mut item = self.item.clone();
+        replace_strings(&mut item, &|text| {
+            replace_placeholders(text, |placeholder| values.get(&placeholder.name).cloned())
+        });
+        item
+    }
+}
+
+/// Whether the placeholder is generated rather than prompted for.
+pub fn is_generated(name: &str) -> bool {
+    matches!(name, "uuid" | "now" | "epoch") || name.starts_with("random.")
+}
+
+/// `item` with every generated placeholder replaced by a fresh value.
+pub fn fill_generated(item: &Value) -> Value {
+    match item {
+        Value::String(text) => match whole_placeholder(text).and_then(|p| generated_value(&p)) {
+            Some(value) => value,
+            None => Value::String(replace_placeholders(text, |placeholder| {
+                generated_value(&placeholder).map(|value| match value {
+                    Value::String(text) => text,
+                    value => value.to_string(),
+                })
+            })),
+        },
+        Value::Array(values) => Value::Array(values.iter().map(fill_generated).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(name, value)| (name.clone(), fill_generated(value)))
+                .collect(),
+        ),
+        value => value.clone(),
+    }
+}
+
+/// The placeholder `text` consists of, if it is nothing else.
+fn whole_placeholder(text: &str) -> Option<Placeholder> {
+    let inner = text.trim().strip_prefix("{{")?.strip_suffix("}}")?;
+    if inner.contains("{{") {
+        return None;
+    }
+    parse_placeholders(text).pop()
+}
+
+fn generated_value(placeholder: &Placeholder) -> Option<Value> {
+    let mut rng = rand::thread_rng();
+    let argument = placeholder.default.as_deref();
+    let range = |default: (f64, f64)| {
+        argument
+            .and_then(|range| range.split_once('-'))
+            .and_then(|(min, max)| Some((min.trim().parse().ok()?, max.trim().parse().ok()?)))
+            .filter(|(min, max)| min <= max)
+            .unwrap_or(default)
+    };
+
+    let value = match placeholder.name.as_str() {
+        "uuid" => Value::from(uuid::Uuid::new_v4().to_string()),
+        "now" => Value::from(timestamp(SystemTime::now())?),
+        "epoch" => Value::from(SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs()),
+        "random.int" => {
+            let (min, max) = range((0.0, 1000.0));
+            Value::from(rng.gen_range(min as i64..=max as i64))
+        }
+        "random.float" => {
+            let (min, max) = range((0.0, 1.0));
+            Value::from(rng.gen_range(min..=max))
+        }
+        "random.bool" => Value::from(rng.gen_bool(0.5)),
+        "random.name" => Value::from(format!(
+            "{} {}",
+            FIRST_NAMES.choose(&mut rng)?,
+            LAST_NAMES.choose(&mut rng)?
+        )),
+        "random.email" => Value::from(format!(
+            "{}.{}{}@example.com",
+            FIRST_NAMES.choose(&mut rng)?.to_lowercase(),
+            LAST_NAMES.choose(&mut rng)?.to_lowercase(),
+            rng.gen_range(1..1000)
+        )),
+        "random.timestamp" => {
+            let ago = Duration::from_secs(rng.gen_range(0..365 * 24 * 60 * 60));
+            Value::from(timestamp(SystemTime::now() - ago)?)
+        }
+        "random.choice" => {
+            let choices: Vec<&str> = argument?.split('|').map(str::trim).collect();
+            Value::from(*choices.choose(&mut rng)?)
+        }
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn timestamp(time: SystemTime) -> Option<String> {
+    DateTime::from(time).fmt(DateTimeFormat::DateTime).ok()
+}
+
+fn visit_strings(value: &Value, visit: &mut impl FnMut(&str)) {
+    match value {
+        Value::String(text) => visit(text),
+        Value::Array(values) => values.iter().for_each(|value| visit_strings(value, visit)),
+        Value::Object(map) => map.values().for_each(|value| visit_strings(value, visit)),
+        _ => {}
+    }
+}
+
+fn replace_strings(value: &mut Value, replace: &impl Fn(&str) -> String) {
+    match value {
+        Value::String(text) => *text = replace(text),
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| replace_strings(value, replace)),
+        Value::Object(map) => map
+            .values_mut()
+            .for_each(|value| replace_strings(value, replace)),
+        _ => {}
+    }
+}
+
+fn parse_placeholders(text: &str) -> Vec<Placeholder> {
+    let mut placeholders = Vec::new();
+    replace_placeholders(text, |placeholder| {
+        placeholders.push(placeholder);
+        None
+    });
+    placeholders
+}
+
+/// Calls `replacement` for every `{{...}}` in `text`, keeping the placeholder where it returns
+/// `None`.
+fn replace_placeholders(
+    text: &str,
+    mut replacement: impl FnMut(Placeholder) -> Option<String>,
+) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start..].find("}}") else {
+            break;
+        };
+        let end = start + len + 2;
+        let inner = rest[start + 2..start + len].trim();
+        let placeholder = match inner.split_once(':') {
+            Some((name, default)) => Placeholder {
+                name: name.trim().to_string(),
+                default: Some(default.trim().to_string()),
+            },
+            None => Placeholder {
+                name: inner.to_string(),
+                default: None,
+            },
+        };
+
+        result.push_str(&rest[..start]);
+        match replacement(placeholder) {
+            Some(value) => result.push_str(&value),
+            None => result.push_str(&rest[start..end]),
+        }
+        rest = &rest[end..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn template() -> ItemTemplate {
+        ItemTemplate {
+            table: "Orders".to_string(),
+            name: "Order".to_string(),
+            item: json!({
+                "pk": "CUSTOMER#{{customer}}",
+                "sk": "ORDER#{{uuid}}",
+                "status": "{{status:new}}",
+                "lines": [{ "customer": "{{customer}}", "quantity": 1 }],
+            }),
+        }
+    }
+
+    #[test]
+    fn test_template_placeholders() {
+        let names: Vec<(String, Option<String>)> = template()
+            .placeholders()
+            .into_iter()
+            .map(|placeholder| (placeholder.name, placeholder.default))
+            .collect();
+
+        assert_eq!(
+            names,
+            vec![
+                ("customer".to_string(), None),
+                ("uuid".to_string(), None),
+                ("status".to_string(), Some("new".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fill_generated() {
+        let item = fill_generated(&json!({
+            "pk": "ORDER#{{random.int:5-5}}",
+            "quantity": "{{random.int:1-3}}",
+            "paid": "{{random.bool}}",
+            "status": "{{random.choice:new|paid}}",
+            "customer": "{{customer}}",
+        }));
+
+        assert_eq!(item["pk"], json!("ORDER#5"));
+        assert!((1..=3).contains(&item["quantity"].as_i64().unwrap()));
+        assert!(item["paid"].is_boolean());
+        assert!(["new", "paid"].contains(&item["status"].as_str().unwrap()));
+        assert_eq!(item["customer"], json!("{{customer}}"));
+    }
+
+    #[test]
+    fn test_fill_template() {
+        let values = HashMap::from([
+            ("customer".to_string(), "42".to_string()),
+            ("status".to_string(), "paid".to_string()),
+        ]);
+
+        assert_eq!(
+            template().fill(&values),
+            json!({
+                "pk": "CUSTOMER#42",
+                "sk": "ORDER#{{uuid}}",
+                "status": "paid",
+                "lines": [{ "customer": "42", "quantity": 1 }],
+            })
+        );
+    }
+}
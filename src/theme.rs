@@ -0,0 +1,252 @@
+use ratatui::style::{
+    palette::{
+        material::INDIGO,
+        tailwind::{EMERALD, RED, VIOLET},
+    },
+    Color, Modifier, Style,
+};
+use serde::{Deserialize, Serialize};
+
+/// The colors every component draws with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Theme {
+    pub active_border: Color,
+    pub inactive_border: Color,
+    pub text: Color,
+    pub selection_fg: Color,
+    pub selection_bg: Color,
+    /// Labels, hints, and the status line.
+    pub muted: Color,
+    pub popup_bg: Color,
+    pub error: Color,
+    pub loading: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            active_border: EMERALD.c300,
+            inactive_border: Color::Gray,
+            text: Color::White,
+            selection_fg: Color::White,
+            selection_bg: VIOLET.c600,
+            muted: INDIGO.c700,
+            popup_bg: Color::Black,
+            error: RED.c400,
+            loading: Color::Cyan,
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            active_border: EMERALD.c700,
+            inactive_border: Color::DarkGray,
+            text: Color::Black,
+            selection_fg: Color::Black,
+            selection_bg: VIOLET.c200,
+            muted: INDIGO.c900,
+            popup_bg: Color::White,
+            error: RED.c700,
+            loading: Color::Blue,
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Self {
+            active_border: Color::Yellow,
+            inactive_border: Color::White,
+            text: Color::White,
+            selection_fg: Color::Black,
+            selection_bg: Color::Yellow,
+            muted: Color::White,
+            popup_bg: Color::Black,
+            error: Color::LightRed,
+            loading: Color::Yellow,
+        }
+    }
+
+    /// For deuteranopia and protanopia: taken from the Okabe-Ito palette, it pairs blue with
+    /// orange rather than green with red, so success and failure stay apart.
+    pub fn color_blind() -> Self {
+        Self {
+            active_border: Color::Rgb(86, 180, 233),
+            inactive_border: Color::DarkGray,
+            text: Color::White,
+            selection_fg: Color::White,
+            selection_bg: Color::Rgb(0, 114, 178),
+            muted: Color::Gray,
+            popup_bg: Color::Black,
+            error: Color::Rgb(230, 159, 0),
+            loading: Color::Rgb(204, 121, 167),
+        }
+    }
+
+    pub fn border(&self, active: bool) -> Style {
+        Style::default().fg(if active {
+            self.active_border
+        } else {
+            self.inactive_border
+        })
+    }
+
+    pub fn selected(&self) -> Style {
+        Style::new()
+            .fg(self.selection_fg)
+            .bg(self.selection_bg)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    pub fn popup(&self) -> Style {
+        Style::new().bg(self.popup_bg).fg(self.text)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemePreset {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+    ColorBlind,
+}
+
+/// The `theme` section of the config: a preset plus any colors the user overrides.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ThemeConfig {
+    #[serde(default)]
+    pub preset: ThemePreset,
+    pub active_border: Option<Color>,
+    pub inactive_border: Option<Color>,
+    pub text: Option<Color>,
+    pub selection_fg: Option<Color>,
+    pub selection_bg: Option<Color>,
+    pub muted: Option<Color>,
+    pub popup_bg: Option<Color>,
+    pub error: Option<Color>,
+    pub loading: Option<Color>,
+}
+
+impl ThemeConfig {
+    pub fn resolve(&self) -> Theme {
+        let base = match self.preset {
+            ThemePreset::Dark => Theme::dark(),
+            ThemePreset::Light => Theme::light(),
+            ThemePreset::HighContrast => Theme::high_contrast(),
+            ThemePreset::ColorBlind => Theme::color_blind(),
+        };
+
+        Theme {
+            active_border: self.active_border.unwrap_or(base.active_border),
+            inactive_border: self.inactive_border.unwrap_or(base.inactive_border),
+            text: self.text.unwrap_or(base.text),
+            selection_fg: self.selection_fg.unwrap_or(base.selection_fg),
+            selection_bg: self.selection_bg.unwrap_or(base.selection_bg),
+            muted: self.muted.unwrap_or(base.muted),
+            popup_bg: self.popup_bg.unwrap_or(base.popup_bg),
+            error: self.error.unwrap_or(base.error),
+            loading: self.loading.unwrap_or(base.loading),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_resolve_preset_with_overrides() {
+        let config: ThemeConfig =
+            json5::from_str(r##"{ preset: "light", error: "#ff0000", text: "blue" }"##).unwrap();
+        let theme = config.resolve();
+
+        assert_eq!(theme.error, Color::Rgb(255, 0, 0));
+        assert_eq!(theme.text, Color::Blue);
+        assert_eq!(theme.popup_bg, Theme::light().popup_bg);
+    }
+
+    #[test]
+    fn test_color_blind_preset() {
+        let config: ThemeConfig = json5::from_str(r#"{ preset: "color-blind" }"#).unwrap();
+        assert_eq!(config.resolve(), Theme::color_blind());
+    }
+
+    #[test]
+    fn test_default_theme_is_dark() {
+        assert_eq!(ThemeConfig::default().resolve(), Theme::dark());
+    }
+
+    /// How the named colors look in the Basic profile of macOS Terminal, whose background is
+    /// white.
+    fn macos_terminal_rgb(color: Color) -> (u8, u8, u8) {
+        match color {
+            Color::Rgb(r, g, b) => (r, g, b),
+            Color::Reset => (255, 255, 255),
+            Color::Black => (0, 0, 0),
+            Color::Red => (153, 0, 0),
+            Color::Green => (0, 166, 0),
+            Color::Yellow => (153, 153, 0),
+            Color::Blue => (0, 0, 178),
+            Color::Magenta => (178, 0, 178),
+            Color::Cyan => (0, 166, 178),
+            Color::Gray => (191, 191, 191),
+            Color::DarkGray => (102, 102, 102),
+            Color::LightRed => (229, 0, 0),
+            Color::LightGreen => (0, 217, 0),
+            Color::LightYellow => (229, 229, 0),
+            Color::LightBlue => (0, 0, 255),
+            Color::LightMagenta => (229, 0, 229),
+            Color::LightCyan => (0, 229, 229),
+            Color::White => (229, 229, 229),
+            Color::Indexed(_) => panic!("preset uses an indexed color: {color:?}"),
+        }
+    }
+
+    /// The WCAG contrast ratio of two colors, from 1 for the same color to 21 for black on white.
+    fn contrast_ratio(a: Color, b: Color) -> f64 {
+        let luminance = |color| {
+            let (r, g, b) = macos_terminal_rgb(color);
+            let channel = |value: u8| {
+                let value = value as f64 / 255.0;
+                if value <= 0.03928 {
+                    value / 12.92
+                } else {
+                    ((value + 0.055) / 1.055).powf(2.4)
+                }
+            };
+            0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b)
+        };
+        let (a, b) = (luminance(a), luminance(b));
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    #[test]
+    fn test_light_theme_is_readable_in_macos_terminal() {
+        let theme = Theme::light();
+
+        // Text is drawn both on popups and straight on the terminal background.
+        for background in [theme.popup_bg, Color::Reset] {
+            for text in [theme.text, theme.muted, theme.error, theme.loading] {
+                assert!(
+                    contrast_ratio(text, background) >= 4.5,
+                    "{text:?} on {background:?}"
+                );
+            }
+            for border in [theme.active_border, theme.inactive_border] {
+                assert!(
+                    contrast_ratio(border, background) >= 3.0,
+                    "{border:?} on {background:?}"
+                );
+            }
+        }
+        assert!(contrast_ratio(theme.selection_fg, theme.selection_bg) >= 4.5);
+    }
+}
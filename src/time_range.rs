@@ -0,0 +1,269 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use aws_sdk_dynamodb::primitives::{DateTime, DateTimeFormat};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::data::KeyAttributeType;
+
+/// Separates the bounds of a sort key range, as in `2024-05-01..2024-06-01`, which is queried
+/// with `BETWEEN`.
+const RANGE_SEPARATOR: &str = "..";
+/// Starts a sort key value typed as a lower bound, as in `>ORDER#0042`, which is queried with `>`
+/// and matches the sort keys after it.
+const AFTER_PREFIX: char = '>';
+
+/// Epoch timestamps between these are taken for seconds or milliseconds, which covers 2001 to
+/// 2286 for either.
+const EPOCH_SECONDS: std::ops::Range<f64> = 1e9..1e10;
+const EPOCH_MILLIS: std::ops::Range<f64> = 1e12..1e13;
+
+/// The preset ranges offered for a sort key that holds timestamps: the last hour, day, or week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimeRange {
+    Hour,
+    Day,
+    Week,
+}
+
+impl TimeRange {
+    /// The preset after `range`, cycling back to no range after the last one.
+    pub fn next(range: Option<TimeRange>) -> Option<TimeRange> {
+        match range {
+            None => Some(TimeRange::Hour),
+            Some(TimeRange::Hour) => Some(TimeRange::Day),
+            Some(TimeRange::Day) => Some(TimeRange::Week),
+            Some(TimeRange::Week) => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimeRange::Hour => "last hour",
+            TimeRange::Day => "last day",
+            TimeRange::Week => "last week",
+        }
+    }
+
+    fn duration(&self) -> Duration {
+        match self {
+            TimeRange::Hour => Duration::from_secs(60 * 60),
+            TimeRange::Day => Duration::from_secs(24 * 60 * 60),
+            TimeRange::Week => Duration::from_secs(7 * 24 * 60 * 60),
+        }
+    }
+
+    /// The range ending at `now`, written the way it is typed into the sort key input.
+    pub fn bounds(&self, now: SystemTime, format: TimestampFormat) -> String {
+        last(self.duration(), now, format)
+    }
+}
+
+/// The range from `duration` before `now` until `now`.
+fn last(duration: Duration, now: SystemTime, format: TimestampFormat) -> String {
+    let start = now.checked_sub(duration).unwrap_or(UNIX_EPOCH);
+    format!(
+        "{}{RANGE_SEPARATOR}{}",
+        format.format(start),
+        format.format(now)
+    )
+}
+
+/// How a sort key stores its timestamps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimestampFormat {
+    /// `2024-05-01T12:00:00Z`, which sorts the same as the time it stands for.
+    Iso8601,
+    EpochSeconds,
+    EpochMillis,
+}
+
+impl TimestampFormat {
+    /// The format a sort key of the given type most likely uses.
+    pub fn for_attribute_type(attribute_type: KeyAttributeType) -> Self {
+        match attribute_type {
+            KeyAttributeType::N => TimestampFormat::EpochSeconds,
+            KeyAttributeType::S | KeyAttributeType::B => TimestampFormat::Iso8601,
+        }
+    }
+
+    /// The format every one of the sampled sort key values is in, if they all look like
+    /// timestamps of the same kind.
+    pub fn detect<'a>(values: impl IntoIterator<Item = &'a Value>) -> Option<Self> {
+        let mut detected = None;
+        for value in values {
+            let format = match value {
+                Value::String(text)
+                    if DateTime::from_str(text, DateTimeFormat::DateTime).is_ok() =>
+                {
+                    TimestampFormat::Iso8601
+                }
+                Value::Number(number) => match number.as_f64()? {
+                    number if EPOCH_SECONDS.contains(&number) => TimestampFormat::EpochSeconds,
+                    number if EPOCH_MILLIS.contains(&number) => TimestampFormat::EpochMillis,
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            if detected.is_some_and(|detected| detected != format) {
+                return None;
+            }
+            detected = Some(format);
+        }
+        detected
+    }
+
+    pub fn format(&self, time: SystemTime) -> String {
+        let seconds = time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        match self {
+            TimestampFormat::Iso8601 => DateTime::from_secs(seconds as i64)
+                .fmt(DateTimeFormat::DateTime)
+                .unwrap_or_default(),
+            TimestampFormat::EpochSeconds => seconds.to_string(),
+            TimestampFormat::EpochMillis => time
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis()
+                .to_string(),
+        }
+    }
+}
+
+/// Reads a relative range such as `-15m`, `-2h`, or `-7d` as the range ending at `now`.
+pub fn relative_range(value: &str, now: SystemTime, format: TimestampFormat) -> Option<String> {
+    let value = value.trim().strip_prefix('-')?;
+    let (count, unit) = value.split_at(value.len().checked_sub(1)?);
+    let seconds = match unit {
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return None,
+    };
+    let count: u64 = count.parse().ok()?;
+    Some(last(
+        Duration::from_secs(count.checked_mul(seconds)?),
+        now,
+        format,
+    ))
+}
+
+/// A sort key value as it is typed into the query form. Numbers are read back from JSON as
+/// floats, so whole ones lose their fraction again.
+pub fn sort_key_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => match number.as_f64() {
+            Some(number) if number.fract() == 0.0 => Some(format!("{}", number as i64)),
+            _ => Some(number.to_string()),
+        },
+        _ => None,
+    }
+}
+
+/// The bounds of a sort key value typed as a range, or `None` for a single value.
+pub fn split_range(value: &str) -> Option<(&str, &str)> {
+    value
+        .split_once(RANGE_SEPARATOR)
+        .filter(|(start, end)| !start.is_empty() && !end.is_empty())
+}
+
+/// The bound of a sort key value typed as `>value`, or `None` for any other value.
+pub fn after_bound(value: &str) -> Option<&str> {
+    value
+        .strip_prefix(AFTER_PREFIX)
+        .filter(|bound| !bound.is_empty())
+}
+
+/// The sort key value that queries for the items after `bound`.
+pub fn after(bound: &str) -> String {
+    format!("{AFTER_PREFIX}{bound}")
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_714_564_800);
+
+        assert_eq!(
+            TimeRange::Hour.bounds(now, TimestampFormat::Iso8601),
+            "2024-05-01T11:00:00Z..2024-05-01T12:00:00Z"
+        );
+        assert_eq!(
+            TimeRange::Day.bounds(now, TimestampFormat::EpochSeconds),
+            "1714478400..1714564800"
+        );
+    }
+
+    #[test]
+    fn test_detect() {
+        let iso = [
+            json!("2024-05-01T12:00:00Z"),
+            json!("2024-05-01T12:00:01.250Z"),
+        ];
+        assert_eq!(
+            TimestampFormat::detect(&iso),
+            Some(TimestampFormat::Iso8601)
+        );
+
+        let seconds = [json!(1_714_564_800.0), json!(1_714_564_801)];
+        assert_eq!(
+            TimestampFormat::detect(&seconds),
+            Some(TimestampFormat::EpochSeconds)
+        );
+        assert_eq!(
+            TimestampFormat::detect(&[json!(1_714_564_800_000_u64)]),
+            Some(TimestampFormat::EpochMillis)
+        );
+
+        assert_eq!(TimestampFormat::detect(&[json!("ORDER#1")]), None);
+        assert_eq!(TimestampFormat::detect(&[json!(42)]), None);
+        assert_eq!(
+            TimestampFormat::detect(&[json!(1_714_564_800), json!(1_714_564_800_000_u64)]),
+            None
+        );
+        assert_eq!(TimestampFormat::detect(&[]), None);
+    }
+
+    #[test]
+    fn test_relative_range() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_714_564_800);
+
+        assert_eq!(
+            relative_range("-15m", now, TimestampFormat::EpochMillis).as_deref(),
+            Some("1714563900000..1714564800000")
+        );
+        assert_eq!(
+            relative_range("-2h", now, TimestampFormat::Iso8601).as_deref(),
+            Some("2024-05-01T10:00:00Z..2024-05-01T12:00:00Z")
+        );
+        assert_eq!(relative_range("15m", now, TimestampFormat::Iso8601), None);
+        assert_eq!(relative_range("-m", now, TimestampFormat::Iso8601), None);
+        assert_eq!(relative_range("-3w", now, TimestampFormat::Iso8601), None);
+    }
+
+    #[test]
+    fn test_split_range() {
+        assert_eq!(
+            split_range("2024-05-01..2024-06-01"),
+            Some(("2024-05-01", "2024-06-01"))
+        );
+        assert_eq!(split_range("2024-05-01"), None);
+        assert_eq!(split_range("2024-05-01.."), None);
+    }
+
+    #[test]
+    fn test_after_bound() {
+        assert_eq!(after_bound(&after("ORDER#0042")), Some("ORDER#0042"));
+        assert_eq!(after_bound("ORDER#0042"), None);
+        assert_eq!(after_bound(">"), None);
+    }
+}
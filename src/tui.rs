@@ -8,7 +8,7 @@ use std::{
 
 use color_eyre::Result;
 use crossterm::{
-    cursor,
+    cursor::{self, SetCursorStyle},
     event::{
         DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
         Event as CrosstermEvent, EventStream, KeyEvent, KeyEventKind, MouseEvent,
@@ -187,12 +187,28 @@ impl Tui {
             if self.mouse {
                 crossterm::execute!(stdout(), DisableMouseCapture)?;
             }
-            crossterm::execute!(stdout(), LeaveAlternateScreen, cursor::Show)?;
+            crossterm::execute!(
+                stdout(),
+                LeaveAlternateScreen,
+                SetCursorStyle::DefaultUserShape,
+                cursor::Show
+            )?;
             crossterm::terminal::disable_raw_mode()?;
         }
         Ok(())
     }
 
+    /// A bar while text is typed, and the terminal's own cursor otherwise.
+    pub fn text_cursor(&self, typing: bool) -> Result<()> {
+        let style = if typing {
+            SetCursorStyle::SteadyBar
+        } else {
+            SetCursorStyle::DefaultUserShape
+        };
+        crossterm::execute!(stdout(), style)?;
+        Ok(())
+    }
+
     pub fn cancel(&self) {
         self.cancellation_token.cancel();
     }
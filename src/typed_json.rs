@@ -0,0 +1,377 @@
+use std::collections::HashMap;
+
+use aws_sdk_dynamodb::{primitives::Blob, types::AttributeValue};
+use base64::{prelude::BASE64_STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    data::is_number,
+    util::{dynamodb_to_json, json_to_dynamodb},
+};
+
+/// An attribute value in the DynamoDB JSON format, such as `{"N": "12.50"}` or `{"SS": ["a"]}`,
+/// which keeps what plain JSON loses: sets, the exact text of numbers, and binary values.
+pub fn to_typed(value: &AttributeValue) -> Value {
+    match value {
+        AttributeValue::S(text) => json!({ "S": text }),
+        AttributeValue::N(number) => json!({ "N": number }),
+        AttributeValue::B(blob) => json!({ "B": BASE64_STANDARD.encode(blob.as_ref()) }),
+        AttributeValue::Ss(texts) => json!({ "SS": texts }),
+        AttributeValue::Ns(numbers) => json!({ "NS": numbers }),
+        AttributeValue::Bs(blobs) => {
+            let blobs: Vec<String> = blobs
+                .iter()
+                .map(|blob| BASE64_STANDARD.encode(blob.as_ref()))
+                .collect();
+            json!({ "BS": blobs })
+        }
+        AttributeValue::Bool(flag) => json!({ "BOOL": flag }),
+        AttributeValue::M(map) => json!({ "M": item_to_typed(map) }),
+        AttributeValue::L(list) => {
+            let list: Vec<Value> = list.iter().map(to_typed).collect();
+            json!({ "L": list })
+        }
+        _ => json!({ "NULL": true }),
+    }
+}
+
+/// The attribute value written in the DynamoDB JSON format, if it is.
+pub fn from_typed(value: &Value) -> Option<AttributeValue> {
+    let Value::Object(map) = value else {
+        return None;
+    };
+    if map.len() != 1 {
+        return None;
+    }
+    let (kind, value) = map.iter().next()?;
+    let value = match (kind.as_str(), value) {
+        ("S", Value::String(text)) => AttributeValue::S(text.clone()),
+        ("N", Value::String(number)) => AttributeValue::N(number.clone()),
+        ("B", Value::String(text)) => AttributeValue::B(decode(text)?),
+        ("SS", Value::Array(texts)) => AttributeValue::Ss(strings(texts)?),
+        ("NS", Value::Array(numbers)) => AttributeValue::Ns(strings(numbers)?),
+        ("BS", Value::Array(blobs)) => AttributeValue::Bs(
+            strings(blobs)?
+                .iter()
+                .map(|text| decode(text))
+                .collect::<Option<_>>()?,
+        ),
+        ("BOOL", Value::Bool(flag)) => AttributeValue::Bool(*flag),
+        ("NULL", _) => AttributeValue::Null(true),
+        ("M", value) => AttributeValue::M(item_from_typed(value)?),
+        ("L", Value::Array(list)) => {
+            AttributeValue::L(list.iter().map(from_typed).collect::<Option<_>>()?)
+        }
+        _ => return None,
+    };
+    Some(value)
+}
+
+pub fn item_to_typed(item: &HashMap<String, AttributeValue>) -> Value {
+    Value::Object(
+        item.iter()
+            .map(|(name, value)| (name.clone(), to_typed(value)))
+            .collect(),
+    )
+}
+
+pub fn item_from_typed(item: &Value) -> Option<HashMap<String, AttributeValue>> {
+    let Value::Object(map) = item else {
+        return None;
+    };
+    map.iter()
+        .map(|(name, value)| Some((name.clone(), from_typed(value)?)))
+        .collect()
+}
+
+fn strings(values: &[Value]) -> Option<Vec<String>> {
+    values
+        .iter()
+        .map(|value| value.as_str().map(str::to_string))
+        .collect()
+}
+
+fn decode(text: &str) -> Option<Blob> {
+    BASE64_STANDARD.decode(text.trim()).ok().map(Blob::new)
+}
+
+/// Which of two ways to type a changed value to write it with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TypeChoice {
+    /// The type the value had.
+    Old,
+    /// The type of the JSON it was changed to.
+    New,
+}
+
+/// A changed value that could keep the type it had, or take the type of the JSON it was changed
+/// to, with what each would write.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ambiguity {
+    /// Where the value is in the item, as attribute names and list indexes joined by dots.
+    pub path: String,
+    pub old: String,
+    pub new: String,
+}
+
+/// The attributes of an edited item, typed to be written. Attributes left as they were keep the
+/// value they were read with exactly, while changed ones are typed from their JSON, with hints
+/// from the type they had: in maps and lists only the values that changed are typed again, and
+/// sets stay sets as long as their values fit. Values that could be typed either way are settled
+/// by `choices`, by path; the first that is not is returned instead, to ask about.
+pub fn retype_item(
+    original: &HashMap<String, AttributeValue>,
+    edited: &Map<String, Value>,
+    choices: &HashMap<String, TypeChoice>,
+) -> Result<HashMap<String, AttributeValue>, Ambiguity> {
+    let mut item = HashMap::new();
+    for (name, value) in edited {
+        if let Some(value) = retype(name, original.get(name), value, choices)? {
+            item.insert(name.clone(), value);
+        }
+    }
+    Ok(item)
+}
+
+/// A value typed to be written, or `None` when it is left out.
+fn retype(
+    path: &str,
+    original: Option<&AttributeValue>,
+    edited: &Value,
+    choices: &HashMap<String, TypeChoice>,
+) -> Result<Option<AttributeValue>, Ambiguity> {
+    let Some(original) = original else {
+        return Ok(Some(json_to_dynamodb(edited.clone())));
+    };
+    if dynamodb_to_json(original.clone()) == *edited {
+        return Ok(Some(original.clone()));
+    }
+
+    let choose =
+        |old: Option<AttributeValue>, old_label: String, new: AttributeValue, new_label| {
+            match choices.get(path) {
+                Some(TypeChoice::Old) => Ok(old),
+                Some(TypeChoice::New) => Ok(Some(new)),
+                None => Err(Ambiguity {
+                    path: path.to_string(),
+                    old: old_label,
+                    new: new_label,
+                }),
+            }
+        };
+    let value = match (original, edited) {
+        (AttributeValue::M(map), Value::Object(edited)) => {
+            let mut typed = HashMap::new();
+            for (name, value) in edited {
+                let path = format!("{path}.{name}");
+                if let Some(value) = retype(&path, map.get(name), value, choices)? {
+                    typed.insert(name.clone(), value);
+                }
+            }
+            AttributeValue::M(typed)
+        }
+        (AttributeValue::L(list), Value::Array(edited)) => {
+            let mut typed = Vec::new();
+            for (index, value) in edited.iter().enumerate() {
+                let path = format!("{path}.{index}");
+                if let Some(value) = retype(&path, list.get(index), value, choices)? {
+                    typed.push(value);
+                }
+            }
+            AttributeValue::L(typed)
+        }
+        (AttributeValue::N(_), Value::String(text)) if is_number(text.trim()) => {
+            return choose(
+                Some(AttributeValue::N(text.trim().to_string())),
+                format!("the number {}", text.trim()),
+                AttributeValue::S(text.clone()),
+                format!("the text \"{text}\""),
+            );
+        }
+        (AttributeValue::S(_), Value::Number(number)) => {
+            return choose(
+                Some(AttributeValue::S(number.to_string())),
+                format!("the text \"{number}\""),
+                AttributeValue::N(number.to_string()),
+                format!("the number {number}"),
+            );
+        }
+        (AttributeValue::B(_), Value::String(text)) if decode(text).is_some() => {
+            return choose(
+                decode(text).map(AttributeValue::B),
+                "binary data, read as base64".to_string(),
+                AttributeValue::S(text.clone()),
+                format!("the text \"{text}\""),
+            );
+        }
+        (
+            AttributeValue::Ss(_) | AttributeValue::Ns(_) | AttributeValue::Bs(_),
+            Value::Array(values),
+        ) if values.is_empty() => {
+            return choose(
+                None,
+                "nothing, as sets cannot be empty".to_string(),
+                AttributeValue::L(Vec::new()),
+                "an empty list".to_string(),
+            );
+        }
+        (AttributeValue::Ss(_), Value::Array(values)) => match strings(values) {
+            Some(texts) => AttributeValue::Ss(distinct(texts)),
+            None => json_to_dynamodb(edited.clone()),
+        },
+        (AttributeValue::Ns(numbers), Value::Array(values)) => {
+            match values
+                .iter()
+                .map(|value| {
+                    value
+                        .as_f64()
+                        .map(|number| number_text(numbers, value, number))
+                })
+                .collect::<Option<Vec<String>>>()
+            {
+                Some(numbers) => AttributeValue::Ns(distinct(numbers)),
+                None => json_to_dynamodb(edited.clone()),
+            }
+        }
+        (AttributeValue::Bs(_), Value::Array(values)) => match strings(values).and_then(|texts| {
+            distinct(texts)
+                .iter()
+                .map(|text| decode(text))
+                .collect::<Option<Vec<Blob>>>()
+        }) {
+            Some(blobs) => AttributeValue::Bs(blobs),
+            None => json_to_dynamodb(edited.clone()),
+        },
+        _ => json_to_dynamodb(edited.clone()),
+    };
+    Ok(Some(value))
+}
+
+/// The text of a number of a number set, as it was read if the set held it already.
+fn number_text(numbers: &[String], value: &Value, number: f64) -> String {
+    numbers
+        .iter()
+        .find(|text| text.parse::<f64>().ok() == Some(number))
+        .cloned()
+        .unwrap_or_else(|| value.to_string())
+}
+
+/// The values without repeats, which sets cannot hold, in the order they came.
+fn distinct(values: Vec<String>) -> Vec<String> {
+    let mut distinct = Vec::new();
+    for value in values {
+        if !distinct.contains(&value) {
+            distinct.push(value);
+        }
+    }
+    distinct
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn original() -> HashMap<String, AttributeValue> {
+        item_from_typed(&json!({
+            "pk": { "S": "USER#1" },
+            "price": { "N": "12.50" },
+            "tags": { "SS": ["a", "b"] },
+            "scores": { "NS": ["1.0", "2"] },
+            "code": { "S": "7" },
+            "address": { "M": {
+                "zip": { "N": "0100" },
+                "city": { "S": "Oslo" },
+            } },
+        }))
+        .unwrap()
+    }
+
+    fn edited(item: Value) -> Map<String, Value> {
+        let Value::Object(item) = item else {
+            unreachable!()
+        };
+        item
+    }
+
+    #[test]
+    fn test_typed_round_trip() {
+        let item = original();
+        assert_eq!(item_from_typed(&item_to_typed(&item)), Some(item));
+        assert_eq!(from_typed(&json!({ "N": 1 })), None);
+    }
+
+    #[test]
+    fn test_retype_keeps_untouched_types() {
+        let original = original();
+        let shown = dynamodb_to_json(AttributeValue::M(original.clone()));
+        let mut item = edited(shown);
+        item.insert("tags".to_string(), json!(["a", "c", "c"]));
+        item.insert("scores".to_string(), json!([1.0, 3]));
+        item.insert(
+            "address".to_string(),
+            json!({ "zip": 100.0, "city": "Bergen" }),
+        );
+        item.insert("note".to_string(), json!("new"));
+
+        let typed = retype_item(&original, &item, &HashMap::new()).unwrap();
+
+        assert_eq!(typed["price"], AttributeValue::N("12.50".to_string()));
+        assert_eq!(
+            typed["tags"],
+            AttributeValue::Ss(vec!["a".to_string(), "c".to_string()])
+        );
+        assert_eq!(
+            typed["scores"],
+            AttributeValue::Ns(vec!["1.0".to_string(), "3".to_string()])
+        );
+        let AttributeValue::M(address) = &typed["address"] else {
+            panic!("address is not a map");
+        };
+        assert_eq!(address["zip"], AttributeValue::N("0100".to_string()));
+        assert_eq!(address["city"], AttributeValue::S("Bergen".to_string()));
+        assert_eq!(typed["note"], AttributeValue::S("new".to_string()));
+    }
+
+    #[test]
+    fn test_retype_asks_about_ambiguous_changes() {
+        let original = original();
+        let item = edited(json!({ "pk": "USER#1", "code": 8, "tags": [] }));
+
+        let ambiguity = retype_item(&original, &item, &HashMap::new()).unwrap_err();
+        assert_eq!(ambiguity.path, "code");
+        assert_eq!(ambiguity.old, "the text \"8\"");
+
+        let mut choices = HashMap::from([("code".to_string(), TypeChoice::Old)]);
+        assert_eq!(
+            retype_item(&original, &item, &choices).unwrap_err().path,
+            "tags"
+        );
+
+        choices.insert("tags".to_string(), TypeChoice::Old);
+        let typed = retype_item(&original, &item, &choices).unwrap();
+        assert_eq!(typed["code"], AttributeValue::S("8".to_string()));
+        assert!(!typed.contains_key("tags"));
+    }
+
+    #[test]
+    fn test_retype_number_as_text() {
+        let original = original();
+        let item = edited(json!({ "pk": "USER#1", "price": "12.75" }));
+        assert_eq!(
+            retype_item(&original, &item, &HashMap::new())
+                .unwrap_err()
+                .old,
+            "the number 12.75"
+        );
+
+        // Text `f64` would read as a number is not one to DynamoDB, so it stays text.
+        for text in ["inf", "NaN"] {
+            let item = edited(json!({ "pk": "USER#1", "price": text }));
+            let typed = retype_item(&original, &item, &HashMap::new()).unwrap();
+            assert_eq!(typed["price"], AttributeValue::S(text.to_string()));
+        }
+    }
+}
@@ -1,6 +1,24 @@
+use std::io;
+
 use aws_sdk_dynamodb::types::AttributeValue;
+use base64::{prelude::BASE64_STANDARD, Engine};
 use serde_json::{json, Map, Value};
 
+pub fn json_to_dynamodb(value: Value) -> AttributeValue {
+    match value {
+        Value::String(s) => AttributeValue::S(s),
+        Value::Number(n) => AttributeValue::N(n.to_string()),
+        Value::Bool(b) => AttributeValue::Bool(b),
+        Value::Object(map) => AttributeValue::M(
+            map.into_iter()
+                .map(|(k, v)| (k, json_to_dynamodb(v)))
+                .collect(),
+        ),
+        Value::Array(list) => AttributeValue::L(list.into_iter().map(json_to_dynamodb).collect()),
+        Value::Null => AttributeValue::Null(true),
+    }
+}
+
 pub fn dynamodb_to_json(attr: AttributeValue) -> Value {
     match attr {
         AttributeValue::S(s) => json!(s),
@@ -19,7 +37,67 @@ pub fn dynamodb_to_json(attr: AttributeValue) -> Value {
             let json_list: Vec<Value> = list.into_iter().map(dynamodb_to_json).collect();
             json!(json_list)
         }
+        // Binary values are shown as base64, the way they are typed in the query form.
+        AttributeValue::B(blob) => json!(BASE64_STANDARD.encode(blob.as_ref())),
+        // Sets are shown as lists, which keeping their type when an item is edited relies on.
+        AttributeValue::Ss(texts) => json!(texts),
+        AttributeValue::Ns(numbers) => numbers
+            .into_iter()
+            .map(|number| dynamodb_to_json(AttributeValue::N(number)))
+            .collect(),
+        AttributeValue::Bs(blobs) => blobs
+            .into_iter()
+            .map(|blob| dynamodb_to_json(AttributeValue::B(blob)))
+            .collect(),
         AttributeValue::Null(_) => Value::Null,
         _ => Value::Null, // Handle unsupported types by returning `null`
     }
 }
+
+/// The value as compact JSON, if that takes at most `limit` bytes. Writing stops as soon as the
+/// limit is passed, so a large value costs no more than a small one.
+pub fn json_within(value: &Value, limit: usize) -> Option<String> {
+    struct Limited {
+        bytes: Vec<u8>,
+        limit: usize,
+    }
+
+    impl io::Write for Limited {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.bytes.len() + buf.len() > self.limit {
+                return Err(io::ErrorKind::WriteZero.into());
+            }
+            self.bytes.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut writer = Limited {
+        bytes: Vec::new(),
+        limit,
+    };
+    serde_json::to_writer(&mut writer, value).ok()?;
+    String::from_utf8(writer.bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_json_within() {
+        let value = json!({ "tags": ["a", "b"] });
+
+        assert_eq!(
+            json_within(&value, 20),
+            Some(r#"{"tags":["a","b"]}"#.to_string())
+        );
+        assert_eq!(json_within(&value, 17), None);
+    }
+}
@@ -0,0 +1,134 @@
+use std::sync::RwLock;
+
+use serde::{Deserialize, Serialize};
+
+/// The workspace every AWS client is built for, or `None` for the profile and region the
+/// environment points at.
+static ACTIVE: RwLock<Option<Workspace>> = RwLock::new(None);
+
+/// The region picked at runtime, which wins over the region of the workspace until the next
+/// workspace switch.
+static REGION: RwLock<Option<String>> = RwLock::new(None);
+
+/// The profile picked at runtime, which wins over the profile of the workspace until the next
+/// workspace switch.
+static PROFILE: RwLock<Option<String>> = RwLock::new(None);
+
+/// The profile given on the command line, used in place of the environment's.
+static DEFAULT_PROFILE: RwLock<Option<String>> = RwLock::new(None);
+
+/// A named combination of profile, region, and tables, switched to as a whole.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Workspace {
+    pub name: String,
+    pub profile: Option<String>,
+    pub region: Option<String>,
+    /// The only tables listed while the workspace is active. Every table is listed when empty.
+    #[serde(default)]
+    pub tables: Vec<String>,
+    /// Refuses every write made from the app, such as saving or seeding items.
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+impl Workspace {
+    /// The tables of `all` that the workspace lists, in the order it names them.
+    pub fn visible_tables(&self, all: &[String]) -> Vec<String> {
+        if self.tables.is_empty() {
+            return all.to_vec();
+        }
+        self.tables
+            .iter()
+            .filter(|table| all.contains(table))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Switches the clients built from now on over to `workspace`.
+pub fn activate(workspace: Option<Workspace>) {
+    *ACTIVE.write().unwrap_or_else(|err| err.into_inner()) = workspace;
+    set_region(None);
+    set_profile(None);
+}
+
+pub fn active() -> Option<Workspace> {
+    ACTIVE.read().unwrap_or_else(|err| err.into_inner()).clone()
+}
+
+/// Switches the clients built from now on over to `region`, or back to the region of the
+/// workspace if `None`.
+pub fn set_region(region: Option<String>) {
+    *REGION.write().unwrap_or_else(|err| err.into_inner()) = region;
+}
+
+pub fn region() -> Option<String> {
+    REGION.read().unwrap_or_else(|err| err.into_inner()).clone()
+}
+
+/// Switches the clients built from now on over to `profile`, or back to the profile of the
+/// workspace if `None`.
+pub fn set_profile(profile: Option<String>) {
+    *PROFILE.write().unwrap_or_else(|err| err.into_inner()) = profile;
+}
+
+pub fn set_default_profile(profile: Option<String>) {
+    *DEFAULT_PROFILE
+        .write()
+        .unwrap_or_else(|err| err.into_inner()) = profile;
+}
+
+/// The profile clients are built from, if one is named rather than left to the environment: the
+/// one picked at runtime, else the one the active workspace names, else the one given on the
+/// command line.
+pub fn profile() -> Option<String> {
+    PROFILE
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+        .clone()
+        .or_else(|| active().and_then(|workspace| workspace.profile))
+        .or_else(|| {
+            DEFAULT_PROFILE
+                .read()
+                .unwrap_or_else(|err| err.into_inner())
+                .clone()
+        })
+}
+
+/// Why a write has to be refused, if the active workspace is read-only.
+pub fn refuse_write() -> Option<String> {
+    active()
+        .filter(|workspace| workspace.read_only)
+        .map(|workspace| format!("The {} workspace is read-only", workspace.name))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_visible_tables() {
+        let all = vec![
+            "orders".to_string(),
+            "payments".to_string(),
+            "users".to_string(),
+        ];
+
+        assert_eq!(Workspace::default().visible_tables(&all), all);
+
+        let workspace = Workspace {
+            tables: vec![
+                "users".to_string(),
+                "missing".to_string(),
+                "orders".to_string(),
+            ],
+            ..Workspace::default()
+        };
+        assert_eq!(
+            workspace.visible_tables(&all),
+            vec!["users".to_string(), "orders".to_string()]
+        );
+    }
+}